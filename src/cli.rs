@@ -1,24 +1,166 @@
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
+
+fn output_dir_arg() -> Arg {
+    Arg::new("output-dir")
+        .short('o')
+        .help("Client output location")
+        .required(true)
+}
+
+fn spec_arg() -> Arg {
+    Arg::new("spec")
+        .short('s')
+        .help("Input OpenAPI spec")
+        .required(true)
+}
+
+fn config_arg() -> Arg {
+    Arg::new("config")
+        .short('c')
+        .help("(json/yaml/toml) Configuration with name mappings and ignores")
+        .required(false)
+}
+
+fn target_arg() -> Arg {
+    Arg::new("target")
+        .long("target")
+        .value_parser([
+            "rust_reqwest_async",
+            "axum_mock",
+            "axum_server",
+            "python_httpx",
+            "typescript_fetch",
+        ])
+        .help("Generated project kind, overrides generator.target of the config")
+}
+
+fn strict_arg() -> Arg {
+    Arg::new("strict")
+        .long("strict")
+        .action(ArgAction::SetTrue)
+        .help("Exit with 1 if any operation or component was skipped")
+}
+
+/// Format and destination of the generation report
+fn report_args() -> [Arg; 2] {
+    [
+        Arg::new("report-format")
+            .long("report-format")
+            .value_parser(["text", "json"])
+            .default_value("text")
+            .help("Format of the generation report"),
+        Arg::new("report-file")
+            .long("report-file")
+            .help("Write the generation report to this file instead of stdout"),
+    ]
+}
+
+/// `-v` (info), `-vv` (debug), `-vvv` (trace) and `--quiet` (errors only, no summary)
+fn verbosity_args() -> [Arg; 2] {
+    [
+        Arg::new("verbose")
+            .short('v')
+            .action(ArgAction::Count)
+            .conflicts_with("quiet")
+            .help("Log more details, repeat for debug (-vv) and trace (-vvv) messages"),
+        Arg::new("quiet")
+            .long("quiet")
+            .short('q')
+            .action(ArgAction::SetTrue)
+            .help("Only log errors and skip the summary"),
+    ]
+}
+
+/// Operation filters which override the ones of the config
+fn operation_filter_args() -> [Arg; 3] {
+    [
+        Arg::new("include-tags")
+            .long("include-tags")
+            .value_delimiter(',')
+            .help("Only generate operations with one of these tags (comma separated)"),
+        Arg::new("exclude-tags")
+            .long("exclude-tags")
+            .value_delimiter(',')
+            .help("Skip operations with one of these tags (comma separated)"),
+        Arg::new("methods")
+            .long("methods")
+            .value_delimiter(',')
+            .help("Only generate operations with one of these http methods, e.g. GET,POST"),
+    ]
+}
 
 pub fn cli() -> Command {
     Command::new("opage")
         .about("OpenAPI v3.1 client generator")
-        .arg(
-            Arg::new("output-dir")
-                .short('o')
-                .help("Client output location")
-                .required(true),
+        .after_help(
+            "Calling opage without a subcommand (opage -s spec -o output) is an alias for generate",
+        )
+        .subcommand_negates_reqs(true)
+        .args_conflicts_with_subcommands(true)
+        // Compatibility alias for generate
+        .arg(output_dir_arg())
+        .arg(spec_arg())
+        .arg(config_arg())
+        .arg(target_arg())
+        .arg(strict_arg())
+        .args(verbosity_args())
+        .args(report_args())
+        .args(operation_filter_args())
+        .subcommand(
+            Command::new("generate")
+                .about("Generate a client (or mock server) project")
+                .arg(output_dir_arg())
+                .arg(spec_arg())
+                .arg(config_arg())
+                .arg(target_arg())
+                .arg(strict_arg())
+                .args(verbosity_args())
+                .args(report_args())
+                .args(operation_filter_args()),
+        )
+        .subcommand(
+            Command::new("validate")
+                .about("Report spec features which are not supported by the generator")
+                .arg(spec_arg())
+                .arg(config_arg())
+                .args(operation_filter_args()),
+        )
+        .subcommand(
+            Command::new("list")
+                .visible_alias("list-operations")
+                .about("List the operations which are generated")
+                .arg(spec_arg())
+                .arg(config_arg())
+                .args(operation_filter_args()),
         )
-        .arg(
-            Arg::new("spec")
-                .short('s')
-                .help("Input OpenAPI spec")
-                .required(true),
+        .subcommand(
+            Command::new("ir")
+                .about("Print the parsed operations and models as json")
+                .arg(spec_arg())
+                .arg(config_arg())
+                .args(operation_filter_args()),
         )
-        .arg(
-            Arg::new("config")
-                .short('c')
-                .help("(json) Configuration with name mappings and ignores")
-                .required(false),
+        .subcommand(
+            Command::new("diff")
+                .about(
+                    "Show which files a generation would add, modify or remove in the output, \
+                    or compare the operations and models of two specs (opage diff old new)",
+                )
+                .arg(
+                    output_dir_arg()
+                        .required(false)
+                        .required_unless_present("old"),
+                )
+                .arg(spec_arg().required(false).required_unless_present("old"))
+                .arg(
+                    Arg::new("old")
+                        .help("Previous spec version")
+                        .requires("new")
+                        .conflicts_with_all(["output-dir", "spec"]),
+                )
+                .arg(Arg::new("new").help("Current spec version"))
+                .arg(config_arg())
+                .arg(target_arg())
+                .args(operation_filter_args()),
         )
 }
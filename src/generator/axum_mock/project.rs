@@ -0,0 +1,48 @@
+use std::{fs::File, io::Write, path::Path};
+
+use askama::Template;
+use log::info;
+
+use super::routes::generate_main_content;
+use crate::utils::{config::Config, output::OutputWriter, report::GenerationReport};
+
+#[derive(Template)]
+#[template(path = "axum_mock/cargo.toml.jinja", ext = "txt")]
+struct CargoTomlTemplate {
+    name: String,
+    version: String,
+}
+
+/// Mock server crate which answers every operation with its example response
+pub fn generate_project(output_dir: &str, config: &Config, spec: &oas3::Spec) -> GenerationReport {
+    let output = OutputWriter::new(output_dir);
+    let mut report = GenerationReport::default();
+
+    output
+        .write(
+            "src/main.rs",
+            &generate_main_content(spec, config, &mut report).expect("Failed to generate main.rs"),
+        )
+        .expect("Failed to write main.rs");
+    report.files = output.finish().expect("Failed to write manifest");
+
+    let output_cargo_file_path = format!("{}/Cargo.toml", output_dir);
+    let cargo_file_path = Path::new(&output_cargo_file_path);
+    if cargo_file_path.exists() {
+        info!("{:?} exists and will be skipped", output_cargo_file_path);
+        return report;
+    }
+
+    let cargo_content = CargoTomlTemplate {
+        name: config.project_metadata.name.clone(),
+        version: config.project_metadata.version.clone(),
+    }
+    .render()
+    .expect("Failed to generate Cargo.toml");
+
+    let mut cargo_file = File::create(output_cargo_file_path).expect("Failed to create Cargo.toml");
+    cargo_file
+        .write_all(cargo_content.as_bytes())
+        .expect("Failed to write Cargo.toml");
+    report
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use askama::Template;
+use log::{info, warn};
+use oas3::{
+    spec::{MediaType, Operation},
+    Spec,
+};
+
+use crate::{
+    generator::rust_reqwest_async::{
+        path::utils::is_path_parameter,
+        paths::{collect_operations, resolve_operation_id},
+        templates::raw_string_literal,
+    },
+    parser::component::example::get_media_type_example,
+    utils::{config::Config, report::GenerationReport},
+};
+
+struct RouteTemplate {
+    method: String,
+    handler_name: String,
+    status: u16,
+    content_type: Option<String>,
+    body: String,
+}
+
+struct PathRoutesTemplate {
+    path: String,
+    routes: Vec<RouteTemplate>,
+}
+
+#[derive(Template)]
+#[template(path = "axum_mock/main.rs.jinja", ext = "rs")]
+struct MockServerTemplate {
+    method_routers: Vec<String>,
+    routes: Vec<PathRoutesTemplate>,
+}
+
+/// main.rs of the mock server with one route per (not ignored) operation. Mocked and
+/// skipped operations are added to the report
+pub fn generate_main_content(
+    spec: &Spec,
+    config: &Config,
+    report: &mut GenerationReport,
+) -> Result<String, String> {
+    let mut path_routes: BTreeMap<&String, Vec<RouteTemplate>> = BTreeMap::new();
+
+    for (path, method, operation) in collect_operations(spec, config) {
+        if !is_axum_path(path) {
+            warn!(
+                "{} path parameters within a segment are not supported",
+                path
+            );
+            report.skip_operation(
+                &method,
+                path,
+                "path parameters within a segment are not supported",
+            );
+            continue;
+        }
+
+        if let Some(serde_json::Value::Bool(true)) = operation.extensions.get("serverstream") {
+            warn!(
+                "{} {} websocket operations are not mocked",
+                method.as_str(),
+                path
+            );
+            report.skip_operation(&method, path, "websocket operations are not mocked");
+            continue;
+        }
+
+        let operation_id = resolve_operation_id(config, &method, path, operation).unwrap_or(
+            config
+                .name_mapping
+                .operation_id_from_path(method.as_str(), path),
+        );
+        info!("Mocking {} {}", method.as_str(), path);
+        report.add_operation(&method, path);
+
+        path_routes.entry(path).or_default().push(generate_route(
+            spec,
+            &method,
+            &config.name_mapping.name_to_module_name(&operation_id),
+            operation,
+        ));
+    }
+
+    // Further methods of a path are chained on the method router of the first one
+    let mut method_routers = path_routes
+        .values()
+        .filter_map(|routes| routes.first())
+        .map(|route| route.method.clone())
+        .collect::<Vec<String>>();
+    method_routers.sort();
+    method_routers.dedup();
+
+    MockServerTemplate {
+        method_routers,
+        routes: path_routes
+            .into_iter()
+            .map(|(path, routes)| PathRoutesTemplate {
+                path: path.clone(),
+                routes,
+            })
+            .collect(),
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
+
+/// Axum only supports parameters which span a whole path segment
+pub fn is_axum_path(path: &str) -> bool {
+    path.split('/').all(|path_component| {
+        is_path_parameter(path_component)
+            || !(path_component.contains('{') || path_component.contains('}'))
+    })
+}
+
+/// Route answering with the first successful (or default) response of the operation
+fn generate_route(
+    spec: &Spec,
+    method: &reqwest::Method,
+    handler_name: &str,
+    operation: &Operation,
+) -> RouteTemplate {
+    let responses = operation.responses(spec);
+    let response = responses
+        .iter()
+        .find(|(status, _)| status.starts_with('2'))
+        .or(responses.get_key_value("default"))
+        .or(responses.iter().next());
+
+    let mut route = RouteTemplate {
+        method: method.as_str().to_lowercase(),
+        handler_name: handler_name.to_owned(),
+        status: 200,
+        content_type: None,
+        body: String::new(),
+    };
+
+    let (status, response) = match response {
+        Some(response) => response,
+        None => return route,
+    };
+
+    // Status ranges (2XX) and default are answered with the lowest status of the range
+    route.status = status
+        .parse::<u16>()
+        .unwrap_or(match status.chars().next() {
+            Some(range @ '1'..='5') => range.to_digit(10).unwrap_or(2) as u16 * 100,
+            _ => 200,
+        });
+
+    let content = response
+        .content
+        .get_key_value("application/json")
+        .or(response.content.iter().next());
+    if let Some((content_type, media_type)) = content {
+        route.body = raw_string_literal(&get_body(spec, content_type, media_type));
+        route.content_type = Some(content_type.clone());
+    }
+
+    route
+}
+
+fn get_body(spec: &Spec, content_type: &str, media_type: &MediaType) -> String {
+    let example = match content_type {
+        "application/octet-stream" => None,
+        _ => get_media_type_example(spec, media_type),
+    };
+
+    match (content_type, example) {
+        (_, None) => String::new(),
+        ("text/event-stream", Some(example)) => format!("data: {}\n\n", example),
+        (_, Some(serde_json::Value::String(example))) if content_type != "application/json" => {
+            example
+        }
+        (_, Some(example)) => example.to_string(),
+    }
+}
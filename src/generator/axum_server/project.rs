@@ -0,0 +1,67 @@
+use std::{collections::BTreeMap, path::Path};
+
+use log::info;
+
+use super::routes::generate_server_content;
+use crate::{
+    generator::rust_reqwest_async::{
+        cargo::generate_server_cargo_content, objects::write_object_database,
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{config::Config, output::OutputWriter, report::GenerationReport},
+};
+
+/// Server crate with the objects, an `Api` trait with a function per operation and a router
+/// which extracts the parameters and body of the requests and calls the trait
+pub fn generate_project(
+    output_dir: &str,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    spec: &oas3::Spec,
+) -> GenerationReport {
+    let output = OutputWriter::new(output_dir);
+    let mut report = GenerationReport::default();
+
+    // Operations add their inline objects, so objects are written last
+    let server_content = generate_server_content(spec, object_database, config, &mut report)
+        .expect("Failed to generate server.rs");
+    output
+        .write("src/server.rs", &server_content)
+        .expect("Failed to write server.rs");
+
+    // Requests aren't validated, so objects don't implement the validation module
+    let mut generator_config = config.generator.clone();
+    generator_config.validation = false;
+    write_object_database(
+        &output,
+        object_database,
+        &config.name_mapping,
+        &generator_config,
+        &BTreeMap::new(),
+    )
+    .expect("Write objects failed");
+
+    let mut lib_content = String::new();
+    if !object_database.is_empty() {
+        lib_content += "pub mod objects;\n";
+    }
+    lib_content += "pub mod server;\n";
+    output
+        .write("src/lib.rs", &lib_content)
+        .expect("Failed to write lib.rs");
+    report.objects = object_database.len();
+    report.files = output.finish().expect("Failed to write manifest");
+
+    // Cargo.toml is only written once, so changes of users are kept
+    let cargo_file_path = format!("{}/Cargo.toml", output_dir);
+    if Path::new(&cargo_file_path).exists() {
+        info!("{:?} exists and will be skipped", cargo_file_path);
+        return report;
+    }
+    std::fs::write(
+        &cargo_file_path,
+        generate_server_cargo_content(config).expect("Failed to generate Cargo.toml"),
+    )
+    .expect("Failed to write Cargo.toml");
+    report
+}
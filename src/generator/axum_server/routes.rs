@@ -0,0 +1,371 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use askama::Template;
+use oas3::Spec;
+
+use crate::{
+    generator::{
+        axum_mock::routes::is_axum_path,
+        media_type::TransferMediaType,
+        operation::{generate_operations, OperationDefinition},
+    },
+    parser::component::object_definition::types::{ModuleInfo, ObjectDatabase},
+    utils::{config::Config, report::GenerationReport},
+};
+
+struct QueryFieldTemplate {
+    name: String,
+    real_name: String,
+    type_name: String,
+}
+
+struct QueryStructTemplate {
+    name: String,
+    fields: Vec<QueryFieldTemplate>,
+}
+
+struct ResponseVariantTemplate {
+    name: String,
+    status: u16,
+    /// Type and content type of the body
+    body: Option<(String, String)>,
+    /// Body is serialized as json
+    json: bool,
+}
+
+struct ExtractorTemplate {
+    pattern: String,
+    type_name: String,
+}
+
+struct OperationTemplate {
+    /// Summary and route
+    doc_lines: Vec<String>,
+    path: String,
+    method: String,
+    function_name: String,
+    handler_name: String,
+    /// Arguments of the trait function
+    parameters: Vec<String>,
+    /// Extractors of the handler after the state
+    extractors: Vec<ExtractorTemplate>,
+    /// Variables passed to the trait function
+    arguments: Vec<String>,
+    query_struct: Option<QueryStructTemplate>,
+    response_enum_name: String,
+    response_variants: Vec<ResponseVariantTemplate>,
+    /// Objects of the signature
+    module_imports: Vec<ModuleInfo>,
+}
+
+struct PathRoutesTemplate {
+    path: String,
+    /// (method, handler name)
+    routes: Vec<(String, String)>,
+}
+
+#[derive(Template)]
+#[template(path = "axum_server/server.rs.jinja", ext = "rs")]
+struct ServerTemplate {
+    operations: Vec<OperationTemplate>,
+    module_imports: Vec<ModuleInfo>,
+    method_routers: Vec<String>,
+    routes: Vec<PathRoutesTemplate>,
+}
+
+impl ServerTemplate {
+    fn uses_path(&self) -> bool {
+        self.operations.iter().any(|operation| {
+            operation
+                .extractors
+                .iter()
+                .any(|extractor| extractor.pattern.starts_with("Path("))
+        })
+    }
+
+    fn uses_query(&self) -> bool {
+        self.operations
+            .iter()
+            .any(|operation| operation.query_struct.is_some())
+    }
+
+    fn uses_json(&self) -> bool {
+        self.operations.iter().any(|operation| {
+            operation
+                .extractors
+                .iter()
+                .any(|extractor| extractor.pattern.starts_with("Json("))
+                || operation
+                    .response_variants
+                    .iter()
+                    .any(|response_variant| response_variant.json)
+        })
+    }
+
+    fn uses_header(&self) -> bool {
+        self.operations.iter().any(|operation| {
+            operation
+                .response_variants
+                .iter()
+                .any(|response_variant| response_variant.body.is_some())
+        })
+    }
+}
+
+/// server.rs with the `Api` trait, request and response types per (not ignored) operation
+/// and the router which calls the trait. Generated and skipped operations are added to the
+/// report, inline objects to the object database
+pub fn generate_server_content(
+    spec: &Spec,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    report: &mut GenerationReport,
+) -> Result<String, String> {
+    let operations = generate_operations(spec, object_database, config, report, |operation| {
+        generate_operation(operation, config)
+    })?;
+
+    let mut module_imports = vec![];
+    for module_import in operations
+        .iter()
+        .flat_map(|operation| operation.module_imports.iter())
+    {
+        if !module_imports.contains(module_import) {
+            module_imports.push(module_import.clone());
+        }
+    }
+
+    let mut path_routes: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    for operation in &operations {
+        path_routes
+            .entry(operation.path.clone())
+            .or_default()
+            .push((operation.method.clone(), operation.handler_name.clone()));
+    }
+
+    // Further methods of a path are chained on the method router of the first one
+    let method_routers = path_routes
+        .values()
+        .filter_map(|routes| routes.first())
+        .map(|(method, _)| method.clone())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect();
+
+    ServerTemplate {
+        operations,
+        module_imports,
+        method_routers,
+        routes: path_routes
+            .into_iter()
+            .map(|(path, routes)| PathRoutesTemplate { path, routes })
+            .collect(),
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
+
+fn generate_operation(
+    operation_definition: &OperationDefinition,
+    config: &Config,
+) -> Result<OperationTemplate, String> {
+    let name_mapping = &config.name_mapping;
+    let path = &operation_definition.path;
+    if !is_axum_path(path) {
+        return Err("Path parameters within a segment are not supported".to_owned());
+    }
+
+    let definition_path = vec![path.clone()];
+    let function_name = operation_definition.function_name.clone();
+    let mut module_imports = vec![];
+    let mut add_module_import = |module: &Option<ModuleInfo>| {
+        if let Some(module) = module {
+            if !module_imports.contains(module) {
+                module_imports.push(module.clone());
+            }
+        }
+    };
+
+    let mut parameter_names = BTreeSet::from(["api".to_owned()]);
+    let mut add_parameter_name = |name: &str| match parameter_names.insert(name.to_owned()) {
+        true => Ok(()),
+        false => Err(format!("Parameter {} is used more than once", name)),
+    };
+
+    // Path parameters are extracted as tuple in the order of the path
+    let mut parameters = vec![];
+    let mut extractors = vec![];
+    let mut arguments = vec![];
+    let mut path_names = vec![];
+    let mut path_types = vec![];
+    for path_parameter in &operation_definition.path_parameters {
+        add_parameter_name(&path_parameter.name)?;
+        add_module_import(&path_parameter.module);
+        parameters.push(format!(
+            "{}: {}",
+            path_parameter.name, path_parameter.type_name
+        ));
+        arguments.push(path_parameter.name.clone());
+        path_names.push(path_parameter.name.clone());
+        path_types.push(path_parameter.type_name.clone());
+    }
+    match (path_names.as_slice(), path_types.as_slice()) {
+        ([], _) => (),
+        ([path_name], [path_type]) => extractors.push(ExtractorTemplate {
+            pattern: format!("Path({})", path_name),
+            type_name: format!("Path<{}>", path_type),
+        }),
+        _ => extractors.push(ExtractorTemplate {
+            pattern: format!("Path(({}))", path_names.join(", ")),
+            type_name: format!("Path<({})>", path_types.join(", ")),
+        }),
+    }
+
+    let query_struct = match operation_definition.query_parameters.is_empty() {
+        true => None,
+        false => {
+            add_parameter_name("query")?;
+            let mut fields = vec![];
+            for query_parameter in &operation_definition.query_parameters {
+                let property = &query_parameter.property;
+                if query_parameter.separator.is_some() {
+                    return Err(format!(
+                        "Delimited query parameter {} is not supported by axum_server",
+                        property.real_name
+                    ));
+                }
+                add_module_import(&property.module);
+                fields.push(QueryFieldTemplate {
+                    name: property.name.clone(),
+                    real_name: property.real_name.clone(),
+                    type_name: match property.required {
+                        true => property.type_name.clone(),
+                        false => format!("Option<{}>", property.type_name),
+                    },
+                });
+            }
+            let name = name_mapping.name_to_struct_name(
+                &definition_path,
+                &format!("{}QueryParameters", function_name),
+            );
+            parameters.push(format!("query: {}", name));
+            extractors.push(ExtractorTemplate {
+                pattern: "Query(query)".to_owned(),
+                type_name: format!("Query<{}>", name),
+            });
+            arguments.push("query".to_owned());
+            Some(QueryStructTemplate { name, fields })
+        }
+    };
+
+    // The body is consumed by the last extractor
+    if let Some(request_body) = &operation_definition.request_body {
+        add_parameter_name("body")?;
+        let (pattern, body_type) = match &request_body.media_type {
+            TransferMediaType::ApplicationJson(Some(type_definition)) => {
+                add_module_import(&type_definition.module);
+                ("Json(body)", type_definition.name.clone())
+            }
+            TransferMediaType::ApplicationJson(None) => {
+                ("Json(body)", "serde_json::Value".to_owned())
+            }
+            TransferMediaType::TextPlain => ("body", "String".to_owned()),
+            TransferMediaType::OctetStream => ("body", "axum::body::Bytes".to_owned()),
+            transfer_media_type => {
+                return Err(format!(
+                    "{} request bodies are not supported by axum_server",
+                    transfer_media_type.content_type()
+                ))
+            }
+        };
+        parameters.push(format!("body: {}", body_type));
+        extractors.push(ExtractorTemplate {
+            pattern: pattern.to_owned(),
+            type_name: match pattern.starts_with("Json(") {
+                true => format!("Json<{}>", body_type),
+                false => body_type,
+            },
+        });
+        arguments.push("body".to_owned());
+    }
+
+    let response_enum_name = name_mapping
+        .name_to_struct_name(&definition_path, &format!("{}ResponseType", function_name));
+    let mut response_enum_definition_path = definition_path.clone();
+    response_enum_definition_path.push(response_enum_name.clone());
+    let mut response_variants = vec![];
+    for response in &operation_definition.responses {
+        let (body, json) = match &response.body {
+            Some(body) => {
+                let (type_name, json) = match &body.media_type {
+                    TransferMediaType::ApplicationJson(Some(type_definition)) => {
+                        add_module_import(&type_definition.module);
+                        (type_definition.name.clone(), true)
+                    }
+                    TransferMediaType::ApplicationJson(None) => {
+                        ("serde_json::Value".to_owned(), true)
+                    }
+                    TransferMediaType::OctetStream => ("axum::body::Bytes".to_owned(), false),
+                    // Text, xml and event streams are sent as written by the server
+                    _ => ("String".to_owned(), false),
+                };
+                (Some((type_name, body.content_type.clone())), json)
+            }
+            None => (None, false),
+        };
+        response_variants.push(ResponseVariantTemplate {
+            name: name_mapping.name_to_variant_name(
+                &response_enum_definition_path,
+                &response.canonical_status_code,
+            ),
+            status: response.status,
+            body,
+            json,
+        });
+    }
+
+    // Generated types must not shadow the imported objects
+    for generated_name in query_struct
+        .iter()
+        .map(|query_struct| &query_struct.name)
+        .chain([&response_enum_name])
+    {
+        if module_imports
+            .iter()
+            .any(|module_import| &module_import.name == generated_name)
+        {
+            return Err(format!(
+                "{} is already the name of an object",
+                generated_name
+            ));
+        }
+    }
+
+    let method = operation_definition.method.as_str();
+    let mut doc_lines = vec![];
+    if let Some(summary) = &operation_definition.summary {
+        doc_lines.extend(
+            summary
+                .trim()
+                .lines()
+                .map(|line| line.trim_end().to_owned()),
+        );
+        doc_lines.push(String::new());
+    }
+    doc_lines.push(format!("{} {}", method, path));
+
+    Ok(OperationTemplate {
+        doc_lines,
+        path: path.clone(),
+        method: method.to_lowercase(),
+        handler_name: format!("handle_{}", function_name.trim_start_matches("r#")),
+        function_name,
+        parameters,
+        extractors,
+        arguments,
+        query_struct,
+        response_enum_name,
+        response_variants,
+        module_imports,
+    })
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use oas3::{spec::MediaType, Spec};
+
+use crate::parser::component::object_definition::types::TypeDefinition;
+
+/// Content types which can be sent as request body
+pub const REQUEST_CONTENT_TYPES: [&str; 4] = [
+    "application/json",
+    "application/xml",
+    "text/plain",
+    "application/octet-stream",
+];
+
+/// Content types which can be received as response
+pub const RESPONSE_CONTENT_TYPES: [&str; 6] = [
+    "application/json",
+    "application/xml",
+    "text/plain",
+    "application/octet-stream",
+    "text/event-stream",
+    "application/x-ndjson",
+];
+
+/// Content type without parameters like charset in lowercase, e.g. `Application/JSON; charset=utf-8`
+/// -> `application/json`
+pub fn normalize_content_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Supported content type which handles the content type of the spec. Configured mappings
+/// take precedence, `+json` suffixes (e.g. `application/problem+json`) are handled as json
+pub fn resolve_content_type(
+    content_type: &str,
+    content_type_mapping: &BTreeMap<String, String>,
+) -> String {
+    let content_type = normalize_content_type(content_type);
+    match content_type_mapping.get(&content_type) {
+        Some(mapped_content_type) => mapped_content_type.clone(),
+        None if content_type.ends_with("+json") => "application/json".to_owned(),
+        None => content_type,
+    }
+}
+
+/// Body representation of a supported content type, shared by all generator targets
+#[derive(Clone, Debug)]
+pub enum TransferMediaType {
+    ApplicationJson(Option<TypeDefinition>),
+    ApplicationXml(Option<TypeDefinition>),
+    TextPlain,
+    OctetStream,
+    EventStream(Option<TypeDefinition>),
+    /// Newline delimited json, one item per line
+    Ndjson(Option<TypeDefinition>),
+}
+
+impl TransferMediaType {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TransferMediaType::ApplicationJson(_) => "application/json",
+            TransferMediaType::ApplicationXml(_) => "application/xml",
+            TransferMediaType::TextPlain => "text/plain",
+            TransferMediaType::OctetStream => "application/octet-stream",
+            TransferMediaType::EventStream(_) => "text/event-stream",
+            TransferMediaType::Ndjson(_) => "application/x-ndjson",
+        }
+    }
+}
+
+/// Whether any content type of the body is handled as `content_type`, e.g.
+/// `application/xml; charset=utf-8` as `application/xml`
+pub fn has_content_type(
+    content: &BTreeMap<String, MediaType>,
+    content_type: &str,
+    content_type_mapping: &BTreeMap<String, String>,
+) -> bool {
+    content
+        .keys()
+        .any(|content_key| resolve_content_type(content_key, content_type_mapping) == content_type)
+}
+
+/// Whether any request or response body of the spec is xml
+pub fn uses_xml_content(spec: &Spec, content_type_mapping: &BTreeMap<String, String>) -> bool {
+    spec.operations().any(|(_, _, operation)| {
+        let request_xml = operation
+            .request_body
+            .as_ref()
+            .and_then(|request_body| request_body.resolve(spec).ok())
+            .is_some_and(|request_body| {
+                has_content_type(
+                    &request_body.content,
+                    "application/xml",
+                    content_type_mapping,
+                )
+            });
+        request_xml
+            || operation.responses(spec).values().any(|response| {
+                has_content_type(&response.content, "application/xml", content_type_mapping)
+            })
+    })
+}
+
+/// Whether any response body of the spec is newline delimited json
+pub fn uses_ndjson_content(spec: &Spec, content_type_mapping: &BTreeMap<String, String>) -> bool {
+    spec.operations().any(|(_, _, operation)| {
+        operation.responses(spec).values().any(|response| {
+            has_content_type(
+                &response.content,
+                "application/x-ndjson",
+                content_type_mapping,
+            )
+        })
+    })
+}
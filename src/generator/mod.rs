@@ -1 +1,7 @@
+pub mod axum_mock;
+pub mod axum_server;
+pub mod media_type;
+pub mod operation;
+pub mod python_httpx;
 pub mod rust_reqwest_async;
+pub mod typescript_fetch;
@@ -0,0 +1,377 @@
+use std::collections::BTreeMap;
+
+use log::{error, info, warn};
+use oas3::{spec::Operation, Spec};
+
+use crate::{
+    generator::{
+        media_type::TransferMediaType,
+        rust_reqwest_async::{
+            path::utils::{
+                generate_path_parameter_code, generate_query_parameter_code, generate_request_body,
+                generate_responses, is_path_parameter,
+            },
+            paths::{collect_operations, resolve_operation_id},
+        },
+    },
+    parser::{
+        component::object_definition::types::{ObjectDatabase, PropertyDefinition},
+        security::{get_operation_security, OperationSecurity},
+    },
+    utils::{
+        config::Config, extensions::collect_extensions, log::LogContext, report::GenerationReport,
+    },
+};
+
+/// Query parameter of an [`OperationDefinition`]
+#[derive(Clone, Debug)]
+pub struct QueryParameterDefinition {
+    pub property: PropertyDefinition,
+    pub is_array: bool,
+    /// Delimiter joining array items into a single value, None repeats the parameter per item
+    pub separator: Option<String>,
+}
+
+/// Body in the content type which is used of all the content types of the spec
+#[derive(Clone, Debug)]
+pub struct BodyDefinition {
+    /// Content type of the spec
+    pub content_type: String,
+    pub media_type: TransferMediaType,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResponseDefinition {
+    pub status: u16,
+    /// Reason phrase of the status (or its `status_code_mapping`), e.g. `Not Found`
+    pub canonical_status_code: String,
+    /// None for responses without content
+    pub body: Option<BodyDefinition>,
+}
+
+/// Literal part or parameter of a path template
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    Literal(String),
+    /// Property name of the path parameter
+    Parameter(String),
+}
+
+/// Language independent analysis of an operation for the targets which don't generate rust
+/// code. Types are the rust type names of the object database, inline request and response
+/// objects are added to the database
+#[derive(Clone, Debug)]
+pub struct OperationDefinition {
+    pub operation_id: String,
+    /// snake_case name, prefix of the inline objects
+    pub function_name: String,
+    pub method: reqwest::Method,
+    pub path: String,
+    pub summary: Option<String>,
+    /// In the order of the path template
+    pub path_parameters: Vec<PropertyDefinition>,
+    pub query_parameters: Vec<QueryParameterDefinition>,
+    pub request_body: Option<BodyDefinition>,
+    pub responses: Vec<ResponseDefinition>,
+    /// Security requirements, None if neither the operation nor the spec declares them
+    pub security: Option<OperationSecurity>,
+    /// `Content-Encoding` values (e.g. `gzip`, `br`) of the responses documented by the spec.
+    /// Decompression is enabled per client, so targets only document them
+    pub content_encodings: Vec<String>,
+    /// Configured `x-` extensions of the operation
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+impl OperationDefinition {
+    /// Segments of the path joined by `/`. Parameters within a segment are sent literally
+    pub fn path_segments(&self) -> Vec<PathSegment> {
+        let mut path_parameters = self.path_parameters.iter();
+        self.path
+            .split('/')
+            .map(|path_component| match is_path_parameter(path_component) {
+                true => match path_parameters.next() {
+                    Some(path_parameter) => PathSegment::Parameter(path_parameter.name.clone()),
+                    None => PathSegment::Literal(path_component.to_owned()),
+                },
+                false => PathSegment::Literal(path_component.to_owned()),
+            })
+            .collect()
+    }
+}
+
+/// Generates all (not ignored) operations with `generate_operation` from their definitions.
+/// Generated and skipped operations are added to the report, failed operations don't leave
+/// their inline objects in the database
+pub fn generate_operations<T>(
+    spec: &Spec,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    report: &mut GenerationReport,
+    generate_operation: impl Fn(&OperationDefinition) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    let operations = collect_operations(spec, config);
+
+    // Operations with the same function name would shadow each other
+    let operation_ids = operations
+        .iter()
+        .filter_map(|(path, method, operation)| {
+            resolve_operation_id(config, method, path, operation).map(|operation_id| {
+                let description = format!("{} ({} {})", operation_id, method.as_str(), path);
+                (operation_id, description)
+            })
+        })
+        .collect::<Vec<(String, String)>>();
+    let collisions = config
+        .name_mapping
+        .get_module_name_collisions(&operation_ids);
+    if !collisions.is_empty() {
+        return Err(collisions.join("\n"));
+    }
+
+    let mut generated_operations = vec![];
+    for (path, method, operation) in operations {
+        let checkpoint = object_database.checkpoint();
+        match generate_operation_definition(spec, object_database, config, &method, path, operation)
+            .and_then(|operation_definition| {
+                let _log_context =
+                    LogContext::enter(format!("operation {}", operation_definition.operation_id));
+                generate_operation(&operation_definition)
+            }) {
+            Ok(generated_operation) => {
+                info!("Generated {} {}", method.as_str(), path);
+                generated_operations.push(generated_operation);
+                report.add_operation(&method, path);
+            }
+            Err(err) => {
+                error!("{} {} skipped: {}", method.as_str(), path, err);
+                object_database.rollback(checkpoint);
+                report.skip_operation(&method, path, &err);
+            }
+        }
+    }
+    Ok(generated_operations)
+}
+
+pub fn generate_operation_definition(
+    spec: &Spec,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    method: &reqwest::Method,
+    path: &str,
+    operation: &Operation,
+) -> Result<OperationDefinition, String> {
+    let name_mapping = &config.name_mapping;
+    let operation_id = match resolve_operation_id(config, method, path, operation) {
+        Some(operation_id) => operation_id,
+        None => return Err("Operation has no id".to_owned()),
+    };
+    let _log_context = LogContext::enter(format!("operation {}", operation_id));
+
+    if let Some(serde_json::Value::Bool(true)) = operation.extensions.get("serverstream") {
+        return Err("Websocket operations are not supported by this target".to_owned());
+    }
+
+    let function_name = name_mapping.name_to_function_name(&operation_id);
+    let definition_path = vec![path.to_owned()];
+
+    let path_parameters = generate_path_parameter_code(
+        spec,
+        operation,
+        &definition_path,
+        name_mapping,
+        object_database,
+        &function_name,
+        path,
+    )?
+    .path_format_arguments;
+
+    let skip_request_body = config
+        .generator
+        .skip_request_body_methods
+        .iter()
+        .any(|skip_method| skip_method.eq_ignore_ascii_case(method.as_str()));
+    let request_body = match operation.request_body {
+        Some(_) if skip_request_body => {
+            warn!("Request body of {} requests is skipped", method.as_str());
+            None
+        }
+        Some(ref request_body) => {
+            let request_entity = generate_request_body(
+                spec,
+                object_database,
+                &definition_path,
+                name_mapping,
+                request_body,
+                &config.generator.content_type_mapping,
+                &function_name,
+            )?;
+            if request_entity.content.len() > 1 {
+                warn!("Multiple request body content types not supported yet");
+            }
+            match preferred_content(request_entity.content.into_iter().collect()) {
+                Some(BodyDefinition {
+                    media_type:
+                        TransferMediaType::ApplicationXml(_)
+                        | TransferMediaType::EventStream(_)
+                        | TransferMediaType::Ndjson(_),
+                    content_type,
+                }) => {
+                    return Err(format!(
+                        "{} request bodies are not supported by this target",
+                        content_type
+                    ))
+                }
+                request_body => request_body,
+            }
+        }
+        None => None,
+    };
+
+    let query_parameters_code = generate_query_parameter_code(
+        spec,
+        operation,
+        &definition_path,
+        name_mapping,
+        object_database,
+        &function_name,
+    )?;
+    let mut query_parameters = vec![];
+    for query_parameter in query_parameters_code.query_parameters() {
+        if query_parameter.has_fields() {
+            return Err(format!(
+                "Object query parameter {} is not supported by this target",
+                query_parameter.real_name
+            ));
+        }
+        let property = match query_parameters_code
+            .query_struct
+            .properties
+            .get(&query_parameter.name)
+        {
+            Some(property) => property.clone(),
+            None => {
+                return Err(format!(
+                    "Query parameter {} not found",
+                    query_parameter.name
+                ))
+            }
+        };
+        query_parameters.push(QueryParameterDefinition {
+            property,
+            is_array: query_parameter.is_array,
+            separator: query_parameter.separator,
+        });
+    }
+
+    let mut responses = vec![];
+    for (status, response_entity) in generate_responses(
+        spec,
+        object_database,
+        &definition_path,
+        name_mapping,
+        &operation.responses(spec),
+        &config.generator.content_type_mapping,
+        &function_name,
+    )? {
+        let status = status
+            .parse::<u16>()
+            .map_err(|err| format!("Invalid status code {} {}", status, err))?;
+        responses.push(ResponseDefinition {
+            status,
+            canonical_status_code: response_entity.canonical_status_code,
+            body: preferred_content(response_entity.content.into_iter().collect()),
+        });
+    }
+
+    Ok(OperationDefinition {
+        operation_id,
+        function_name,
+        method: method.clone(),
+        path: path.to_owned(),
+        summary: operation.summary.clone().or(operation.description.clone()),
+        path_parameters,
+        query_parameters,
+        request_body,
+        responses,
+        security: get_operation_security(spec, operation)?,
+        content_encodings: get_content_encodings(spec, operation),
+        extensions: collect_extensions(&config.generator, &operation.extensions),
+    })
+}
+
+/// Enum and const values of the `Content-Encoding` headers of all responses
+fn get_content_encodings(spec: &Spec, operation: &Operation) -> Vec<String> {
+    let mut content_encodings = vec![];
+    for response in operation.responses(spec).values() {
+        for (header_name, header) in &response.headers {
+            if !header_name.eq_ignore_ascii_case("content-encoding") {
+                continue;
+            }
+            let schema = match header
+                .resolve(spec)
+                .ok()
+                .and_then(|header| header.schema)
+                .and_then(|schema| schema.resolve(spec).ok())
+            {
+                Some(schema) => schema,
+                None => continue,
+            };
+            for content_encoding in schema
+                .enum_values
+                .iter()
+                .chain(schema.const_value.iter())
+                .filter_map(|value| value.as_str())
+            {
+                if !content_encodings
+                    .iter()
+                    .any(|known| known == content_encoding)
+                {
+                    content_encodings.push(content_encoding.to_owned());
+                }
+            }
+        }
+    }
+    content_encodings
+}
+
+/// Json content, or the first content type
+fn preferred_content(content: Vec<(String, TransferMediaType)>) -> Option<BodyDefinition> {
+    let json_index = content
+        .iter()
+        .position(|(_, media_type)| matches!(media_type, TransferMediaType::ApplicationJson(_)));
+    content
+        .into_iter()
+        .nth(json_index.unwrap_or(0))
+        .map(|(content_type, media_type)| BodyDefinition {
+            content_type,
+            media_type,
+        })
+}
+
+/// Outer type and type arguments of a generic rust type name of the object database,
+/// `HashMap<String, Vec<Pet>>` -> (`HashMap`, [`String`, `Vec<Pet>`])
+pub fn split_generic_type(type_name: &str) -> Option<(&str, Vec<&str>)> {
+    let (outer_type, type_arguments) = type_name.split_once('<')?;
+    let type_arguments = type_arguments.strip_suffix('>')?;
+
+    // Arguments are split at top level commas
+    let mut arguments = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, character) in type_arguments.char_indices() {
+        match character {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(type_arguments[start..index].trim());
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    arguments.push(type_arguments[start..].trim());
+    Some((
+        outer_type.rsplit("::").next().unwrap_or(outer_type),
+        arguments,
+    ))
+}
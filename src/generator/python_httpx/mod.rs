@@ -0,0 +1,4 @@
+pub mod models;
+pub mod names;
+pub mod paths;
+pub mod project;
@@ -0,0 +1,218 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use askama::Template;
+
+use super::names::{
+    python_class_name, python_identifier, python_literal, python_type, referenced_objects,
+};
+use crate::{
+    parser::component::object_definition::types::{
+        ObjectDatabase, ObjectDefinition, StructDefinition,
+    },
+    utils::config::Config,
+};
+
+/// Attributes of `pydantic.BaseModel` which fields would shadow
+const BASE_MODEL_ATTRIBUTES: [&str; 13] = [
+    "construct",
+    "copy",
+    "dict",
+    "from_orm",
+    "json",
+    "model_config",
+    "model_fields",
+    "parse_file",
+    "parse_obj",
+    "parse_raw",
+    "schema",
+    "schema_json",
+    "validate",
+];
+
+struct FieldTemplate {
+    name: String,
+    type_name: String,
+    /// Json name as python string literal, if it differs from the field name
+    alias: Option<String>,
+    required: bool,
+}
+
+impl FieldTemplate {
+    /// `= ...` part of the field definition
+    fn default_code(&self) -> String {
+        match (self.required, &self.alias) {
+            (true, None) => String::new(),
+            (true, Some(alias)) => format!(" = pydantic.Field(alias={})", alias),
+            (false, None) => " = None".to_owned(),
+            (false, Some(alias)) => format!(" = pydantic.Field(default=None, alias={})", alias),
+        }
+    }
+}
+
+struct ModelTemplate {
+    name: String,
+    fields: Vec<FieldTemplate>,
+}
+
+struct TypeAliasTemplate {
+    name: String,
+    type_name: String,
+}
+
+#[derive(Template)]
+#[template(path = "python_httpx/models.py.jinja", ext = "txt")]
+struct ModelsTemplate {
+    models: Vec<ModelTemplate>,
+    type_aliases: Vec<TypeAliasTemplate>,
+    /// pydantic `extra` setting of all models
+    extra: String,
+}
+
+/// models.py with a pydantic model per struct and type aliases for enums (unions), primitives
+/// and consts (literals) of the object database
+pub fn generate_models_content(
+    object_database: &ObjectDatabase,
+    config: &Config,
+) -> Result<String, String> {
+    let mut models = vec![];
+    let mut type_aliases = BTreeMap::new();
+    // Type names of aliases which reference other objects, to define them after their targets
+    let mut alias_references = BTreeMap::new();
+
+    let mut object_definitions = vec![];
+    for object_definition in object_database.values() {
+        object_definitions.push(object_definition);
+        if let ObjectDefinition::Struct(struct_definition) = object_definition {
+            object_definitions.extend(
+                struct_definition
+                    .local_objects
+                    .values()
+                    .map(|local_object| local_object.as_ref()),
+            );
+        }
+    }
+
+    for object_definition in object_definitions {
+        let (name, type_names) = match object_definition {
+            ObjectDefinition::Struct(struct_definition) => {
+                models.push(generate_model(struct_definition));
+                continue;
+            }
+            ObjectDefinition::Enum(enum_definition) => (
+                &enum_definition.name,
+                enum_definition
+                    .values
+                    .values()
+                    .map(|enum_value| enum_value.value_type.name.clone())
+                    .collect::<Vec<String>>(),
+            ),
+            ObjectDefinition::Primitive(primitive_definition) => (
+                &primitive_definition.name,
+                vec![primitive_definition.primitive_type.name.clone()],
+            ),
+            ObjectDefinition::Const(const_definition) => {
+                let type_name = match python_literal(&const_definition.value) {
+                    Some(literal) => format!("typing.Literal[{}]", literal),
+                    None => "typing.Any".to_owned(),
+                };
+                type_aliases.insert(const_definition.name.clone(), type_name);
+                continue;
+            }
+        };
+
+        let mut python_types = vec![];
+        for type_name in &type_names {
+            let python_type = python_type(type_name, "");
+            if !python_types.contains(&python_type) {
+                python_types.push(python_type);
+            }
+        }
+        let type_name = match python_types.as_slice() {
+            [single_type] => single_type.clone(),
+            _ => format!("typing.Union[{}]", python_types.join(", ")),
+        };
+        type_aliases.insert(name.clone(), type_name);
+        alias_references.insert(
+            name.clone(),
+            type_names
+                .iter()
+                .flat_map(|type_name| referenced_objects(type_name))
+                .collect::<Vec<String>>(),
+        );
+    }
+
+    // Aliases are evaluated on import. Classes are defined first and aliases after the aliases
+    // they reference
+    let mut ordered_aliases = vec![];
+    let mut visited = BTreeSet::new();
+    for name in type_aliases.keys() {
+        order_alias(name, &alias_references, &mut visited, &mut ordered_aliases);
+    }
+
+    ModelsTemplate {
+        models,
+        type_aliases: ordered_aliases
+            .into_iter()
+            .filter_map(|name| {
+                type_aliases.get(&name).map(|type_name| TypeAliasTemplate {
+                    name: python_class_name(&name),
+                    type_name: type_name.clone(),
+                })
+            })
+            .collect(),
+        extra: match config.generator.deny_unknown_fields {
+            true => "forbid".to_owned(),
+            false => "ignore".to_owned(),
+        },
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
+
+fn generate_model(struct_definition: &StructDefinition) -> ModelTemplate {
+    ModelTemplate {
+        name: python_class_name(&struct_definition.name),
+        fields: struct_definition
+            .properties
+            .values()
+            .map(|property| {
+                let mut name = python_identifier(&property.name);
+                if BASE_MODEL_ATTRIBUTES.contains(&name.as_str()) {
+                    name.push('_');
+                }
+                FieldTemplate {
+                    alias: match property.real_name == name {
+                        true => None,
+                        false => {
+                            python_literal(&serde_json::Value::String(property.real_name.clone()))
+                        }
+                    },
+                    name,
+                    type_name: match property.required {
+                        true => python_type(&property.type_name, ""),
+                        false => {
+                            format!("typing.Optional[{}]", python_type(&property.type_name, ""))
+                        }
+                    },
+                    required: property.required,
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Depth first order of the aliases by their references
+fn order_alias(
+    name: &str,
+    alias_references: &BTreeMap<String, Vec<String>>,
+    visited: &mut BTreeSet<String>,
+    ordered_aliases: &mut Vec<String>,
+) {
+    if !visited.insert(name.to_owned()) {
+        return;
+    }
+    for referenced_name in alias_references.get(name).into_iter().flatten() {
+        order_alias(referenced_name, alias_references, visited, ordered_aliases);
+    }
+    ordered_aliases.push(name.to_owned());
+}
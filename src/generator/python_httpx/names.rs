@@ -0,0 +1,97 @@
+use crate::generator::operation::split_generic_type;
+
+/// Python keywords which can't be used as names
+const PYTHON_KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Python name of a generated (rust) identifier. Raw identifiers lose their `r#` prefix, names
+/// with a leading underscore (private in pydantic) get a `field` prefix and keywords get a `_`
+/// suffix
+pub fn python_identifier(name: &str) -> String {
+    let name = name.trim_start_matches("r#");
+    let name = match name.starts_with('_') {
+        true => format!("field{}", name),
+        false => name.to_owned(),
+    };
+    match PYTHON_KEYWORDS.contains(&name.as_str()) {
+        true => format!("{}_", name),
+        false => name,
+    }
+}
+
+/// Python name of a generated struct name, names with a leading underscore get a `Model` prefix
+pub fn python_class_name(name: &str) -> String {
+    match name.strip_prefix('_') {
+        Some(name) => python_identifier(&format!("Model{}", name.trim_start_matches('_'))),
+        None => python_identifier(name),
+    }
+}
+
+/// Python type annotation of a generated rust type name. Objects are prefixed with
+/// `models_prefix`, e.g. `models.` outside of the models module
+pub fn python_type(type_name: &str, models_prefix: &str) -> String {
+    let type_name = type_name.trim();
+    if let Some((outer_type, inner_types)) = split_generic_type(type_name) {
+        let inner_types = inner_types
+            .into_iter()
+            .map(|inner_type| python_type(inner_type, models_prefix))
+            .collect::<Vec<String>>();
+        return match (outer_type, inner_types.as_slice()) {
+            ("Box", [inner_type]) => inner_type.clone(),
+            ("Vec", [item_type]) => format!("list[{}]", item_type),
+            ("Option", [inner_type]) => format!("typing.Optional[{}]", inner_type),
+            ("HashMap" | "BTreeMap", [key_type, value_type]) => {
+                format!("dict[{}, {}]", key_type, value_type)
+            }
+            _ => "typing.Any".to_owned(),
+        };
+    }
+
+    match primitive_python_type(type_name) {
+        Some(primitive_type) => primitive_type.to_owned(),
+        None => format!("{}{}", models_prefix, python_class_name(type_name)),
+    }
+}
+
+fn primitive_python_type(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "bool" => Some("bool"),
+        "String" | "str" | "&str" => Some("str"),
+        "f32" | "f64" => Some("float"),
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "usize" => Some("int"),
+        "serde_json::Value" => Some("typing.Any"),
+        _ => None,
+    }
+}
+
+/// Object names referenced by a generated rust type name
+pub fn referenced_objects(type_name: &str) -> Vec<String> {
+    let type_name = type_name.trim();
+    match split_generic_type(type_name) {
+        Some((_, inner_types)) => inner_types
+            .into_iter()
+            .flat_map(referenced_objects)
+            .collect(),
+        None => match primitive_python_type(type_name) {
+            Some(_) => vec![],
+            None => vec![type_name.to_owned()],
+        },
+    }
+}
+
+/// Python literal of a json value, None for arrays and objects
+pub fn python_literal(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => Some("None".to_owned()),
+        serde_json::Value::Bool(true) => Some("True".to_owned()),
+        serde_json::Value::Bool(false) => Some("False".to_owned()),
+        serde_json::Value::Number(number) => Some(number.to_string()),
+        // Json string escapes are valid python string escapes
+        serde_json::Value::String(_) => Some(value.to_string()),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => None,
+    }
+}
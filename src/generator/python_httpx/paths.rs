@@ -0,0 +1,242 @@
+use std::collections::BTreeSet;
+
+use askama::Template;
+use oas3::Spec;
+
+use super::names::{python_identifier, python_literal, python_type};
+use crate::{
+    generator::{
+        media_type::TransferMediaType,
+        operation::{generate_operations, OperationDefinition, PathSegment},
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{config::Config, report::GenerationReport},
+};
+
+const MODELS_PREFIX: &str = "models.";
+
+struct QueryParameterTemplate {
+    /// Query name as python string literal
+    real_name: String,
+    name: String,
+    required: bool,
+    is_array: bool,
+    /// Delimiter (python string literal) joining array items into a single value, None
+    /// repeats the parameter per item
+    separator: Option<String>,
+}
+
+impl QueryParameterTemplate {
+    /// Optional parameters are only sent if they are set
+    fn indent(&self) -> &'static str {
+        match self.required {
+            true => "",
+            false => "    ",
+        }
+    }
+}
+
+struct ResponseTemplate {
+    status: u16,
+    body_code: String,
+}
+
+struct OperationTemplate {
+    function_name: String,
+    docstring: String,
+    method: String,
+    path_code: String,
+    parameters: Vec<String>,
+    keyword_parameters: Vec<String>,
+    query_parameters: Vec<QueryParameterTemplate>,
+    /// Keyword arguments of the request which send the body
+    body_arguments: Vec<String>,
+    responses: Vec<ResponseTemplate>,
+    body_type: String,
+}
+
+#[derive(Template)]
+#[template(path = "python_httpx/paths.py.jinja", ext = "txt")]
+struct PathsTemplate {
+    operations: Vec<OperationTemplate>,
+}
+
+/// paths.py with an async function per (not ignored) operation. Generated and skipped
+/// operations are added to the report, inline objects to the object database
+pub fn generate_paths_content(
+    spec: &Spec,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    report: &mut GenerationReport,
+) -> Result<String, String> {
+    PathsTemplate {
+        operations: generate_operations(spec, object_database, config, report, generate_operation)?,
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
+
+fn generate_operation(
+    operation_definition: &OperationDefinition,
+) -> Result<OperationTemplate, String> {
+    let mut parameter_names = BTreeSet::from(["client".to_owned()]);
+    let mut add_parameter_name = |name: &str| match parameter_names.insert(name.to_owned()) {
+        true => Ok(()),
+        false => Err(format!("Parameter {} is used more than once", name)),
+    };
+
+    // Path parameters are positional, followed by the body
+    let mut parameters = vec![];
+    for path_parameter in &operation_definition.path_parameters {
+        let name = python_identifier(&path_parameter.name);
+        add_parameter_name(&name)?;
+        parameters.push(format!(
+            "{}: {}",
+            name,
+            python_type(&path_parameter.type_name, MODELS_PREFIX)
+        ));
+    }
+    let path_code = get_path_code(operation_definition);
+
+    let mut body_arguments = vec![];
+    if let Some(request_body) = &operation_definition.request_body {
+        add_parameter_name("body")?;
+        let (body_type, body_argument) = match &request_body.media_type {
+            TransferMediaType::ApplicationJson(Some(type_definition)) => {
+                let body_type = python_type(&type_definition.name, MODELS_PREFIX);
+                let body_argument = format!(
+                    "json=pydantic.TypeAdapter({}).dump_python(body, mode=\"json\", by_alias=True, exclude_none=True)",
+                    body_type
+                );
+                (body_type, body_argument)
+            }
+            TransferMediaType::ApplicationJson(None) => {
+                ("typing.Any".to_owned(), "json=body".to_owned())
+            }
+            TransferMediaType::TextPlain => ("str".to_owned(), "content=body".to_owned()),
+            TransferMediaType::OctetStream => ("bytes".to_owned(), "content=body".to_owned()),
+            transfer_media_type => {
+                return Err(format!(
+                    "{} request bodies are not supported by python_httpx",
+                    transfer_media_type.content_type()
+                ))
+            }
+        };
+        parameters.push(format!("body: {}", body_type));
+        body_arguments.push(body_argument);
+        body_arguments.push(format!(
+            "headers={{\"Content-Type\": {}}}",
+            python_string(&request_body.content_type)
+        ));
+    }
+
+    // Query parameters are keyword only
+    let mut keyword_parameters = vec![];
+    let mut query_parameters = vec![];
+    for query_parameter in &operation_definition.query_parameters {
+        let property = &query_parameter.property;
+        let name = python_identifier(&property.name);
+        add_parameter_name(&name)?;
+        let type_name = python_type(&property.type_name, MODELS_PREFIX);
+        keyword_parameters.push(match property.required {
+            true => format!("{}: {}", name, type_name),
+            false => format!("{}: typing.Optional[{}] = None", name, type_name),
+        });
+        query_parameters.push(QueryParameterTemplate {
+            real_name: python_string(&property.real_name),
+            name,
+            required: property.required,
+            is_array: query_parameter.is_array,
+            separator: query_parameter.separator.as_deref().map(python_string),
+        });
+    }
+
+    let mut responses = vec![];
+    let mut body_types = vec![];
+    for response in &operation_definition.responses {
+        let (body_type, body_code) = match response.body.as_ref().map(|body| &body.media_type) {
+            Some(TransferMediaType::ApplicationJson(Some(type_definition))) => {
+                let body_type = python_type(&type_definition.name, MODELS_PREFIX);
+                let body_code = format!(
+                    "pydantic.TypeAdapter({}).validate_python(_response.json())",
+                    body_type
+                );
+                (body_type, body_code)
+            }
+            Some(TransferMediaType::ApplicationJson(None)) => {
+                ("typing.Any".to_owned(), "_response.json()".to_owned())
+            }
+            Some(TransferMediaType::TextPlain) => ("str".to_owned(), "_response.text".to_owned()),
+            Some(TransferMediaType::OctetStream) => {
+                ("bytes".to_owned(), "_response.content".to_owned())
+            }
+            // Xml and event streams are read from the httpx response
+            _ => continue,
+        };
+        if !body_types.contains(&body_type) {
+            body_types.push(body_type);
+        }
+        responses.push(ResponseTemplate {
+            status: response.status,
+            body_code,
+        });
+    }
+    body_types.push("None".to_owned());
+
+    let method = operation_definition.method.as_str();
+    let mut docstring = format!("{} {}", method, operation_definition.path);
+    if let Some(summary) = &operation_definition.summary {
+        docstring = format!(
+            "{}\n\n    {}",
+            summary.trim().replace('\n', "\n    "),
+            docstring
+        );
+    }
+
+    Ok(OperationTemplate {
+        function_name: python_identifier(&operation_definition.function_name),
+        docstring: docstring
+            .replace('\\', "\\\\")
+            .replace("\"\"\"", "\\\"\\\"\\\""),
+        method: method.to_owned(),
+        path_code,
+        parameters,
+        keyword_parameters,
+        query_parameters,
+        body_arguments,
+        responses,
+        body_type: match body_types.as_slice() {
+            [single_type] => single_type.clone(),
+            _ => format!("typing.Union[{}]", body_types.join(", ")),
+        },
+    })
+}
+
+/// Python string (or f-string if there are path parameters) of the path
+fn get_path_code(operation_definition: &OperationDefinition) -> String {
+    if operation_definition.path_parameters.is_empty() {
+        return python_string(&operation_definition.path);
+    }
+
+    let path_format_string = operation_definition
+        .path_segments()
+        .into_iter()
+        .map(|path_segment| match path_segment {
+            PathSegment::Parameter(name) => {
+                format!("{{_encode_path({})}}", python_identifier(&name))
+            }
+            PathSegment::Literal(path_component) => {
+                let path_component = python_string(&path_component);
+                path_component[1..path_component.len() - 1]
+                    .replace('{', "{{")
+                    .replace('}', "}}")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("/");
+    format!("f\"{}\"", path_format_string)
+}
+
+fn python_string(value: &str) -> String {
+    python_literal(&serde_json::Value::String(value.to_owned())).unwrap_or_default()
+}
@@ -0,0 +1,81 @@
+use std::{fs::File, io::Write, path::Path};
+
+use askama::Template;
+use log::info;
+
+use super::{
+    models::generate_models_content, names::python_identifier, paths::generate_paths_content,
+};
+use crate::{
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{config::Config, output::OutputWriter, report::GenerationReport},
+};
+
+#[derive(Template)]
+#[template(path = "python_httpx/pyproject.toml.jinja", ext = "txt")]
+struct PyprojectTemplate {
+    package_name: String,
+    version: String,
+}
+
+/// Python package with pydantic models and async httpx functions per operation
+pub fn generate_project(
+    output_dir: &str,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    spec: &oas3::Spec,
+) -> GenerationReport {
+    let output = OutputWriter::new(output_dir);
+    let mut report = GenerationReport::default();
+    // Projects without name are named after the spec
+    let project_name = match config.project_metadata.name.is_empty() {
+        true => spec.info.title.clone(),
+        false => config.project_metadata.name.clone(),
+    };
+    let package_name = python_identifier(&config.name_mapping.name_to_module_name(&project_name));
+
+    // Operations add their inline objects, so models are generated last
+    let paths_content = generate_paths_content(spec, object_database, config, &mut report)
+        .expect("Failed to generate paths.py");
+    output
+        .write(&format!("{}/paths.py", package_name), &paths_content)
+        .expect("Failed to write paths.py");
+    output
+        .write(
+            &format!("{}/models.py", package_name),
+            &generate_models_content(object_database, config)
+                .expect("Failed to generate models.py"),
+        )
+        .expect("Failed to write models.py");
+    output
+        .write(
+            &format!("{}/__init__.py", package_name),
+            "# Generated by opage, changes are overwritten\nfrom . import models, paths\n",
+        )
+        .expect("Failed to write __init__.py");
+    report.objects = object_database.len();
+    report.files = output.finish().expect("Failed to write manifest");
+
+    let output_pyproject_file_path = format!("{}/pyproject.toml", output_dir);
+    if Path::new(&output_pyproject_file_path).exists() {
+        info!(
+            "{:?} exists and will be skipped",
+            output_pyproject_file_path
+        );
+        return report;
+    }
+
+    let pyproject_content = PyprojectTemplate {
+        package_name,
+        version: config.project_metadata.version.clone(),
+    }
+    .render()
+    .expect("Failed to generate pyproject.toml");
+
+    let mut pyproject_file =
+        File::create(output_pyproject_file_path).expect("Failed to create pyproject.toml");
+    pyproject_file
+        .write_all(pyproject_content.as_bytes())
+        .expect("Failed to write pyproject.toml");
+    report
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+
+use askama::Template;
+use log::warn;
+use oas3::{spec::SecurityScheme, Spec};
+
+use super::readme::OperationSummary;
+use crate::{parser::security::get_security_schemes, utils::name_mapping::NameMapping};
+
+/// Variant of `Auth` with the credentials of a security scheme
+struct AuthVariant {
+    scheme_name: String,
+    description: String,
+    name: String,
+    /// Tuple or struct fields including the brackets
+    fields: String,
+    pattern: String,
+    /// Expression which adds the credentials to `request_builder`
+    apply: String,
+    /// Struct with the credentials of the scheme alone
+    credentials_name: String,
+    /// Tuple or struct fields of the credentials struct including the brackets
+    credentials_fields: String,
+    /// Pattern which binds the fields of `self` like `pattern`
+    credentials_pattern: String,
+    /// Fields of example credentials including the brackets, for generated tests
+    example_fields: String,
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/auth.rs.jinja", ext = "rs")]
+struct AuthTemplate {
+    variants: Vec<AuthVariant>,
+}
+
+/// Variant of a security scheme, Err with the reason if the scheme has no credentials which
+/// are sent with the request
+fn get_auth_variant(
+    scheme_name: &str,
+    security_scheme: &SecurityScheme,
+    name_mapping: &NameMapping,
+) -> Result<AuthVariant, String> {
+    let name = name_mapping.name_to_struct_name(&vec![], scheme_name);
+    let token_variant = |description: String, apply: String| AuthVariant {
+        scheme_name: scheme_name.to_owned(),
+        description,
+        name: name.clone(),
+        fields: "(String)".to_owned(),
+        pattern: format!("Auth::{}(credentials)", name),
+        apply,
+        credentials_name: credentials_type_name(&name),
+        credentials_fields: "(pub String);".to_owned(),
+        credentials_pattern: "Self(credentials)".to_owned(),
+        example_fields: "(\"token\".to_owned())".to_owned(),
+    };
+
+    let auth_variant = match security_scheme {
+        SecurityScheme::Http { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => {
+            AuthVariant {
+                scheme_name: scheme_name.to_owned(),
+                description: "http basic authentication".to_owned(),
+                name: name.clone(),
+                fields: " {\n        username: String,\n        password: Option<String>,\n    }"
+                    .to_owned(),
+                pattern: format!("Auth::{} {{ username, password }}", name),
+                apply: "request_builder.basic_auth(username, password.as_ref())".to_owned(),
+                credentials_name: credentials_type_name(&name),
+                credentials_fields:
+                    " {\n    pub username: String,\n    pub password: Option<String>,\n}".to_owned(),
+                credentials_pattern: "Self { username, password }".to_owned(),
+                example_fields:
+                    " { username: \"user\".to_owned(), password: Some(\"password\".to_owned()) }"
+                        .to_owned(),
+            }
+        }
+        SecurityScheme::Http { scheme, .. } if scheme.eq_ignore_ascii_case("bearer") => {
+            token_variant(
+                "http bearer token".to_owned(),
+                "request_builder.bearer_auth(credentials)".to_owned(),
+            )
+        }
+        SecurityScheme::Http { scheme, .. } => token_variant(
+            format!("credentials of the http `{}` scheme", scheme),
+            format!(
+                "request_builder.header(\"authorization\", format!(\"{} {{}}\", credentials))",
+                scheme
+            ),
+        ),
+        SecurityScheme::ApiKey { name, location, .. } => match location.as_str() {
+            "header" => token_variant(
+                format!("api key in the `{}` header", name),
+                format!("request_builder.header({:?}, credentials)", name),
+            ),
+            "query" => token_variant(
+                format!("api key in the `{}` query parameter", name),
+                format!("request_builder.query(&[({:?}, credentials)])", name),
+            ),
+            "cookie" => token_variant(
+                format!("api key in the `{}` cookie", name),
+                format!(
+                    "request_builder.header(\"cookie\", format!(\"{}={{}}\", credentials))",
+                    name
+                ),
+            ),
+            _ => return Err(format!("unknown api key location {}", location)),
+        },
+        SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIdConnect { .. } => token_variant(
+            "access token which is sent as bearer token".to_owned(),
+            "request_builder.bearer_auth(credentials)".to_owned(),
+        ),
+        SecurityScheme::MutualTls { .. } => {
+            return Err("client certificates are set on the reqwest::Client".to_owned())
+        }
+    };
+    Ok(auth_variant)
+}
+
+/// Struct with the credentials of the scheme of the `Auth` variant
+pub fn credentials_type_name(variant_name: &str) -> String {
+    format!("{}Credentials", variant_name)
+}
+
+fn get_auth_variants(spec: &Spec, name_mapping: &NameMapping) -> Result<Vec<AuthVariant>, String> {
+    Ok(get_security_schemes(spec)?
+        .iter()
+        .filter_map(|(scheme_name, security_scheme)| {
+            get_auth_variant(scheme_name, security_scheme, name_mapping).ok()
+        })
+        .collect())
+}
+
+/// `Auth` variant names by the name of their security scheme. Schemes without credentials
+/// of the request (mutual TLS) have no variant
+pub fn get_auth_variant_names(
+    spec: &Spec,
+    name_mapping: &NameMapping,
+) -> Result<BTreeMap<String, String>, String> {
+    Ok(get_auth_variants(spec, name_mapping)?
+        .into_iter()
+        .map(|auth_variant| (auth_variant.scheme_name, auth_variant.name))
+        .collect())
+}
+
+/// Fields of example credentials of the `Auth` variants and credentials structs by the name
+/// of their security scheme, e.g. `("token".to_owned())`
+pub fn get_credentials_examples(
+    spec: &Spec,
+    name_mapping: &NameMapping,
+) -> Result<BTreeMap<String, String>, String> {
+    Ok(get_auth_variants(spec, name_mapping)?
+        .into_iter()
+        .map(|auth_variant| (auth_variant.scheme_name, auth_variant.example_fields))
+        .collect())
+}
+
+/// auth.rs with an `Auth` variant per security scheme of the spec
+pub fn generate_auth_content(spec: &Spec, name_mapping: &NameMapping) -> Result<String, String> {
+    let mut variants = vec![];
+    for (scheme_name, security_scheme) in get_security_schemes(spec)? {
+        match get_auth_variant(&scheme_name, &security_scheme, name_mapping) {
+            Ok(auth_variant) => variants.push(auth_variant),
+            Err(err) => warn!("Security scheme {} skipped, {}", scheme_name, err),
+        }
+    }
+    AuthTemplate { variants }
+        .render()
+        .map_err(|err| err.to_string())
+}
+
+/// An operation has an `auth` parameter, which is defined by the auth module
+pub fn uses_auth(operations: &[OperationSummary]) -> bool {
+    operations.iter().any(|operation| {
+        operation
+            .functions
+            .iter()
+            .any(|function| function.signature.contains("crate::auth::"))
+    })
+}
@@ -0,0 +1,24 @@
+use askama::Template;
+
+use crate::utils::config::AwsSigV4Config;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/aws_sigv4.rs.jinja", ext = "rs")]
+struct AwsSigV4Template {
+    region: String,
+    service: String,
+}
+
+impl AwsSigV4Template {
+    fn string_literal(&self, value: &str) -> String {
+        format!("{:?}", value)
+    }
+}
+
+pub fn generate_aws_sigv4_content(aws_sigv4: &AwsSigV4Config) -> Result<String, String> {
+    let template = AwsSigV4Template {
+        region: aws_sigv4.region.clone(),
+        service: aws_sigv4.service.clone(),
+    };
+    template.render().map_err(|e| e.to_string())
+}
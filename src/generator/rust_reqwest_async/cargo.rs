@@ -1,18 +1,553 @@
+use std::collections::BTreeSet;
+
 use askama::Template;
 
-use crate::utils::config::ProjectMetadata;
+use crate::utils::{
+    config::{Config, DependencyConfig},
+    name_mapping::TypeOverride,
+    template_override::apply_template_override,
+};
+
+/// Dependency of a generated Cargo.toml
+pub struct CargoDependency {
+    pub name: String,
+    pub version: Option<String>,
+    pub path: Option<String>,
+    pub default_features: bool,
+    pub features: Vec<String>,
+    /// Only enabled by a feature of the generated crate
+    pub optional: bool,
+}
+
+impl CargoDependency {
+    fn named(name: &str) -> Self {
+        CargoDependency {
+            name: name.to_owned(),
+            version: None,
+            path: None,
+            default_features: true,
+            features: vec![],
+            optional: false,
+        }
+    }
+
+    fn new(name: &str, version: &str) -> Self {
+        CargoDependency {
+            version: Some(version.to_owned()),
+            ..CargoDependency::named(name)
+        }
+    }
+
+    fn features(mut self, features: &[&str]) -> Self {
+        self.features = features.iter().map(|&feature| feature.to_owned()).collect();
+        self
+    }
+
+    fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Version and path are replaced, features are added to the ones the generated code needs
+    fn apply(&mut self, dependency_config: &DependencyConfig) {
+        match dependency_config {
+            DependencyConfig::Version(version) => self.version = Some(version.clone()),
+            DependencyConfig::Detailed {
+                version,
+                path,
+                default_features,
+                features,
+            } => {
+                if version.is_some() {
+                    self.version = version.clone();
+                }
+                if path.is_some() {
+                    self.path = path.clone();
+                }
+                if let Some(default_features) = default_features {
+                    self.default_features = *default_features;
+                }
+                for feature in features {
+                    if !self.features.contains(feature) {
+                        self.features.push(feature.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Version string or inline table
+    pub fn value(&self) -> String {
+        if self.path.is_none()
+            && self.default_features
+            && self.features.is_empty()
+            && !self.optional
+        {
+            if let Some(ref version) = self.version {
+                return toml_string(version);
+            }
+        }
+
+        let mut entries = vec![];
+        if let Some(ref version) = self.version {
+            entries.push(format!("version = {}", toml_string(version)));
+        }
+        if let Some(ref path) = self.path {
+            entries.push(format!("path = {}", toml_string(path)));
+        }
+        if !self.default_features {
+            entries.push("default-features = false".to_owned());
+        }
+        if !self.features.is_empty() {
+            entries.push(format!("features = {}", toml_array(&self.features)));
+        }
+        if self.optional {
+            entries.push("optional = true".to_owned());
+        }
+        format!("{{ {} }}", entries.join(", "))
+    }
+}
+
+pub struct CargoFeature {
+    pub name: String,
+    /// Features of dependencies which are enabled with this one
+    pub dependency_features: Vec<String>,
+    /// Part of the default features
+    pub default: bool,
+}
 
 #[derive(Template)]
 #[template(path = "rust_reqwest_async/cargo.toml.jinja", ext = "txt")]
 struct CargoTomlTemplate {
     name: String,
     version: String,
+    edition: String,
+    license: Option<String>,
+    repository: Option<String>,
+    dependencies: Vec<CargoDependency>,
+    /// Features per tag (empty if modules aren't gated) and optional features
+    features: Vec<CargoFeature>,
+    generate_tests: bool,
+}
+
+impl CargoTomlTemplate {
+    fn new(
+        config: &Config,
+        name: &str,
+        dependencies: Vec<CargoDependency>,
+        features: Vec<CargoFeature>,
+    ) -> Self {
+        let project_metadata = &config.project_metadata;
+        CargoTomlTemplate {
+            name: name.to_owned(),
+            version: project_metadata.version.clone(),
+            edition: project_metadata.edition.clone(),
+            license: project_metadata.license.clone(),
+            repository: project_metadata.repository.clone(),
+            dependencies,
+            features,
+            generate_tests: false,
+        }
+    }
+
+    /// Rendered template, or its override of the template directory
+    fn render_with_override(&self, config: &Config) -> Result<String, String> {
+        let content = self.render().map_err(|e| e.to_string())?;
+        apply_template_override(
+            &config.generator,
+            "cargo.toml.jinja",
+            content,
+            serde_json::json!({
+                "name": self.name,
+                "version": self.version,
+                "dependencies": self
+                    .dependencies
+                    .iter()
+                    .map(|dependency| (dependency.name.clone(), dependency.value()))
+                    .collect::<std::collections::BTreeMap<String, String>>(),
+            }),
+        )
+    }
+
+    fn toml_string(&self, value: &str) -> String {
+        toml_string(value)
+    }
+
+    fn toml_array(&self, values: &[String]) -> String {
+        toml_array(values)
+    }
+
+    fn default_features(&self) -> String {
+        toml_array(
+            &self
+                .features
+                .iter()
+                .filter(|feature| feature.default)
+                .map(|feature| feature.name.clone())
+                .collect::<Vec<String>>(),
+        )
+    }
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/workspace_cargo.toml.jinja", ext = "txt")]
+struct WorkspaceCargoTomlTemplate {
+    members: Vec<String>,
+}
+
+fn toml_string(value: &str) -> String {
+    format!("{:?}", value)
+}
+
+fn toml_array(values: &[String]) -> String {
+    format!(
+        "[{}]",
+        values
+            .iter()
+            .map(|value| toml_string(value))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+/// Applies the dependencies of the config. Dependencies which the generated code doesn't use
+/// are only added if `add_missing` is set
+fn apply_dependency_configs(
+    config: &Config,
+    mut dependencies: Vec<CargoDependency>,
+    add_missing: bool,
+) -> Vec<CargoDependency> {
+    for (name, dependency_config) in &config.project_metadata.dependencies {
+        match dependencies
+            .iter_mut()
+            .find(|dependency| &dependency.name == name)
+        {
+            Some(dependency) => dependency.apply(dependency_config),
+            None if add_missing => {
+                let mut dependency = CargoDependency::named(name);
+                dependency.apply(dependency_config);
+                dependencies.push(dependency);
+            }
+            None => (),
+        }
+    }
+    dependencies
+}
+
+/// Crates which type overrides import, e.g. `uuid` of `uuid::Uuid`
+fn get_type_override_crates(config: &Config) -> BTreeSet<String> {
+    config
+        .name_mapping
+        .type_overrides
+        .values()
+        .filter_map(TypeOverride::import)
+        .filter_map(|(path, _)| path.split("::").next().map(str::to_owned))
+        .collect()
+}
+
+fn get_features(
+    config: &Config,
+    features: &BTreeSet<String>,
+    models_crate: Option<&str>,
+) -> Vec<CargoFeature> {
+    if !config.generator.tag_features {
+        return vec![];
+    }
+    features
+        .iter()
+        .map(|feature| CargoFeature {
+            name: feature.clone(),
+            dependency_features: models_crate
+                .map(|models_crate| vec![format!("{}/{}", models_crate, feature)])
+                .unwrap_or_default(),
+            default: true,
+        })
+        .collect()
+}
+
+pub fn generate_cargo_content(
+    config: &Config,
+    xml: bool,
+    ndjson: bool,
+    used_crates: &BTreeSet<String>,
+    features: &BTreeSet<String>,
+    models_crate: Option<&str>,
+) -> Result<String, String> {
+    let used_crates = used_crates
+        .union(&get_type_override_crates(config))
+        .cloned()
+        .collect::<BTreeSet<String>>();
+    let mut dependencies = vec![];
+    if let Some(models_crate) = models_crate {
+        dependencies.push(CargoDependency {
+            path: Some(format!("../{}", models_crate)),
+            ..CargoDependency::named(models_crate)
+        });
+    }
+    if used_crates.contains("futures") {
+        dependencies.push(CargoDependency::new("futures", "0.3"));
+    }
+    let mut reqwest_features = vec!["json"];
+    for response_compression in &config.generator.response_compression {
+        if !reqwest_features.contains(&response_compression.reqwest_feature()) {
+            reqwest_features.push(response_compression.reqwest_feature());
+        }
+    }
+    // Client certificates are read from PEM files, which needs rustls
+    if config.generator.client_tls {
+        reqwest_features.push("rustls-tls");
+    }
+    // Ndjson responses are read with bytes_stream
+    if ndjson {
+        reqwest_features.push("stream");
+    }
+    dependencies.append(&mut vec![
+        CargoDependency::new("percent-encoding", "2.3"),
+        // Unix sockets are supported since 0.12.23
+        CargoDependency::new(
+            "reqwest",
+            match config.generator.unix_socket {
+                Some(_) => "0.12.23",
+                None => "0.12.9",
+            },
+        )
+        .features(&reqwest_features),
+        CargoDependency::new("serde", "1.0.215").features(&["derive"]),
+        CargoDependency::new("serde_json", "1.0.132"),
+    ]);
+    if used_crates.contains("tungstenite") {
+        dependencies.push(CargoDependency::new("tungstenite", "0.24.0"));
+    }
+    // `format: uri` types of objects and parameters
+    if config.name_mapping.type_mapping.uri || used_crates.contains("url") {
+        dependencies.push(CargoDependency::new("url", "2").features(&["serde"]));
+    }
+    if used_crates.contains("uuid") {
+        dependencies.push(CargoDependency::new("uuid", "1").features(&["v4"]));
+    }
+    if config.generator.request_options {
+        dependencies.push(CargoDependency::new("tokio", "1").features(&["time"]));
+    }
+    // Validators are defined by the models crate if there is one
+    if config.generator.validation && models_crate.is_none() {
+        dependencies.push(CargoDependency::new("regex", "1"));
+    }
+    if xml {
+        dependencies.push(CargoDependency::new("quick-xml", "0.37").features(&["serialize"]));
+    }
+    // Bodies without Content-Length are buffered into a new response
+    if config.generator.max_response_body_size.is_some() {
+        dependencies.push(CargoDependency::new("http", "1"));
+    }
+    if config.generator.capture_undefined_response {
+        dependencies.push(CargoDependency::new("bytes", "1"));
+    }
+    if config.generator.aws_sigv4.is_some() {
+        dependencies.push(CargoDependency::new("aws-credential-types", "1"));
+        dependencies.push(CargoDependency::new("aws-sigv4", "1"));
+    }
+
+    let mut features = get_features(config, features, models_crate);
+    if config.generator.request_logging {
+        dependencies.push(CargoDependency::new("log", "0.4").optional());
+        features.push(CargoFeature {
+            name: "logging".to_owned(),
+            dependency_features: vec!["dep:log".to_owned()],
+            default: false,
+        });
+    }
+
+    if config.generator.unix_socket.is_some() {
+        features.push(CargoFeature {
+            name: "unix-socket".to_owned(),
+            dependency_features: vec![],
+            default: false,
+        });
+    }
+
+    if config.generator.tag_services
+        && config.generator.service_traits
+        && config.generator.service_mocks
+    {
+        dependencies.push(CargoDependency::new("mockall", "0.13").optional());
+        features.push(CargoFeature {
+            name: "mock".to_owned(),
+            dependency_features: vec!["dep:mockall".to_owned()],
+            default: false,
+        });
+    }
+
+    let mut template = CargoTomlTemplate::new(
+        config,
+        &config.project_metadata.name,
+        apply_dependency_configs(config, dependencies, true),
+        features,
+    );
+    template.generate_tests = config.generator.generate_tests;
+    template.render_with_override(config)
+}
+
+/// Crate of the objects without reqwest which is shared with the client crate
+pub fn generate_models_cargo_content(
+    config: &Config,
+    name: &str,
+    features: &BTreeSet<String>,
+) -> Result<String, String> {
+    let mut dependencies = vec![
+        CargoDependency::new("serde", "1.0.215").features(&["derive"]),
+        CargoDependency::new("serde_json", "1.0.132"),
+    ];
+    let used_crates = get_type_override_crates(config);
+    if config.name_mapping.type_mapping.uri || used_crates.contains("url") {
+        dependencies.push(CargoDependency::new("url", "2").features(&["serde"]));
+    }
+    if used_crates.contains("uuid") {
+        dependencies.push(CargoDependency::new("uuid", "1"));
+    }
+    if config.generator.validation {
+        dependencies.push(CargoDependency::new("regex", "1"));
+    }
+
+    CargoTomlTemplate::new(
+        config,
+        name,
+        apply_dependency_configs(config, dependencies, false),
+        get_features(config, features, None),
+    )
+    .render_with_override(config)
+}
+
+/// Server crate of the axum_server target with the objects and the router
+pub fn generate_server_cargo_content(config: &Config) -> Result<String, String> {
+    let dependencies = vec![
+        CargoDependency::new("axum", "0.8"),
+        CargoDependency::new("axum-extra", "0.10").features(&["query"]),
+        CargoDependency::new("serde", "1.0.215").features(&["derive"]),
+        CargoDependency::new("serde_json", "1.0.132"),
+    ];
+
+    CargoTomlTemplate::new(
+        config,
+        &config.project_metadata.name,
+        apply_dependency_configs(config, dependencies, true),
+        vec![],
+    )
+    .render_with_override(config)
+}
+
+/// Tables of Cargo.toml which list dependencies
+const DEPENDENCY_TABLES: [&str; 2] = ["dependencies", "dev-dependencies"];
+
+fn parse_cargo_table(cargo_content: &str) -> Result<toml::Table, String> {
+    cargo_content
+        .parse::<toml::Table>()
+        .map_err(|err| format!("Invalid Cargo.toml {}", err))
+}
+
+fn get_table_keys(cargo_table: &toml::Table, table_name: &str) -> BTreeSet<String> {
+    cargo_table
+        .get(table_name)
+        .and_then(toml::Value::as_table)
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// `key = value` lines of a table of a generated Cargo.toml
+fn get_table_lines<'a>(cargo_content: &'a str, table_name: &str) -> Vec<&'a str> {
+    let header = format!("[{}]", table_name);
+    cargo_content
+        .lines()
+        .skip_while(|line| line.trim() != header)
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .filter(|line| line.contains('='))
+        .collect()
+}
+
+/// Adds lines to the end of a table, the table is appended if it doesn't exist
+fn insert_table_lines(cargo_content: &str, table_name: &str, table_lines: &[&str]) -> String {
+    let header = format!("[{}]", table_name);
+    let lines = cargo_content.split_inclusive('\n').collect::<Vec<&str>>();
+    let (insert_index, mut content) = match lines.iter().position(|line| line.trim() == header) {
+        Some(header_index) => {
+            let table_end = lines[header_index + 1..]
+                .iter()
+                .position(|line| line.trim_start().starts_with('['))
+                .map_or(lines.len(), |position| header_index + 1 + position);
+            // Blank lines before the next table are kept there
+            let insert_index = (header_index + 1..table_end)
+                .rev()
+                .find(|&index| !lines[index].trim().is_empty())
+                .map_or(header_index + 1, |index| index + 1);
+            (insert_index, lines[..insert_index].concat())
+        }
+        None => {
+            let mut content = cargo_content.to_owned();
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content += &format!("\n{}\n", header);
+            (lines.len(), content)
+        }
+    };
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for table_line in table_lines {
+        content += &format!("{}\n", table_line);
+    }
+    content + &lines[insert_index..].concat()
+}
+
+/// Existing Cargo.toml with the dependencies of the generated one which it lacks, everything
+/// else is kept. Returns the content and the names of the added dependencies
+pub fn add_missing_dependencies(
+    cargo_content: &str,
+    generated_content: &str,
+) -> Result<(String, Vec<String>), String> {
+    let cargo_table = parse_cargo_table(cargo_content)?;
+    let generated_table = parse_cargo_table(generated_content)?;
+
+    let mut content = cargo_content.to_owned();
+    let mut added_dependencies = vec![];
+    for table_name in DEPENDENCY_TABLES {
+        let dependencies = get_table_keys(&cargo_table, table_name);
+        let generated_dependencies = get_table_keys(&generated_table, table_name);
+        let (missing_names, missing_lines): (Vec<&str>, Vec<&str>) =
+            get_table_lines(generated_content, table_name)
+                .into_iter()
+                .filter_map(|line| {
+                    let name = line.split_once('=')?.0.trim();
+                    (generated_dependencies.contains(name) && !dependencies.contains(name))
+                        .then_some((name, line))
+                })
+                .unzip();
+        if missing_lines.is_empty() {
+            continue;
+        }
+        added_dependencies.extend(missing_names.into_iter().map(str::to_owned));
+        content = insert_table_lines(&content, table_name, &missing_lines);
+    }
+    Ok((content, added_dependencies))
+}
+
+/// Features of the generated Cargo.toml which the existing one lacks
+pub fn get_missing_features(
+    cargo_content: &str,
+    generated_content: &str,
+) -> Result<Vec<String>, String> {
+    let features = get_table_keys(&parse_cargo_table(cargo_content)?, "features");
+    Ok(
+        get_table_keys(&parse_cargo_table(generated_content)?, "features")
+            .into_iter()
+            .filter(|feature| !features.contains(feature))
+            .collect(),
+    )
 }
 
-pub fn generate_cargo_content(project_metadata: &ProjectMetadata) -> Result<String, String> {
-    let template = CargoTomlTemplate {
-        name: project_metadata.name.clone(),
-        version: project_metadata.version.clone(),
+pub fn generate_workspace_cargo_content(members: &[String]) -> Result<String, String> {
+    let template = WorkspaceCargoTomlTemplate {
+        members: members.to_vec(),
     };
     template.render().map_err(|e| e.to_string())
 }
@@ -0,0 +1,94 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::parser::spec_diff::{ChangeLevel, SpecChange, SpecChangeKind};
+
+pub const CHANGELOG_FILE_NAME: &str = "CHANGELOG.md";
+const CHANGELOG_TITLE: &str = "# Changelog";
+
+/// `YYYY-MM-DD` (UTC) of `time`
+pub fn format_date(time: SystemTime) -> String {
+    let days = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / 86400)
+        .unwrap_or_default() as i64;
+
+    // Civil date of the days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = match shifted_month < 10 {
+        true => shifted_month + 3,
+        false => shifted_month - 9,
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// `## version - date` section with the changes grouped like keep a changelog
+pub fn generate_changelog_entry(version: &str, date: &str, changes: &[SpecChange]) -> String {
+    let mut entry = format!("## {} - {}\n", version, date);
+    for (kind, heading) in [
+        (SpecChangeKind::Added, "Added"),
+        (SpecChangeKind::Changed, "Changed"),
+        (SpecChangeKind::Removed, "Removed"),
+    ] {
+        let lines = changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .map(|change| {
+                let mut line = format!("- `{}`", change.subject);
+                if let Some(ref detail) = change.detail {
+                    line += &format!(": {}", detail);
+                }
+                if change.level == ChangeLevel::Breaking {
+                    line += " (breaking)";
+                }
+                line
+            })
+            .collect::<Vec<String>>();
+        if lines.is_empty() {
+            continue;
+        }
+        entry += &format!("\n### {}\n\n{}\n", heading, lines.join("\n"));
+    }
+    entry
+}
+
+/// Changelog with the entry above the previous ones
+pub fn add_changelog_entry(changelog_content: Option<&str>, entry: &str) -> String {
+    let changelog_content = changelog_content.unwrap_or_default();
+    let previous_entries = changelog_content
+        .trim_start()
+        .strip_prefix(CHANGELOG_TITLE)
+        .unwrap_or(changelog_content)
+        .trim();
+    match previous_entries.is_empty() {
+        true => format!("{}\n\n{}", CHANGELOG_TITLE, entry),
+        false => format!("{}\n\n{}\n{}\n", CHANGELOG_TITLE, entry, previous_entries),
+    }
+}
+
+/// Adds the changes to `CHANGELOG.md` of the crate, which is never overwritten
+pub fn write_changelog(
+    crate_dir: &str,
+    version: &str,
+    changes: &[SpecChange],
+) -> Result<(), String> {
+    let changelog_path = Path::new(crate_dir).join(CHANGELOG_FILE_NAME);
+    let changelog_content = fs::read_to_string(&changelog_path).ok();
+    let entry = generate_changelog_entry(version, &format_date(SystemTime::now()), changes);
+    fs::write(
+        &changelog_path,
+        add_changelog_entry(changelog_content.as_deref(), &entry),
+    )
+    .map_err(|err| format!("Unable to write {} {}", CHANGELOG_FILE_NAME, err))
+}
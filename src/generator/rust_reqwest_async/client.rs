@@ -0,0 +1,64 @@
+use askama::Template;
+
+use crate::utils::config::GeneratorConfig;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/client.rs.jinja", ext = "rs")]
+struct ClientTemplate {
+    content_encodings: Vec<&'static str>,
+    reqwest_features: Vec<&'static str>,
+    /// `ApiClientBuilder` with TLS and proxy settings
+    client_tls: bool,
+    /// The builder also builds the `ApiClient` of the services
+    api_client: bool,
+    /// Default path of the unix socket clients
+    unix_socket: Option<String>,
+    /// `ClientOptions` with the proxy settings
+    client_options: bool,
+}
+
+impl ClientTemplate {
+    fn string_literal(&self, value: &str) -> String {
+        format!("{:?}", value)
+    }
+
+    /// New reqwest builder, which decompresses the responses if there is a compression
+    fn client_builder_expression(&self) -> &'static str {
+        match self.content_encodings.is_empty() {
+            true => "reqwest::Client::builder()",
+            false => "client_builder()",
+        }
+    }
+}
+
+/// client.rs is generated for response compression, the client builder and unix sockets
+pub fn uses_client(config: &GeneratorConfig) -> bool {
+    !config.response_compression.is_empty()
+        || config.client_tls
+        || config.unix_socket.is_some()
+        || config.client_options
+}
+
+/// client.rs with a `reqwest::ClientBuilder` which decompresses the configured compressions
+/// the `ApiClientBuilder` of `client_tls`, the unix socket clients and `ClientOptions`
+pub fn generate_client_content(config: &GeneratorConfig) -> Result<String, String> {
+    let mut response_compression = config.response_compression.to_vec();
+    response_compression.sort();
+    response_compression.dedup();
+    ClientTemplate {
+        content_encodings: response_compression
+            .iter()
+            .map(|response_compression| response_compression.content_encoding())
+            .collect(),
+        reqwest_features: response_compression
+            .iter()
+            .map(|response_compression| response_compression.reqwest_feature())
+            .collect(),
+        client_tls: config.client_tls,
+        api_client: config.tag_services,
+        unix_socket: config.unix_socket.clone(),
+        client_options: config.client_options,
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
@@ -0,0 +1,23 @@
+use askama::Template;
+
+use super::readme::OperationSummary;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/conditional.rs.jinja", ext = "rs")]
+struct ConditionalTemplate {}
+
+pub fn generate_conditional_content() -> Result<String, String> {
+    ConditionalTemplate {}
+        .render()
+        .map_err(|err| err.to_string())
+}
+
+/// An operation has a `cache_validation` parameter, which is defined by the conditional module
+pub fn uses_conditional(operations: &[OperationSummary]) -> bool {
+    operations.iter().any(|operation| {
+        operation
+            .functions
+            .iter()
+            .any(|function| function.signature.contains("crate::conditional::"))
+    })
+}
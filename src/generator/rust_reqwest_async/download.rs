@@ -0,0 +1,21 @@
+use askama::Template;
+
+use super::readme::OperationSummary;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/download.rs.jinja", ext = "rs")]
+struct DownloadTemplate {}
+
+pub fn generate_download_content() -> Result<String, String> {
+    DownloadTemplate {}.render().map_err(|err| err.to_string())
+}
+
+/// An operation has a `_to_file` function, which needs the download module
+pub fn uses_download(operations: &[OperationSummary]) -> bool {
+    operations.iter().any(|operation| {
+        operation
+            .functions
+            .iter()
+            .any(|function| function.signature.contains("crate::download::"))
+    })
+}
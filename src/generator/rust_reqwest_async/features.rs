@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use convert_case::Casing;
+
+use super::readme::OperationSummary;
+use crate::{
+    parser::component::object_definition::types::{ModuleInfo, ObjectDatabase, ObjectDefinition},
+    utils::name_mapping::NameMapping,
+};
+
+const OBJECTS_MODULE_PATH: &str = "crate::objects::";
+
+/// Features of the generated crate which aren't tags, and the `default` key of `[features]`
+const RESERVED_FEATURE_NAMES: [&str; 4] = ["default", "logging", "mock", "unix-socket"];
+
+/// Cargo feature of a tag, e.g. `Robot Motion` -> `robot_motion`. Tags named like a built-in
+/// feature are prefixed, e.g. `Logging` -> `tag_logging`
+pub fn get_tag_feature_name(tag: &str) -> String {
+    let feature = tag
+        .to_case(convert_case::Case::Snake)
+        .chars()
+        .filter(|character| character.is_ascii_alphanumeric() || *character == '_')
+        .collect::<String>();
+    match RESERVED_FEATURE_NAMES.contains(&feature.as_str()) {
+        true => format!("tag_{}", feature),
+        false => feature,
+    }
+}
+
+/// Features of the tags of an operation, empty if the operation isn't gated
+pub fn get_operation_features(operation: &OperationSummary) -> BTreeSet<String> {
+    operation
+        .tags
+        .iter()
+        .map(|tag| get_tag_feature_name(tag))
+        .filter(|feature| !feature.is_empty())
+        .collect()
+}
+
+/// Features of all operations which are enabled by default
+pub fn get_all_features(operations: &[OperationSummary]) -> BTreeSet<String> {
+    operations.iter().flat_map(get_operation_features).collect()
+}
+
+/// `#[cfg(..)]` line which enables a module with any of the features
+pub fn get_cfg_attribute(features: &BTreeSet<String>) -> String {
+    if features.is_empty() {
+        return String::new();
+    }
+    let features = features
+        .iter()
+        .map(|feature| format!("feature = \"{}\"", feature))
+        .collect::<Vec<String>>();
+    match features.len() {
+        1 => format!("#[cfg({})]\n", features[0]),
+        _ => format!("#[cfg(any({}))]\n", features.join(", ")),
+    }
+}
+
+/// Object modules referenced by generated code
+pub fn get_used_object_modules(code: &str) -> BTreeSet<String> {
+    code.match_indices(OBJECTS_MODULE_PATH)
+        .map(|(start, _)| {
+            code[start + OBJECTS_MODULE_PATH.len()..]
+                .chars()
+                .take_while(|character| character.is_ascii_alphanumeric() || *character == '_')
+                .collect::<String>()
+        })
+        .filter(|module_name| !module_name.is_empty())
+        .collect()
+}
+
+fn get_object_modules(object_definition: &ObjectDefinition) -> Vec<&ModuleInfo> {
+    match object_definition {
+        ObjectDefinition::Struct(struct_definition) => {
+            let mut modules = struct_definition.get_required_modules();
+            // Local objects are written into the module of the struct
+            for local_object in struct_definition.local_objects.values() {
+                modules.append(&mut get_object_modules(local_object));
+            }
+            modules
+        }
+        ObjectDefinition::Enum(enum_definition) => enum_definition.get_required_modules(),
+        ObjectDefinition::Primitive(primitive_definition) => {
+            primitive_definition.primitive_type.module.iter().collect()
+        }
+        ObjectDefinition::Const(_) => vec![],
+    }
+}
+
+/// Features which enable an object module. Objects (and the objects they use) are gated by
+/// the tags of all operations which use them. Objects used by untagged operations or by no
+/// operation at all are always compiled and missing in the result
+pub fn get_object_features(
+    operations: &[OperationSummary],
+    object_database: &ObjectDatabase,
+    name_mapping: &NameMapping,
+) -> BTreeMap<String, BTreeSet<String>> {
+    let object_dependencies = object_database
+        .iter()
+        .map(|(name, object_definition)| {
+            let dependencies = get_object_modules(object_definition)
+                .iter()
+                .filter_map(|module| module.path.strip_prefix(OBJECTS_MODULE_PATH))
+                .map(|module_name| module_name.to_owned())
+                .collect::<BTreeSet<String>>();
+            (name_mapping.name_to_module_name(name), dependencies)
+        })
+        .collect::<BTreeMap<String, BTreeSet<String>>>();
+
+    let mut object_features: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut ungated_objects = BTreeSet::new();
+    for operation in operations {
+        let features = get_operation_features(operation);
+
+        let mut used_objects = BTreeSet::new();
+        let mut pending_objects = operation.object_modules.iter().collect::<Vec<&String>>();
+        while let Some(module_name) = pending_objects.pop() {
+            if !used_objects.insert(module_name.clone()) {
+                continue;
+            }
+            if let Some(dependencies) = object_dependencies.get(module_name) {
+                pending_objects.extend(dependencies);
+            }
+        }
+
+        for module_name in used_objects {
+            match features.is_empty() {
+                true => {
+                    ungated_objects.insert(module_name);
+                }
+                false => object_features
+                    .entry(module_name)
+                    .or_default()
+                    .extend(features.iter().cloned()),
+            }
+        }
+    }
+
+    object_features.retain(|module_name, _| !ungated_objects.contains(module_name));
+    object_features
+}
@@ -0,0 +1,39 @@
+use askama::Template;
+
+use crate::utils::name_mapping::TypeMapping;
+
+/// Newtype of a string format
+struct FormatType {
+    name: &'static str,
+    format: &'static str,
+    /// Function of the template which checks values
+    validator: &'static str,
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/formats.rs.jinja", ext = "rs")]
+struct FormatsTemplate {
+    formats: Vec<FormatType>,
+}
+
+/// Newtypes of the mapped formats other than `uri`, which is mapped to `url::Url`
+pub fn generate_formats_content(type_mapping: &TypeMapping) -> Result<String, String> {
+    let mut formats = vec![];
+    if type_mapping.email {
+        formats.push(FormatType {
+            name: "Email",
+            format: "email",
+            validator: "is_email",
+        });
+    }
+    if type_mapping.hostname {
+        formats.push(FormatType {
+            name: "Hostname",
+            format: "hostname",
+            validator: "is_hostname",
+        });
+    }
+    FormatsTemplate { formats }
+        .render()
+        .map_err(|err| err.to_string())
+}
@@ -1,6 +1,26 @@
+pub mod auth;
+pub mod aws_sigv4;
 pub mod cargo;
+pub mod changelog;
+pub mod client;
+pub mod conditional;
+pub mod download;
+pub mod features;
+pub mod formats;
 pub mod objects;
+pub mod pagination;
 pub mod path;
+pub mod path_encoding;
 pub mod paths;
+pub mod prelude;
 pub mod project;
+pub mod rate_limit;
+pub mod readme;
+pub mod request_options;
+pub mod services;
 pub mod templates;
+pub mod undefined_response;
+pub mod validation;
+pub mod version;
+pub mod webhooks;
+pub mod xml;
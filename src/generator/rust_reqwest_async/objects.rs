@@ -1,53 +1,98 @@
-use std::{
-    fs::{self, File},
-    io::Write,
-};
+use std::collections::{BTreeMap, BTreeSet};
 
 use askama::Template;
 use log::error;
 
 use crate::{
-    generator::rust_reqwest_async::templates::BaseTemplate,
+    generator::rust_reqwest_async::{
+        features::get_cfg_attribute,
+        templates::{BaseTemplate, StructTemplateOptions},
+        validation::get_validated_structs,
+    },
     parser::component::object_definition::{
         get_object_name,
         types::{ObjectDatabase, ObjectDefinition},
     },
-    utils::name_mapping::NameMapping,
+    utils::{
+        config::GeneratorConfig,
+        extensions::{apply_extension_callback, collect_extensions, ExtensionTarget},
+        name_mapping::NameMapping,
+        output::OutputWriter,
+        template_override::apply_template_override,
+    },
 };
 
 pub fn write_object_database(
-    output_dir: &str,
+    output: &OutputWriter,
     object_database: &ObjectDatabase,
     name_mapping: &NameMapping,
+    config: &GeneratorConfig,
+    object_features: &BTreeMap<String, BTreeSet<String>>,
 ) -> Result<(), String> {
-    fs::create_dir_all(format!("{}/src/objects/", output_dir))
-        .expect("Creating objects dir failed");
+    let object_names = object_database
+        .keys()
+        .map(|object_name| (object_name.clone(), object_name.clone()))
+        .collect::<Vec<(String, String)>>();
+    let collisions = name_mapping.get_module_name_collisions(&object_names);
+    if !collisions.is_empty() {
+        return Err(collisions.join("\n"));
+    }
+
+    let validated_structs = get_validated_structs(object_database);
+    let struct_template_options = StructTemplateOptions {
+        skip_read_write_only: config.skip_read_write_only,
+        deny_unknown_fields: config.deny_unknown_fields,
+        validated_structs: match config.validation {
+            true => Some(&validated_structs),
+            false => None,
+        },
+        property_serde: Some(&config.property_serde),
+    };
 
     for (_, object_definition) in object_database {
         let object_name = get_object_name(object_definition);
 
         let module_name = name_mapping.name_to_module_name(object_name);
 
-        let mut object_file =
-            match File::create(format!("{}/src/objects/{}.rs", output_dir, module_name)) {
-                Ok(file) => file,
-                Err(err) => {
-                    error!(
-                        "Unable to create file {}.rs {}",
-                        module_name,
-                        err.to_string()
-                    );
-                    continue;
-                }
-            };
-
         let template: BaseTemplate = match object_definition {
-            ObjectDefinition::Struct(struct_definition) => struct_definition.into(),
+            ObjectDefinition::Struct(struct_definition) => {
+                BaseTemplate::from_struct(struct_definition, &struct_template_options)
+            }
             ObjectDefinition::Enum(enum_definition) => enum_definition.into(),
             ObjectDefinition::Primitive(primitive_definition) => primitive_definition.into(),
-        };
+            ObjectDefinition::Const(const_definition) => const_definition.into(),
+        }
+        .derives(&config.derives.models)
+        .conversions();
 
-        let rendered_template = match template.render() {
+        let extensions = match object_database.get_source(object_name) {
+            Some(source) => collect_extensions(config, &source.schema.extensions),
+            None => BTreeMap::new(),
+        };
+        let rendered_template = match template
+            .render()
+            .map_err(|err| err.to_string())
+            .and_then(|content| {
+                apply_extension_callback(
+                    config,
+                    ExtensionTarget::Object,
+                    object_name,
+                    &extensions,
+                    content,
+                )
+            })
+            .and_then(|content| {
+                apply_template_override(
+                    config,
+                    "base.rs.jinja",
+                    content,
+                    serde_json::json!({
+                        "name": object_name,
+                        "module": module_name,
+                        "extensions": extensions,
+                    }),
+                )
+            }) {
             Ok(rendered_template) => rendered_template,
             Err(err) => {
                 error!(
@@ -59,40 +104,26 @@ pub fn write_object_database(
             }
         };
 
-        object_file
-            .write(rendered_template.as_bytes())
-            .map_err(|err| {
-                format!(
-                    "Failed to write to object file {}.rs {}",
-                    module_name,
-                    err.to_string()
-                )
-            })?;
-    }
-
-    let mut object_mod_file = match File::create(format!("{}/src/objects/mod.rs", output_dir)) {
-        Ok(file) => file,
-        Err(err) => {
-            return Err(format!(
-                "Unable to create file {} {}",
-                format!("{}/src/objects/mod.rs", output_dir),
-                err.to_string()
-            ))
-        }
-    };
-
-    for (struct_name, _) in object_database {
-        match object_mod_file.write(
-            format!(
-                "pub mod {};\n",
-                name_mapping.name_to_module_name(struct_name)
+        output
+            .write(
+                &format!("src/objects/{}.rs", module_name),
+                &rendered_template,
             )
-            .to_string()
-            .as_bytes(),
-        ) {
-            Ok(_) => (),
-            Err(err) => return Err(format!("Failed to write to mod {}", err.to_string())),
-        }
+            .map_err(|err| format!("Failed to write to object file {}.rs {}", module_name, err))?;
     }
-    Ok(())
+
+    let object_mod_content = object_database
+        .keys()
+        .map(|struct_name| {
+            let module_name = name_mapping.name_to_module_name(struct_name);
+            let cfg_attribute = object_features
+                .get(&module_name)
+                .map(get_cfg_attribute)
+                .unwrap_or_default();
+            format!("{}pub mod {};\n", cfg_attribute, module_name)
+        })
+        .collect::<String>();
+    output
+        .write("src/objects/mod.rs", &object_mod_content)
+        .map_err(|err| format!("Failed to write to mod {}", err))
 }
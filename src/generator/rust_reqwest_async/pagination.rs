@@ -0,0 +1,24 @@
+use askama::Template;
+use oas3::Spec;
+
+use super::path::pagination::get_pagination_extension;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/pagination.rs.jinja", ext = "rs")]
+struct PaginationTemplate {}
+
+pub fn generate_pagination_content() -> Result<String, String> {
+    PaginationTemplate {}
+        .render()
+        .map_err(|err| err.to_string())
+}
+
+/// An operation follows `Link` headers, whose values are parsed by the pagination module
+pub fn uses_link_pagination(spec: &Spec) -> bool {
+    spec.operations().any(|(_, _, operation)| {
+        matches!(
+            get_pagination_extension(operation),
+            Ok(Some(pagination)) if pagination.link_header
+        )
+    })
+}
@@ -0,0 +1,99 @@
+use oas3::spec::Operation;
+
+use crate::utils::config::Config;
+
+use super::utils::FunctionParameter;
+
+/// Parameters which are shared by all calls of a batch, all others are part of the items
+const SHARED_PARAMETERS: [&str; 4] = ["client", "server", "auth", "request_options"];
+
+pub struct BatchCode {
+    /// Parameters of the batch function before the items
+    pub shared_parameters: Vec<(String, String)>,
+    /// Type of a batch item, a tuple for multiple parameters
+    pub item_type: String,
+    /// Pattern which destructures an item into its parameters
+    pub item_pattern: String,
+    /// Arguments of the request function
+    pub arguments: Vec<String>,
+}
+
+/// Operation has `x-batch: true` or its id is listed in `generator.batch_operations`
+pub fn is_batch_operation(
+    config: &Config,
+    operation: &Operation,
+    operation_id: &str,
+) -> Result<bool, String> {
+    match operation.extensions.get("batch") {
+        Some(serde_json::Value::Bool(batch)) => Ok(*batch),
+        Some(extension_value) => Err(format!("Invalid x-batch value {}", extension_value)),
+        None => Ok(config
+            .generator
+            .batch_operations
+            .iter()
+            .any(|batch_operation| batch_operation == operation_id)),
+    }
+}
+
+pub fn generate_batch_code(function_parameters: &[FunctionParameter]) -> Result<BatchCode, String> {
+    let mut shared_parameters = vec![];
+    let mut item_parameters = vec![];
+    let mut arguments = vec![];
+    for function_parameter in function_parameters {
+        let type_name = match function_parameter.reference {
+            true => format!("&{}", function_parameter.type_name),
+            false => function_parameter.type_name.clone(),
+        };
+        if SHARED_PARAMETERS.contains(&function_parameter.name.as_str()) {
+            shared_parameters.push((function_parameter.name.clone(), type_name));
+            arguments.push(function_parameter.name.clone());
+            continue;
+        }
+        // Items are owned, borrowed parameters (e.g. &str) get a reference to them
+        item_parameters.push(function_parameter);
+        arguments.push(match function_parameter.reference {
+            true => format!("&{}", function_parameter.name),
+            false => function_parameter.name.clone(),
+        });
+    }
+
+    let (item_type, item_pattern) = match item_parameters.as_slice() {
+        [] => return Err("Batch operations require parameters or a request body".to_owned()),
+        [item_parameter] => (owned_type_name(item_parameter), item_parameter.name.clone()),
+        _ => (
+            format!(
+                "({})",
+                item_parameters
+                    .iter()
+                    .map(|item_parameter| owned_type_name(item_parameter))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            format!(
+                "({})",
+                item_parameters
+                    .iter()
+                    .map(|item_parameter| item_parameter.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        ),
+    };
+
+    Ok(BatchCode {
+        shared_parameters,
+        item_type,
+        item_pattern,
+        arguments,
+    })
+}
+
+fn owned_type_name(function_parameter: &FunctionParameter) -> String {
+    match (
+        function_parameter.reference,
+        function_parameter.type_name.as_str(),
+    ) {
+        (true, "str") => "String".to_owned(),
+        _ => function_parameter.type_name.clone(),
+    }
+}
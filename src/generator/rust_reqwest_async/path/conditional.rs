@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+
+use oas3::{
+    spec::{Operation, ParameterIn, Response},
+    Spec,
+};
+
+/// Entity tag support of an operation which accepts `If-None-Match` or `If-Match`
+pub struct ConditionalCode {
+    /// Variant of 304 Not Modified responses, if `If-None-Match` is accepted
+    pub not_modified_variant: Option<String>,
+    /// A response declares an `ETag` header, which `_with_etag` returns
+    pub etag: bool,
+}
+
+/// Conditional request headers (lowercase) which are parameters of the operation
+pub fn get_conditional_headers(spec: &Spec, operation: &Operation) -> Vec<String> {
+    operation
+        .parameters
+        .iter()
+        .filter_map(|parameter| parameter.resolve(spec).ok())
+        .filter(|parameter| parameter.location == ParameterIn::Header)
+        .map(|parameter| parameter.name.to_ascii_lowercase())
+        .filter(|name| is_conditional_header(name))
+        .collect()
+}
+
+pub fn is_conditional_header(name: &str) -> bool {
+    name.eq_ignore_ascii_case("if-none-match") || name.eq_ignore_ascii_case("if-match")
+}
+
+pub fn declares_etag(responses: &BTreeMap<String, Response>) -> bool {
+    responses.values().any(|response| {
+        response
+            .headers
+            .keys()
+            .any(|header_name| header_name.eq_ignore_ascii_case("etag"))
+    })
+}
@@ -0,0 +1,244 @@
+use askama::Template;
+use log::{trace, warn};
+use oas3::{spec::Operation, Spec};
+
+use crate::{
+    generator::{
+        media_type::TransferMediaType,
+        rust_reqwest_async::templates::{
+            ConstDefinitionTemplate, EnumDefinitionTemplate, PrimitiveDefinitionTemplate,
+            StructDefinitionTemplate,
+        },
+    },
+    parser::component::object_definition::{
+        oas3_type_to_string,
+        types::{to_unique_list, ModuleInfo, ObjectDatabase},
+    },
+    utils::config::Config,
+};
+
+use super::security::{get_auth_parameter, AuthParameter};
+use super::utils::{
+    generate_path_parameter_code, generate_query_parameter_code, generate_request_body,
+    generate_responses, FunctionParameter, QueryParameter,
+};
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/event_stream.rs.jinja", ext = "rs")]
+struct EventStreamRequestTemplate {
+    // Base
+    module_imports: Vec<ModuleInfo>,
+    struct_definitions: Vec<StructDefinitionTemplate>,
+    enum_definitions: Vec<EnumDefinitionTemplate>,
+    primitive_definitions: Vec<PrimitiveDefinitionTemplate>,
+    const_definitions: Vec<ConstDefinitionTemplate>,
+    // EventStream
+    event_stream_struct_name: String,
+    event_type_name: String,
+    event_type_json: bool,
+    function_name: String,
+    function_parameters: Vec<FunctionParameter>,
+    path_format_string: String,
+    path_parameter_arguments: String,
+    request_method: String,
+    request_content_variable_name: Option<String>,
+    query_parameters_mutable: bool,
+    query_parameters: Vec<QueryParameter>,
+    auth: Option<AuthParameter>,
+    aws_sigv4: bool,
+}
+
+impl EventStreamRequestTemplate {
+    /// Credentials are added to the request builder before it is sent
+    fn modifies_request_builder(&self) -> bool {
+        self.auth.is_some() || self.aws_sigv4
+    }
+}
+
+pub fn generate_operation(
+    spec: &Spec,
+    config: &Config,
+    method: &reqwest::Method,
+    path: &str,
+    operation: &Operation,
+    object_database: &mut ObjectDatabase,
+) -> Result<String, String> {
+    let name_mapping = &config.name_mapping;
+    trace!("Generating event stream {} {}", method.as_str(), path);
+    let operation_definition_path: Vec<String> = vec![path.to_owned()];
+    let function_name = match operation.operation_id {
+        Some(ref operation_id) => name_mapping.name_to_function_name(operation_id),
+        None => return Err("No operation_id found".to_owned()),
+    };
+
+    let response_entities = generate_responses(
+        spec,
+        object_database,
+        &operation_definition_path,
+        name_mapping,
+        &operation.responses(spec),
+        &config.generator.content_type_mapping,
+        &function_name,
+    )?;
+
+    let event_media_type = response_entities
+        .iter()
+        .filter(|(response_key, _)| response_key.starts_with("2"))
+        .flat_map(|(_, response_entity)| response_entity.content.values())
+        .find(|transfer_media_type| {
+            matches!(transfer_media_type, TransferMediaType::EventStream(_))
+        });
+
+    let mut module_imports = vec![ModuleInfo {
+        name: "reqwest".to_owned(),
+        path: String::new(),
+    }];
+
+    let (event_type_name, event_type_json) = match event_media_type {
+        Some(TransferMediaType::EventStream(Some(type_definition))) => {
+            if let Some(ref module) = type_definition.module {
+                module_imports.push(module.clone());
+            }
+            (type_definition.name.clone(), true)
+        }
+        Some(TransferMediaType::EventStream(None)) => {
+            (oas3_type_to_string(&oas3::spec::SchemaType::String), false)
+        }
+        _ => return Err("No text/event-stream response found".to_owned()),
+    };
+
+    let path_parameter_code = generate_path_parameter_code(
+        spec,
+        operation,
+        &operation_definition_path,
+        name_mapping,
+        object_database,
+        &function_name,
+        path,
+    )?;
+
+    let query_parameter_code = generate_query_parameter_code(
+        spec,
+        operation,
+        &operation_definition_path,
+        name_mapping,
+        object_database,
+        &function_name,
+    )?;
+
+    let mut function_parameters = vec![
+        FunctionParameter {
+            name: "client".to_owned(),
+            type_name: "reqwest::Client".to_owned(),
+            reference: true,
+        },
+        FunctionParameter {
+            name: "server".to_owned(),
+            type_name: "str".to_owned(),
+            reference: true,
+        },
+    ];
+
+    // Request Body
+    let mut request_content_variable_name = None;
+    if let Some(ref request_body) = operation.request_body {
+        let request_entity = match generate_request_body(
+            spec,
+            object_database,
+            &operation_definition_path,
+            name_mapping,
+            request_body,
+            &config.generator.content_type_mapping,
+            &function_name,
+        ) {
+            Ok(request_entity) => request_entity,
+            Err(err) => return Err(format!("Failed to generated request body {}", err)),
+        };
+
+        match request_entity.content.get("application/json") {
+            Some(TransferMediaType::ApplicationJson(Some(type_definition))) => {
+                let variable_name =
+                    name_mapping.name_to_property_name(&operation_definition_path, "content");
+                if let Some(ref module) = type_definition.module {
+                    module_imports.push(module.clone());
+                }
+                function_parameters.push(FunctionParameter {
+                    name: variable_name.clone(),
+                    type_name: type_definition.name.clone(),
+                    reference: false,
+                });
+                request_content_variable_name = Some(variable_name);
+            }
+            _ => warn!("Only application/json request bodies are supported for event streams"),
+        }
+    }
+
+    let path_parameters_struct = &path_parameter_code.parameters_struct;
+    if !path_parameters_struct.properties.is_empty() {
+        function_parameters.push(FunctionParameter {
+            name: path_parameter_code.parameters_struct_variable_name.clone(),
+            type_name: path_parameters_struct.name.clone(),
+            reference: false,
+        });
+    }
+
+    let query_struct = &query_parameter_code.query_struct;
+    if !query_struct.properties.is_empty() {
+        function_parameters.push(FunctionParameter {
+            name: query_parameter_code.query_struct_variable_name.clone(),
+            type_name: query_struct.name.clone(),
+            reference: false,
+        });
+    }
+
+    let auth = get_auth_parameter(
+        config,
+        spec,
+        operation,
+        &operation_definition_path,
+        &function_name,
+    )?;
+    if let Some(ref auth) = auth {
+        function_parameters.push(auth.function_parameter());
+    }
+
+    module_imports.extend(
+        query_struct
+            .get_required_modules()
+            .iter()
+            .map(|&module| module.clone()),
+    );
+
+    EventStreamRequestTemplate {
+        module_imports: to_unique_list(&module_imports),
+        struct_definitions: vec![
+            Into::<StructDefinitionTemplate>::into(path_parameters_struct)
+                .serializable(false)
+                .derives(&config.generator.derives.parameters),
+            Into::<StructDefinitionTemplate>::into(query_struct)
+                .serializable(false)
+                .derives(&config.generator.derives.parameters),
+        ],
+        enum_definitions: vec![],
+        primitive_definitions: vec![],
+        const_definitions: vec![],
+        event_stream_struct_name: name_mapping.name_to_struct_name(
+            &operation_definition_path,
+            &format!("{}EventStream", function_name),
+        ),
+        event_type_name,
+        event_type_json,
+        function_name: function_name.clone(),
+        function_parameters,
+        path_format_string: path_parameter_code.path_format_string.clone(),
+        path_parameter_arguments: path_parameter_code.format_arguments().join(", "),
+        request_method: method.as_str().to_lowercase(),
+        request_content_variable_name,
+        query_parameters_mutable: query_parameter_code.query_parameters_mutable(),
+        query_parameters: query_parameter_code.query_parameters(),
+        auth,
+        aws_sigv4: config.generator.aws_sigv4.is_some(),
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
@@ -1,127 +1,377 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use askama::Template;
 use log::{trace, warn};
-use oas3::{
-    spec::{Operation, ParameterIn},
-    Spec,
-};
+use oas3::{spec::Operation, Spec};
 
 use crate::{
-    generator::rust_reqwest_async::{
-        path::utils::ResponseEntity,
-        templates::{
-            EnumDefinitionTemplate, PrimitiveDefinitionTemplate, StructDefinitionTemplate,
+    generator::{
+        media_type::{normalize_content_type, TransferMediaType},
+        rust_reqwest_async::{
+            path::utils::ResponseEntity,
+            templates::{
+                raw_string_literal, ConstDefinitionTemplate, EnumDefinitionTemplate,
+                PrimitiveDefinitionTemplate, StructDefinitionTemplate,
+            },
+            validation::is_validated_type,
         },
     },
     parser::component::{
+        example::get_media_type_explicit_example,
         object_definition::{
             oas3_type_to_string,
             types::{
                 to_unique_list, EnumDefinition, EnumValue, ModuleInfo, ObjectDatabase,
-                PropertyDefinition, StructDefinition, TypeDefinition,
+                PropertyDefinition, TypeDefinition,
             },
         },
-        type_definition::get_type_from_schema,
     },
-    utils::name_mapping::NameMapping,
+    utils::{
+        config::Config,
+        extensions::{apply_extension_callback, collect_extensions, ExtensionTarget},
+        name_mapping::NameMapping,
+        template_override::apply_template_override,
+    },
 };
 
+use super::batch::{generate_batch_code, is_batch_operation, BatchCode};
+use super::conditional::{declares_etag, get_conditional_headers, ConditionalCode};
+use super::http_test;
+use super::idempotency::get_idempotency_key_header;
+use super::links::{get_link_helpers, LinkHelper};
+use super::pagination::{generate_pagination_code, get_pagination_extension, PaginationCode};
+use super::response_result::{add_response_result_enums, get_status_variants, ResponseResultCode};
+use super::security::{get_auth_parameter, AuthParameter};
+use super::server::get_operation_server;
 use super::utils::{
-    generate_request_body, generate_responses, is_path_parameter, RequestEntity, TransferMediaType,
+    generate_path_parameter_code, generate_query_parameter_code, generate_request_body,
+    generate_responses, get_flat_parameters, FunctionParameter, PathParameterCode, QueryParameter,
+    QueryParametersCode, RequestEntity, ResponseEntities,
 };
 
-#[derive(Debug)]
-struct QueryParameter {
-    is_required: bool,
-    is_array: bool,
-    real_name: String,
-    name: String,
-    struct_name: String,
-}
-
-#[derive(Debug)]
-struct FunctionParameter {
-    name: String,
-    type_name: String,
-    reference: bool,
-}
-
 #[derive(Template)]
 #[template(path = "rust_reqwest_async/http.rs.jinja", ext = "rs")]
-struct HttpRequestTemplate {
+pub(super) struct HttpRequestTemplate {
     // Base
     module_imports: Vec<ModuleInfo>,
     struct_definitions: Vec<StructDefinitionTemplate>,
     enum_definitions: Vec<EnumDefinitionTemplate>,
     primitive_definitions: Vec<PrimitiveDefinitionTemplate>,
-    name_mapping: NameMapping,
+    const_definitions: Vec<ConstDefinitionTemplate>,
+    pub(super) name_mapping: NameMapping,
     // Request
     operation_definition_path: Vec<String>,
-    response_enum_definition_path: Vec<String>,
-    response_type_name: String,
+    pub(super) response_enum_definition_path: Vec<String>,
+    pub(super) response_type_name: String,
     function_visibility: String,
-    function_name: String,
-    function_parameters: Vec<FunctionParameter>,
+    pub(super) function_name: String,
+    pub(super) function_parameters: Vec<FunctionParameter>,
+    /// Statements which assemble the parameter structs of flat parameters
+    parameter_assignments: Vec<String>,
+    /// Function parameters which are path or query parameters of the spec
+    pub(super) flat_parameter_names: Vec<String>,
     path_format_string: String,
     path_parameter_arguments: String,
-    request_body_content_types_count: usize,
-    request_media_type: String,
+    pub(super) request_body_content_types_count: usize,
+    pub(super) request_media_type: String,
+    /// Content type of the spec which is sent, e.g. `application/vnd.api+json`
+    pub(super) request_content_type: String,
     request_content_variable_name: Option<String>,
+    /// The content is an `Option` and only sent if it is given
+    pub(super) request_body_optional: bool,
     request_method: String,
     has_response_any_multi_content_type: bool,
 
     query_parameters_mutable: bool,
-    query_parameters: Vec<QueryParameter>,
+    pub(super) query_parameters: Vec<QueryParameter>,
 
-    responses: HashMap<String, ResponseEntity>,
+    pub(super) responses: BTreeMap<String, ResponseEntity>,
     multi_request_type_functions: Vec<MultiRequestTypeFunction>,
-
-    media_type_enum_name: fn(&Vec<String>, &NameMapping, &TransferMediaType) -> String,
+    pagination: Option<PaginationCode>,
+    batch: Option<BatchCode>,
+    conditional: Option<ConditionalCode>,
+    /// Header which is sent with the given or a new UUID
+    idempotency_key_header: Option<String>,
+    /// Credentials of the security requirements which are added to the request
+    pub(super) auth: Option<AuthParameter>,
+    /// Requests are signed with AWS Signature Version 4
+    aws_sigv4: bool,
+    /// Server of the operation or its path, or the configured one
+    operation_server: Option<String>,
+    /// 429 variants contain the `RateLimitInfo` of the response
+    rate_limit: bool,
+    /// Patterns of the response enum variants and their status codes
+    status_variants: Vec<(String, String)>,
+    link_helpers: Vec<LinkHelper>,
+    /// `into_result()` of the response enum
+    response_result: Option<ResponseResultCode>,
+    /// Responses with a larger `Content-Length` or body are returned as `BodyTooLarge`
+    max_response_body_size: Option<u64>,
+    /// Statuses with binary body which `_to_file` writes to disk
+    download_statuses: Vec<u16>,
+    request_options: bool,
+    capture_undefined_response: bool,
+    request_logging: bool,
+    retry_request: bool,
+    /// `reqwest::Error` or the `RequestError` of the validation module
+    error_type_name: String,
+    validate_request_content: bool,
+    pub(super) path_parameters: Vec<PropertyDefinition>,
+    examples: Vec<OperationExampleTemplate>,
 }
 
 impl HttpRequestTemplate {
-    fn media_type_enum_name(
+    /// Optional crates which the code of the operation uses
+    fn used_crates(&self) -> BTreeSet<String> {
+        let mut used_crates = BTreeSet::new();
+        // Pages and batch results are streamed
+        if self.pagination.is_some() || self.batch.is_some() {
+            used_crates.insert("futures".to_owned());
+        }
+        // Idempotency keys are generated if the caller doesn't pass one
+        if self.idempotency_key_header.is_some() {
+            used_crates.insert("uuid".to_owned());
+        }
+        used_crates
+    }
+
+    /// The main function is `_with_etag`, which is wrapped by a function without the ETag
+    fn returns_etag(&self) -> bool {
+        self.conditional
+            .as_ref()
+            .is_some_and(|conditional| conditional.etag)
+    }
+
+    /// Credentials are added to the request builder of the pages before it is sent
+    fn server_constant_name(&self) -> String {
+        format!(
+            "{}_SERVER",
+            self.function_name.trim_end_matches('_').to_uppercase()
+        )
+    }
+
+    /// Borrowed parameters of the page stream live as long as the stream
+    fn pages_parameter_type(&self, function_parameter: &FunctionParameter) -> String {
+        match (
+            function_parameter.reference,
+            function_parameter.name.as_str(),
+        ) {
+            (true, _) => format!("&'a {}", function_parameter.type_name),
+            (false, "auth") => match self.auth {
+                Some(ref auth) => format!("Option<&'a {}>", auth.type_name),
+                None => function_parameter.type_name.clone(),
+            },
+            (false, _) => match function_parameter.type_name.strip_prefix("Option<&") {
+                Some(type_name) => format!("Option<&'a {}", type_name),
+                None => function_parameter.type_name.clone(),
+            },
+        }
+    }
+
+    fn string_literal(&self, value: &str) -> String {
+        format!("{:?}", value)
+    }
+
+    fn function_argument_names(&self) -> Vec<&str> {
+        self.function_parameters
+            .iter()
+            .map(|function_parameter| function_parameter.name.as_str())
+            .collect()
+    }
+
+    /// Start of the value of a status variant, 429 values are wrapped with their rate limit
+    fn rate_limit_value_start(&self, response_key: &str, content: bool) -> &'static str {
+        match (self.rate_limit && response_key == "429", content) {
+            (false, _) => "",
+            (true, true) => "(crate::rate_limit::RateLimited { rate_limit, content: ",
+            (true, false) => "(rate_limit",
+        }
+    }
+
+    fn rate_limit_value_end(&self, response_key: &str, content: bool) -> &'static str {
+        match (self.rate_limit && response_key == "429", content) {
+            (false, _) => "",
+            (true, true) => " })",
+            (true, false) => ")",
+        }
+    }
+
+    fn status_variant_name(&self, response_entity: &ResponseEntity) -> String {
+        status_variant_name(
+            &self.response_enum_definition_path,
+            &self.name_mapping,
+            response_entity,
+        )
+    }
+
+    fn value_enum_name(&self, response_entity: &ResponseEntity) -> String {
+        value_enum_name(
+            &self.response_enum_definition_path,
+            &self.name_mapping,
+            response_entity,
+        )
+    }
+
+    fn media_type_variant_name(
         &self,
-        operation_definition_path: &Vec<String>,
-        name_mapping: &NameMapping,
-        transfer_media_type: &TransferMediaType,
+        response_entity: &ResponseEntity,
+        transfer_media_type: TransferMediaType,
     ) -> String {
-        (self.media_type_enum_name)(operation_definition_path, name_mapping, transfer_media_type)
+        let mut enum_definition_path = self.operation_definition_path.clone();
+        enum_definition_path.push(self.value_enum_name(response_entity));
+        media_type_variant_name(
+            &enum_definition_path,
+            &self.name_mapping,
+            &transfer_media_type,
+        )
     }
+
+    /// Content type as matched against the normalized response header
+    fn normalize_content_type(&self, content_type: &str) -> String {
+        normalize_content_type(content_type)
+    }
+}
+
+/// Function returning a request or response example of the spec
+struct OperationExampleTemplate {
+    function_name: String,
+    type_name: String,
+    value: String,
 }
 
 pub fn generate_operation(
     spec: &Spec,
-    name_mapping: &NameMapping,
+    config: &Config,
     method: &reqwest::Method,
     path: &str,
     operation: &Operation,
     object_database: &mut ObjectDatabase,
 ) -> Result<String, String> {
+    generate_operation_code(
+        spec,
+        config,
+        method,
+        path,
+        operation,
+        object_database,
+        false,
+    )
+    .map(|(code, _, _)| code)
+}
+
+/// Wiremock based integration test of the operation, None if the operation can't be tested
+pub fn generate_operation_test(
+    spec: &Spec,
+    config: &Config,
+    method: &reqwest::Method,
+    path: &str,
+    operation: &Operation,
+    object_database: &mut ObjectDatabase,
+) -> Result<Option<String>, String> {
+    generate_operation_code(spec, config, method, path, operation, object_database, true)
+        .map(|(_, test_code, _)| test_code)
+}
+
+/// Code of the operation and its test if `generate_test` is set, both from a single template,
+/// with the optional crates which the code uses
+pub fn generate_operation_code(
+    spec: &Spec,
+    config: &Config,
+    method: &reqwest::Method,
+    path: &str,
+    operation: &Operation,
+    object_database: &mut ObjectDatabase,
+    generate_test: bool,
+) -> Result<(String, Option<String>, BTreeSet<String>), String> {
+    let template =
+        generate_operation_template(spec, config, method, path, operation, object_database)?;
+    let test_code = match generate_test {
+        true => {
+            http_test::generate_operation_test(spec, config, method, path, operation, &template)
+                .map_err(|err| format!("Failed to generate test {}", err))?
+        }
+        false => None,
+    };
+    let extensions = collect_extensions(&config.generator, &operation.extensions);
+    let content = apply_extension_callback(
+        &config.generator,
+        ExtensionTarget::Operation,
+        &template.function_name,
+        &extensions,
+        template.render().map_err(|err| err.to_string())?,
+    )?;
+    let code = apply_template_override(
+        &config.generator,
+        "http.rs.jinja",
+        content,
+        serde_json::json!({
+            "function_name": template.function_name,
+            "method": method.as_str(),
+            "path": path,
+            "extensions": extensions,
+        }),
+    )?;
+    Ok((code, test_code, template.used_crates()))
+}
+
+fn generate_operation_template(
+    spec: &Spec,
+    config: &Config,
+    method: &reqwest::Method,
+    path: &str,
+    operation: &Operation,
+    object_database: &mut ObjectDatabase,
+) -> Result<HttpRequestTemplate, String> {
     trace!("Generating {} {}", method.as_str(), path);
+    let name_mapping = &config.name_mapping;
     let operation_definition_path: Vec<String> = vec![path.to_owned()];
     let function_name = match operation.operation_id {
-        Some(ref operation_id) => name_mapping.name_to_module_name(operation_id),
+        Some(ref operation_id) => name_mapping.name_to_function_name(operation_id),
         None => return Err("No operation_id found".to_owned()),
     };
 
-    let response_entities = match generate_responses(
+    let mut response_entities = match generate_responses(
         spec,
         object_database,
         &operation_definition_path,
         name_mapping,
         &operation.responses(spec),
+        &config.generator.content_type_mapping,
         &function_name,
     ) {
         Ok(response_entities) => response_entities,
         Err(err) => return Err(err),
     };
 
+    for (_, response_entity) in response_entities.iter_mut() {
+        response_entity.content.retain(
+            |content_type, transfer_media_type| match transfer_media_type {
+                TransferMediaType::EventStream(_) => {
+                    warn!(
+                        "{} is only supported for event stream operations",
+                        content_type
+                    );
+                    false
+                }
+                TransferMediaType::Ndjson(_) => {
+                    warn!(
+                        "{} is only supported for ndjson stream operations",
+                        content_type
+                    );
+                    false
+                }
+                _ => true,
+            },
+        );
+    }
+
     // Path parameters
     let path_parameter_code = match generate_path_parameter_code(
+        spec,
+        operation,
         &operation_definition_path,
         name_mapping,
+        object_database,
         &function_name,
         path,
     ) {
@@ -156,7 +406,8 @@ pub fn generate_operation(
     for (_, entity) in &response_entities {
         for (_, content) in &entity.content {
             match content {
-                TransferMediaType::ApplicationJson(ref type_definition) => match type_definition {
+                TransferMediaType::ApplicationJson(ref type_definition)
+                | TransferMediaType::ApplicationXml(ref type_definition) => match type_definition {
                     Some(type_definition) => match type_definition.module {
                         Some(ref module_info) => {
                             module_imports.push(module_info.clone());
@@ -166,6 +417,9 @@ pub fn generate_operation(
                     None => (),
                 },
                 TransferMediaType::TextPlain => (),
+                TransferMediaType::OctetStream => (),
+                TransferMediaType::EventStream(_) => (),
+                TransferMediaType::Ndjson(_) => (),
             }
         }
     }
@@ -177,24 +431,23 @@ pub fn generate_operation(
             continue;
         }
 
-        let response_code_enum_name = name_mapping.name_to_struct_name(
-            &response_enum_definition_path,
-            &format!("{}Value", entity.canonical_status_code),
-        );
+        let response_code_enum_name =
+            value_enum_name(&response_enum_definition_path, name_mapping, entity);
 
         let mut response_enum = EnumDefinition {
             name: response_code_enum_name.clone(),
             used_modules: vec![],
-            values: HashMap::new(),
+            values: BTreeMap::new(),
         };
         let mut enum_definition_path = operation_definition_path.clone();
         enum_definition_path.push(response_code_enum_name);
 
         for (_, transfer_media_type) in &entity.content {
             let transfer_media_type_name =
-                media_type_enum_name(&enum_definition_path, name_mapping, transfer_media_type);
+                media_type_variant_name(&enum_definition_path, name_mapping, transfer_media_type);
             let enum_value = &match transfer_media_type {
-                TransferMediaType::ApplicationJson(type_definition) => match type_definition {
+                TransferMediaType::ApplicationJson(type_definition)
+                | TransferMediaType::ApplicationXml(type_definition) => match type_definition {
                     Some(type_definition) => EnumValue {
                         name: transfer_media_type_name,
                         value_type: type_definition.clone(),
@@ -214,6 +467,14 @@ pub fn generate_operation(
                         module: None,
                     },
                 },
+                TransferMediaType::OctetStream => EnumValue {
+                    name: transfer_media_type_name,
+                    value_type: TypeDefinition {
+                        name: "Vec<u8>".to_owned(),
+                        module: None,
+                    },
+                },
+                TransferMediaType::EventStream(_) | TransferMediaType::Ndjson(_) => continue,
             };
 
             response_enum
@@ -227,20 +488,26 @@ pub fn generate_operation(
     let mut response_enum = EnumDefinition {
         name: response_enum_name.clone(),
         used_modules: vec![],
-        values: HashMap::new(),
+        values: BTreeMap::new(),
     };
 
     for (status_code, entity) in &response_entities {
-        let response_enum_name = name_mapping.name_to_struct_name(
-            &response_enum_definition_path,
-            &format!("{}", entity.canonical_status_code),
-        );
+        let response_enum_name =
+            status_variant_name(&response_enum_definition_path, name_mapping, entity);
 
         let enum_value = &match entity.content.len() {
-            0 => continue,
+            // Responses without content, e.g. 204 No Content
+            0 => EnumValue {
+                name: response_enum_name,
+                value_type: TypeDefinition {
+                    name: String::new(),
+                    module: None,
+                },
+            },
             1 => match entity.content.values().next() {
                 Some(transfer_media_type) => match transfer_media_type {
-                    TransferMediaType::ApplicationJson(type_definition) => match type_definition {
+                    TransferMediaType::ApplicationJson(type_definition)
+                    | TransferMediaType::ApplicationXml(type_definition) => match type_definition {
                         Some(type_definition) => EnumValue {
                             name: response_enum_name,
                             value_type: type_definition.clone(),
@@ -261,6 +528,14 @@ pub fn generate_operation(
                             module: None,
                         },
                     },
+                    TransferMediaType::OctetStream => EnumValue {
+                        name: response_enum_name,
+                        value_type: TypeDefinition {
+                            name: "Vec<u8>".to_owned(),
+                            module: None,
+                        },
+                    },
+                    TransferMediaType::EventStream(_) | TransferMediaType::Ndjson(_) => continue,
                 },
                 None => {
                     return Err(format!(
@@ -272,10 +547,7 @@ pub fn generate_operation(
             _ => EnumValue {
                 name: response_enum_name,
                 value_type: TypeDefinition {
-                    name: name_mapping.name_to_struct_name(
-                        &response_enum_definition_path,
-                        &format!("{}Value", entity.canonical_status_code),
-                    ),
+                    name: value_enum_name(&response_enum_definition_path, name_mapping, entity),
                     module: None,
                 },
             },
@@ -290,15 +562,36 @@ pub fn generate_operation(
         "UndefinedResponse".to_string(),
         EnumValue {
             name: "UndefinedResponse".to_owned(),
-            value_type: TypeDefinition {
-                name: "reqwest::Response".to_owned(),
-                module: Some(ModuleInfo {
-                    name: "reqwest".to_owned(),
-                    path: String::new(),
-                }),
+            value_type: match config.generator.capture_undefined_response {
+                true => TypeDefinition {
+                    name: "crate::undefined_response::UndefinedResponse".to_owned(),
+                    module: None,
+                },
+                false => TypeDefinition {
+                    name: "reqwest::Response".to_owned(),
+                    module: Some(ModuleInfo {
+                        name: "reqwest".to_owned(),
+                        path: String::new(),
+                    }),
+                },
             },
         },
     );
+    if config.generator.max_response_body_size.is_some() {
+        response_enum.values.insert(
+            "BodyTooLarge".to_owned(),
+            EnumValue {
+                name: "BodyTooLarge".to_owned(),
+                value_type: TypeDefinition {
+                    name: "reqwest::Response".to_owned(),
+                    module: Some(ModuleInfo {
+                        name: "reqwest".to_owned(),
+                        path: String::new(),
+                    }),
+                },
+            },
+        );
+    }
     response_enums.push(response_enum);
 
     // Query params
@@ -316,7 +609,16 @@ pub fn generate_operation(
 
     // Request Body
     trace!("Generating request body");
+    let skip_request_body = config
+        .generator
+        .skip_request_body_methods
+        .iter()
+        .any(|skip_method| skip_method.eq_ignore_ascii_case(method.as_str()));
     let request_body = match operation.request_body {
+        Some(_) if skip_request_body => {
+            warn!("Request body of {} requests is skipped", method.as_str());
+            None
+        }
         Some(ref request_body) => {
             match generate_request_body(
                 spec,
@@ -324,6 +626,7 @@ pub fn generate_operation(
                 &operation_definition_path,
                 name_mapping,
                 request_body,
+                &config.generator.content_type_mapping,
                 &function_name,
             ) {
                 Ok(request_body) => Some(request_body),
@@ -345,6 +648,12 @@ pub fn generate_operation(
 
     let multi_content_request_body = request_body_content_types_count > 1;
 
+    // Request bodies are only validated if the generated crate contains the validation module
+    let validated_object_database = match config.generator.validation {
+        true => Some(&*object_database),
+        false => None,
+    };
+
     let multi_request_type_functions = match request_body {
         Some(ref request_entity) => match generate_multi_request_type_functions(
             &operation_definition_path,
@@ -382,12 +691,14 @@ pub fn generate_operation(
     };
 
     let mut request_content_variable_name = None;
+    let mut validate_request_content = false;
 
     if !multi_content_request_body {
         if let Some(request_body) = &request_body {
             for (_, transfer_media_type) in &request_body.content {
                 match transfer_media_type {
-                    TransferMediaType::ApplicationJson(ref type_definition_opt) => {
+                    TransferMediaType::ApplicationJson(ref type_definition_opt)
+                    | TransferMediaType::ApplicationXml(ref type_definition_opt) => {
                         match type_definition_opt {
                             Some(ref type_definition) => {
                                 let variable_name = name_mapping
@@ -403,6 +714,10 @@ pub fn generate_operation(
                                     reference: false,
                                 });
                                 request_content_variable_name = Some(variable_name);
+                                validate_request_content =
+                                    validated_object_database.is_some_and(|object_database| {
+                                        is_validated_type(object_database, &type_definition.name)
+                                    });
                             }
                             None => trace!("Empty request body not added to function params"),
                         }
@@ -417,17 +732,61 @@ pub fn generate_operation(
                         });
                         request_content_variable_name = Some(variable_name);
                     }
+                    TransferMediaType::OctetStream => {
+                        let variable_name = name_mapping
+                            .name_to_property_name(&operation_definition_path, "content");
+                        function_parameters.push(FunctionParameter {
+                            name: variable_name.clone(),
+                            type_name: "Vec<u8>".to_owned(),
+                            reference: false,
+                        });
+                        request_content_variable_name = Some(variable_name);
+                    }
+                    TransferMediaType::EventStream(_) => {
+                        warn!("Event stream request bodies are not supported")
+                    }
+                    TransferMediaType::Ndjson(_) => {
+                        warn!("Ndjson request bodies are not supported")
+                    }
                 }
             }
         }
     }
 
+    // Optional bodies are only sent if they are given
+    let request_body_required = request_body
+        .as_ref()
+        .is_none_or(|request_body| request_body.required);
+    let request_body_optional = match request_content_variable_name {
+        _ if request_body_required => false,
+        _ if multi_content_request_body => {
+            warn!("Request bodies with multiple content types are always required");
+            false
+        }
+        Some(ref variable_name) => {
+            if let Some(content_parameter) = function_parameters
+                .iter_mut()
+                .find(|function_parameter| &function_parameter.name == variable_name)
+            {
+                content_parameter.type_name = match content_parameter.reference {
+                    true => format!("Option<&{}>", content_parameter.type_name),
+                    false => format!("Option<{}>", content_parameter.type_name),
+                };
+                content_parameter.reference = false;
+            }
+            true
+        }
+        None => false,
+    };
+
     trace!("Generating source code");
     let struct_definition_templates = vec![
         Into::<StructDefinitionTemplate>::into(&path_parameter_code.parameters_struct)
-            .serializable(false),
+            .serializable(false)
+            .derives(&config.generator.derives.parameters),
         Into::<StructDefinitionTemplate>::into(&query_parameter_code.query_struct)
-            .serializable(false),
+            .serializable(false)
+            .derives(&config.generator.derives.parameters),
     ];
 
     module_imports.extend(
@@ -445,286 +804,554 @@ pub fn generate_operation(
             .map(|&module| module.clone()),
     );
 
-    if !multi_content_request_body && path_parameter_code.parameters_struct.properties.len() > 0 {
-        function_parameters.push(FunctionParameter {
-            name: path_parameter_code.parameters_struct_variable_name.clone(),
-            type_name: path_parameter_code.parameters_struct.name.clone(),
-            reference: false,
-        });
+    // Path and query parameters follow the request body, they are added once all other
+    // arguments are known
+    let parameters_index = function_parameters.len();
+
+    let mut multi_request_type_functions = multi_request_type_functions.unwrap_or(vec![]);
+    if let Some(object_database) = validated_object_database {
+        for function in multi_request_type_functions.iter_mut() {
+            function.validate_request_content = function
+                .function_parameters
+                .iter()
+                .filter(|parameter| {
+                    function.request_content_variable_name.as_ref() == Some(&parameter.name)
+                })
+                .any(|parameter| is_validated_type(object_database, &parameter.type_name));
+        }
     }
+    let conditional_headers = get_conditional_headers(spec, operation);
+    let conditional = match conditional_headers.is_empty() {
+        true => None,
+        false if multi_content_request_body => {
+            warn!(
+                "Conditional requests with multiple request body content types are not supported"
+            );
+            None
+        }
+        false => {
+            function_parameters.push(FunctionParameter {
+                name: "cache_validation".to_owned(),
+                type_name: "Option<&crate::conditional::CacheValidation>".to_owned(),
+                reference: false,
+            });
+            let not_modified_variant = match conditional_headers
+                .iter()
+                .any(|conditional_header| conditional_header == "if-none-match")
+            {
+                // A documented 304 body keeps its own variant
+                true if response_entities
+                    .get("304")
+                    .is_some_and(|response_entity| !response_entity.content.is_empty()) =>
+                {
+                    None
+                }
+                true => {
+                    // The 304 response is returned with its ETag instead of the empty variant
+                    response_entities.remove("304");
+                    Some(add_not_modified_variant(
+                        name_mapping,
+                        &response_enum_definition_path,
+                        &mut response_enums,
+                    )?)
+                }
+                false => None,
+            };
+            Some(ConditionalCode {
+                not_modified_variant,
+                etag: declares_etag(&operation.responses(spec)),
+            })
+        }
+    };
 
-    let query_struct = &query_parameter_code.query_struct;
-    if query_struct.properties.len() > 0 {
-        function_parameters.push(FunctionParameter {
-            name: query_parameter_code.query_struct_variable_name.clone(),
-            type_name: query_struct.name.clone(),
-            reference: false,
-        });
+    let rate_limit = add_rate_limit_info(&mut response_enums);
+    let (status_variants, link_helpers) = match response_enums.last() {
+        Some(response_enum) => (
+            get_status_variants(response_enum, config.generator.capture_undefined_response),
+            get_link_helpers(spec, config, operation, response_enum, object_database),
+        ),
+        None => return Err("Response enum missing".to_owned()),
+    };
+    let response_result = match config.generator.response_results {
+        true => Some(add_response_result_enums(
+            name_mapping,
+            &operation_definition_path,
+            &function_name,
+            config.generator.capture_undefined_response,
+            &mut response_enums,
+        )?),
+        false => None,
+    };
+
+    let operation_id = operation.operation_id.as_deref().unwrap_or_default();
+    let idempotency_key_header =
+        match get_idempotency_key_header(config, spec, operation, operation_id) {
+            Some(_) if multi_content_request_body => {
+                warn!(
+                    "Idempotency keys with multiple request body content types are not supported"
+                );
+                None
+            }
+            Some(idempotency_key_header) => {
+                function_parameters.push(FunctionParameter {
+                    name: "idempotency_key".to_owned(),
+                    type_name: "Option<&str>".to_owned(),
+                    reference: false,
+                });
+                Some(idempotency_key_header)
+            }
+            None => None,
+        };
+
+    let auth = match get_auth_parameter(
+        config,
+        spec,
+        operation,
+        &operation_definition_path,
+        &function_name,
+    )? {
+        Some(_) if multi_content_request_body => {
+            warn!("Security with multiple request body content types is not supported");
+            None
+        }
+        Some(auth) => {
+            function_parameters.push(auth.function_parameter());
+            Some(auth)
+        }
+        None => None,
+    };
+
+    if config.generator.request_options {
+        function_parameters.push(request_options_parameter());
+        multi_request_type_functions
+            .iter_mut()
+            .for_each(|function| {
+                function
+                    .function_parameters
+                    .push(request_options_parameter())
+            });
     }
 
+    let argument_names = function_parameters
+        .iter()
+        .chain(
+            multi_request_type_functions
+                .iter()
+                .flat_map(|function| function.function_parameters.iter()),
+        )
+        .map(|parameter| parameter.name.as_str())
+        .collect::<BTreeSet<&str>>();
+    let flat_parameters = get_flat_parameters(
+        config.generator.flat_parameters,
+        &path_parameter_code,
+        &query_parameter_code,
+        &argument_names,
+    );
+
+    // Functions of multiple content types take the flat parameters instead of the main function
+    let mut parameter_assignments = vec![];
+    let mut flat_parameter_names = vec![];
+    let parameters = match flat_parameters {
+        Some(ref flat_parameters) if !multi_content_request_body => {
+            parameter_assignments = flat_parameters.assignments.clone();
+            flat_parameter_names = flat_parameters
+                .function_parameters
+                .iter()
+                .map(|parameter| parameter.name.clone())
+                .collect();
+            flat_parameters.function_parameters.clone()
+        }
+        _ => {
+            let mut parameters = vec![];
+            if !multi_content_request_body
+                && path_parameter_code.parameters_struct.properties.len() > 0
+            {
+                parameters.push(FunctionParameter {
+                    name: path_parameter_code.parameters_struct_variable_name.clone(),
+                    type_name: path_parameter_code.parameters_struct.name.clone(),
+                    reference: false,
+                });
+            }
+
+            let query_struct = &query_parameter_code.query_struct;
+            if query_struct.properties.len() > 0 {
+                parameters.push(FunctionParameter {
+                    name: query_parameter_code.query_struct_variable_name.clone(),
+                    type_name: query_struct.name.clone(),
+                    reference: false,
+                });
+            }
+            parameters
+        }
+    };
+    function_parameters.splice(parameters_index..parameters_index, parameters);
+
+    if let Some(ref flat_parameters) = flat_parameters {
+        for function in multi_request_type_functions.iter_mut() {
+            function.function_parameters.retain(|parameter| {
+                parameter.name != path_parameter_code.parameters_struct_variable_name
+                    && parameter.name != query_parameter_code.query_struct_variable_name
+            });
+            // After client and server
+            function
+                .function_parameters
+                .splice(2..2, flat_parameters.function_parameters.iter().cloned());
+            function.parameter_assignments = flat_parameters.assignments.clone();
+        }
+    }
+
+    let pagination = match get_pagination_extension(operation)? {
+        Some(_) if request_body.is_some() => {
+            warn!("Pagination with request body is not supported");
+            None
+        }
+        Some(pagination_extension) => {
+            match generate_pagination_code(
+                &pagination_extension,
+                &response_entities,
+                object_database,
+            ) {
+                Ok(pagination_code) => {
+                    if let Some(ref module) = pagination_code.item_type.module {
+                        module_imports.push(module.clone());
+                    }
+                    Some(pagination_code)
+                }
+                Err(err) => {
+                    warn!("Pagination skipped {}", err);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    let batch = match is_batch_operation(config, operation, operation_id)? {
+        true if multi_content_request_body => {
+            warn!("Batch with multiple request body content types is not supported");
+            None
+        }
+        true => match generate_batch_code(&function_parameters) {
+            Ok(batch_code) => Some(batch_code),
+            Err(err) => {
+                warn!("Batch skipped {}", err);
+                None
+            }
+        },
+        false => None,
+    };
+
+    let examples = generate_operation_examples(
+        spec,
+        operation,
+        &function_name,
+        &request_body,
+        &response_entities,
+    );
+
     let function_visibility = match multi_content_request_body {
         true => "",
         false => "pub",
     };
 
-    let request_media_type = match request_body {
+    // The main function of multiple request types is private
+    let download_statuses = match multi_content_request_body {
+        true => vec![],
+        false => response_entities
+            .iter()
+            .filter(|(_, response_entity)| {
+                response_entity.content.values().any(|transfer_media_type| {
+                    matches!(transfer_media_type, TransferMediaType::OctetStream)
+                })
+            })
+            .filter_map(|(response_key, _)| response_key.parse::<u16>().ok())
+            .collect(),
+    };
+
+    let has_xml_content = request_body
+        .iter()
+        .flat_map(|request_body| request_body.content.values())
+        .chain(
+            response_entities
+                .values()
+                .flat_map(|response_entity| response_entity.content.values()),
+        )
+        .any(|transfer_media_type| {
+            matches!(transfer_media_type, TransferMediaType::ApplicationXml(_))
+        });
+
+    let (request_media_type, request_content_type) = match request_body {
         Some(request_body) => {
             if request_body.content.len() > 1 {
                 warn!("Multiple request body content types not supported yet");
             }
-            let mut media_type = String::new();
-            for (_, transfer_media_type) in request_body.content {
-                media_type = match transfer_media_type {
-                    TransferMediaType::ApplicationJson(_) => "application/json".to_owned(),
-                    TransferMediaType::TextPlain => "text/plain".to_owned(),
-                };
+            let mut media_type = (String::new(), String::new());
+            for (content_type, transfer_media_type) in request_body.content {
+                media_type = (transfer_media_type.content_type().to_owned(), content_type);
                 // TODO: multiple request types not supported
                 break;
             }
             media_type
         }
-        None => String::new(),
+        None => (String::new(), String::new()),
     };
 
+    if validate_request_content
+        || multi_request_type_functions
+            .iter()
+            .any(|function| function.validate_request_content)
+    {
+        module_imports.push(ModuleInfo {
+            name: "Validate".to_owned(),
+            path: "crate::validation".to_owned(),
+        });
+    }
+
     let template = HttpRequestTemplate {
         module_imports: to_unique_list(&module_imports),
         struct_definitions: struct_definition_templates,
         enum_definitions: response_enums
             .iter()
-            .map(|enum_def| Into::<EnumDefinitionTemplate>::into(enum_def).serializable(false))
+            .map(|enum_def| {
+                Into::<EnumDefinitionTemplate>::into(enum_def)
+                    .serializable(false)
+                    .derives(&config.generator.derives.responses)
+            })
             .collect(),
         primitive_definitions: vec![],
+        const_definitions: vec![],
         response_type_name: response_enum_name,
         function_visibility: function_visibility.to_owned(),
         function_name: function_name,
         function_parameters: function_parameters,
+        parameter_assignments,
+        flat_parameter_names,
+        path_parameter_arguments: path_parameter_code.format_arguments().join(", "),
         path_format_string: path_parameter_code.path_format_string,
-        path_parameter_arguments: path_parameter_code
-            .path_format_arguments
-            .iter()
-            .map(|property| {
-                format!(
-                    "{}.{}",
-                    path_parameter_code.parameters_struct_variable_name, property.name
-                )
-            })
-            .collect::<Vec<String>>()
-            .join(", "),
         request_media_type: request_media_type,
+        request_content_type,
         request_body_content_types_count: request_body_content_types_count,
         request_content_variable_name: request_content_variable_name,
+        request_body_optional,
         request_method: method.as_str().to_lowercase(),
         has_response_any_multi_content_type: has_response_any_multi_content_type,
-        query_parameters_mutable: query_struct
-            .properties
-            .iter()
-            .filter(|(_, property)| !property.required || property.type_name.starts_with("Vec<"))
-            .collect::<Vec<(&String, &PropertyDefinition)>>()
-            .len()
-            > 0,
-        query_parameters: query_struct
-            .properties
-            .iter()
-            .map(|(_, property)| QueryParameter {
-                real_name: property.real_name.clone(),
-                name: property.name.clone(),
-                struct_name: query_parameter_code.query_struct_variable_name.clone(),
-                is_required: property.required,
-                is_array: property.type_name.starts_with("Vec<"),
-            })
-            .collect(),
+        query_parameters_mutable: query_parameter_code.query_parameters_mutable(),
+        query_parameters: query_parameter_code.query_parameters(),
         responses: response_entities,
-        multi_request_type_functions: multi_request_type_functions.unwrap_or(vec![]),
-        media_type_enum_name: media_type_enum_name,
+        multi_request_type_functions,
+        pagination,
+        batch,
+        conditional,
+        rate_limit,
+        status_variants,
+        link_helpers,
+        response_result,
+        max_response_body_size: config.generator.max_response_body_size,
+        download_statuses,
+        request_options: config.generator.request_options,
+        capture_undefined_response: config.generator.capture_undefined_response,
+        request_logging: config.generator.request_logging,
+        // The server deduplicates retries of requests with the same idempotency key
+        retry_request: is_idempotent_method(method) || idempotency_key_header.is_some(),
+        idempotency_key_header,
+        auth,
+        aws_sigv4: config.generator.aws_sigv4.is_some(),
+        operation_server: get_operation_server(config, spec, path, operation, operation_id),
+        error_type_name: match (
+            config.generator.validation,
+            has_xml_content,
+            config.generator.aws_sigv4.is_some(),
+        ) {
+            (true, _, _) => "crate::validation::RequestError".to_owned(),
+            (false, true, _) => "crate::xml::XmlRequestError".to_owned(),
+            (false, false, true) => "crate::aws_sigv4::SignedRequestError".to_owned(),
+            (false, false, false) => "reqwest::Error".to_owned(),
+        },
+        validate_request_content,
         name_mapping: name_mapping.clone(),
         operation_definition_path: operation_definition_path.clone(),
         response_enum_definition_path: response_enum_definition_path.clone(),
+        path_parameters: path_parameter_code.path_format_arguments,
+        examples,
     };
 
-    template.render().map_err(|err| err.to_string())
+    Ok(template)
 }
 
-fn media_type_enum_name(
-    definition_path: &Vec<String>,
-    name_mapping: &NameMapping,
-    transfer_media_type: &TransferMediaType,
-) -> String {
-    let name = match transfer_media_type {
-        TransferMediaType::ApplicationJson(_) => "Json",
-        TransferMediaType::TextPlain => "Text",
-    };
-    name_mapping.name_to_struct_name(definition_path, name)
-}
+/// Example functions of json request and response bodies with `example`/`examples`
+fn generate_operation_examples(
+    spec: &Spec,
+    operation: &Operation,
+    function_name: &str,
+    request_body: &Option<RequestEntity>,
+    response_entities: &ResponseEntities,
+) -> Vec<OperationExampleTemplate> {
+    let mut examples = vec![];
+
+    let request_media_type = operation
+        .request_body
+        .as_ref()
+        .and_then(|request_body| request_body.resolve(spec).ok())
+        .and_then(|request_body| request_body.content.get("application/json").cloned());
+    let request_type = request_body
+        .as_ref()
+        .and_then(|request_body| request_body.content.get("application/json"));
+    if let (Some(media_type), Some(TransferMediaType::ApplicationJson(Some(type_definition)))) =
+        (request_media_type, request_type)
+    {
+        if let Some(example) = get_media_type_explicit_example(spec, &media_type) {
+            examples.push(OperationExampleTemplate {
+                function_name: format!("{}_request_example", function_name.trim_end_matches('_')),
+                type_name: type_definition.name.clone(),
+                value: raw_string_literal(&example.to_string()),
+            });
+        }
+    }
 
-struct PathParameterCode {
-    pub parameters_struct_variable_name: String,
-    pub parameters_struct: StructDefinition,
-    pub path_format_string: String,
-    pub path_format_arguments: Vec<PropertyDefinition>,
+    let responses = operation.responses(spec);
+    for (status_code, response_entity) in response_entities {
+        let response_type = response_entity.content.get("application/json");
+        let response_media_type = responses
+            .get(status_code)
+            .and_then(|response| response.content.get("application/json"));
+        if let (Some(TransferMediaType::ApplicationJson(Some(type_definition))), Some(media_type)) =
+            (response_type, response_media_type)
+        {
+            if let Some(example) = get_media_type_explicit_example(spec, media_type) {
+                examples.push(OperationExampleTemplate {
+                    function_name: format!(
+                        "{}_{}_response_example",
+                        function_name.trim_end_matches('_'),
+                        status_code
+                    ),
+                    type_name: type_definition.name.clone(),
+                    value: raw_string_literal(&example.to_string()),
+                });
+            }
+        }
+    }
+
+    examples
 }
 
-fn generate_path_parameter_code(
-    definition_path: &Vec<String>,
+/// `NotModified(Option<String>)` variant with the ETag of 304 responses of the response enum
+fn add_not_modified_variant(
     name_mapping: &NameMapping,
-    function_name: &str,
-    path: &str,
-) -> Result<PathParameterCode, String> {
-    trace!("Generating path parameters");
-    let path_parameters_struct_name = name_mapping.name_to_struct_name(
-        &definition_path,
-        &format!("{}PathParameters", function_name),
+    response_enum_definition_path: &Vec<String>,
+    response_enums: &mut [EnumDefinition],
+) -> Result<String, String> {
+    let response_enum = match response_enums.last_mut() {
+        Some(response_enum) => response_enum,
+        None => return Err("Response enum missing".to_owned()),
+    };
+    let variant_name = name_mapping.name_to_variant_name(
+        response_enum_definition_path,
+        &name_mapping.status_code_to_canonical_name(reqwest::StatusCode::NOT_MODIFIED)?,
+    );
+    response_enum.values.insert(
+        "304".to_owned(),
+        EnumValue {
+            name: variant_name.clone(),
+            value_type: TypeDefinition {
+                name: "Option<String>".to_owned(),
+                module: None,
+            },
+        },
     );
+    Ok(variant_name)
+}
 
-    let parameters_struct_variable_name =
-        name_mapping.name_to_property_name(definition_path, "path_parameters");
-
-    let mut path_parameters_definition_path = definition_path.clone();
-    path_parameters_definition_path.push(path_parameters_struct_name.clone());
-
-    let path_parameters_ordered = path
-        .split("/")
-        .filter(|&path_component| is_path_parameter(&path_component))
-        .map(|path_component| path_component.replace("{", "").replace("}", ""))
-        .map(|path_component| PropertyDefinition {
-            module: None,
-            name: name_mapping
-                .name_to_property_name(&path_parameters_definition_path, &path_component),
-            real_name: path_component,
-            required: true,
-            type_name: "&str".to_owned(),
-        })
-        .collect::<Vec<PropertyDefinition>>();
-    let path_struct_definition = StructDefinition {
-        name: path_parameters_struct_name,
-        used_modules: vec![],
-        local_objects: HashMap::new(),
-        properties: path_parameters_ordered
-            .iter()
-            .map(|path_component| {
-                (
-                    path_component.name.clone(),
-                    PropertyDefinition {
-                        module: None,
-                        name: path_component.name.clone(),
-                        real_name: path_component.real_name.clone(),
-                        required: path_component.required,
-                        type_name: "String".to_owned(),
-                    },
-                )
-            })
-            .collect::<HashMap<String, PropertyDefinition>>(),
+/// 429 variants additionally contain the rate limit headers of the response
+fn add_rate_limit_info(response_enums: &mut [EnumDefinition]) -> bool {
+    let rate_limit_value = match response_enums
+        .last_mut()
+        .and_then(|response_enum| response_enum.values.get_mut("429"))
+    {
+        Some(rate_limit_value) => rate_limit_value,
+        None => return false,
     };
+    rate_limit_value.value_type.name = match rate_limit_value.value_type.name.is_empty() {
+        true => "crate::rate_limit::RateLimitInfo".to_owned(),
+        false => format!(
+            "crate::rate_limit::RateLimited<{}>",
+            rate_limit_value.value_type.name
+        ),
+    };
+    true
+}
 
-    let path_format_string = path
-        .split("/")
-        .map(|path_component| {
-            return match is_path_parameter(path_component) {
-                true => String::from("{}"),
-                _ => path_component.to_owned(),
-            };
-        })
-        .collect::<Vec<String>>()
-        .join("/");
-
-    Ok(PathParameterCode {
-        parameters_struct_variable_name,
-        parameters_struct: path_struct_definition,
-        path_format_string: path_format_string,
-        path_format_arguments: path_parameters_ordered,
-    })
+fn request_options_parameter() -> FunctionParameter {
+    FunctionParameter {
+        name: "request_options".to_owned(),
+        type_name: "Option<&crate::request_options::RequestOptions>".to_owned(),
+        reference: false,
+    }
 }
 
-struct QueryParametersCode {
-    pub query_struct: StructDefinition,
-    pub query_struct_variable_name: String,
+/// Only requests which can be repeated without side effects are retried
+fn is_idempotent_method(method: &reqwest::Method) -> bool {
+    [
+        reqwest::Method::GET,
+        reqwest::Method::HEAD,
+        reqwest::Method::PUT,
+        reqwest::Method::DELETE,
+        reqwest::Method::OPTIONS,
+    ]
+    .contains(method)
 }
 
-fn generate_query_parameter_code(
-    spec: &Spec,
-    operation: &Operation,
-    definition_path: &Vec<String>,
+/// Variant of the response enum for a status
+pub(super) fn status_variant_name(
+    response_enum_definition_path: &Vec<String>,
     name_mapping: &NameMapping,
-    object_database: &mut ObjectDatabase,
-    function_name: &str,
-) -> Result<QueryParametersCode, String> {
-    trace!("Generating query params");
-    let mut query_struct = StructDefinition {
-        name: name_mapping.name_to_struct_name(
-            &definition_path,
-            &format!("{}QueryParameters", &function_name),
-        ),
-        properties: HashMap::new(),
-        used_modules: vec![],
-        local_objects: HashMap::new(),
-    };
-
-    let query_struct_variable_name =
-        name_mapping.name_to_property_name(&definition_path, "query_parameters");
-
-    let mut query_parameters_definition_path = definition_path.clone();
-    query_parameters_definition_path.push(query_struct.name.clone());
-
-    for parameter_ref in &operation.parameters {
-        let parameter = match parameter_ref.resolve(spec) {
-            Ok(parameter) => parameter,
-            Err(err) => return Err(format!("Failed to resolve parameter {}", err.to_string())),
-        };
-        if parameter.location != ParameterIn::Query {
-            continue;
-        }
+    response_entity: &ResponseEntity,
+) -> String {
+    name_mapping.name_to_variant_name(
+        response_enum_definition_path,
+        &response_entity.canonical_status_code,
+    )
+}
 
-        let parameter_type = match parameter.schema {
-            Some(schema) => match schema.resolve(spec) {
-                Ok(object_schema) => get_type_from_schema(
-                    spec,
-                    object_database,
-                    query_parameters_definition_path.clone(),
-                    &object_schema,
-                    Some(&parameter.name),
-                    name_mapping,
-                ),
-                Err(err) => {
-                    return Err(format!(
-                        "Failed to resolve parameter {} {}",
-                        parameter.name,
-                        err.to_string()
-                    ))
-                }
-            },
-            None => return Err(format!("Parameter {} has no schema", parameter.name)),
-        };
+/// Enum of the content types of a status with multiple ones
+fn value_enum_name(
+    response_enum_definition_path: &Vec<String>,
+    name_mapping: &NameMapping,
+    response_entity: &ResponseEntity,
+) -> String {
+    name_mapping.name_to_struct_name(
+        response_enum_definition_path,
+        &format!("{}Value", response_entity.canonical_status_code),
+    )
+}
 
-        let _ = match parameter_type {
-            Ok(parameter_type) => query_struct.properties.insert(
-                name_mapping
-                    .name_to_property_name(&query_parameters_definition_path, &parameter.name),
-                PropertyDefinition {
-                    name: name_mapping
-                        .name_to_property_name(&query_parameters_definition_path, &parameter.name),
-                    module: parameter_type.module,
-                    real_name: parameter.name,
-                    required: match parameter.required {
-                        Some(required) => required,
-                        None => false,
-                    },
-                    type_name: parameter_type.name,
-                },
-            ),
-            Err(err) => return Err(err),
-        };
+/// Name of a media type in variants and functions, e.g. `Json`
+fn media_type_name(transfer_media_type: &TransferMediaType) -> &'static str {
+    match transfer_media_type {
+        TransferMediaType::ApplicationJson(_) => "Json",
+        TransferMediaType::ApplicationXml(_) => "Xml",
+        TransferMediaType::TextPlain => "Text",
+        TransferMediaType::OctetStream => "Binary",
+        TransferMediaType::EventStream(_) => "Events",
+        TransferMediaType::Ndjson(_) => "Lines",
     }
+}
 
-    Ok(QueryParametersCode {
-        query_struct_variable_name,
-        query_struct,
-    })
+fn media_type_variant_name(
+    definition_path: &Vec<String>,
+    name_mapping: &NameMapping,
+    transfer_media_type: &TransferMediaType,
+) -> String {
+    name_mapping.name_to_variant_name(definition_path, media_type_name(transfer_media_type))
 }
 
 struct MultiRequestTypeFunction {
     function_name: String,
     function_parameters: Vec<FunctionParameter>,
+    parameter_assignments: Vec<String>,
     request_media_type: String,
+    request_content_type: String,
     request_content_variable_name: Option<String>,
+    validate_request_content: bool,
 }
 
 fn generate_multi_request_type_functions(
@@ -741,13 +1368,14 @@ fn generate_multi_request_type_functions(
         return function_definitions;
     }
 
-    for (_, transfer_media_type) in &request_entity.content {
+    for (content_type, transfer_media_type) in &request_entity.content {
         let content_function_name = name_mapping.name_to_property_name(
             &definition_path,
             &format!(
                 "{}{}",
                 function_name,
-                media_type_enum_name(&definition_path, name_mapping, &transfer_media_type)
+                name_mapping
+                    .name_to_struct_name(&definition_path, media_type_name(transfer_media_type))
             ),
         );
         let mut function_parameters: Vec<FunctionParameter> = vec![
@@ -782,7 +1410,8 @@ fn generate_multi_request_type_functions(
 
         let mut request_content_variable_name = None;
         match transfer_media_type {
-            TransferMediaType::ApplicationJson(ref type_definition_opt) => {
+            TransferMediaType::ApplicationJson(ref type_definition_opt)
+            | TransferMediaType::ApplicationXml(ref type_definition_opt) => {
                 match type_definition_opt {
                     Some(ref type_definition) => {
                         let variable_name =
@@ -812,16 +1441,32 @@ fn generate_multi_request_type_functions(
 
                 request_content_variable_name = Some(variable_name);
             }
+            TransferMediaType::OctetStream => {
+                let variable_name = name_mapping.name_to_property_name(definition_path, "content");
+                function_parameters.push(FunctionParameter {
+                    name: variable_name.clone(),
+                    type_name: "Vec<u8>".to_owned(),
+                    reference: false,
+                });
+
+                request_content_variable_name = Some(variable_name);
+            }
+            TransferMediaType::EventStream(_) => {
+                warn!("Event stream request bodies are not supported")
+            }
+            TransferMediaType::Ndjson(_) => {
+                warn!("Ndjson request bodies are not supported")
+            }
         }
 
         function_definitions.push(MultiRequestTypeFunction {
             function_name: content_function_name,
             function_parameters: function_parameters,
+            parameter_assignments: vec![],
             request_content_variable_name: request_content_variable_name,
-            request_media_type: match transfer_media_type {
-                TransferMediaType::ApplicationJson(_) => "application/json".to_owned(),
-                TransferMediaType::TextPlain => "text/plain".to_owned(),
-            },
+            request_media_type: transfer_media_type.content_type().to_owned(),
+            request_content_type: content_type.clone(),
+            validate_request_content: false,
         });
     }
 
@@ -0,0 +1,473 @@
+use askama::Template;
+use log::warn;
+use oas3::{
+    spec::{Operation, ParameterIn},
+    Spec,
+};
+use serde_json::Value;
+
+use crate::{
+    generator::{media_type::TransferMediaType, rust_reqwest_async::templates::raw_string_literal},
+    parser::component::{
+        example::{get_media_type_example, get_schema_example},
+        object_definition::types::PropertyDefinition,
+    },
+    utils::config::Config,
+};
+
+use super::{
+    http_request::{status_variant_name, HttpRequestTemplate},
+    security::AuthParameter,
+    utils::QueryParameter,
+};
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/http_test.rs.jinja", ext = "rs")]
+struct HttpTestTemplate {
+    crate_name: String,
+    module_name: String,
+    function_name: String,
+    matchers: Vec<&'static str>,
+    request_method: String,
+    expected_path: String,
+    expected_query: Vec<(String, String)>,
+    request_content_type: Option<String>,
+    response_status: String,
+    response_body: String,
+    arguments: Vec<String>,
+    response_type_name: String,
+    response_variant: String,
+}
+
+pub fn generate_operation_test(
+    spec: &Spec,
+    config: &Config,
+    method: &reqwest::Method,
+    path: &str,
+    operation: &Operation,
+    request_template: &HttpRequestTemplate,
+) -> Result<Option<String>, String> {
+    let function_name = &request_template.function_name;
+    if request_template.request_body_content_types_count > 1 {
+        warn!("Tests for multiple request content types are not supported");
+        return Ok(None);
+    }
+    if request_template.request_media_type == "application/xml" {
+        warn!("Tests for xml request bodies are not supported");
+        return Ok(None);
+    }
+
+    let name_mapping = &request_template.name_mapping;
+    let definition_path = vec![path.to_owned()];
+    let path_parameters_variable_name =
+        name_mapping.name_to_property_name(&definition_path, "path_parameters");
+    let query_parameters_variable_name =
+        name_mapping.name_to_property_name(&definition_path, "query_parameters");
+    let content_variable_name = name_mapping.name_to_property_name(&definition_path, "content");
+
+    let crate_name = config.project_metadata.name.replace('-', "_");
+    let mut expected_path = path.to_owned();
+    let mut expected_query = vec![];
+    let mut arguments = vec![];
+    for function_parameter in &request_template.function_parameters {
+        let argument = match function_parameter.name.as_str() {
+            "client" => "&client".to_owned(),
+            "server" => "&server.uri()".to_owned(),
+            "request_options" => "None".to_owned(),
+            "auth" if request_template.auth.is_some() => match request_template.auth {
+                Some(ref auth) if !auth.required => "None".to_owned(),
+                // Credentials of the generated crate, the test is a crate of its own
+                Some(AuthParameter {
+                    example: Some(ref example),
+                    ..
+                }) => format!(
+                    "&{}",
+                    example.replace("crate::", &format!("{}::", crate_name))
+                ),
+                _ => {
+                    warn!("Test skipped, the required schemes have no credentials");
+                    return Ok(None);
+                }
+            },
+            name if name == path_parameters_variable_name => {
+                let mut fields = String::new();
+                for path_parameter in &request_template.path_parameters {
+                    let value = get_path_parameter_argument(
+                        spec,
+                        operation,
+                        path_parameter,
+                        &mut expected_path,
+                    );
+                    fields += &format!("{}: {}, ", path_parameter.name, value);
+                }
+                format!("{} {{ {}}}", function_parameter.type_name, fields)
+            }
+            name if name == query_parameters_variable_name => {
+                let mut fields = String::new();
+                for query_parameter in &request_template.query_parameters {
+                    let value = get_query_parameter_argument(
+                        spec,
+                        operation,
+                        query_parameter,
+                        &mut expected_query,
+                    );
+                    fields += &format!("{}: {}, ", query_parameter.name, value);
+                }
+                format!("{} {{ {}}}", function_parameter.type_name, fields)
+            }
+            name if name == content_variable_name => {
+                let example = get_request_body_example(
+                    spec,
+                    operation,
+                    &request_template.request_content_type,
+                );
+                let content = match request_template.request_media_type.as_str() {
+                    "application/json" => json_value_code(&example.unwrap_or(Value::Null)),
+                    "text/plain" => format!(
+                        "&String::from({:?})",
+                        example
+                            .as_ref()
+                            .and_then(|example| example.as_str())
+                            .unwrap_or("text")
+                    ),
+                    _ => "b\"binary\".to_vec()".to_owned(),
+                };
+                match request_template.request_body_optional {
+                    true => format!("Some({})", content),
+                    false => content,
+                }
+            }
+            name if request_template
+                .flat_parameter_names
+                .iter()
+                .any(|flat_parameter_name| flat_parameter_name == name) =>
+            {
+                match (
+                    request_template
+                        .path_parameters
+                        .iter()
+                        .find(|path_parameter| path_parameter.name == name),
+                    request_template
+                        .query_parameters
+                        .iter()
+                        .find(|query_parameter| query_parameter.name == name),
+                ) {
+                    (Some(path_parameter), _) => get_path_parameter_argument(
+                        spec,
+                        operation,
+                        path_parameter,
+                        &mut expected_path,
+                    ),
+                    (None, Some(query_parameter)) => get_query_parameter_argument(
+                        spec,
+                        operation,
+                        query_parameter,
+                        &mut expected_query,
+                    ),
+                    (None, None) => {
+                        warn!("Test skipped, unknown flat parameter {}", name);
+                        return Ok(None);
+                    }
+                }
+            }
+            name => {
+                warn!("Test skipped, unknown function parameter {}", name);
+                return Ok(None);
+            }
+        };
+        arguments.push(argument);
+    }
+
+    // Prefer a successful response with content
+    let response = request_template
+        .responses
+        .iter()
+        .find(|(status, response)| status.starts_with('2') && !response.content.is_empty())
+        .or(request_template
+            .responses
+            .iter()
+            .find(|(status, _)| status.starts_with('2')))
+        .or(request_template.responses.iter().next());
+
+    let (response_status, response_variant, response_body) = match response {
+        Some((status, response_entity)) => match response_entity.content.iter().next() {
+            Some((content_type, transfer_media_type)) => {
+                let example = operation
+                    .responses(spec)
+                    .get(status)
+                    .and_then(|response| response.content.get(content_type))
+                    .and_then(|media_type| get_media_type_example(spec, media_type));
+                let response_body = match transfer_media_type {
+                    TransferMediaType::ApplicationJson(_) => format!(
+                        ".set_body_json(serde_json::from_str::<serde_json::Value>({}).unwrap())",
+                        raw_string_literal(
+                            &example
+                                .unwrap_or(Value::Object(serde_json::Map::new()))
+                                .to_string()
+                        )
+                    ),
+                    TransferMediaType::TextPlain => format!(
+                        ".set_body_string({:?})",
+                        example
+                            .as_ref()
+                            .and_then(|example| example.as_str())
+                            .unwrap_or("text")
+                    ),
+                    TransferMediaType::ApplicationXml(_) => {
+                        warn!("Tests for xml response bodies are not supported");
+                        return Ok(None);
+                    }
+                    _ => ".set_body_bytes(b\"binary\".to_vec())".to_owned(),
+                };
+                (
+                    status.clone(),
+                    status_variant_name(
+                        &request_template.response_enum_definition_path,
+                        name_mapping,
+                        response_entity,
+                    ),
+                    response_body,
+                )
+            }
+            None => (
+                status.clone(),
+                status_variant_name(
+                    &request_template.response_enum_definition_path,
+                    name_mapping,
+                    response_entity,
+                ),
+                String::new(),
+            ),
+        },
+        None => (
+            "200".to_owned(),
+            "UndefinedResponse".to_owned(),
+            String::new(),
+        ),
+    };
+
+    let request_content_type = match request_template.request_media_type.as_str() {
+        "application/json" => Some(request_template.request_content_type.clone()),
+        _ => None,
+    };
+
+    let mut matchers = vec![];
+    if request_content_type.is_some() {
+        matchers.push("header");
+    }
+    matchers.extend(["method", "path"]);
+    if !expected_query.is_empty() {
+        matchers.push("query_param");
+    }
+
+    let template = HttpTestTemplate {
+        crate_name,
+        module_name: match operation.operation_id {
+            Some(ref operation_id) => config.name_mapping.name_to_module_name(operation_id),
+            None => function_name.clone(),
+        },
+        function_name: function_name.clone(),
+        matchers,
+        request_method: method.as_str().to_owned(),
+        expected_path: encode_non_ascii(&expected_path),
+        expected_query,
+        request_content_type,
+        response_status,
+        response_body,
+        arguments,
+        response_type_name: request_template.response_type_name.clone(),
+        response_variant,
+    };
+
+    template.render().map(Some).map_err(|err| err.to_string())
+}
+
+/// Requests are matched by their url encoded path, e.g. `/größen` -> `/gr%C3%B6%C3%9Fen`
+fn encode_non_ascii(path: &str) -> String {
+    path.chars()
+        .map(|character| match character.is_ascii() {
+            true => character.to_string(),
+            false => character
+                .to_string()
+                .bytes()
+                .map(|byte| format!("%{:02X}", byte))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Value of the path parameter, its example replaces the parameter in the expected path
+fn get_path_parameter_argument(
+    spec: &Spec,
+    operation: &Operation,
+    path_parameter: &PropertyDefinition,
+    expected_path: &mut String,
+) -> String {
+    let value = get_path_parameter_example(spec, operation, path_parameter);
+    *expected_path = expected_path.replace(&format!("{{{}}}", path_parameter.real_name), &value);
+    match path_parameter.type_name.as_str() {
+        "String" => format!("{:?}.to_owned()", value),
+        _ => format!(
+            "{:?}.parse().expect(\"Invalid path parameter example\")",
+            value
+        ),
+    }
+}
+
+/// Value of the query parameter, the query pairs of its example are expected
+fn get_query_parameter_argument(
+    spec: &Spec,
+    operation: &Operation,
+    query_parameter: &QueryParameter,
+    expected_query: &mut Vec<(String, String)>,
+) -> String {
+    let example = get_query_parameter_example(spec, operation, &query_parameter.real_name);
+    expected_query.extend(
+        get_query_pairs(query_parameter, &example)
+            .into_iter()
+            .map(|(name, value)| (name, format!("{:?}", value))),
+    );
+    let value = json_value_code(&example);
+    match query_parameter.is_required {
+        true => value,
+        false => format!("Some({})", value),
+    }
+}
+
+/// Path parameters are only replaced by examples which don't need url encoding
+fn get_path_parameter_example(
+    spec: &Spec,
+    operation: &Operation,
+    path_parameter: &PropertyDefinition,
+) -> String {
+    let fallback_example = match path_parameter.type_name.as_str() {
+        "bool" => "true",
+        _ => "1",
+    };
+    let example = match get_parameter_example(
+        spec,
+        operation,
+        ParameterIn::Path,
+        &path_parameter.real_name,
+    ) {
+        Some(Value::String(example)) => example,
+        Some(Value::Number(example)) => example.to_string(),
+        Some(Value::Bool(example)) => example.to_string(),
+        _ => return fallback_example.to_owned(),
+    };
+
+    match !example.is_empty()
+        && example
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.~".contains(c))
+    {
+        true => example,
+        false => fallback_example.to_owned(),
+    }
+}
+
+fn get_query_parameter_example(spec: &Spec, operation: &Operation, parameter_name: &str) -> Value {
+    get_parameter_example(spec, operation, ParameterIn::Query, parameter_name)
+        .unwrap_or(Value::String("string".to_owned()))
+}
+
+fn get_parameter_example(
+    spec: &Spec,
+    operation: &Operation,
+    location: ParameterIn,
+    parameter_name: &str,
+) -> Option<Value> {
+    let parameter = operation
+        .parameters
+        .iter()
+        .filter_map(|parameter_ref| parameter_ref.resolve(spec).ok())
+        .find(|parameter| parameter.location == location && parameter.name == parameter_name)?;
+
+    if let Some(example) = parameter.example {
+        return Some(example);
+    }
+
+    match parameter.schema {
+        Some(ref schema_ref) => schema_ref
+            .resolve(spec)
+            .ok()
+            .map(|schema| get_schema_example(spec, &schema)),
+        None => None,
+    }
+}
+
+fn get_request_body_example(
+    spec: &Spec,
+    operation: &Operation,
+    content_type: &str,
+) -> Option<Value> {
+    let request_body = operation.request_body.as_ref()?.resolve(spec).ok()?;
+    let media_type = request_body.content.get(content_type)?;
+    get_media_type_example(spec, media_type)
+}
+
+/// Query pairs as sent by the generated code for the parameter style
+fn get_query_pairs(query_parameter: &QueryParameter, value: &Value) -> Vec<(String, String)> {
+    match (value, &query_parameter.separator) {
+        (Value::Object(fields), separator) if query_parameter.has_fields() => {
+            let field_pairs = fields
+                .iter()
+                .filter(|(_, field_value)| !field_value.is_null())
+                .map(|(field_name, field_value)| {
+                    let field_value = match field_value {
+                        Value::String(field_value) => field_value.clone(),
+                        field_value => field_value.to_string(),
+                    };
+                    (field_name.clone(), field_value)
+                });
+            match separator {
+                _ if query_parameter.deep_object => field_pairs
+                    .map(|(field_name, field_value)| {
+                        (
+                            format!("{}[{}]", query_parameter.real_name, field_name),
+                            field_value,
+                        )
+                    })
+                    .collect(),
+                Some(separator) => vec![(
+                    query_parameter.real_name.clone(),
+                    field_pairs
+                        .flat_map(|(field_name, field_value)| [field_name, field_value])
+                        .collect::<Vec<String>>()
+                        .join(separator),
+                )],
+                None => field_pairs.collect(),
+            }
+        }
+        (Value::Array(_), Some(separator)) => vec![(
+            query_parameter.real_name.clone(),
+            get_query_values(value).join(separator),
+        )],
+        _ => get_query_values(value)
+            .into_iter()
+            .map(|value| (query_parameter.real_name.clone(), value))
+            .collect(),
+    }
+}
+
+/// Query values as sent by the generated code (`to_string` of each value or array item)
+fn get_query_values(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(value) => vec![value.clone()],
+        Value::Bool(value) => vec![value.to_string()],
+        Value::Number(value) => match value.as_f64() {
+            Some(float_value) if value.is_f64() => vec![float_value.to_string()],
+            _ => vec![value.to_string()],
+        },
+        Value::Array(values) => values.iter().flat_map(get_query_values).collect(),
+        _ => vec![],
+    }
+}
+
+/// Code which deserializes the json value into the inferred type
+fn json_value_code(value: &Value) -> String {
+    format!(
+        "serde_json::from_str({}).unwrap()",
+        raw_string_literal(&value.to_string())
+    )
+}
@@ -0,0 +1,38 @@
+use oas3::{
+    spec::{Operation, ParameterIn},
+    Spec,
+};
+
+use crate::utils::config::Config;
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+pub fn is_idempotency_key_header(name: &str) -> bool {
+    name.eq_ignore_ascii_case(IDEMPOTENCY_KEY_HEADER)
+}
+
+/// Name of the `Idempotency-Key` header parameter of the operation, or the default name if
+/// its id is listed in `generator.idempotency_key_operations`
+pub fn get_idempotency_key_header(
+    config: &Config,
+    spec: &Spec,
+    operation: &Operation,
+    operation_id: &str,
+) -> Option<String> {
+    let header_parameter = operation
+        .parameters
+        .iter()
+        .filter_map(|parameter| parameter.resolve(spec).ok())
+        .find(|parameter| {
+            parameter.location == ParameterIn::Header && is_idempotency_key_header(&parameter.name)
+        });
+    match header_parameter {
+        Some(header_parameter) => Some(header_parameter.name),
+        None => config
+            .generator
+            .idempotency_key_operations
+            .iter()
+            .any(|idempotency_key_operation| idempotency_key_operation == operation_id)
+            .then(|| IDEMPOTENCY_KEY_HEADER.to_owned()),
+    }
+}
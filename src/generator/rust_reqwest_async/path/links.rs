@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+
+use log::warn;
+use oas3::{
+    spec::{Link, ObjectOrReference, Operation},
+    Spec,
+};
+
+use crate::{
+    generator::rust_reqwest_async::features::{get_cfg_attribute, get_tag_feature_name},
+    parser::component::object_definition::types::{
+        EnumDefinition, ObjectDatabase, ObjectDefinition, StructDefinition,
+    },
+    utils::config::Config,
+};
+
+use super::utils::{generate_path_parameter_code, generate_query_parameter_code};
+
+/// Types which are converted with `to_string()` if the linked parameter is a string
+const DISPLAY_TYPES: [&str; 11] = [
+    "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64", "bool",
+];
+
+/// Function of a response enum which fills the parameters of a linked operation with values
+/// of the response body, e.g. `get_user_path_parameters(&self) -> Option<GetUserPathParameters>`
+pub struct LinkHelper {
+    pub function_name: String,
+    pub link_name: String,
+    /// Path of the function of the linked operation
+    pub linked_function: String,
+    pub return_type: String,
+    /// `#[cfg(..)]` of the module of the linked operation
+    pub cfg_attribute: String,
+    /// Pattern of each variant with the link and the expression of the parameters, other
+    /// variants return `None`
+    pub variants: Vec<(String, String)>,
+}
+
+/// Links of the responses which can be filled from `$response.body#/..` values
+pub fn get_link_helpers(
+    spec: &Spec,
+    config: &Config,
+    operation: &Operation,
+    response_enum: &EnumDefinition,
+    object_database: &ObjectDatabase,
+) -> Vec<LinkHelper> {
+    let mut link_helpers: Vec<LinkHelper> = vec![];
+
+    for (response_key, response) in operation.responses(spec) {
+        let variant = match response_enum.values.get(&response_key) {
+            Some(variant) => variant,
+            None => continue,
+        };
+        let body_struct = match object_database.get(&variant.value_type.name) {
+            Some(ObjectDefinition::Struct(body_struct)) => body_struct,
+            _ => {
+                if !response.links.is_empty() {
+                    warn!(
+                        "Links of {} responses without object body skipped",
+                        response_key
+                    );
+                }
+                continue;
+            }
+        };
+
+        for (link_name, link) in &response.links {
+            let (operation_id, parameters) = match resolve_link(spec, link) {
+                Some(Link::Id {
+                    operation_id,
+                    parameters,
+                    ..
+                }) => (operation_id, parameters),
+                Some(Link::Ref { .. }) => {
+                    warn!("Link {} skipped, only operationId is supported", link_name);
+                    continue;
+                }
+                None => {
+                    warn!("Link {} not found", link_name);
+                    continue;
+                }
+            };
+
+            let parameter_structs =
+                match get_linked_parameter_structs(spec, config, &operation_id, object_database) {
+                    Some(parameter_structs) => parameter_structs,
+                    None => {
+                        warn!("Operation {} of link {} not found", operation_id, link_name);
+                        continue;
+                    }
+                };
+
+            for (location, parameters_struct) in &parameter_structs.structs {
+                let located_parameters = parameters
+                    .iter()
+                    .filter_map(|(name, expression)| match name.split_once('.') {
+                        Some((parameter_location, name)) if parameter_location == *location => {
+                            Some((name, expression))
+                        }
+                        Some(_) => None,
+                        None => Some((name.as_str(), expression)),
+                    })
+                    .collect::<BTreeMap<&str, &String>>();
+                if !parameters_struct
+                    .properties
+                    .values()
+                    .any(|property| located_parameters.contains_key(property.real_name.as_str()))
+                {
+                    continue;
+                }
+
+                let return_type = format!(
+                    "{}::{}",
+                    parameter_structs.module_path, parameters_struct.name
+                );
+                let expression = match get_parameters_expression(
+                    &return_type,
+                    parameters_struct,
+                    &located_parameters,
+                    body_struct,
+                    object_database,
+                ) {
+                    Ok(expression) => expression,
+                    Err(err) => {
+                        warn!("Link {} skipped: {}", link_name, err);
+                        continue;
+                    }
+                };
+
+                let function_name = format!(
+                    "{}_{}_parameters",
+                    config.name_mapping.name_to_function_name(link_name),
+                    location
+                );
+                let pattern = format!("{}(value)", variant.name);
+                match link_helpers
+                    .iter_mut()
+                    .find(|link_helper| link_helper.function_name == function_name)
+                {
+                    Some(link_helper) => link_helper.variants.push((pattern, expression)),
+                    None => link_helpers.push(LinkHelper {
+                        function_name,
+                        link_name: link_name.clone(),
+                        linked_function: format!(
+                            "{}::{}",
+                            parameter_structs.module_path, parameter_structs.function_name
+                        ),
+                        return_type,
+                        cfg_attribute: parameter_structs.cfg_attribute.clone(),
+                        variants: vec![(pattern, expression)],
+                    }),
+                }
+            }
+        }
+    }
+    link_helpers
+}
+
+/// Link of a response or its reference to the links of the components
+fn resolve_link(spec: &Spec, link: &ObjectOrReference<Link>) -> Option<Link> {
+    match link {
+        ObjectOrReference::Object(link) => Some(link.clone()),
+        ObjectOrReference::Ref { ref_path } => {
+            let name = ref_path.strip_prefix("#/components/links/")?;
+            resolve_link(spec, spec.components.as_ref()?.links.get(name)?)
+        }
+    }
+}
+
+struct LinkedParameterStructs {
+    module_path: String,
+    function_name: String,
+    cfg_attribute: String,
+    /// Path and query parameters struct by parameter location
+    structs: Vec<(&'static str, StructDefinition)>,
+}
+
+/// Parameter structs of the generated operation `operation_id`
+fn get_linked_parameter_structs(
+    spec: &Spec,
+    config: &Config,
+    operation_id: &str,
+    object_database: &ObjectDatabase,
+) -> Option<LinkedParameterStructs> {
+    let (path, method, operation) = spec
+        .operations()
+        .find(|(_, _, operation)| operation.operation_id.as_deref() == Some(operation_id))?;
+    if config.ignore.path_ignored(&path) || config.ignore.operation_ignored(&method, operation) {
+        return None;
+    }
+
+    let name_mapping = &config.name_mapping;
+    let function_name = name_mapping.name_to_function_name(operation_id);
+    let definition_path = vec![path.clone()];
+    // Objects of the parameters are added by the linked operation itself
+    let mut object_database = object_database.clone();
+    let path_parameter_code = generate_path_parameter_code(
+        spec,
+        operation,
+        &definition_path,
+        name_mapping,
+        &mut object_database,
+        &function_name,
+        &path,
+    )
+    .ok()?;
+    let query_parameter_code = generate_query_parameter_code(
+        spec,
+        operation,
+        &definition_path,
+        name_mapping,
+        &mut object_database,
+        &function_name,
+    )
+    .ok()?;
+
+    let cfg_attribute = match config.generator.tag_features {
+        true => get_cfg_attribute(
+            &operation
+                .tags
+                .iter()
+                .map(|tag| get_tag_feature_name(tag))
+                .filter(|feature| !feature.is_empty())
+                .collect(),
+        ),
+        false => String::new(),
+    };
+
+    Some(LinkedParameterStructs {
+        module_path: format!(
+            "crate::paths::{}",
+            name_mapping.name_to_module_name(operation_id)
+        ),
+        function_name,
+        cfg_attribute: cfg_attribute.trim_end().to_owned(),
+        structs: vec![
+            ("path", path_parameter_code.parameters_struct),
+            ("query", query_parameter_code.query_struct),
+        ],
+    })
+}
+
+/// Struct expression of the parameters with the values of the response body `value`.
+/// Parameters without value are `None`, required ones fail
+fn get_parameters_expression(
+    struct_path: &str,
+    parameters_struct: &StructDefinition,
+    parameters: &BTreeMap<&str, &String>,
+    body_struct: &StructDefinition,
+    object_database: &ObjectDatabase,
+) -> Result<String, String> {
+    let mut fields = vec![];
+    for property in parameters_struct.properties.values() {
+        let value = match parameters.get(property.real_name.as_str()) {
+            Some(expression) => Some(get_body_value(
+                expression,
+                &property.type_name,
+                body_struct,
+                object_database,
+            )?),
+            None => None,
+        };
+        let value = match (value, property.required) {
+            (Some(value), true) => value,
+            (Some(value), false) => format!("Some({})", value),
+            (None, false) => "None".to_owned(),
+            (None, true) => return Err(format!("{} has no value", property.real_name)),
+        };
+        fields.push(format!("{}: {}", property.name, value));
+    }
+    Ok(format!("{} {{ {} }}", struct_path, fields.join(", ")))
+}
+
+/// Expression of the body property of a `$response.body#/..` pointer with the type of the
+/// parameter, optional properties return `None` with `?`
+fn get_body_value(
+    expression: &str,
+    type_name: &str,
+    body_struct: &StructDefinition,
+    object_database: &ObjectDatabase,
+) -> Result<String, String> {
+    let pointer = match expression.strip_prefix("$response.body#/") {
+        Some(pointer) => pointer,
+        None => return Err(format!("{} is not a response body value", expression)),
+    };
+
+    let mut value = "value".to_owned();
+    let mut current_struct = body_struct;
+    let segments = pointer.split('/').collect::<Vec<&str>>();
+    for (index, segment) in segments.iter().enumerate() {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        let property = match current_struct
+            .properties
+            .values()
+            .find(|property| property.real_name == segment)
+        {
+            Some(property) => property,
+            None => {
+                return Err(format!(
+                    "{} has no property {}",
+                    current_struct.name, segment
+                ))
+            }
+        };
+        value = format!("{}.{}", value, property.name);
+
+        if index + 1 < segments.len() {
+            if !property.required {
+                value += ".as_ref()?";
+            }
+            // Recursive objects are boxed
+            let type_name = property
+                .type_name
+                .strip_prefix("Box<")
+                .and_then(|type_name| type_name.strip_suffix('>'))
+                .unwrap_or(&property.type_name);
+            current_struct = match object_database.get(type_name) {
+                Some(ObjectDefinition::Struct(property_struct)) => property_struct,
+                _ => return Err(format!("{} is not an object", property.real_name)),
+            };
+            continue;
+        }
+
+        let value = match property.required {
+            true => format!("{}.clone()", value),
+            false => format!("{}.clone()?", value),
+        };
+        return match type_name {
+            _ if property.type_name == type_name => Ok(value),
+            "String" if DISPLAY_TYPES.contains(&property.type_name.as_str()) => {
+                Ok(format!("{}.to_string()", value))
+            }
+            _ => Err(format!(
+                "{} is {} instead of {}",
+                property.real_name, property.type_name, type_name
+            )),
+        };
+    }
+    Err(format!("{} has no property", expression))
+}
@@ -1,3 +1,14 @@
+pub mod batch;
+pub mod conditional;
+pub mod event_stream_request;
 pub mod http_request;
+mod http_test;
+pub mod idempotency;
+pub mod links;
+pub mod ndjson_request;
+pub mod pagination;
+pub mod response_result;
+pub mod security;
+pub mod server;
 pub mod utils;
 pub mod websocket_request;
@@ -0,0 +1,122 @@
+use oas3::spec::Operation;
+use serde::Deserialize;
+
+use crate::{
+    generator::media_type::TransferMediaType,
+    parser::component::object_definition::types::{
+        ObjectDatabase, ObjectDefinition, TypeDefinition,
+    },
+};
+
+use super::utils::ResponseEntities;
+
+/// Content of the `x-pagination` operation extension
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PaginationExtension {
+    /// Response property which contains the page items. The response itself is used if not set
+    pub items: Option<String>,
+    /// Query parameter which receives the next cursor
+    pub cursor_parameter: Option<String>,
+    /// Response property which contains the next cursor
+    pub next_cursor: Option<String>,
+    /// Follow `Link: <...>; rel="next"` headers (RFC 5988)
+    #[serde(default)]
+    pub link_header: bool,
+}
+
+pub struct PaginationCursor {
+    pub parameter: String,
+    pub response_property: String,
+}
+
+pub struct PaginationCode {
+    pub items_property: Option<String>,
+    pub item_type: TypeDefinition,
+    pub cursor: Option<PaginationCursor>,
+    pub link_header: bool,
+}
+
+pub fn get_pagination_extension(
+    operation: &Operation,
+) -> Result<Option<PaginationExtension>, String> {
+    let extension_value = match operation.extensions.get("pagination") {
+        Some(extension_value) => extension_value,
+        None => return Ok(None),
+    };
+
+    let pagination: PaginationExtension = serde_json::from_value(extension_value.clone())
+        .map_err(|err| format!("Invalid x-pagination value {}", err))?;
+
+    let has_cursor = pagination.cursor_parameter.is_some() && pagination.next_cursor.is_some();
+    if !has_cursor && !pagination.link_header {
+        return Err(
+            "x-pagination requires cursor_parameter and next_cursor or link_header".to_owned(),
+        );
+    }
+
+    Ok(Some(pagination))
+}
+
+fn get_vec_item_type(type_name: &str) -> Option<&str> {
+    type_name
+        .strip_prefix("Vec<")
+        .and_then(|item_type| item_type.strip_suffix(">"))
+}
+
+pub fn generate_pagination_code(
+    pagination: &PaginationExtension,
+    response_entities: &ResponseEntities,
+    object_database: &ObjectDatabase,
+) -> Result<PaginationCode, String> {
+    let page_type = match response_entities
+        .get("200")
+        .and_then(|response_entity| response_entity.content.get("application/json"))
+    {
+        Some(TransferMediaType::ApplicationJson(Some(page_type))) => page_type,
+        _ => return Err("Pagination requires a 200 application/json response".to_owned()),
+    };
+
+    let (items_type_name, items_module) = match pagination.items {
+        Some(ref items_property) => {
+            let page_struct = match object_database.get(&page_type.name) {
+                Some(ObjectDefinition::Struct(page_struct)) => page_struct,
+                _ => return Err(format!("Page type {} is not a struct", page_type.name)),
+            };
+            match page_struct
+                .properties
+                .values()
+                .find(|property| &property.real_name == items_property)
+            {
+                Some(property) => (property.type_name.clone(), property.module.clone()),
+                None => {
+                    return Err(format!(
+                        "Page type {} has no property {}",
+                        page_type.name, items_property
+                    ))
+                }
+            }
+        }
+        None => (page_type.name.clone(), page_type.module.clone()),
+    };
+
+    let item_type_name = match get_vec_item_type(&items_type_name) {
+        Some(item_type_name) => item_type_name.to_owned(),
+        None => return Err(format!("Page items {} are not an array", items_type_name)),
+    };
+
+    Ok(PaginationCode {
+        items_property: pagination.items.clone(),
+        item_type: TypeDefinition {
+            name: item_type_name,
+            module: items_module,
+        },
+        cursor: match (&pagination.cursor_parameter, &pagination.next_cursor) {
+            (Some(parameter), Some(response_property)) => Some(PaginationCursor {
+                parameter: parameter.clone(),
+                response_property: response_property.clone(),
+            }),
+            _ => None,
+        },
+        link_header: pagination.link_header,
+    })
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    parser::component::object_definition::types::EnumDefinition, utils::name_mapping::NameMapping,
+};
+
+/// `into_result()` of a response enum, which splits its variants into a success and an error enum
+pub struct ResponseResultCode {
+    pub success_enum_name: String,
+    pub error_enum_name: String,
+    pub variants: Vec<ResponseResultVariant>,
+}
+
+pub struct ResponseResultVariant {
+    pub name: String,
+    /// Pattern and constructor of the variant without the enum, e.g. `Ok(value)`
+    pub pattern: String,
+    pub success: bool,
+    /// Variants of any status are successful if this condition on their `value` holds
+    pub success_condition: Option<String>,
+}
+
+/// Pattern of each variant of the response enum and the expression of its status code
+/// for `status()`, e.g. `("Ok(_)", "Some(200)")`
+pub fn get_status_variants(
+    response_enum: &EnumDefinition,
+    capture_undefined_response: bool,
+) -> Vec<(String, String)> {
+    response_enum
+        .values
+        .iter()
+        .map(|(response_key, value)| match response_key.as_str() {
+            "UndefinedResponse" if capture_undefined_response => (
+                format!("{}(response)", value.name),
+                "Some(response.status)".to_owned(),
+            ),
+            "UndefinedResponse" | "BodyTooLarge" => (
+                format!("{}(response)", value.name),
+                "Some(response.status().as_u16())".to_owned(),
+            ),
+            _ => (
+                match value.value_type.name.is_empty() {
+                    true => value.name.clone(),
+                    false => format!("{}(_)", value.name),
+                },
+                format!("Some({})", response_key),
+            ),
+        })
+        .collect()
+}
+
+/// Responses of these statuses are successful
+fn is_success_status(status_code: &str) -> bool {
+    status_code.starts_with('2') || status_code == "304"
+}
+
+/// Adds the success and the error enum of the response enum, the last one of `response_enums`.
+/// Documented 2xx and 304 responses are successful, `UndefinedResponse` and `BodyTooLarge` by
+/// the status of the response
+pub fn add_response_result_enums(
+    name_mapping: &NameMapping,
+    operation_definition_path: &Vec<String>,
+    function_name: &str,
+    capture_undefined_response: bool,
+    response_enums: &mut Vec<EnumDefinition>,
+) -> Result<ResponseResultCode, String> {
+    let response_enum = match response_enums.last() {
+        Some(response_enum) => response_enum,
+        None => return Err("Response enum missing".to_owned()),
+    };
+
+    let mut success_enum = EnumDefinition {
+        name: name_mapping.name_to_struct_name(
+            operation_definition_path,
+            &format!("{}ResponseSuccess", function_name),
+        ),
+        used_modules: vec![],
+        values: BTreeMap::new(),
+    };
+    let mut error_enum = EnumDefinition {
+        name: name_mapping.name_to_struct_name(
+            operation_definition_path,
+            &format!("{}ResponseError", function_name),
+        ),
+        used_modules: vec![],
+        values: BTreeMap::new(),
+    };
+
+    let mut variants = vec![];
+    for (response_key, value) in &response_enum.values {
+        let success_condition = match response_key.as_str() {
+            "UndefinedResponse" if capture_undefined_response => {
+                Some("(200..300).contains(&value.status)".to_owned())
+            }
+            "UndefinedResponse" | "BodyTooLarge" => Some("value.status().is_success()".to_owned()),
+            _ => None,
+        };
+        let success = is_success_status(response_key);
+        if success || success_condition.is_some() {
+            success_enum
+                .values
+                .insert(response_key.clone(), value.clone());
+        }
+        if !success || success_condition.is_some() {
+            error_enum
+                .values
+                .insert(response_key.clone(), value.clone());
+        }
+
+        variants.push(ResponseResultVariant {
+            name: value.name.clone(),
+            pattern: match value.value_type.name.is_empty() {
+                true => value.name.clone(),
+                false => format!("{}(value)", value.name),
+            },
+            success,
+            success_condition,
+        });
+    }
+
+    let response_result_code = ResponseResultCode {
+        success_enum_name: success_enum.name.clone(),
+        error_enum_name: error_enum.name.clone(),
+        variants,
+    };
+    response_enums.push(success_enum);
+    response_enums.push(error_enum);
+    Ok(response_result_code)
+}
@@ -0,0 +1,244 @@
+use log::warn;
+use oas3::{spec::Operation, Spec};
+
+use crate::{
+    generator::rust_reqwest_async::auth::{
+        credentials_type_name, get_auth_variant_names, get_credentials_examples,
+    },
+    parser::security::{get_operation_security, OperationSecurity},
+    utils::{config::Config, name_mapping::NameMapping},
+};
+
+use super::utils::FunctionParameter;
+
+/// `auth` parameter of an operation with security requirements
+#[derive(Clone, Debug)]
+pub struct AuthParameter {
+    /// The requirements don't allow anonymous requests
+    pub required: bool,
+    /// `crate::auth::Auth` or the requirement enum of the operation
+    pub type_name: String,
+    /// Enum of the allowed scheme combinations if a single `Auth` can't express them
+    pub requirement: Option<AuthRequirement>,
+    /// Credentials of generated tests, `None` if the required schemes have no credentials of
+    /// the request
+    pub example: Option<String>,
+}
+
+/// Enum with a variant per security requirement object of an operation
+#[derive(Clone, Debug)]
+pub struct AuthRequirement {
+    pub name: String,
+    pub function_name: String,
+    pub variants: Vec<AuthRequirementVariant>,
+}
+
+/// Schemes of a requirement, which are all sent with the request
+#[derive(Clone, Debug)]
+pub struct AuthRequirementVariant {
+    pub name: String,
+    pub scheme_names: Vec<String>,
+    /// Credentials of the schemes which are sent with the request
+    pub fields: Vec<AuthRequirementField>,
+}
+
+/// Credentials of a scheme, typed by the scheme so other credentials aren't accepted
+#[derive(Clone, Debug)]
+pub struct AuthRequirementField {
+    pub name: String,
+    pub type_name: String,
+    /// Credentials of generated tests
+    pub example: String,
+}
+
+impl AuthRequirementVariant {
+    pub fn field_names(&self) -> String {
+        self.fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+
+    /// Variant with example credentials, for generated tests
+    pub fn example(&self, requirement_name: &str) -> String {
+        match self.fields.len() {
+            0 => format!("{}::{}", requirement_name, self.name),
+            1 => format!(
+                "{}::{}({})",
+                requirement_name, self.name, self.fields[0].example
+            ),
+            _ => format!(
+                "{}::{} {{ {} }}",
+                requirement_name,
+                self.name,
+                self.fields
+                    .iter()
+                    .map(|field| format!("{}: {}", field.name, field.example))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        self.scheme_names
+            .iter()
+            .map(|scheme_name| format!("`{}`", scheme_name))
+            .collect::<Vec<String>>()
+            .join(" and ")
+    }
+}
+
+impl AuthParameter {
+    pub fn function_parameter(&self) -> FunctionParameter {
+        match self.required {
+            true => FunctionParameter {
+                name: "auth".to_owned(),
+                type_name: self.type_name.clone(),
+                reference: true,
+            },
+            false => FunctionParameter {
+                name: "auth".to_owned(),
+                type_name: format!("Option<&{}>", self.type_name),
+                reference: false,
+            },
+        }
+    }
+}
+
+/// Auth parameter of an operation with its own or the global security requirements if
+/// `generator.security` is enabled
+pub fn get_auth_parameter(
+    config: &Config,
+    spec: &Spec,
+    operation: &Operation,
+    definition_path: &Vec<String>,
+    function_name: &str,
+) -> Result<Option<AuthParameter>, String> {
+    if !config.generator.security {
+        return Ok(None);
+    }
+    let security = match get_operation_security(spec, operation)? {
+        Some(security) => security,
+        None => return Ok(None),
+    };
+    let auth_variant_names = get_auth_variant_names(spec, &config.name_mapping)?;
+    if auth_variant_names.is_empty() {
+        warn!("Security skipped, the spec has no security schemes with request credentials");
+        return Ok(None);
+    }
+    let credentials_examples = get_credentials_examples(spec, &config.name_mapping)?;
+
+    let requirement = match is_single_scheme(&security) {
+        true => None,
+        false => Some(generate_auth_requirement(
+            &config.name_mapping,
+            definition_path,
+            function_name,
+            &security,
+            |scheme_name| {
+                Some((
+                    credentials_type_name(auth_variant_names.get(scheme_name)?),
+                    credentials_examples.get(scheme_name)?.clone(),
+                ))
+            },
+        )),
+    };
+    let example = match requirement {
+        Some(ref requirement) => requirement
+            .variants
+            .first()
+            .map(|variant| variant.example(&requirement.name)),
+        None => security
+            .requirements
+            .iter()
+            .flatten()
+            .next()
+            .and_then(|scheme_name| {
+                Some(format!(
+                    "crate::auth::Auth::{}{}",
+                    auth_variant_names.get(scheme_name)?,
+                    credentials_examples.get(scheme_name)?
+                ))
+            }),
+    };
+    Ok(Some(AuthParameter {
+        required: !security.is_optional(),
+        type_name: match requirement {
+            Some(ref requirement) => requirement.name.clone(),
+            None => "crate::auth::Auth".to_owned(),
+        },
+        requirement,
+        example,
+    }))
+}
+
+/// Any `Auth` satisfies requirements with at most one scheme besides anonymous requests
+fn is_single_scheme(security: &OperationSecurity) -> bool {
+    let mut requirements = security
+        .requirements
+        .iter()
+        .filter(|requirement| !requirement.is_empty());
+    match (requirements.next(), requirements.next()) {
+        (Some(requirement), None) => requirement.len() == 1,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn generate_auth_requirement(
+    name_mapping: &NameMapping,
+    definition_path: &Vec<String>,
+    function_name: &str,
+    security: &OperationSecurity,
+    get_credentials: impl Fn(&str) -> Option<(String, String)>,
+) -> AuthRequirement {
+    let name = name_mapping.name_to_struct_name(
+        definition_path,
+        &format!("{}AuthRequirement", function_name),
+    );
+    let mut variant_definition_path = definition_path.clone();
+    variant_definition_path.push(name.clone());
+
+    let mut variants: Vec<AuthRequirementVariant> = vec![];
+    for scheme_names in &security.requirements {
+        // Empty requirements make the parameter optional instead
+        if scheme_names.is_empty() {
+            continue;
+        }
+        let variant_name = scheme_names
+            .iter()
+            .map(|scheme_name| {
+                name_mapping.name_to_variant_name(&variant_definition_path, scheme_name)
+            })
+            .collect::<Vec<String>>()
+            .join("And");
+        if variants.iter().any(|variant| variant.name == variant_name) {
+            continue;
+        }
+        variants.push(AuthRequirementVariant {
+            name: variant_name,
+            scheme_names: scheme_names.clone(),
+            fields: scheme_names
+                .iter()
+                .filter_map(|scheme_name| {
+                    get_credentials(scheme_name).map(|(type_name, example_fields)| {
+                        AuthRequirementField {
+                            name: name_mapping
+                                .name_to_property_name(&variant_definition_path, scheme_name),
+                            type_name: format!("crate::auth::{}", type_name),
+                            example: format!("crate::auth::{}{}", type_name, example_fields),
+                        }
+                    })
+                })
+                .collect(),
+        });
+    }
+
+    AuthRequirement {
+        name,
+        function_name: function_name.to_owned(),
+        variants,
+    }
+}
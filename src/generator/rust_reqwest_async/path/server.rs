@@ -0,0 +1,41 @@
+use oas3::{
+    spec::{Operation, Server},
+    Spec,
+};
+
+use crate::utils::config::Config;
+
+/// Url of the server with the default values of its variables
+fn get_server_url(server: &Server) -> String {
+    server
+        .variables
+        .iter()
+        .fold(server.url.clone(), |url, (name, variable)| {
+            url.replace(&format!("{{{}}}", name), &variable.default)
+        })
+}
+
+/// Server of the operation configured in `generator.operation_servers`, or the first server of
+/// the operation or its path in the spec
+pub fn get_operation_server(
+    config: &Config,
+    spec: &Spec,
+    path: &str,
+    operation: &Operation,
+    operation_id: &str,
+) -> Option<String> {
+    if let Some(server) = config.generator.operation_servers.get(operation_id) {
+        return Some(server.clone());
+    }
+
+    let path_servers = spec
+        .paths
+        .as_ref()
+        .and_then(|paths| paths.get(path))
+        .map(|path_item| &path_item.servers);
+    operation
+        .servers
+        .first()
+        .or(path_servers.and_then(|path_servers| path_servers.first()))
+        .map(get_server_url)
+}
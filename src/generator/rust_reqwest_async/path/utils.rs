@@ -1,19 +1,26 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet};
 
-use log::{error, trace};
+use log::{error, info, trace, warn};
 use oas3::{
-    spec::{MediaType, ObjectOrReference, ObjectSchema, RequestBody, Response},
+    spec::{
+        MediaType, ObjectOrReference, ObjectSchema, Operation, Parameter, ParameterIn,
+        ParameterStyle, RequestBody, Response, SchemaType, SchemaTypeSet,
+    },
     Spec,
 };
 use reqwest::StatusCode;
 
 use crate::{
+    generator::media_type::{has_content_type, resolve_content_type, TransferMediaType},
     parser::component::{
         object_definition::{
-            get_object_or_ref_struct_name, is_object_empty,
-            types::{ModuleInfo, ObjectDatabase, TypeDefinition},
+            get_const_value, get_object_name, get_object_or_ref_struct_name, is_object_empty,
+            types::{
+                ModuleInfo, ObjectDatabase, PropertyConstraints, PropertyDefinition,
+                StructDefinition, TypeDefinition,
+            },
         },
-        type_definition::get_type_from_schema,
+        type_definition::{get_type_from_schema, get_type_override},
     },
     utils::name_mapping::NameMapping,
 };
@@ -24,24 +31,135 @@ pub fn is_path_parameter(path_component: &str) -> bool {
     path_component.starts_with("{") && path_component.ends_with("}")
 }
 
-#[derive(Clone, Debug)]
-pub enum TransferMediaType {
-    ApplicationJson(Option<TypeDefinition>),
-    TextPlain,
-}
-
 #[derive(Clone, Debug)]
 pub struct ResponseEntity {
     pub canonical_status_code: String,
-    pub content: HashMap<ContentTypeValue, TransferMediaType>,
+    pub content: BTreeMap<ContentTypeValue, TransferMediaType>,
 }
 
 #[derive(Clone, Debug)]
 pub struct RequestEntity {
-    pub content: HashMap<ContentTypeValue, TransferMediaType>,
+    pub content: BTreeMap<ContentTypeValue, TransferMediaType>,
+    /// False only if the spec sets `required: false`
+    pub required: bool,
+}
+
+pub type ResponseEntities = BTreeMap<String, ResponseEntity>;
+
+#[derive(Debug)]
+pub struct QueryParameter {
+    pub is_required: bool,
+    pub is_array: bool,
+    pub real_name: String,
+    pub name: String,
+    pub struct_name: String,
+    /// Delimiter joining array items (or object fields and values) into a single value,
+    /// None repeats the parameter per item (or sends each field as its own pair)
+    pub separator: Option<String>,
+    /// Object fields are sent as `name[field]=value`
+    pub deep_object: bool,
+    pub is_object: bool,
+}
+
+impl QueryParameter {
+    /// Code converting the parameter variable into a single query value
+    pub fn value_code(&self, variable_name: &str) -> String {
+        match (self.is_array, &self.separator) {
+            (true, Some(separator)) => format!(
+                "{}.iter().map(|query_parameter_item| query_parameter_item.to_string()).collect::<Vec<String>>().join({:?})",
+                variable_name, separator
+            ),
+            _ => format!("{}.to_string()", variable_name),
+        }
+    }
+
+    /// Code converting the parameter struct field into a single query value
+    pub fn field_value_code(&self) -> String {
+        self.value_code(&format!("{}.{}", self.struct_name, self.name))
+    }
+
+    /// Parameter is serialized from the fields of its json object
+    pub fn has_fields(&self) -> bool {
+        self.is_object || self.deep_object
+    }
+
+    /// Parameter is pushed as multiple pairs
+    pub fn is_unrolled(&self) -> bool {
+        self.has_fields() || (self.is_array && self.separator.is_none())
+    }
+}
+
+/// Serialization of a query parameter derived from its `style` and `explode` settings
+#[derive(Clone, Debug, Default)]
+pub struct QueryParameterStyle {
+    pub separator: Option<String>,
+    pub deep_object: bool,
+    /// Schema of the parameter is an object
+    pub is_object: bool,
+}
+
+impl QueryParameterStyle {
+    pub fn from_parameter(parameter: &Parameter) -> Self {
+        let style = parameter.style.unwrap_or(ParameterStyle::Form);
+        let explode = parameter
+            .explode
+            .unwrap_or(matches!(style, ParameterStyle::Form));
+        match style {
+            ParameterStyle::DeepObject => QueryParameterStyle {
+                separator: None,
+                deep_object: true,
+                is_object: false,
+            },
+            ParameterStyle::SpaceDelimited if !explode => QueryParameterStyle {
+                separator: Some(" ".to_owned()),
+                deep_object: false,
+                is_object: false,
+            },
+            ParameterStyle::PipeDelimited if !explode => QueryParameterStyle {
+                separator: Some("|".to_owned()),
+                deep_object: false,
+                is_object: false,
+            },
+            ParameterStyle::Form if !explode => QueryParameterStyle {
+                separator: Some(",".to_owned()),
+                deep_object: false,
+                is_object: false,
+            },
+            _ => QueryParameterStyle::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionParameter {
+    pub name: String,
+    pub type_name: String,
+    pub reference: bool,
 }
 
-pub type ResponseEntities = HashMap<String, ResponseEntity>;
+pub fn is_event_stream_operation(
+    responses: &BTreeMap<String, Response>,
+    content_type_mapping: &BTreeMap<String, String>,
+) -> bool {
+    responses.iter().any(|(response_key, response)| {
+        response_key.starts_with("2")
+            && has_content_type(&response.content, "text/event-stream", content_type_mapping)
+    })
+}
+
+pub fn is_ndjson_operation(
+    responses: &BTreeMap<String, Response>,
+    content_type_mapping: &BTreeMap<String, String>,
+) -> bool {
+    responses.iter().any(|(response_key, response)| {
+        response_key.starts_with("2")
+            && has_content_type(
+                &response.content,
+                "application/x-ndjson",
+                content_type_mapping,
+            )
+    })
+}
 
 fn parse_json_data(
     spec: &Spec,
@@ -65,14 +183,31 @@ fn parse_json_data(
         return Ok(None);
     }
 
+    if let Some(type_definition) = get_type_override(name_mapping, json_schema_object_or_ref) {
+        return Ok(Some(type_definition));
+    }
+
     let json_object_definition_opt = match json_schema_object_or_ref {
-        ObjectOrReference::Ref { ref_path: _ } => match get_object_or_ref_struct_name(
-            spec,
-            &definition_path,
-            name_mapping,
-            &json_schema_object_or_ref,
-        ) {
-            Ok((_, object_name)) => Some(TypeDefinition {
+        ObjectOrReference::Ref { ref_path } => {
+            // Components are renamed if their name is used by another schema
+            let object_name = match object_database.get_by_ref(ref_path) {
+                Some(object_definition) => get_object_name(object_definition).clone(),
+                None => match get_object_or_ref_struct_name(
+                    spec,
+                    &definition_path,
+                    name_mapping,
+                    &json_schema_object_or_ref,
+                ) {
+                    Ok((_, object_name)) => object_name,
+                    Err(err) => {
+                        return Err(format!(
+                            "Unable to determine response type ref name {}",
+                            err
+                        ))
+                    }
+                },
+            };
+            Some(TypeDefinition {
                 module: Some(ModuleInfo {
                     path: format!(
                         "crate::objects::{}",
@@ -80,15 +215,9 @@ fn parse_json_data(
                     ),
                     name: object_name.clone(),
                 }),
-                name: object_name.clone(),
-            }),
-            Err(err) => {
-                return Err(format!(
-                    "Unable to determine response type ref name {}",
-                    err
-                ))
-            }
-        },
+                name: object_name,
+            })
+        }
         ObjectOrReference::Object(object_schema) => match get_type_from_schema(
             spec,
             object_database,
@@ -115,7 +244,7 @@ fn generate_json_content(
     object_database: &mut ObjectDatabase,
     json_media_type: &MediaType,
     content_object_name: &str,
-) -> Result<TransferMediaType, String> {
+) -> Result<Option<TypeDefinition>, String> {
     let json_schema_object_or_ref = match json_media_type.schema {
         Some(ref schema) => schema,
         None => return Err(format!("Failed to parse response json data",)),
@@ -140,13 +269,11 @@ fn generate_json_content(
                 "{} empty json request body object skipped",
                 content_object_name
             );
-            return Ok(TransferMediaType::ApplicationJson(None));
+            return Ok(None);
         }
     };
 
-    Ok(TransferMediaType::ApplicationJson(Some(
-        json_object_type_definition,
-    )))
+    Ok(Some(json_object_type_definition))
 }
 
 fn generate_content_type(
@@ -160,6 +287,7 @@ fn generate_content_type(
 ) -> Result<TransferMediaType, String> {
     match content_type {
         "text/plain" => Ok(TransferMediaType::TextPlain),
+        "application/octet-stream" => Ok(TransferMediaType::OctetStream),
         "application/json" => generate_json_content(
             spec,
             definition_path,
@@ -167,7 +295,41 @@ fn generate_content_type(
             object_database,
             media_type,
             &format!("{}Json", content_object_name),
-        ),
+        )
+        .map(TransferMediaType::ApplicationJson),
+        "application/xml" => generate_json_content(
+            spec,
+            definition_path,
+            name_mapping,
+            object_database,
+            media_type,
+            &format!("{}Xml", content_object_name),
+        )
+        .map(TransferMediaType::ApplicationXml),
+        "text/event-stream" => match media_type.schema {
+            Some(_) => generate_json_content(
+                spec,
+                definition_path,
+                name_mapping,
+                object_database,
+                media_type,
+                &format!("{}Event", content_object_name),
+            )
+            .map(TransferMediaType::EventStream),
+            None => Ok(TransferMediaType::EventStream(None)),
+        },
+        "application/x-ndjson" => match media_type.schema {
+            Some(_) => generate_json_content(
+                spec,
+                definition_path,
+                name_mapping,
+                object_database,
+                media_type,
+                &format!("{}Item", content_object_name),
+            )
+            .map(TransferMediaType::Ndjson),
+            None => Ok(TransferMediaType::Ndjson(None)),
+        },
         _ => Err(format!("Content-Type {} is not supported", content_type)),
     }
 }
@@ -178,9 +340,10 @@ fn generated_content_types_from_content_map(
     definition_path: &Vec<String>,
     name_mapping: &NameMapping,
     content: &BTreeMap<String, MediaType>,
+    content_type_mapping: &BTreeMap<String, String>,
     content_object_name: &str,
-) -> HashMap<ContentTypeValue, TransferMediaType> {
-    let mut content_map = HashMap::new();
+) -> BTreeMap<ContentTypeValue, TransferMediaType> {
+    let mut content_map = BTreeMap::new();
 
     for (content_type, media_type) in content {
         match generate_content_type(
@@ -188,7 +351,7 @@ fn generated_content_types_from_content_map(
             definition_path,
             name_mapping,
             object_database,
-            content_type,
+            &resolve_content_type(content_type, content_type_mapping),
             media_type,
             content_object_name,
         ) {
@@ -197,6 +360,19 @@ fn generated_content_types_from_content_map(
                     error!("Content-Type {} is already in content map", content_type);
                     continue;
                 }
+                if content_map
+                    .values()
+                    .any(|existing_media_type: &TransferMediaType| {
+                        existing_media_type.content_type() == transfer_media_type.content_type()
+                    })
+                {
+                    warn!(
+                        "Content-Type {} skipped, it is handled as {} which is already in content map",
+                        content_type,
+                        transfer_media_type.content_type()
+                    );
+                    continue;
+                }
                 content_map.insert(content_type.clone(), transfer_media_type);
             }
             Err(err) => error!("{} failed: {}", content_type, err),
@@ -212,6 +388,7 @@ pub fn generate_request_body(
     definition_path: &Vec<String>,
     name_mapping: &NameMapping,
     request_body: &ObjectOrReference<RequestBody>,
+    content_type_mapping: &BTreeMap<String, String>,
     function_name: &str,
 ) -> Result<RequestEntity, String> {
     let request = match request_body.resolve(spec) {
@@ -231,8 +408,10 @@ pub fn generate_request_body(
             definition_path,
             name_mapping,
             &request.content,
+            content_type_mapping,
             &format!("{}RequestBody", function_name),
         ),
+        required: request.required != Some(false),
     })
 }
 
@@ -242,6 +421,7 @@ pub fn generate_responses(
     definition_path: &Vec<String>,
     name_mapping: &NameMapping,
     responses: &BTreeMap<String, Response>,
+    content_type_mapping: &BTreeMap<String, String>,
     function_name: &str,
 ) -> Result<ResponseEntities, String> {
     let mut response_entities = ResponseEntities::new();
@@ -275,6 +455,7 @@ pub fn generate_responses(
                     definition_path,
                     name_mapping,
                     &response.content,
+                    content_type_mapping,
                     &format!("{}{}", &function_name, &canonical_status_code),
                 ),
             },
@@ -282,3 +463,462 @@ pub fn generate_responses(
     }
     Ok(response_entities)
 }
+
+pub struct PathParameterCode {
+    pub parameters_struct_variable_name: String,
+    pub parameters_struct: StructDefinition,
+    pub path_format_string: String,
+    pub path_format_arguments: Vec<PropertyDefinition>,
+}
+
+impl PathParameterCode {
+    /// Percent-encoded path parameters in the order of `path_format_string`
+    pub fn format_arguments(&self) -> Vec<String> {
+        self.path_format_arguments
+            .iter()
+            .map(|property| {
+                format!(
+                    "crate::path_encoding::encode_path_parameter(&{}.{})",
+                    self.parameters_struct_variable_name, property.name
+                )
+            })
+            .collect()
+    }
+}
+
+/// Type of a path parameter by its schema. Only primitives can be formatted into the path,
+/// parameters with other (or without) schemas are strings
+fn get_path_parameter_type(
+    spec: &Spec,
+    operation: &Operation,
+    definition_path: &[String],
+    name_mapping: &NameMapping,
+    object_database: &mut ObjectDatabase,
+    parameter_name: &str,
+) -> Result<TypeDefinition, String> {
+    let string_type = TypeDefinition {
+        name: "String".to_owned(),
+        module: None,
+    };
+
+    let parameter = operation
+        .parameters
+        .iter()
+        .filter_map(|parameter_ref| parameter_ref.resolve(spec).ok())
+        .find(|parameter| {
+            parameter.location == ParameterIn::Path && parameter.name == parameter_name
+        });
+    let object_schema = match parameter.and_then(|parameter| parameter.schema) {
+        Some(schema) => match schema.resolve(spec) {
+            Ok(object_schema) => object_schema,
+            Err(err) => {
+                return Err(format!(
+                    "Failed to resolve parameter {} {}",
+                    parameter_name, err
+                ))
+            }
+        },
+        None => return Ok(string_type),
+    };
+
+    match object_schema.schema_type {
+        Some(SchemaTypeSet::Single(
+            SchemaType::Boolean | SchemaType::Integer | SchemaType::Number | SchemaType::String,
+        )) if get_const_value(&object_schema).is_none() => get_type_from_schema(
+            spec,
+            object_database,
+            definition_path.to_vec(),
+            &object_schema,
+            Some(parameter_name),
+            name_mapping,
+        ),
+        _ => {
+            trace!("Path parameter {} is formatted as String", parameter_name);
+            Ok(string_type)
+        }
+    }
+}
+
+/// Names of the `{placeholders}` of a path template
+fn get_path_placeholders(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|placeholder| placeholder.split_once('}'))
+        .map(|(placeholder_name, _)| placeholder_name)
+        .collect()
+}
+
+/// Mismatches between the placeholders of the path template and the declared path parameters.
+/// Undeclared placeholders are generated as String fields, declared parameters which are not
+/// in the template are missing in the path parameters struct
+pub fn get_path_parameter_issues(spec: &Spec, operation: &Operation, path: &str) -> Vec<String> {
+    let placeholders = get_path_placeholders(path);
+    let declared_parameters = operation
+        .parameters
+        .iter()
+        .filter_map(|parameter_ref| parameter_ref.resolve(spec).ok())
+        .filter(|parameter| parameter.location == ParameterIn::Path)
+        .map(|parameter| parameter.name)
+        .collect::<Vec<String>>();
+    // Names which only differ in case or separators are likely typos
+    let normalized_name = |name: &str| {
+        name.chars()
+            .filter(|character| character.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase()
+    };
+
+    let mut issues = vec![];
+    for path_component in path.split('/') {
+        if !is_path_parameter(path_component) && path_component.contains('{') {
+            issues.push(format!(
+                "Placeholder in path segment {} is not supported, it is sent literally",
+                path_component
+            ));
+        }
+    }
+
+    for &placeholder in &placeholders {
+        if declared_parameters.iter().any(|name| name == placeholder) {
+            continue;
+        }
+        let similar_parameter = declared_parameters
+            .iter()
+            .find(|name| normalized_name(name) == normalized_name(placeholder));
+        issues.push(match similar_parameter {
+            Some(similar_parameter) => format!(
+                "Placeholder {{{}}} is not declared as path parameter (declared: {})",
+                placeholder, similar_parameter
+            ),
+            None => format!(
+                "Placeholder {{{}}} is not declared as path parameter and generated as String",
+                placeholder
+            ),
+        });
+    }
+
+    for declared_parameter in &declared_parameters {
+        if !placeholders.contains(&declared_parameter.as_str()) {
+            issues.push(format!(
+                "Path parameter {} is not in the path template and skipped",
+                declared_parameter
+            ));
+        }
+    }
+
+    issues
+}
+
+pub fn generate_path_parameter_code(
+    spec: &Spec,
+    operation: &Operation,
+    definition_path: &Vec<String>,
+    name_mapping: &NameMapping,
+    object_database: &mut ObjectDatabase,
+    function_name: &str,
+    path: &str,
+) -> Result<PathParameterCode, String> {
+    trace!("Generating path parameters");
+    for issue in get_path_parameter_issues(spec, operation, path) {
+        warn!("{}", issue);
+    }
+    let path_parameters_struct_name = name_mapping.name_to_struct_name(
+        &definition_path,
+        &format!("{}PathParameters", function_name),
+    );
+
+    let parameters_struct_variable_name =
+        name_mapping.name_to_property_name(definition_path, "path_parameters");
+
+    let mut path_parameters_definition_path = definition_path.clone();
+    path_parameters_definition_path.push(path_parameters_struct_name.clone());
+
+    let mut path_parameters_ordered = vec![];
+    for path_component in path
+        .split("/")
+        .filter(|&path_component| is_path_parameter(&path_component))
+        .map(|path_component| path_component.replace("{", "").replace("}", ""))
+    {
+        let parameter_type = get_path_parameter_type(
+            spec,
+            operation,
+            &path_parameters_definition_path,
+            name_mapping,
+            object_database,
+            &path_component,
+        )?;
+        path_parameters_ordered.push(PropertyDefinition {
+            module: parameter_type.module,
+            name: name_mapping
+                .name_to_property_name(&path_parameters_definition_path, &path_component),
+            real_name: path_component,
+            required: true,
+            type_name: parameter_type.name,
+            default_value: None,
+            read_only: false,
+            write_only: false,
+            constraints: PropertyConstraints::default(),
+        });
+    }
+    let path_struct_definition = StructDefinition {
+        name: path_parameters_struct_name,
+        used_modules: vec![],
+        local_objects: BTreeMap::new(),
+        example: None,
+        properties: path_parameters_ordered
+            .iter()
+            .map(|path_parameter| (path_parameter.name.clone(), path_parameter.clone()))
+            .collect::<BTreeMap<String, PropertyDefinition>>(),
+    };
+
+    let path_format_string = path
+        .split("/")
+        .map(|path_component| {
+            return match is_path_parameter(path_component) {
+                true => String::from("{}"),
+                _ => path_component.to_owned(),
+            };
+        })
+        .collect::<Vec<String>>()
+        .join("/");
+
+    Ok(PathParameterCode {
+        parameters_struct_variable_name,
+        parameters_struct: path_struct_definition,
+        path_format_string: path_format_string,
+        path_format_arguments: path_parameters_ordered,
+    })
+}
+
+/// Parameters of the download and batch functions, which only their templates declare
+const TEMPLATE_PARAMETER_NAMES: [&str; 4] = ["file_path", "progress", "requests", "concurrency"];
+
+/// Path and query parameters which are passed as arguments instead of parameter structs
+pub struct FlatParameters {
+    pub function_parameters: Vec<FunctionParameter>,
+    /// Statements which assemble the parameter structs from the arguments
+    pub assignments: Vec<String>,
+}
+
+/// Flat parameters of operations with at most `max_parameters` path and query parameters.
+/// None if the operation keeps the structs, e.g. because an argument would be named like
+/// another one or like one of the other `argument_names` of the functions
+pub fn get_flat_parameters(
+    max_parameters: Option<usize>,
+    path_parameter_code: &PathParameterCode,
+    query_parameter_code: &QueryParametersCode,
+    argument_names: &BTreeSet<&str>,
+) -> Option<FlatParameters> {
+    let query_properties = query_parameter_code
+        .query_struct
+        .properties
+        .values()
+        .collect::<Vec<&PropertyDefinition>>();
+    let parameter_count = path_parameter_code.path_format_arguments.len() + query_properties.len();
+    if parameter_count == 0 || parameter_count > max_parameters? {
+        return None;
+    }
+
+    let mut flat_parameters = FlatParameters {
+        function_parameters: vec![],
+        assignments: vec![],
+    };
+    for (struct_definition, variable_name, properties) in [
+        (
+            &path_parameter_code.parameters_struct,
+            &path_parameter_code.parameters_struct_variable_name,
+            path_parameter_code.path_format_arguments.iter().collect(),
+        ),
+        (
+            &query_parameter_code.query_struct,
+            &query_parameter_code.query_struct_variable_name,
+            query_properties,
+        ),
+    ] {
+        if properties.is_empty() {
+            continue;
+        }
+        for property in &properties {
+            flat_parameters.function_parameters.push(FunctionParameter {
+                name: property.name.clone(),
+                type_name: match property.required {
+                    true => property.type_name.clone(),
+                    false => format!("Option<{}>", property.type_name),
+                },
+                reference: false,
+            });
+        }
+        flat_parameters.assignments.push(format!(
+            "let {} = {} {{ {} }};",
+            variable_name,
+            struct_definition.name,
+            properties
+                .iter()
+                .map(|property| property.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        ));
+    }
+
+    let mut parameter_names = BTreeSet::new();
+    let unique_names = flat_parameters.function_parameters.iter().all(|parameter| {
+        !TEMPLATE_PARAMETER_NAMES.contains(&parameter.name.as_str())
+            && !argument_names.contains(parameter.name.as_str())
+            && parameter.name != path_parameter_code.parameters_struct_variable_name
+            && parameter.name != query_parameter_code.query_struct_variable_name
+            && parameter_names.insert(parameter.name.as_str())
+    });
+    if !unique_names {
+        info!("Parameters are passed as structs, their names collide with other arguments");
+        return None;
+    }
+    Some(flat_parameters)
+}
+
+pub struct QueryParametersCode {
+    pub query_struct: StructDefinition,
+    pub query_struct_variable_name: String,
+    /// Serialization styles by property name
+    pub query_parameter_styles: BTreeMap<String, QueryParameterStyle>,
+}
+
+impl QueryParametersCode {
+    pub fn query_parameters(&self) -> Vec<QueryParameter> {
+        self.query_struct
+            .properties
+            .iter()
+            .map(|(property_name, property)| {
+                let style = self
+                    .query_parameter_styles
+                    .get(property_name)
+                    .cloned()
+                    .unwrap_or_default();
+                QueryParameter {
+                    real_name: property.real_name.clone(),
+                    name: property.name.clone(),
+                    struct_name: self.query_struct_variable_name.clone(),
+                    is_required: property.required,
+                    is_array: property.type_name.starts_with("Vec<"),
+                    separator: style.separator,
+                    deep_object: style.deep_object,
+                    is_object: style.is_object,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether the template pushes query pairs after creating the list
+    pub fn query_parameters_mutable(&self) -> bool {
+        self.query_parameters()
+            .iter()
+            .any(|query_parameter| !query_parameter.is_required || query_parameter.is_unrolled())
+    }
+}
+
+pub fn generate_query_parameter_code(
+    spec: &Spec,
+    operation: &Operation,
+    definition_path: &Vec<String>,
+    name_mapping: &NameMapping,
+    object_database: &mut ObjectDatabase,
+    function_name: &str,
+) -> Result<QueryParametersCode, String> {
+    trace!("Generating query params");
+    let mut query_struct = StructDefinition {
+        name: name_mapping.name_to_struct_name(
+            &definition_path,
+            &format!("{}QueryParameters", &function_name),
+        ),
+        properties: BTreeMap::new(),
+        used_modules: vec![],
+        local_objects: BTreeMap::new(),
+        example: None,
+    };
+
+    let query_struct_variable_name =
+        name_mapping.name_to_property_name(&definition_path, "query_parameters");
+
+    let mut query_parameters_definition_path = definition_path.clone();
+    query_parameters_definition_path.push(query_struct.name.clone());
+
+    let mut query_parameter_styles = BTreeMap::new();
+
+    for parameter_ref in &operation.parameters {
+        let parameter = match parameter_ref.resolve(spec) {
+            Ok(parameter) => parameter,
+            Err(err) => return Err(format!("Failed to resolve parameter {}", err.to_string())),
+        };
+        if parameter.location != ParameterIn::Query {
+            continue;
+        }
+
+        let mut query_parameter_style = QueryParameterStyle::from_parameter(&parameter);
+
+        let (parameter_type, parameter_default) = match parameter.schema {
+            Some(ref schema) => match schema.resolve(spec) {
+                Ok(object_schema) => {
+                    query_parameter_style.is_object = matches!(
+                        object_schema.schema_type,
+                        Some(SchemaTypeSet::Single(SchemaType::Object))
+                    ) || !object_schema.properties.is_empty();
+                    (
+                        match get_type_override(name_mapping, schema) {
+                            Some(type_definition) => Ok(type_definition),
+                            None => get_type_from_schema(
+                                spec,
+                                object_database,
+                                query_parameters_definition_path.clone(),
+                                &object_schema,
+                                Some(&parameter.name),
+                                name_mapping,
+                            ),
+                        },
+                        object_schema.default.clone(),
+                    )
+                }
+                Err(err) => {
+                    return Err(format!(
+                        "Failed to resolve parameter {} {}",
+                        parameter.name,
+                        err.to_string()
+                    ))
+                }
+            },
+            None => return Err(format!("Parameter {} has no schema", parameter.name)),
+        };
+
+        query_parameter_styles.insert(
+            name_mapping.name_to_property_name(&query_parameters_definition_path, &parameter.name),
+            query_parameter_style,
+        );
+
+        let _ = match parameter_type {
+            Ok(parameter_type) => query_struct.properties.insert(
+                name_mapping
+                    .name_to_property_name(&query_parameters_definition_path, &parameter.name),
+                PropertyDefinition {
+                    name: name_mapping
+                        .name_to_property_name(&query_parameters_definition_path, &parameter.name),
+                    module: parameter_type.module,
+                    real_name: parameter.name,
+                    required: match parameter.required {
+                        Some(required) => required,
+                        None => false,
+                    },
+                    type_name: parameter_type.name,
+                    default_value: parameter_default,
+                    read_only: false,
+                    write_only: false,
+                    constraints: PropertyConstraints::default(),
+                },
+            ),
+            Err(err) => return Err(err),
+        };
+    }
+
+    Ok(QueryParametersCode {
+        query_struct_variable_name,
+        query_struct,
+        query_parameter_styles,
+    })
+}
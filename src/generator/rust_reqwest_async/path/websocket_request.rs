@@ -1,44 +1,22 @@
 use super::utils::{
-    generate_request_body, generate_responses, is_path_parameter, TransferMediaType,
+    generate_path_parameter_code, generate_query_parameter_code, generate_request_body,
+    generate_responses, FunctionParameter, QueryParameter,
 };
+use crate::generator::media_type::TransferMediaType;
 use crate::generator::rust_reqwest_async::templates::{
-    EnumDefinitionTemplate, PrimitiveDefinitionTemplate, StructDefinitionTemplate,
+    ConstDefinitionTemplate, EnumDefinitionTemplate, PrimitiveDefinitionTemplate,
+    StructDefinitionTemplate,
 };
 use crate::{
-    parser::component::{
-        object_definition::{
-            oas3_type_to_string,
-            types::{
-                ModuleInfo, ObjectDatabase, PropertyDefinition, StructDefinition, TypeDefinition,
-            },
-        },
-        type_definition::get_type_from_schema,
+    parser::component::object_definition::{
+        oas3_type_to_string,
+        types::{ModuleInfo, ObjectDatabase, TypeDefinition},
     },
-    utils::name_mapping::NameMapping,
+    utils::config::Config,
 };
 use askama::Template;
 use log::error;
-use oas3::{
-    spec::{FromRef, ObjectOrReference, ObjectSchema, Operation, ParameterIn},
-    Spec,
-};
-use std::collections::HashMap;
-
-#[derive(Debug)]
-struct QueryParameter {
-    is_required: bool,
-    is_array: bool,
-    real_name: String,
-    name: String,
-    struct_name: String,
-}
-
-#[derive(Debug)]
-struct FunctionParameter {
-    name: String,
-    type_name: String,
-    reference: bool,
-}
+use oas3::{spec::Operation, Spec};
 
 #[derive(Template)]
 #[template(path = "rust_reqwest_async/websocket.rs.jinja", ext = "rs")]
@@ -48,9 +26,11 @@ struct WebSocketRequestTemplate {
     struct_definitions: Vec<StructDefinitionTemplate>,
     enum_definitions: Vec<EnumDefinitionTemplate>,
     primitive_definitions: Vec<PrimitiveDefinitionTemplate>,
+    const_definitions: Vec<ConstDefinitionTemplate>,
     // WebSocket
     socket_stream_struct_name: String,
     response_type_name: String,
+    request_type_name: Option<String>,
     function_name: String,
     function_parameters: Vec<FunctionParameter>,
     path_format_string: String,
@@ -61,15 +41,16 @@ struct WebSocketRequestTemplate {
 
 pub fn generate_operation(
     spec: &Spec,
-    name_mapping: &NameMapping,
+    config: &Config,
     path: &str,
     operation: &Operation,
     object_database: &mut ObjectDatabase,
 ) -> Result<String, String> {
+    let name_mapping = &config.name_mapping;
     let operation_definition_path: Vec<String> = vec![path.to_owned()];
 
     let function_name = match operation.operation_id {
-        Some(ref operation_id) => name_mapping.name_to_module_name(operation_id),
+        Some(ref operation_id) => name_mapping.name_to_function_name(operation_id),
         None => return Err("No operation_id found".to_owned()),
     };
 
@@ -79,6 +60,7 @@ pub fn generate_operation(
         &operation_definition_path,
         name_mapping,
         &operation.responses(spec),
+        &config.generator.content_type_mapping,
         &function_name,
     ) {
         Ok(response_entities) => response_entities,
@@ -114,70 +96,45 @@ pub fn generate_operation(
             name: oas3_type_to_string(&oas3::spec::SchemaType::String),
             module: None,
         },
+        TransferMediaType::ApplicationXml(_) => {
+            return Err("Websocket with xml response body is not supported".to_owned())
+        }
+        TransferMediaType::OctetStream => {
+            return Err("Websocket with binary response body is not supported".to_owned())
+        }
+        TransferMediaType::EventStream(_) => {
+            return Err("Websocket with event stream response body is not supported".to_owned())
+        }
+        TransferMediaType::Ndjson(_) => {
+            return Err("Websocket with ndjson response body is not supported".to_owned())
+        }
     };
 
-    let path_parameters_struct_name = format!(
-        "{}PathParameters",
-        name_mapping.name_to_struct_name(&operation_definition_path, &function_name)
+    let mut path_parameter_code = generate_path_parameter_code(
+        spec,
+        operation,
+        &operation_definition_path,
+        name_mapping,
+        object_database,
+        &function_name,
+        path,
+    )?;
+    // Websocket functions keep their parameters named after the structs
+    path_parameter_code.parameters_struct_variable_name = name_mapping.name_to_property_name(
+        &operation_definition_path,
+        &path_parameter_code.parameters_struct.name,
     );
-    let mut path_parameters_definition_path = operation_definition_path.clone();
-    path_parameters_definition_path.push(path_parameters_struct_name.clone());
-
-    let path_parameters_ordered = path
-        .split("/")
-        .filter(|&path_component| is_path_parameter(&path_component))
-        .map(|path_component| path_component.replace("{", "").replace("}", ""))
-        .map(|path_component| PropertyDefinition {
-            module: None,
-            name: name_mapping
-                .name_to_property_name(&path_parameters_definition_path, &path_component),
-            real_name: path_component,
-            required: true,
-            type_name: "&str".to_owned(),
-        })
-        .collect::<Vec<PropertyDefinition>>();
-    let path_struct_definition = StructDefinition {
-        name: path_parameters_struct_name,
-        used_modules: vec![],
-        properties: path_parameters_ordered
-            .iter()
-            .map(|path_component| {
-                (
-                    path_component.name.clone(),
-                    PropertyDefinition {
-                        module: None,
-                        name: path_component.name.clone(),
-                        real_name: path_component.real_name.clone(),
-                        required: path_component.required,
-                        type_name: "String".to_owned(),
-                    },
-                )
-            })
-            .collect::<HashMap<String, PropertyDefinition>>(),
-        local_objects: HashMap::new(),
-    };
-    let mut struct_definitions = vec![&path_struct_definition];
-
-    let path_format_string = path
-        .split("/")
-        .map(|path_component| {
-            return match is_path_parameter(path_component) {
-                true => String::from("{}"),
-                _ => path_component.to_owned(),
-            };
-        })
-        .collect::<Vec<String>>()
-        .join("/");
 
     let mut function_parameters: Vec<FunctionParameter> = vec![];
+    let mut struct_definitions = vec![];
 
-    if !path_struct_definition.properties.is_empty() {
+    if !path_parameter_code.parameters_struct.properties.is_empty() {
         function_parameters.push(FunctionParameter {
-            name: name_mapping
-                .name_to_property_name(&operation_definition_path, &path_struct_definition.name),
-            type_name: path_struct_definition.name.clone(),
+            name: path_parameter_code.parameters_struct_variable_name.clone(),
+            type_name: path_parameter_code.parameters_struct.name.clone(),
             reference: false,
         });
+        struct_definitions.push(&path_parameter_code.parameters_struct);
     }
 
     let mut module_imports = vec![
@@ -224,88 +181,27 @@ pub fn generate_operation(
     }
 
     // Query params
-    let mut query_struct = StructDefinition {
-        name: format!(
-            "{}QueryParameters",
-            name_mapping.name_to_struct_name(&operation_definition_path, &function_name)
-        ),
-        properties: HashMap::new(),
-        used_modules: vec![],
-        local_objects: HashMap::new(),
-    };
-    let mut query_operation_definition_path = operation_definition_path.clone();
-    query_operation_definition_path.push(query_struct.name.clone());
-
-    for parameter_ref in &operation.parameters {
-        let parameter = match parameter_ref.resolve(spec) {
-            Ok(parameter) => parameter,
-            Err(err) => return Err(format!("Failed to resolve parameter {}", err.to_string())),
-        };
-        if parameter.location != ParameterIn::Query {
-            continue;
-        }
-
-        let parameter_type = match parameter.schema {
-            Some(schema) => match schema {
-                ObjectOrReference::Object(object_schema) => get_type_from_schema(
-                    spec,
-                    object_database,
-                    query_operation_definition_path.clone(),
-                    &object_schema,
-                    Some(&parameter.name),
-                    name_mapping,
-                ),
-                ObjectOrReference::Ref { ref_path } => {
-                    match ObjectSchema::from_ref(spec, &ref_path) {
-                        Ok(object_schema) => get_type_from_schema(
-                            spec,
-                            object_database,
-                            vec![],
-                            &object_schema,
-                            Some(&parameter.name),
-                            name_mapping,
-                        ),
-                        Err(err) => {
-                            return Err(format!(
-                                "Failed to resolve parameter {} {}",
-                                parameter.name,
-                                err.to_string()
-                            ))
-                        }
-                    }
-                }
-            },
-            None => return Err(format!("Parameter {} has no schema", parameter.name)),
-        };
-
-        let _ = match parameter_type {
-            Ok(parameter_type) => query_struct.properties.insert(
-                name_mapping
-                    .name_to_property_name(&query_operation_definition_path, &parameter.name),
-                PropertyDefinition {
-                    name: name_mapping
-                        .name_to_property_name(&query_operation_definition_path, &parameter.name),
-                    module: parameter_type.module,
-                    real_name: parameter.name,
-                    required: match parameter.required {
-                        Some(required) => required,
-                        None => false,
-                    },
-                    type_name: parameter_type.name,
-                },
-            ),
-            Err(err) => return Err(err),
-        };
-    }
+    let mut query_parameter_code = generate_query_parameter_code(
+        spec,
+        operation,
+        &operation_definition_path,
+        name_mapping,
+        object_database,
+        &function_name,
+    )?;
+    query_parameter_code.query_struct_variable_name = name_mapping.name_to_property_name(
+        &operation_definition_path,
+        &query_parameter_code.query_struct.name,
+    );
+    let query_struct = &query_parameter_code.query_struct;
 
-    if query_struct.properties.len() > 0 {
+    if !query_struct.properties.is_empty() {
         function_parameters.push(FunctionParameter {
-            name: name_mapping
-                .name_to_property_name(&operation_definition_path, &query_struct.name),
+            name: query_parameter_code.query_struct_variable_name.clone(),
             type_name: query_struct.name.clone(),
             reference: false,
         });
-        struct_definitions.push(&query_struct);
+        struct_definitions.push(query_struct);
     }
 
     function_parameters.push(FunctionParameter {
@@ -323,6 +219,7 @@ pub fn generate_operation(
                 &operation_definition_path,
                 name_mapping,
                 request_body,
+                &config.generator.content_type_mapping,
                 &function_name,
             ) {
                 Ok(request_body) => Some(request_body),
@@ -337,6 +234,7 @@ pub fn generate_operation(
         None => None,
     };
 
+    let mut request_type_name = None;
     if let Some(ref request_body) = request_body {
         if request_body.content.len() > 1 {
             error!("RequestBody with multiple content types is not supported")
@@ -351,6 +249,11 @@ pub fn generate_operation(
                                 module_imports.push(module.clone());
                             }
                         }
+                        module_imports.push(ModuleInfo {
+                            name: "Message".to_owned(),
+                            path: "tungstenite".to_owned(),
+                        });
+                        request_type_name = Some(type_definition.name.clone());
                         function_parameters.push(FunctionParameter {
                             name: name_mapping.name_to_property_name(
                                 &operation_definition_path,
@@ -367,25 +270,26 @@ pub fn generate_operation(
                     type_name: oas3_type_to_string(&oas3::spec::SchemaType::String),
                     reference: true,
                 }),
+                TransferMediaType::OctetStream => function_parameters.push(FunctionParameter {
+                    name: "request_bytes".to_owned(),
+                    type_name: "Vec<u8>".to_owned(),
+                    reference: true,
+                }),
+                TransferMediaType::ApplicationXml(_) => {
+                    error!("Xml websocket request bodies are not supported")
+                }
+                TransferMediaType::EventStream(_) => {
+                    error!("Event stream request bodies are not supported")
+                }
+                TransferMediaType::Ndjson(_) => {
+                    error!("Ndjson request bodies are not supported")
+                }
             }
             break;
         }
     }
 
-    let mut path_parameter_arguments = path_parameters_ordered
-        .iter()
-        .map(|parameter| {
-            format!(
-                "{}.{}",
-                name_mapping.name_to_property_name(
-                    &operation_definition_path,
-                    &path_struct_definition.name
-                ),
-                name_mapping.name_to_property_name(&operation_definition_path, &parameter.name)
-            )
-        })
-        .collect::<Vec<String>>()
-        .join(",");
+    let mut path_parameter_arguments = path_parameter_code.format_arguments().join(",");
     if path_parameter_arguments.len() > 0 {
         path_parameter_arguments += ","
     }
@@ -394,38 +298,27 @@ pub fn generate_operation(
         module_imports: module_imports,
         enum_definitions: vec![],
         primitive_definitions: vec![],
+        const_definitions: vec![],
         struct_definitions: struct_definitions
             .iter()
-            .map(|&s| Into::<StructDefinitionTemplate>::into(s).serializable(false))
+            .map(|&s| {
+                Into::<StructDefinitionTemplate>::into(s)
+                    .serializable(false)
+                    .derives(&config.generator.derives.parameters)
+            })
             .collect(),
         socket_stream_struct_name: format!(
             "{}Stream",
             name_mapping.name_to_struct_name(&operation_definition_path, &function_name)
         ),
         response_type_name: socket_transfer_type_definition.name.clone(),
+        request_type_name,
         function_name: function_name.clone(),
         function_parameters: function_parameters,
-        path_format_string: path_format_string,
+        path_format_string: path_parameter_code.path_format_string,
         path_parameter_arguments: path_parameter_arguments,
-        query_parameters_mutable: query_struct
-            .properties
-            .iter()
-            .filter(|(_, property)| !property.required || property.type_name.starts_with("Vec<"))
-            .collect::<Vec<(&String, &PropertyDefinition)>>()
-            .len()
-            > 0,
-        query_parameters: query_struct
-            .properties
-            .iter()
-            .map(|(_, property)| QueryParameter {
-                real_name: property.real_name.clone(),
-                name: property.name.clone(),
-                struct_name: name_mapping
-                    .name_to_property_name(&operation_definition_path, &query_struct.name),
-                is_required: property.required,
-                is_array: property.type_name.starts_with("Vec<"),
-            })
-            .collect(),
+        query_parameters_mutable: query_parameter_code.query_parameters_mutable(),
+        query_parameters: query_parameter_code.query_parameters(),
     }
     .render()
     .map_err(|err| err.to_string())
@@ -0,0 +1,11 @@
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/path_encoding.rs.jinja", ext = "rs")]
+struct PathEncodingTemplate {}
+
+pub fn generate_path_encoding_content() -> Result<String, String> {
+    PathEncodingTemplate {}
+        .render()
+        .map_err(|err| err.to_string())
+}
@@ -1,86 +1,219 @@
-use std::{
-    fs::{self, File},
-    io::Write,
-};
+use std::{borrow::Cow, collections::BTreeSet, sync::Arc};
 
 use log::{error, info};
 use oas3::{spec::Operation, Spec};
+use rayon::prelude::*;
 
-use crate::{parser::component::object_definition::types::ObjectDatabase, utils::config::Config};
+use crate::{
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{
+        config::{Config, OperationIdFallback},
+        log::LogContext,
+        output::OutputWriter,
+        report::GenerationReport,
+    },
+};
 
-use super::path::{http_request, websocket_request};
+use super::{
+    features::{get_cfg_attribute, get_operation_features, get_used_object_modules},
+    path::{
+        event_stream_request, http_request, ndjson_request,
+        utils::{is_event_stream_operation, is_ndjson_operation},
+        websocket_request,
+    },
+    prelude::get_public_items,
+    readme::{get_function_signatures, OperationSummary},
+    services::{generate_service_methods, get_services},
+};
 
-pub fn generate_paths(
-    output_path: &str,
-    spec: &Spec,
-    object_database: &mut ObjectDatabase,
+/// Operations (path, method, operation) which are generated, ignored paths are skipped
+pub fn collect_operations<'a>(
+    spec: &'a Spec,
     config: &Config,
-) -> Result<u32, String> {
-    let mut generated_path_count = 0;
+) -> Vec<(&'a String, reqwest::Method, &'a Operation)> {
+    let mut operations = vec![];
 
     let paths = match spec.paths {
         Some(ref paths) => paths,
-        None => return Ok(generated_path_count),
-    };
-
-    fs::create_dir_all(format!("{}/src/paths", output_path)).expect("Creating objects dir failed");
-
-    let mut mod_file = match File::create(format!("{}/src/paths/mod.rs", output_path)) {
-        Ok(file) => file,
-        Err(err) => {
-            return Err(format!("Unable to create file mod.rs {}", err.to_string()));
-        }
+        None => return operations,
     };
 
     for (name, path_item) in paths {
-        if config.ignore.path_ignored(&name) {
+        if config.ignore.path_ignored(name) {
             info!("{} ignored", name);
             continue;
         }
 
         info!("{}", name);
 
-        let mut operations = vec![];
+        let mut path_operations = vec![];
         if let Some(ref operation) = path_item.get {
-            operations.push((reqwest::Method::GET, operation));
+            path_operations.push((reqwest::Method::GET, operation));
         }
         if let Some(ref operation) = path_item.post {
-            operations.push((reqwest::Method::POST, operation));
+            path_operations.push((reqwest::Method::POST, operation));
         }
         if let Some(ref operation) = path_item.delete {
-            operations.push((reqwest::Method::DELETE, operation));
+            path_operations.push((reqwest::Method::DELETE, operation));
         }
         if let Some(ref operation) = path_item.put {
-            operations.push((reqwest::Method::PUT, operation));
+            path_operations.push((reqwest::Method::PUT, operation));
         }
         if let Some(ref operation) = path_item.patch {
-            operations.push((reqwest::Method::PATCH, operation));
+            path_operations.push((reqwest::Method::PATCH, operation));
+        }
+
+        for (method, operation) in path_operations {
+            if config.ignore.operation_ignored(&method, operation) {
+                info!("{} {} ignored", method.as_str(), name);
+                continue;
+            }
+            operations.push((name, method, operation));
         }
+    }
+
+    operations
+}
+
+pub fn generate_paths(
+    output: &OutputWriter,
+    spec: &Spec,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    report: &mut GenerationReport,
+) -> Result<Vec<OperationSummary>, String> {
+    let mut generated_operations_summary = vec![];
+
+    if spec.paths.is_none() {
+        return Ok(generated_operations_summary);
+    }
+
+    let operations = collect_operations(spec, config);
+
+    // Operations with the same module would overwrite each others file
+    let operation_ids = operations
+        .iter()
+        .filter_map(|(path, method, operation)| {
+            resolve_operation_id(config, method, path, operation).map(|operation_id| {
+                let description = format!("{} ({} {})", operation_id, method.as_str(), path);
+                (operation_id, description)
+            })
+        })
+        .collect::<Vec<(String, String)>>();
+    let collisions = config
+        .name_mapping
+        .get_module_name_collisions(&operation_ids);
+    if !collisions.is_empty() {
+        return Err(collisions.join("\n"));
+    }
 
-        for operation in operations {
-            match write_operation_to_file(
+    // Operations are generated in parallel. Each one works on its own layer on top of the
+    // shared object database and only returns the objects it added (inline request/response
+    // types)
+    let shared_object_database = Arc::new(std::mem::take(object_database));
+    let generated_operations = operations
+        .par_iter()
+        .map(|(name, method, operation)| {
+            let mut operation_object_database = ObjectDatabase::layer(&shared_object_database);
+            let operation_result = write_operation_to_file(
                 spec,
-                &operation.0,
-                &name,
-                operation.1,
+                method,
+                name,
+                operation,
+                &mut operation_object_database,
+                config,
+                output,
+            );
+            (
+                operation_result,
+                operation_object_database.added_since(&shared_object_database),
+            )
+        })
+        .collect::<Vec<(Result<(OperationSummary, bool), String>, ObjectDatabase)>>();
+    // All layers are dropped, so the database isn't shared anymore
+    *object_database = Arc::try_unwrap(shared_object_database)
+        .unwrap_or_else(|shared_object_database| (*shared_object_database).clone());
+
+    // Merged in spec order to keep the output reproducible
+    let mut mod_content = String::new();
+    let mut test_mod_content = String::new();
+    for ((name, method, operation), (mut operation_result, added_objects)) in
+        operations.iter().zip(generated_operations)
+    {
+        // An object name was used for different schemas by other operations in parallel.
+        // The operation is generated again with all objects known to pick unique names
+        if let Err(conflicting_names) = object_database.merge(added_objects) {
+            info!(
+                "{} {} generated again, {} used by other operations",
+                method.as_str(),
+                name,
+                conflicting_names.join(", ")
+            );
+            operation_result = write_operation_to_file(
+                spec,
+                method,
+                name,
+                operation,
                 object_database,
-                &config,
-                output_path,
-            ) {
-                Ok(operation_id) => {
-                    mod_file
-                        .write(format!("pub mod {};\n", operation_id).as_bytes())
-                        .expect("Failed to write to mod.rs");
-                    ()
-                }
-                Err(err) => {
-                    error!("{}", err);
+                config,
+                output,
+            );
+        }
+
+        match operation_result {
+            Ok((operation_summary, has_test)) => {
+                let cfg_attribute = match config.generator.tag_features {
+                    true => get_cfg_attribute(&get_operation_features(&operation_summary)),
+                    false => String::new(),
+                };
+                mod_content += &format!(
+                    "{}pub mod {};\n",
+                    cfg_attribute, operation_summary.module_name
+                );
+                if has_test {
+                    test_mod_content +=
+                        &format!("{}mod {};\n", cfg_attribute, operation_summary.module_name);
                 }
+                generated_operations_summary.push(operation_summary);
+                report.add_operation(method, name);
+            }
+            Err(err) => {
+                error!("{} {} skipped: {}", method.as_str(), name, err);
+                report.skip_operation(method, name, &err);
             }
-            generated_path_count += 1;
         }
     }
-    Ok(generated_path_count)
+
+    output
+        .write("src/paths/mod.rs", &mod_content)
+        .map_err(|err| format!("Unable to write mod.rs {}", err))?;
+
+    if !test_mod_content.is_empty() {
+        output
+            .write("tests/operations/main.rs", &test_mod_content)
+            .map_err(|err| format!("Unable to write tests main.rs {}", err))?;
+    }
+    Ok(generated_operations_summary)
+}
+
+/// operationId of the operation or the configured fallback name
+pub fn resolve_operation_id(
+    config: &Config,
+    method: &reqwest::Method,
+    path: &str,
+    operation: &Operation,
+) -> Option<String> {
+    match operation.operation_id {
+        Some(ref operation_id) => Some(operation_id.clone()),
+        None => match config.generator.operation_id_fallback {
+            OperationIdFallback::None => None,
+            OperationIdFallback::MethodPath => Some(
+                config
+                    .name_mapping
+                    .operation_id_from_path(method.as_str(), path),
+            ),
+        },
+    }
 }
 
 fn write_operation_to_file(
@@ -90,14 +223,28 @@ fn write_operation_to_file(
     operation: &Operation,
     object_database: &mut ObjectDatabase,
     config: &Config,
-    output_path: &str,
-) -> Result<String, String> {
-    let operation_id = match operation.operation_id {
-        Some(ref operation_id) => &config.name_mapping.name_to_module_name(operation_id),
+    output: &OutputWriter,
+) -> Result<(OperationSummary, bool), String> {
+    let mut operation = Cow::Borrowed(operation);
+    let (operation_id, function_name) = match resolve_operation_id(config, method, path, &operation)
+    {
+        Some(operation_id) => {
+            if operation.operation_id.is_none() {
+                operation.to_mut().operation_id = Some(operation_id.clone());
+            }
+            (
+                &config.name_mapping.name_to_module_name(&operation_id),
+                config.name_mapping.name_to_function_name(&operation_id),
+            )
+        }
         None => {
-            return Err(format!("{} {} has no id", path, method.as_str()));
+            return Err("Operation has no id".to_owned());
         }
     };
+    let _log_context = LogContext::enter(format!(
+        "operation {}",
+        operation.operation_id.as_deref().unwrap_or_default()
+    ));
 
     let generate_websocket = match operation.extensions.get("serverstream") {
         Some(extension_value) => match extension_value {
@@ -107,44 +254,117 @@ fn write_operation_to_file(
         None => &false,
     };
 
+    let mut test_code = None;
+    // Optional crates are tracked by the kind of the operation
+    let mut used_crates = BTreeSet::new();
     let request_code = match generate_websocket {
         true => match websocket_request::generate_operation(
             spec,
-            &config.name_mapping,
+            config,
             &path,
             &operation,
             object_database,
         ) {
-            Ok(request_code) => request_code,
+            Ok(request_code) => {
+                used_crates.insert("tungstenite".to_owned());
+                request_code
+            }
             Err(err) => return Err(format!("Failed to generated websocket code {}", err)),
         },
-        _ => match http_request::generate_operation(
+        _ if is_event_stream_operation(
+            &operation.responses(spec),
+            &config.generator.content_type_mapping,
+        ) =>
+        {
+            match event_stream_request::generate_operation(
+                spec,
+                config,
+                method,
+                path,
+                &operation,
+                object_database,
+            ) {
+                Ok(request_code) => request_code,
+                Err(err) => return Err(format!("Failed to generate event stream code {}", err)),
+            }
+        }
+        _ if is_ndjson_operation(
+            &operation.responses(spec),
+            &config.generator.content_type_mapping,
+        ) =>
+        {
+            match ndjson_request::generate_operation(
+                spec,
+                config,
+                method,
+                path,
+                &operation,
+                object_database,
+            ) {
+                Ok(request_code) => {
+                    used_crates.insert("futures".to_owned());
+                    request_code
+                }
+                Err(err) => return Err(format!("Failed to generate ndjson stream code {}", err)),
+            }
+        }
+        _ => match http_request::generate_operation_code(
             spec,
-            &config.name_mapping,
+            config,
             method,
             &path,
             &operation,
             object_database,
+            config.generator.generate_tests,
         ) {
-            Ok(request_code) => request_code,
+            Ok((request_code, request_test_code, request_crates)) => {
+                test_code = request_test_code;
+                used_crates = request_crates;
+                request_code
+            }
             Err(err) => {
                 return Err(format!("Failed to generate code {}", err));
             }
         },
     };
 
-    let mut path_file = match File::create(format!("{}/src/paths/{}.rs", output_path, operation_id))
-    {
-        Ok(file) => file,
-        Err(err) => {
-            return Err(format!(
-                "Unable to create file {}.rs {}",
-                operation_id,
-                err.to_string()
-            ));
+    let mut request_code = request_code;
+    if config.generator.tag_services {
+        let service_methods = generate_service_methods(
+            &request_code,
+            &function_name,
+            &get_services(
+                &operation.tags,
+                &config.name_mapping,
+                config.generator.tag_features,
+            ),
+        )
+        .map_err(|err| format!("Failed to generate service methods {}", err))?;
+        if !service_methods.is_empty() {
+            request_code = format!("{}\n{}", request_code.trim_end(), service_methods);
         }
-    };
+    }
+
+    output
+        .write(&format!("src/paths/{}.rs", operation_id), &request_code)
+        .map_err(|err| format!("Unable to write file {}.rs {}", operation_id, err))?;
 
-    path_file.write(request_code.as_bytes()).unwrap();
-    Ok(operation_id.clone())
+    if let Some(ref test_code) = test_code {
+        output
+            .write(&format!("tests/operations/{}.rs", operation_id), test_code)
+            .map_err(|err| format!("Unable to write test {}.rs {}", operation_id, err))?;
+    }
+    Ok((
+        OperationSummary {
+            module_name: operation_id.clone(),
+            method: method.as_str().to_owned(),
+            path: path.to_owned(),
+            tags: operation.tags.clone(),
+            functions: get_function_signatures(&request_code, &function_name),
+            object_modules: get_used_object_modules(&request_code),
+            used_crates,
+            items: get_public_items(&request_code),
+        },
+        test_code.is_some(),
+    ))
 }
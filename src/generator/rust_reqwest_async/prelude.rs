@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    auth::uses_auth,
+    conditional::uses_conditional,
+    download::uses_download,
+    features::{get_cfg_attribute, get_operation_features},
+    readme::OperationSummary,
+};
+use crate::{
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{config::GeneratorConfig, name_mapping::NameMapping},
+};
+
+const ITEM_KEYWORDS: [&str; 6] = ["struct ", "enum ", "fn ", "async fn ", "type ", "const "];
+
+/// Names of the top level public items of generated code, methods are indented and skipped
+pub fn get_public_items(code: &str) -> BTreeSet<String> {
+    code.lines()
+        .filter_map(|line| line.strip_prefix("pub "))
+        .filter_map(|declaration| {
+            ITEM_KEYWORDS
+                .iter()
+                .find_map(|keyword| declaration.strip_prefix(keyword))
+        })
+        .map(|declaration| {
+            declaration
+                .chars()
+                .take_while(|character| character.is_ascii_alphanumeric() || *character == '_')
+                .collect::<String>()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Re-exports all objects (with their local objects) and the items of the path modules, so
+/// `use <crate>::prelude::*` imports functions, parameters, responses and models at once.
+/// Path items which are defined by several path modules are skipped because they would be
+/// ambiguous, they stay reachable through `crate::paths`. Gated modules are re-exported with
+/// the same features, optional client types if they are enabled
+pub fn generate_prelude_content(
+    operations: &[OperationSummary],
+    object_database: &ObjectDatabase,
+    name_mapping: &NameMapping,
+    object_features: &BTreeMap<String, BTreeSet<String>>,
+    config: &GeneratorConfig,
+) -> String {
+    let mut prelude_content = String::new();
+
+    for (name, _) in object_database {
+        let module_name = name_mapping.name_to_module_name(name);
+        let cfg_attribute = object_features
+            .get(&module_name)
+            .map(get_cfg_attribute)
+            .unwrap_or_default();
+        prelude_content += &format!(
+            "{}pub use crate::objects::{}::*;\n",
+            cfg_attribute, module_name
+        );
+    }
+
+    let mut item_counts: BTreeMap<&String, usize> = BTreeMap::new();
+    for item in operations.iter().flat_map(|operation| &operation.items) {
+        *item_counts.entry(item).or_default() += 1;
+    }
+
+    for operation in operations {
+        let items = operation
+            .items
+            .iter()
+            .filter(|item| item_counts.get(item) == Some(&1))
+            .map(|item| item.as_str())
+            .collect::<Vec<&str>>();
+        if items.is_empty() {
+            continue;
+        }
+
+        let cfg_attribute = match config.tag_features {
+            true => get_cfg_attribute(&get_operation_features(operation)),
+            false => String::new(),
+        };
+        prelude_content += &format!(
+            "{}pub use crate::paths::{}::{{{}}};\n",
+            cfg_attribute,
+            operation.module_name,
+            items.join(", ")
+        );
+    }
+
+    if config.request_options {
+        prelude_content += "pub use crate::request_options::RequestOptions;\n";
+    }
+    if config.capture_undefined_response {
+        prelude_content += "pub use crate::undefined_response::UndefinedResponse;\n";
+    }
+    if uses_conditional(operations) {
+        prelude_content += "pub use crate::conditional::CacheValidation;\n";
+    }
+    if uses_download(operations) {
+        prelude_content += "pub use crate::download::DownloadError;\n";
+    }
+    if uses_auth(operations) {
+        prelude_content += "pub use crate::auth::Auth;\n";
+    }
+
+    prelude_content
+}
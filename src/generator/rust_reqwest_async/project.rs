@@ -1,53 +1,501 @@
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
 
-use log::info;
+use log::{info, warn};
 
-use super::cargo::generate_cargo_content;
+use super::auth::{generate_auth_content, uses_auth};
+use super::aws_sigv4::generate_aws_sigv4_content;
+use super::cargo::{
+    add_missing_dependencies, generate_cargo_content, generate_models_cargo_content,
+    generate_workspace_cargo_content, get_missing_features,
+};
+use super::changelog::write_changelog;
+use super::client::{generate_client_content, uses_client};
+use super::conditional::{generate_conditional_content, uses_conditional};
+use super::download::{generate_download_content, uses_download};
+use super::features::{get_all_features, get_object_features};
+use super::formats::generate_formats_content;
 use super::objects::write_object_database;
+use super::pagination::{generate_pagination_content, uses_link_pagination};
+use super::path_encoding::generate_path_encoding_content;
 use super::paths::generate_paths;
+use super::prelude::generate_prelude_content;
+use super::rate_limit::{generate_rate_limit_content, uses_rate_limit};
+use super::readme::generate_readme_content;
+use super::request_options::generate_request_options_content;
+use super::services::generate_services_content;
+use super::undefined_response::generate_undefined_response_content;
+use super::validation::generate_validation_content;
+use super::version::{
+    get_package_version, load_ir_snapshot, suggest_version, IR_SNAPSHOT_FILE_NAME,
+};
+use super::webhooks::generate_webhooks_content;
+use super::xml::generate_xml_content;
+use crate::generator::media_type::{uses_ndjson_content, uses_xml_content};
 use crate::parser::component::object_definition::types::ObjectDatabase;
-use crate::utils::config::Config;
+use crate::parser::ir::parse_spec_ir;
+use crate::parser::spec_diff::diff_spec_ir;
+use crate::utils::config::{Config, OutputLayout, VersionBump};
+use crate::utils::output::OutputWriter;
+use crate::utils::report::GenerationReport;
 
+/// Generates the client and reports what was generated
 pub fn generate_project(
     output_dir: &str,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    spec: &oas3::Spec,
+) -> GenerationReport {
+    let mut report = GenerationReport::default();
+    match config.generator.layout {
+        OutputLayout::Crate => {
+            generate_crate(output_dir, None, object_database, config, spec, &mut report)
+        }
+        OutputLayout::Workspace => {
+            let name = &config.project_metadata.name;
+            let models_crate_name = format!("{}-models", name);
+            let client_crate_name = format!("{}-client", name);
+
+            // The client crate is generated like a single crate with its own name
+            let mut client_config = config.clone();
+            client_config.project_metadata.name = client_crate_name.clone();
+
+            generate_crate(
+                &format!("{}/{}", output_dir, client_crate_name),
+                Some(&models_crate_name),
+                object_database,
+                &client_config,
+                spec,
+                &mut report,
+            );
+
+            write_cargo_file(
+                output_dir,
+                &generate_workspace_cargo_content(&[models_crate_name, client_crate_name])
+                    .expect("Failed to generate workspace Cargo.toml"),
+            );
+        }
+    }
+    report
+}
+
+/// Generates the crate with objects and paths, or with paths only if the objects are written
+/// to `models_crate_name` next to it
+fn generate_crate(
+    output_dir: &str,
+    models_crate_name: Option<&str>,
     mut object_database: &mut ObjectDatabase,
     config: &Config,
     spec: &oas3::Spec,
+    report: &mut GenerationReport,
 ) {
-    let generated_paths = generate_paths(output_dir, &spec, &mut object_database, &config)
-        .expect("Failed to generated paths");
+    let output = OutputWriter::new(output_dir);
+    // The snapshot of the previous generation is read before it's overwritten
+    let (previous_ir, spec_ir) = match (config.generator.version_bump, config.generator.changelog) {
+        (VersionBump::Off, false) => (None, None),
+        _ => (
+            load_ir_snapshot(output_dir),
+            Some(parse_spec_ir(spec, config).expect("Failed to parse spec")),
+        ),
+    };
+    // The models crate is a sibling of the crate
+    let models_dir = models_crate_name.map(|models_crate_name| {
+        Path::new(output_dir)
+            .with_file_name(models_crate_name)
+            .to_string_lossy()
+            .into_owned()
+    });
+    let models_output = models_dir.as_deref().map(OutputWriter::new);
+    let models_crate =
+        models_crate_name.map(|models_crate_name| models_crate_name.replace('-', "_"));
+
+    let generated_operations =
+        generate_paths(&output, &spec, &mut object_database, &config, report)
+            .expect("Failed to generated paths");
 
-    write_object_database(output_dir, &object_database, &config.name_mapping)
-        .expect("Write objects failed");
+    // Payloads of webhooks are objects, so they are generated before the objects are written
+    let webhooks_content = generate_webhooks_content(spec, config, object_database)
+        .expect("Failed to generate webhooks");
+
+    let object_features = match config.generator.tag_features {
+        true => get_object_features(&generated_operations, object_database, &config.name_mapping),
+        false => BTreeMap::new(),
+    };
+    write_object_database(
+        models_output.as_ref().unwrap_or(&output),
+        &object_database,
+        &config.name_mapping,
+        &config.generator,
+        &object_features,
+    )
+    .expect("Write objects failed");
     // 4. Project setup
-    let mut lib_file =
-        File::create(format!("{}/src/lib.rs", output_dir)).expect("Failed to create lib.rs");
+    let mut lib_content = String::new();
+    let mut models_lib_content = String::new();
+    let xml = uses_xml_content(spec, &config.generator.content_type_mapping);
+    let ndjson = uses_ndjson_content(spec, &config.generator.content_type_mapping);
+    let features = get_all_features(&generated_operations);
+    let used_crates = generated_operations
+        .iter()
+        .flat_map(|operation| operation.used_crates.iter().cloned())
+        .collect::<BTreeSet<String>>();
 
     if object_database.len() > 0 {
-        lib_file
-            .write("pub mod objects;\n".to_string().as_bytes())
-            .unwrap();
+        match models_crate {
+            Some(ref models_crate) => {
+                lib_content += &format!("pub use {}::objects;\n", models_crate);
+                models_lib_content += "pub mod objects;\n";
+            }
+            None => lib_content += "pub mod objects;\n",
+        }
+    }
+
+    if !generated_operations.is_empty() {
+        lib_content += "pub mod paths;\n";
+
+        output
+            .write(
+                "src/path_encoding.rs",
+                &generate_path_encoding_content().expect("Failed to generate path_encoding.rs"),
+            )
+            .expect("Failed to write path_encoding.rs");
+        lib_content += "pub mod path_encoding;\n";
+    }
+
+    if !object_database.is_empty()
+        || !generated_operations.is_empty()
+        || config.generator.request_options
+        || config.generator.capture_undefined_response
+    {
+        output
+            .write(
+                "src/prelude.rs",
+                &generate_prelude_content(
+                    &generated_operations,
+                    object_database,
+                    &config.name_mapping,
+                    &object_features,
+                    &config.generator,
+                ),
+            )
+            .expect("Failed to write prelude.rs");
+        lib_content += "pub mod prelude;\n";
+    }
+
+    if config.generator.tag_services && !generated_operations.is_empty() {
+        output
+            .write(
+                "src/services.rs",
+                &generate_services_content(
+                    &generated_operations,
+                    &config.name_mapping,
+                    &config.generator,
+                )
+                .expect("Failed to generate services.rs"),
+            )
+            .expect("Failed to write services.rs");
+        lib_content += "pub mod services;\n";
+    }
+
+    if config.generator.request_options {
+        output
+            .write(
+                "src/request_options.rs",
+                &generate_request_options_content(config.generator.aws_sigv4.is_some())
+                    .expect("Failed to generate request_options.rs"),
+            )
+            .expect("Failed to write request_options.rs");
+        lib_content += "pub mod request_options;\n";
+    }
+
+    if let Some(ref aws_sigv4) = config.generator.aws_sigv4 {
+        output
+            .write(
+                "src/aws_sigv4.rs",
+                &generate_aws_sigv4_content(aws_sigv4).expect("Failed to generate aws_sigv4.rs"),
+            )
+            .expect("Failed to write aws_sigv4.rs");
+        lib_content += "pub mod aws_sigv4;\n";
+    }
+
+    if config.generator.capture_undefined_response {
+        output
+            .write(
+                "src/undefined_response.rs",
+                &generate_undefined_response_content()
+                    .expect("Failed to generate undefined_response.rs"),
+            )
+            .expect("Failed to write undefined_response.rs");
+        lib_content += "pub mod undefined_response;\n";
+    }
+
+    if uses_client(&config.generator) {
+        output
+            .write(
+                "src/client.rs",
+                &generate_client_content(&config.generator).expect("Failed to generate client.rs"),
+            )
+            .expect("Failed to write client.rs");
+        lib_content += "pub mod client;\n";
+    }
+
+    if uses_conditional(&generated_operations) {
+        output
+            .write(
+                "src/conditional.rs",
+                &generate_conditional_content().expect("Failed to generate conditional.rs"),
+            )
+            .expect("Failed to write conditional.rs");
+        lib_content += "pub mod conditional;\n";
+    }
+
+    if uses_auth(&generated_operations) {
+        output
+            .write(
+                "src/auth.rs",
+                &generate_auth_content(spec, &config.name_mapping)
+                    .expect("Failed to generate auth.rs"),
+            )
+            .expect("Failed to write auth.rs");
+        lib_content += "pub mod auth;\n";
+    }
+
+    // The retries of request options honor the Retry-After header
+    if config.generator.request_options || uses_rate_limit(spec) {
+        output
+            .write(
+                "src/rate_limit.rs",
+                &generate_rate_limit_content().expect("Failed to generate rate_limit.rs"),
+            )
+            .expect("Failed to write rate_limit.rs");
+        lib_content += "pub mod rate_limit;\n";
     }
 
-    if generated_paths > 0 {
-        lib_file
-            .write("pub mod paths;\n".to_string().as_bytes())
-            .unwrap();
+    if uses_download(&generated_operations) {
+        output
+            .write(
+                "src/download.rs",
+                &generate_download_content().expect("Failed to generate download.rs"),
+            )
+            .expect("Failed to write download.rs");
+        lib_content += "pub mod download;\n";
     }
 
-    let output_cargo_file_path = format!("{}/Cargo.toml", output_dir);
+    if uses_link_pagination(spec) {
+        output
+            .write(
+                "src/pagination.rs",
+                &generate_pagination_content().expect("Failed to generate pagination.rs"),
+            )
+            .expect("Failed to write pagination.rs");
+        lib_content += "pub mod pagination;\n";
+    }
+
+    if config.generator.validation {
+        output
+            .write(
+                "src/validation.rs",
+                &generate_validation_content(
+                    xml,
+                    true,
+                    config.generator.aws_sigv4.is_some(),
+                    models_crate.as_deref(),
+                )
+                .expect("Failed to generate validation.rs"),
+            )
+            .expect("Failed to write validation.rs");
+        lib_content += "pub mod validation;\n";
+
+        // Objects implement the validators of their own crate
+        if let Some(ref models_output) = models_output {
+            models_output
+                .write(
+                    "src/validation.rs",
+                    &generate_validation_content(false, false, false, None)
+                        .expect("Failed to generate validation.rs"),
+                )
+                .expect("Failed to write validation.rs");
+            models_lib_content += "pub mod validation;\n";
+        }
+    }
+
+    if let Some(ref webhooks_content) = webhooks_content {
+        output
+            .write("src/webhooks.rs", webhooks_content)
+            .expect("Failed to write webhooks.rs");
+        lib_content += "pub mod webhooks;\n";
+    }
+
+    if xml {
+        output
+            .write(
+                "src/xml.rs",
+                &generate_xml_content(config.generator.aws_sigv4.is_some())
+                    .expect("Failed to generate xml.rs"),
+            )
+            .expect("Failed to write xml.rs");
+        lib_content += "pub mod xml;\n";
+    }
+
+    let type_mapping = &config.name_mapping.type_mapping;
+    if type_mapping.uses_formats_module() {
+        // Objects use the newtypes of their own crate
+        let formats_content =
+            generate_formats_content(type_mapping).expect("Failed to generate formats.rs");
+        models_output
+            .as_ref()
+            .unwrap_or(&output)
+            .write("src/formats.rs", &formats_content)
+            .expect("Failed to write formats.rs");
+        match models_crate {
+            Some(ref models_crate) => {
+                lib_content += &format!("pub use {}::formats;\n", models_crate);
+                models_lib_content += "pub mod formats;\n";
+            }
+            None => lib_content += "pub mod formats;\n",
+        }
+    }
+
+    output
+        .write("src/lib.rs", &lib_content)
+        .expect("Failed to write lib.rs");
+    output
+        .write(
+            "README.md",
+            &generate_readme_content(config, spec, &generated_operations)
+                .expect("Failed to generate README.md"),
+        )
+        .expect("Failed to write README.md");
+    if let Some(ref spec_ir) = spec_ir {
+        output
+            .write(
+                IR_SNAPSHOT_FILE_NAME,
+                &spec_ir.to_json().expect("Failed to serialize IR"),
+            )
+            .expect("Failed to write IR snapshot");
+    }
+    report
+        .files
+        .add(output.finish().expect("Failed to write manifest"));
+    report.objects = object_database.len();
+
+    let mut crate_dirs = vec![output_dir.to_owned()];
+    if let (Some(models_output), Some(models_dir), Some(models_crate_name)) =
+        (models_output, models_dir, models_crate_name)
+    {
+        models_output
+            .write("src/lib.rs", &models_lib_content)
+            .expect("Failed to write lib.rs");
+        report
+            .files
+            .add(models_output.finish().expect("Failed to write manifest"));
+
+        write_cargo_file(
+            &models_dir,
+            &generate_models_cargo_content(config, models_crate_name, &features)
+                .expect("Failed to generate Cargo.toml"),
+        );
+        crate_dirs.push(models_dir);
+    }
+
+    write_cargo_file(
+        output_dir,
+        &generate_cargo_content(
+            config,
+            xml,
+            ndjson,
+            &used_crates,
+            &features,
+            models_crate_name,
+        )
+        .expect("Failed to generate Cargo.toml"),
+    );
+
+    // Versions are only bumped in Cargo.toml files of previous generations
+    if let (Some(previous_ir), Some(spec_ir)) = (previous_ir, spec_ir) {
+        let changes = diff_spec_ir(&previous_ir, &spec_ir);
+        let crate_dirs = crate_dirs.iter().map(String::as_str).collect::<Vec<&str>>();
+        if config.generator.version_bump != VersionBump::Off {
+            match suggest_version(config.generator.version_bump, &changes, &crate_dirs) {
+                Ok(version) => report.version = Some(version),
+                Err(err) => warn!("Version not suggested: {}", err),
+            }
+        }
+
+        if config.generator.changelog && !changes.is_empty() {
+            // Entries are named after the (bumped) version of Cargo.toml
+            let version = fs::read_to_string(Path::new(output_dir).join("Cargo.toml"))
+                .ok()
+                .and_then(|cargo_content| get_package_version(&cargo_content))
+                .unwrap_or_else(|| config.project_metadata.version.clone());
+            if let Err(err) = write_changelog(output_dir, &version, &changes) {
+                warn!("Changelog not written: {}", err);
+            }
+        }
+    }
+}
+
+/// Cargo.toml is only written once, so changes of users are kept. Dependencies which the
+/// generated code needs are added to an existing one
+fn write_cargo_file(crate_dir: &str, content: &str) {
+    let output_cargo_file_path = format!("{}/Cargo.toml", crate_dir);
     let cargo_file_path = Path::new(&output_cargo_file_path);
     if cargo_file_path.exists() {
-        info!("{:?} exists and will be skipped", output_cargo_file_path);
+        update_cargo_file(cargo_file_path, content);
         return;
     }
 
     let mut cargo_file = File::create(output_cargo_file_path).expect("Failed to create Cargo.toml");
     cargo_file
-        .write(
-            generate_cargo_content(&config.project_metadata)
-                .expect("Failed to generate Cargo.toml")
-                .as_bytes(),
-        )
+        .write(content.as_bytes())
         .expect("Failed to write Cargo.toml");
 }
+
+fn update_cargo_file(cargo_file_path: &Path, content: &str) {
+    let cargo_content = match fs::read_to_string(cargo_file_path) {
+        Ok(cargo_content) => cargo_content,
+        Err(err) => {
+            warn!("{:?} not updated: {}", cargo_file_path, err);
+            return;
+        }
+    };
+
+    match get_missing_features(&cargo_content, content) {
+        Ok(missing_features) if !missing_features.is_empty() => warn!(
+            "{:?} lacks the features {}",
+            cargo_file_path,
+            missing_features.join(", ")
+        ),
+        Ok(_) => (),
+        Err(err) => {
+            warn!("{:?} not updated: {}", cargo_file_path, err);
+            return;
+        }
+    }
+
+    match add_missing_dependencies(&cargo_content, content) {
+        Ok((_, added_dependencies)) if added_dependencies.is_empty() => {
+            info!("{:?} exists and will be skipped", cargo_file_path)
+        }
+        Ok((cargo_content, added_dependencies)) => {
+            match fs::write(cargo_file_path, cargo_content) {
+                Ok(()) => info!(
+                    "Added dependencies {} to {:?}",
+                    added_dependencies.join(", "),
+                    cargo_file_path
+                ),
+                Err(err) => warn!(
+                    "{:?} lacks the dependencies {}: {}",
+                    cargo_file_path,
+                    added_dependencies.join(", "),
+                    err
+                ),
+            }
+        }
+        Err(err) => warn!("{:?} not updated: {}", cargo_file_path, err),
+    }
+}
@@ -0,0 +1,16 @@
+use askama::Template;
+use oas3::Spec;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/rate_limit.rs.jinja", ext = "rs")]
+struct RateLimitTemplate {}
+
+pub fn generate_rate_limit_content() -> Result<String, String> {
+    RateLimitTemplate {}.render().map_err(|err| err.to_string())
+}
+
+/// An operation documents 429 responses, whose variants contain the rate limit headers
+pub fn uses_rate_limit(spec: &Spec) -> bool {
+    spec.operations()
+        .any(|(_, _, operation)| operation.responses(spec).contains_key("429"))
+}
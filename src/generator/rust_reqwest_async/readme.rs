@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use askama::Template;
+use oas3::Spec;
+
+use crate::utils::config::Config;
+
+/// Generated operation as listed in the README
+#[derive(Clone, Debug)]
+pub struct OperationSummary {
+    pub module_name: String,
+    pub method: String,
+    pub path: String,
+    pub tags: Vec<String>,
+    /// Public async functions of the path module
+    pub functions: Vec<FunctionSignature>,
+    /// Modules of the objects used by the path module
+    pub object_modules: BTreeSet<String>,
+    /// Optional dependencies used by the path module
+    pub used_crates: BTreeSet<String>,
+    /// Names of the top level public items of the path module
+    pub items: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FunctionSignature {
+    pub name: String,
+    pub signature: String,
+}
+
+struct OperationGroupTemplate {
+    name: String,
+    operations: Vec<OperationSummary>,
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/readme.md.jinja", ext = "txt")]
+struct ReadmeTemplate {
+    name: String,
+    crate_name: String,
+    version: String,
+    title: String,
+    description: Option<String>,
+    server: String,
+    example_operation: Option<OperationSummary>,
+    groups: Vec<OperationGroupTemplate>,
+}
+
+const UNTAGGED_GROUP_NAME: &str = "Other";
+
+/// Signatures of the public async functions `function_name` or `function_name_*` in the generated code
+pub fn get_function_signatures(code: &str, function_name: &str) -> Vec<FunctionSignature> {
+    let mut signatures = vec![];
+    for (start, _) in code.match_indices("pub async fn ") {
+        // Only top level functions, methods are indented
+        if !code[..start].is_empty() && !code[..start].ends_with('\n') {
+            continue;
+        }
+
+        let signature_code = &code[start + "pub ".len()..];
+        let end = signature_code.find('{').unwrap_or(signature_code.len());
+        let signature = signature_code[..end]
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .replace("( ", "(")
+            .replace(", )", ")");
+        let name = signature["async fn ".len()..]
+            .split(['(', '<'])
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+        if name == function_name
+            || name.starts_with(&format!("{}_", function_name.trim_end_matches('_')))
+        {
+            signatures.push(FunctionSignature { name, signature });
+        }
+    }
+    signatures
+}
+
+/// README.md of the generated crate with a quick start and all operations grouped by tag
+pub fn generate_readme_content(
+    config: &Config,
+    spec: &Spec,
+    operations: &[OperationSummary],
+) -> Result<String, String> {
+    let mut tag_groups: BTreeMap<&str, Vec<OperationSummary>> = BTreeMap::new();
+    let mut untagged_operations = vec![];
+    for operation in operations {
+        if operation.tags.is_empty() {
+            untagged_operations.push(operation.clone());
+        }
+        for tag in &operation.tags {
+            tag_groups.entry(tag).or_default().push(operation.clone());
+        }
+    }
+
+    let mut groups = tag_groups
+        .into_iter()
+        .map(|(name, operations)| OperationGroupTemplate {
+            name: name.to_owned(),
+            operations,
+        })
+        .collect::<Vec<OperationGroupTemplate>>();
+    if !untagged_operations.is_empty() {
+        groups.push(OperationGroupTemplate {
+            name: UNTAGGED_GROUP_NAME.to_owned(),
+            operations: untagged_operations,
+        });
+    }
+
+    let template = ReadmeTemplate {
+        name: config.project_metadata.name.clone(),
+        crate_name: config.project_metadata.name.replace('-', "_"),
+        version: config.project_metadata.version.clone(),
+        title: spec.info.title.clone(),
+        description: spec
+            .info
+            .description
+            .as_ref()
+            .map(|description| description.trim().to_owned()),
+        server: spec
+            .servers
+            .first()
+            .map(|server| server.url.clone())
+            .unwrap_or("http://localhost:8080".to_owned()),
+        // Websocket functions connect to a host instead of taking a client
+        example_operation: operations
+            .iter()
+            .find(|operation| {
+                operation.functions.first().is_some_and(|function| {
+                    function
+                        .signature
+                        .contains("(client: &reqwest::Client, server: &str")
+                })
+            })
+            .cloned(),
+        groups,
+    };
+    template.render().map_err(|err| err.to_string())
+}
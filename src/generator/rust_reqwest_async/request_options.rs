@@ -0,0 +1,16 @@
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/request_options.rs.jinja", ext = "rs")]
+struct RequestOptionsTemplate {
+    retry_status_codes: Vec<u16>,
+    aws_sigv4: bool,
+}
+
+pub fn generate_request_options_content(aws_sigv4: bool) -> Result<String, String> {
+    let template = RequestOptionsTemplate {
+        retry_status_codes: vec![429, 502, 503, 504],
+        aws_sigv4,
+    };
+    template.render().map_err(|e| e.to_string())
+}
@@ -0,0 +1,266 @@
+use askama::Template;
+
+use super::{
+    features::{get_cfg_attribute, get_tag_feature_name},
+    readme::{get_function_signatures, OperationSummary},
+};
+use crate::utils::{config::GeneratorConfig, name_mapping::NameMapping};
+
+/// Service of operations without tags, like their group in the README
+const UNTAGGED_SERVICE_NAME: &str = "other";
+
+/// Service struct of a tag with the function of `ApiClient` which returns it
+#[derive(Clone)]
+pub struct ServiceDefinition {
+    pub description: String,
+    pub struct_name: String,
+    pub accessor_name: String,
+    /// Trait of the service methods, e.g. `UsersApi`
+    pub trait_name: String,
+    /// `#[cfg(..)]` of the tag feature
+    pub cfg_attribute: String,
+}
+
+/// Method of a service which calls a function of the path module with the client and
+/// server of the `ApiClient`
+#[derive(Clone)]
+pub struct ServiceMethod {
+    pub name: String,
+    /// Generic parameters including `<>`, e.g. `<F: FnMut(u64, Option<u64>)>`
+    pub generics: String,
+    /// Parameters after `client` and `server`
+    pub parameters: Vec<String>,
+    pub arguments: Vec<String>,
+    pub return_type: String,
+}
+
+/// Trait of a service with the methods which don't take or return `impl` types
+pub struct ServiceTrait {
+    pub service: ServiceDefinition,
+    pub methods: Vec<ServiceMethod>,
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/services.rs.jinja", ext = "rs")]
+struct ServicesTemplate {
+    services: Vec<ServiceDefinition>,
+    /// Empty without `service_traits`
+    traits: Vec<ServiceTrait>,
+    mocks: bool,
+    /// `ApiClient::builder()` returns the `ApiClientBuilder` of the client module
+    client_tls: bool,
+    /// `ApiClient::unix_socket(path)` of the `unix-socket` feature
+    unix_socket: bool,
+    /// `ApiClient::with_options(options, server)` builds the client with `ClientOptions`
+    client_options: bool,
+}
+
+impl ServicesTemplate {
+    /// Trait methods use the types of the path modules
+    fn uses_prelude(&self) -> bool {
+        self.traits
+            .iter()
+            .any(|service_trait| !service_trait.methods.is_empty())
+    }
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/service_methods.rs.jinja", ext = "rs")]
+struct ServiceMethodsTemplate<'a> {
+    services: &'a [ServiceDefinition],
+    methods: Vec<ServiceMethod>,
+}
+
+/// Services of the tags of an operation, `OtherService` for untagged ones
+pub fn get_services(
+    tags: &[String],
+    name_mapping: &NameMapping,
+    tag_features: bool,
+) -> Vec<ServiceDefinition> {
+    if tags.is_empty() {
+        let name = name_mapping.name_to_struct_name(&vec![], UNTAGGED_SERVICE_NAME);
+        return vec![ServiceDefinition {
+            description: "Operations without tags".to_owned(),
+            struct_name: format!("{}Service", name),
+            accessor_name: name_mapping.name_to_function_name(UNTAGGED_SERVICE_NAME),
+            trait_name: format!("{}Api", name),
+            cfg_attribute: String::new(),
+        }];
+    }
+
+    let mut services: Vec<ServiceDefinition> = vec![];
+    for tag in tags {
+        let name = name_mapping.name_to_struct_name(&vec![], tag);
+        let struct_name = format!("{}Service", name);
+        if services
+            .iter()
+            .any(|service| service.struct_name == struct_name)
+        {
+            continue;
+        }
+        let feature = get_tag_feature_name(tag);
+        let cfg_attribute = match tag_features && !feature.is_empty() {
+            true => get_cfg_attribute(&[feature].into()),
+            false => String::new(),
+        };
+        services.push(ServiceDefinition {
+            description: format!("Operations tagged `{}`", tag),
+            struct_name,
+            accessor_name: name_mapping.name_to_function_name(tag),
+            trait_name: format!("{}Api", name),
+            cfg_attribute: cfg_attribute.trim_end().to_owned(),
+        });
+    }
+    services
+}
+
+/// Splits parameters at top level commas, commas of generics, tuples and closures are kept
+fn split_parameters(parameters: &str) -> Vec<String> {
+    let mut split_parameters = vec![];
+    let mut depth = 0;
+    let mut parameter = String::new();
+    for character in parameters.chars() {
+        match character {
+            '<' | '(' | '[' => depth += 1,
+            // `->` of closure types is no closing bracket
+            '>' if parameter.ends_with('-') => (),
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                split_parameters.push(parameter.trim().to_owned());
+                parameter.clear();
+                continue;
+            }
+            _ => (),
+        }
+        parameter.push(character);
+    }
+    if !parameter.trim().is_empty() {
+        split_parameters.push(parameter.trim().to_owned());
+    }
+    split_parameters
+}
+
+/// Method of a function signature like `async fn name<G>(client: &reqwest::Client,
+/// server: &str, ..) -> T`. Functions without client and server (websockets) have no method
+fn get_service_method(name: &str, signature: &str) -> Option<ServiceMethod> {
+    let declaration = signature.strip_prefix("async fn ")?.strip_prefix(name)?;
+    let (generics, declaration) = match declaration.starts_with('<') {
+        true => {
+            let mut depth = 0;
+            let end = declaration.char_indices().find_map(|(index, character)| {
+                match character {
+                    '<' => depth += 1,
+                    '>' => depth -= 1,
+                    _ => (),
+                }
+                (depth == 0).then_some(index)
+            })?;
+            (&declaration[..end + 1], &declaration[end + 1..])
+        }
+        false => ("", declaration),
+    };
+    let parameters_end = declaration.rfind(") -> ")?;
+    let parameters = split_parameters(declaration.strip_prefix('(')?.get(..parameters_end - 1)?);
+    let return_type = declaration[parameters_end + ") -> ".len()..].trim();
+
+    if parameters.len() < 2
+        || parameters[0] != "client: &reqwest::Client"
+        || parameters[1] != "server: &str"
+    {
+        return None;
+    }
+    let parameters = parameters[2..].to_vec();
+    let arguments = parameters
+        .iter()
+        .map(|parameter| {
+            let name = parameter.split(':').next().unwrap_or_default().trim();
+            name.strip_prefix("mut ").unwrap_or(name).to_owned()
+        })
+        .collect();
+
+    Some(ServiceMethod {
+        name: name.to_owned(),
+        generics: generics.to_owned(),
+        parameters,
+        arguments,
+        return_type: return_type.to_owned(),
+    })
+}
+
+/// `impl` blocks of the services which are appended to the code of a path module, so the
+/// methods use the types of the module
+pub fn generate_service_methods(
+    request_code: &str,
+    function_name: &str,
+    services: &[ServiceDefinition],
+) -> Result<String, String> {
+    let methods = get_function_signatures(request_code, function_name)
+        .iter()
+        .filter_map(|function| get_service_method(&function.name, &function.signature))
+        .collect::<Vec<ServiceMethod>>();
+    if methods.is_empty() {
+        return Ok(String::new());
+    }
+    ServiceMethodsTemplate { services, methods }
+        .render()
+        .map_err(|err| err.to_string())
+}
+
+/// Methods of traits can't take `impl` parameters or return `impl` types which the service
+/// implementation could name, e.g. download progress callbacks or streams
+fn is_trait_method(method: &ServiceMethod) -> bool {
+    method.generics.is_empty()
+        && !method.return_type.contains("impl ")
+        && !method
+            .parameters
+            .iter()
+            .any(|parameter| parameter.contains("impl "))
+}
+
+/// services.rs with the `ApiClient` and the service structs of the tags of all operations.
+/// The service traits use the types of the path modules through the prelude
+pub fn generate_services_content(
+    operations: &[OperationSummary],
+    name_mapping: &NameMapping,
+    config: &GeneratorConfig,
+) -> Result<String, String> {
+    let mut traits: Vec<ServiceTrait> = vec![];
+    for operation in operations {
+        let methods = operation
+            .functions
+            .iter()
+            .filter_map(|function| get_service_method(&function.name, &function.signature))
+            .filter(is_trait_method)
+            .collect::<Vec<ServiceMethod>>();
+        for service in get_services(&operation.tags, name_mapping, config.tag_features) {
+            match traits
+                .iter_mut()
+                .find(|service_trait| service_trait.service.struct_name == service.struct_name)
+            {
+                Some(service_trait) => service_trait.methods.extend(methods.iter().cloned()),
+                None => traits.push(ServiceTrait {
+                    service,
+                    methods: methods.clone(),
+                }),
+            }
+        }
+    }
+
+    let services = traits
+        .iter()
+        .map(|service_trait| service_trait.service.clone())
+        .collect();
+    if !config.service_traits {
+        traits.clear();
+    }
+    ServicesTemplate {
+        services,
+        traits,
+        mocks: config.service_mocks,
+        client_tls: config.client_tls,
+        unix_socket: config.unix_socket.is_some(),
+        client_options: config.client_options,
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
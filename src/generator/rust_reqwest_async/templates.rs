@@ -1,15 +1,55 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use askama::Template;
 
-use crate::parser::component::object_definition::types::{
-    to_unique_list, EnumDefinition, EnumValue, ModuleInfo, PrimitiveDefinition, PropertyDefinition,
-    StructDefinition,
+use super::validation::get_validation_code;
+use crate::{
+    parser::component::object_definition::{
+        get_object_name,
+        types::{
+            to_unique_list, ConstDefinition, EnumDefinition, EnumValue, ModuleInfo,
+            ObjectDefinition, PrimitiveDefinition, PropertyDefinition, StructDefinition,
+        },
+    },
+    utils::config::{DeriveConfig, PropertySerde},
 };
 
 pub struct PrimitiveDefinitionTemplate {
+    pub derives: Vec<String>,
     pub name: String,
     pub type_name: String,
 }
 
+impl PrimitiveDefinitionTemplate {
+    pub fn derives(mut self, derives: &[String]) -> Self {
+        self.derives = derives.to_vec();
+        self
+    }
+
+    pub fn derive_list(&self) -> String {
+        get_derive_list(true, &self.derives)
+    }
+
+    /// Newtypes of scalar types are displayed and parsed like their value
+    pub fn displayable(&self) -> bool {
+        matches!(self.type_name.as_str(), "String" | "i32" | "f64" | "bool")
+    }
+}
+
+/// Contents of the `#[derive(..)]` attribute, serializable types derive serde first
+fn get_derive_list(serializable: bool, derives: &[String]) -> String {
+    let serde_derives = match serializable {
+        true => vec!["Serialize".to_owned(), "Deserialize".to_owned()],
+        false => vec![],
+    };
+    serde_derives
+        .iter()
+        .chain(derives)
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 fn get_serialization_imports() -> Vec<ModuleInfo> {
     vec![
         ModuleInfo {
@@ -26,6 +66,7 @@ fn get_serialization_imports() -> Vec<ModuleInfo> {
 impl From<&PrimitiveDefinition> for PrimitiveDefinitionTemplate {
     fn from(primitive_definition: &PrimitiveDefinition) -> Self {
         PrimitiveDefinitionTemplate {
+            derives: DeriveConfig::default().models,
             name: primitive_definition.name.clone(),
             type_name: primitive_definition.primitive_type.name.clone(),
         }
@@ -37,21 +78,57 @@ impl From<&PrimitiveDefinition> for BaseTemplate {
         BaseTemplate {
             struct_definitions: vec![],
             enum_definitions: vec![],
-            primitive_definitions: vec![PrimitiveDefinitionTemplate {
-                name: primitive_definition.name.clone(),
-                type_name: primitive_definition.primitive_type.name.clone(),
-            }],
+            primitive_definitions: vec![primitive_definition.into()],
+            const_definitions: vec![],
             module_imports: to_unique_list(
                 &primitive_definition
                     .primitive_type
                     .module
                     .as_ref()
-                    .map_or(vec![], |module| vec![module.clone()]),
+                    .map_or(vec![], |module| vec![module.clone()])
+                    .into_iter()
+                    .chain(get_serialization_imports())
+                    .collect::<Vec<ModuleInfo>>(),
             ),
         }
     }
 }
 
+pub struct ConstDefinitionTemplate {
+    pub name: String,
+    /// Json of the value as raw string literal
+    pub value: String,
+    /// Text of the value as string literal, strings without quotes (`dog`) and other values
+    /// as json (`4`)
+    pub display_value: String,
+}
+
+impl From<&ConstDefinition> for ConstDefinitionTemplate {
+    fn from(const_definition: &ConstDefinition) -> Self {
+        let display_value = match const_definition.value {
+            serde_json::Value::String(ref value) => value.clone(),
+            ref value => value.to_string(),
+        };
+        ConstDefinitionTemplate {
+            name: const_definition.name.clone(),
+            value: raw_string_literal(&const_definition.value.to_string()),
+            display_value: format!("{:?}", display_value),
+        }
+    }
+}
+
+impl From<&ConstDefinition> for BaseTemplate {
+    fn from(const_definition: &ConstDefinition) -> Self {
+        BaseTemplate {
+            struct_definitions: vec![],
+            enum_definitions: vec![],
+            primitive_definitions: vec![],
+            const_definitions: vec![const_definition.into()],
+            module_imports: get_serialization_imports(),
+        }
+    }
+}
+
 pub struct EnumValueTemplate {
     pub name: String,
     pub value_type: String,
@@ -68,8 +145,11 @@ impl From<&EnumValue> for EnumValueTemplate {
 
 pub struct EnumDefinitionTemplate {
     pub serializable: bool,
+    pub derives: Vec<String>,
     pub name: String,
     pub values: Vec<EnumValueTemplate>,
+    /// Variants which get `From` and `TryFrom` implementations for their inner type
+    pub conversions: Vec<EnumValueTemplate>,
 }
 
 impl EnumDefinitionTemplate {
@@ -77,18 +157,51 @@ impl EnumDefinitionTemplate {
         self.serializable = serializable;
         self
     }
+
+    pub fn derives(mut self, derives: &[String]) -> Self {
+        self.derives = derives.to_vec();
+        self
+    }
+
+    pub fn derive_list(&self) -> String {
+        get_derive_list(self.serializable, &self.derives)
+    }
+
+    /// Conversions of all variants whose inner type isn't used by another variant, `From`
+    /// implementations of the same type would conflict
+    pub fn conversions(mut self) -> Self {
+        let mut type_counts: BTreeMap<&String, usize> = BTreeMap::new();
+        for value in &self.values {
+            *type_counts.entry(&value.value_type).or_default() += 1;
+        }
+
+        self.conversions = self
+            .values
+            .iter()
+            .filter(|value| {
+                !value.value_type.is_empty() && type_counts.get(&value.value_type) == Some(&1)
+            })
+            .map(|value| EnumValueTemplate {
+                name: value.name.clone(),
+                value_type: value.value_type.clone(),
+            })
+            .collect();
+        self
+    }
 }
 
 impl From<&EnumDefinition> for EnumDefinitionTemplate {
     fn from(enum_definition: &EnumDefinition) -> Self {
         EnumDefinitionTemplate {
             serializable: true,
+            derives: DeriveConfig::default().models,
             name: enum_definition.name.clone(),
             values: enum_definition
                 .values
                 .iter()
                 .map(|(_, value)| value.into())
                 .collect(),
+            conversions: vec![],
         }
     }
 }
@@ -106,6 +219,7 @@ impl From<&EnumDefinition> for BaseTemplate {
             struct_definitions: vec![],
             enum_definitions: vec![EnumDefinitionTemplate::from(enum_definition)],
             primitive_definitions: vec![],
+            const_definitions: vec![],
             module_imports: to_unique_list(&module_imports),
         }
     }
@@ -129,10 +243,41 @@ impl From<&PropertyDefinition> for PropertyTemplate {
     }
 }
 
+pub struct PropertyDefaultTemplate {
+    pub name: String,
+    pub value: String,
+}
+
 pub struct StructDefinitionTemplate {
     pub serializable: bool,
+    pub derives: Vec<String>,
     pub name: String,
     pub properties: Vec<PropertyDefinition>,
+    /// All properties implement Default and no schema defaults are set
+    pub derive_default: bool,
+    /// Values of a custom Default impl if schema defaults are set
+    pub default_values: Vec<PropertyDefaultTemplate>,
+    /// Schema example as raw string literal of its json
+    pub example: Option<String>,
+    /// readOnly properties are skipped when serializing, writeOnly ones when deserializing
+    pub skip_read_write_only: bool,
+    /// Deserialization fails on fields which aren't properties
+    pub deny_unknown_fields: bool,
+    /// Body of the `Validate` impl, None if the struct isn't validated
+    pub validation_code: Option<Vec<String>>,
+    /// Contents of `#[serde(..)]` attributes by property name
+    pub serde_attributes: BTreeMap<String, Vec<String>>,
+}
+
+/// Generator options of structs written to the objects module
+#[derive(Default)]
+pub struct StructTemplateOptions<'a> {
+    pub skip_read_write_only: bool,
+    pub deny_unknown_fields: bool,
+    /// Structs which implement `Validate`, None if no validation is generated
+    pub validated_structs: Option<&'a BTreeSet<String>>,
+    /// Serde functions by `Struct.property`
+    pub property_serde: Option<&'a BTreeMap<String, PropertySerde>>,
 }
 
 impl StructDefinitionTemplate {
@@ -140,45 +285,257 @@ impl StructDefinitionTemplate {
         self.serializable = serializable;
         self
     }
-}
 
-impl From<&StructDefinition> for StructDefinitionTemplate {
-    fn from(struct_definition: &StructDefinition) -> Self {
+    pub fn derives(mut self, derives: &[String]) -> Self {
+        self.derives = derives.to_vec();
+        self
+    }
+
+    pub fn derive_list(&self) -> String {
+        get_derive_list(self.serializable, &self.derives)
+    }
+
+    pub fn property_serde_attributes(&self, property_name: &str) -> Vec<String> {
+        self.serde_attributes
+            .get(property_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Skipped properties are optional because they are missing in one direction
+    pub fn new(struct_definition: &StructDefinition, options: &StructTemplateOptions) -> Self {
+        let skip_read_write_only = options.skip_read_write_only;
+        let properties = struct_definition
+            .properties
+            .values()
+            .cloned()
+            .map(|mut property| {
+                if skip_read_write_only && (property.read_only || property.write_only) {
+                    property.required = false;
+                }
+                property
+            })
+            .collect::<Vec<PropertyDefinition>>();
+
+        let default_value_codes = properties
+            .iter()
+            .map(get_default_value_code)
+            .collect::<Vec<Option<String>>>();
+
+        let is_defaultable =
+            properties
+                .iter()
+                .zip(&default_value_codes)
+                .all(|(property, default_value_code)| {
+                    !property.required
+                        || default_value_code.is_some()
+                        || is_default_type(&property.type_name)
+                });
+        let has_default_values = default_value_codes.iter().any(Option::is_some);
+
+        let default_values = match is_defaultable && has_default_values {
+            true => properties
+                .iter()
+                .zip(default_value_codes)
+                .map(|(property, default_value_code)| PropertyDefaultTemplate {
+                    name: property.name.clone(),
+                    value: default_value_code.unwrap_or("Default::default()".to_owned()),
+                })
+                .collect(),
+            false => vec![],
+        };
+
         StructDefinitionTemplate {
             serializable: true,
+            derives: DeriveConfig::default().models,
             name: struct_definition.name.clone(),
-            properties: struct_definition
-                .properties
-                .iter()
-                .map(|(_, property)| property.clone())
-                .collect(),
+            derive_default: is_defaultable && !has_default_values,
+            default_values,
+            example: struct_definition
+                .example
+                .as_ref()
+                .map(|example| raw_string_literal(&example.to_string())),
+            skip_read_write_only,
+            deny_unknown_fields: options.deny_unknown_fields,
+            serde_attributes: get_serde_attributes(
+                &struct_definition.name,
+                &properties,
+                options.property_serde,
+            ),
+            validation_code: options
+                .validated_structs
+                .filter(|validated_structs| validated_structs.contains(&struct_definition.name))
+                .map(|validated_structs| get_validation_code(&properties, validated_structs)),
+            properties,
         }
     }
 }
 
+/// Attributes of the properties which are configured by their spec or rust name
+fn get_serde_attributes(
+    struct_name: &str,
+    properties: &[PropertyDefinition],
+    property_serde: Option<&BTreeMap<String, PropertySerde>>,
+) -> BTreeMap<String, Vec<String>> {
+    let property_serde = match property_serde {
+        Some(property_serde) => property_serde,
+        None => return BTreeMap::new(),
+    };
+    properties
+        .iter()
+        .filter_map(|property| {
+            property_serde
+                .get(&format!("{}.{}", struct_name, property.real_name))
+                .or_else(|| property_serde.get(&format!("{}.{}", struct_name, property.name)))
+                .map(|serde| (property.name.clone(), serde.attributes(property.required)))
+        })
+        .collect()
+}
+
+/// Raw string literal with enough `#` to contain any value
+pub fn raw_string_literal(value: &str) -> String {
+    let mut hashes = String::from("#");
+    while value.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{}\"{}\"{}", hashes, value, hashes)
+}
+
+fn is_default_type(type_name: &str) -> bool {
+    matches!(type_name, "bool" | "String" | "f64" | "i32") || type_name.starts_with("Vec<")
+}
+
+/// Converts a schema `default` value into a rust expression of the property type
+fn get_default_value_code(property: &PropertyDefinition) -> Option<String> {
+    let default_value = property.default_value.as_ref()?;
+    let value_code = match (property.type_name.as_str(), default_value) {
+        ("bool", serde_json::Value::Bool(value)) => value.to_string(),
+        ("String", serde_json::Value::String(value)) => format!("String::from({:?})", value),
+        ("i32", serde_json::Value::Number(value)) => value
+            .as_i64()
+            .and_then(|value| i32::try_from(value).ok())?
+            .to_string(),
+        ("f64", serde_json::Value::Number(value)) => format!("{}_f64", value.as_f64()?),
+        _ => return None,
+    };
+
+    match property.required {
+        true => Some(value_code),
+        false => Some(format!("Some({})", value_code)),
+    }
+}
+
+impl From<&StructDefinition> for StructDefinitionTemplate {
+    fn from(struct_definition: &StructDefinition) -> Self {
+        StructDefinitionTemplate::new(struct_definition, &StructTemplateOptions::default())
+    }
+}
+
 impl From<&StructDefinition> for BaseTemplate {
     fn from(struct_definition: &StructDefinition) -> Self {
+        BaseTemplate::from_struct(struct_definition, &StructTemplateOptions::default())
+    }
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/base.rs.jinja", ext = "rs")]
+pub struct BaseTemplate {
+    pub module_imports: Vec<ModuleInfo>,
+    pub struct_definitions: Vec<StructDefinitionTemplate>,
+    pub enum_definitions: Vec<EnumDefinitionTemplate>,
+    pub primitive_definitions: Vec<PrimitiveDefinitionTemplate>,
+    pub const_definitions: Vec<ConstDefinitionTemplate>,
+}
+
+impl BaseTemplate {
+    pub fn from_struct(
+        struct_definition: &StructDefinition,
+        options: &StructTemplateOptions,
+    ) -> Self {
+        let struct_definition_template = StructDefinitionTemplate::new(struct_definition, options);
+
         let mut module_imports = struct_definition
             .get_required_modules()
             .iter()
             .map(|&module| module.clone())
             .collect::<Vec<ModuleInfo>>();
         module_imports.append(&mut get_serialization_imports());
+        if struct_definition_template.validation_code.is_some() {
+            module_imports.push(ModuleInfo {
+                name: "Validate".to_owned(),
+                path: "crate::validation".to_owned(),
+            });
+        }
 
-        BaseTemplate {
-            struct_definitions: vec![StructDefinitionTemplate::from(struct_definition)],
+        let mut template = BaseTemplate {
+            struct_definitions: vec![struct_definition_template],
             enum_definitions: vec![],
             primitive_definitions: vec![],
-            module_imports: to_unique_list(&module_imports),
+            const_definitions: vec![],
+            module_imports: vec![],
+        };
+
+        // Local objects are defined next to the struct instead of in their own module
+        for local_object in struct_definition.local_objects.values() {
+            let local_template = match local_object.as_ref() {
+                ObjectDefinition::Struct(local_struct_definition) => {
+                    BaseTemplate::from_struct(local_struct_definition, options)
+                }
+                ObjectDefinition::Enum(enum_definition) => enum_definition.into(),
+                ObjectDefinition::Primitive(primitive_definition) => primitive_definition.into(),
+                ObjectDefinition::Const(const_definition) => const_definition.into(),
+            };
+            template.append(local_template);
         }
+
+        module_imports.append(&mut template.module_imports);
+        module_imports.retain(|module| {
+            !struct_definition
+                .local_objects
+                .values()
+                .any(|local_object| get_object_name(local_object) == &module.name)
+        });
+        template.module_imports = to_unique_list(&module_imports);
+        template
     }
-}
 
-#[derive(Template)]
-#[template(path = "rust_reqwest_async/base.rs.jinja", ext = "rs")]
-pub struct BaseTemplate {
-    pub module_imports: Vec<ModuleInfo>,
-    pub struct_definitions: Vec<StructDefinitionTemplate>,
-    pub enum_definitions: Vec<EnumDefinitionTemplate>,
-    pub primitive_definitions: Vec<PrimitiveDefinitionTemplate>,
+    /// Sets the derives of all structs, enums and primitive newtypes
+    pub fn derives(mut self, derives: &[String]) -> Self {
+        self.struct_definitions = self
+            .struct_definitions
+            .into_iter()
+            .map(|struct_definition| struct_definition.derives(derives))
+            .collect();
+        self.enum_definitions = self
+            .enum_definitions
+            .into_iter()
+            .map(|enum_definition| enum_definition.derives(derives))
+            .collect();
+        self.primitive_definitions = self
+            .primitive_definitions
+            .into_iter()
+            .map(|primitive_definition| primitive_definition.derives(derives))
+            .collect();
+        self
+    }
+
+    /// Adds `From` and `TryFrom` conversions between all enums and their variant types
+    pub fn conversions(mut self) -> Self {
+        self.enum_definitions = self
+            .enum_definitions
+            .into_iter()
+            .map(|enum_definition| enum_definition.conversions())
+            .collect();
+        self
+    }
+
+    fn append(&mut self, mut other: BaseTemplate) {
+        self.module_imports.append(&mut other.module_imports);
+        self.struct_definitions
+            .append(&mut other.struct_definitions);
+        self.enum_definitions.append(&mut other.enum_definitions);
+        self.primitive_definitions
+            .append(&mut other.primitive_definitions);
+        self.const_definitions.append(&mut other.const_definitions);
+    }
 }
@@ -0,0 +1,11 @@
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/undefined_response.rs.jinja", ext = "rs")]
+struct UndefinedResponseTemplate {}
+
+pub fn generate_undefined_response_content() -> Result<String, String> {
+    UndefinedResponseTemplate {}
+        .render()
+        .map_err(|err| err.to_string())
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeSet;
+
+use askama::Template;
+use log::warn;
+
+use crate::parser::component::object_definition::types::{
+    ObjectDatabase, ObjectDefinition, PropertyDefinition,
+};
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/validation.rs.jinja", ext = "rs")]
+struct ValidationTemplate {
+    /// Request errors include xml (de)serialization errors
+    xml: bool,
+    /// `RequestError` of http functions, which depends on reqwest
+    request_error: bool,
+    /// Request errors include signing errors of `aws_sigv4`
+    aws_sigv4: bool,
+    /// Crate whose validation module is re-exported instead of defining the validators
+    models_crate: Option<String>,
+}
+
+pub fn generate_validation_content(
+    xml: bool,
+    request_error: bool,
+    aws_sigv4: bool,
+    models_crate: Option<&str>,
+) -> Result<String, String> {
+    ValidationTemplate {
+        xml,
+        request_error,
+        aws_sigv4,
+        models_crate: models_crate.map(str::to_owned),
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
+
+/// Name of the generated object within `Vec<>` and `Box<>`
+fn get_inner_type_name(type_name: &str) -> &str {
+    let mut inner_type_name = type_name;
+    while let Some(wrapped_type_name) = inner_type_name
+        .strip_prefix("Vec<")
+        .or(inner_type_name.strip_prefix("Box<"))
+        .and_then(|wrapped_type_name| wrapped_type_name.strip_suffix('>'))
+    {
+        inner_type_name = wrapped_type_name;
+    }
+    inner_type_name
+}
+
+fn has_checks(
+    object_database: &ObjectDatabase,
+    struct_name: &str,
+    visited_structs: &mut BTreeSet<String>,
+) -> bool {
+    if !visited_structs.insert(struct_name.to_owned()) {
+        return false;
+    }
+
+    let struct_definition = match object_database.get(struct_name) {
+        Some(ObjectDefinition::Struct(struct_definition)) => struct_definition,
+        _ => return false,
+    };
+    struct_definition.properties.values().any(|property| {
+        !get_constraint_checks(property).is_empty()
+            || has_checks(
+                object_database,
+                get_inner_type_name(&property.type_name),
+                visited_structs,
+            )
+    })
+}
+
+/// Whether the type is (a Vec or Box of) a struct which implements `Validate`
+pub fn is_validated_type(object_database: &ObjectDatabase, type_name: &str) -> bool {
+    has_checks(
+        object_database,
+        get_inner_type_name(type_name),
+        &mut BTreeSet::new(),
+    )
+}
+
+/// Structs with constraints of their own or of nested structs
+pub fn get_validated_structs(object_database: &ObjectDatabase) -> BTreeSet<String> {
+    object_database
+        .keys()
+        .filter(|&struct_name| is_validated_type(object_database, struct_name))
+        .cloned()
+        .collect()
+}
+
+/// Pattern of the property if the regex crate supports it
+fn get_valid_pattern(property: &PropertyDefinition) -> Option<&str> {
+    let pattern = property.constraints.pattern.as_deref()?;
+    regex::Regex::new(pattern).ok().map(|_| pattern)
+}
+
+/// Checks of the property constraints which apply to its type
+fn get_constraint_checks(property: &PropertyDefinition) -> Vec<String> {
+    let constraints = &property.constraints;
+    let to_usize_code = |value: Option<u64>| match value {
+        Some(value) => format!("Some({})", value),
+        None => "None".to_owned(),
+    };
+    let to_f64_code = |value: Option<f64>| match value {
+        Some(value) => format!("Some({:?})", value),
+        None => "None".to_owned(),
+    };
+    let (value, number_value) = match property.required {
+        true => (
+            format!("&self.{}", property.name),
+            format!("self.{}", property.name),
+        ),
+        false => ("value".to_owned(), "*value".to_owned()),
+    };
+
+    let mut checks = vec![];
+    match property.type_name.as_str() {
+        // Format newtypes dereference to str
+        "String" | "crate::formats::Email" | "crate::formats::Hostname" => {
+            if constraints.min_length.is_some() || constraints.max_length.is_some() {
+                checks.push(format!(
+                    "crate::validation::check_length({:?}, {}, {}, {})?;",
+                    property.real_name,
+                    value,
+                    to_usize_code(constraints.min_length),
+                    to_usize_code(constraints.max_length)
+                ));
+            }
+            // Patterns which aren't supported by the regex crate are reported and not checked
+            if let Some(pattern) = get_valid_pattern(property) {
+                // The regex is compiled once on its first check
+                checks.push("{".to_owned());
+                checks.push(
+                    "static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();"
+                        .to_owned(),
+                );
+                checks.push(format!(
+                    "crate::validation::check_pattern({:?}, {}, &PATTERN, {:?})?;",
+                    property.real_name, value, pattern
+                ));
+                checks.push("}".to_owned());
+            }
+        }
+        "i32" | "f64" if constraints.minimum.is_some() || constraints.maximum.is_some() => {
+            checks.push(format!(
+                "crate::validation::check_range({:?}, {} as f64, {}, {})?;",
+                property.real_name,
+                number_value,
+                to_f64_code(constraints.minimum),
+                to_f64_code(constraints.maximum)
+            ));
+        }
+        type_name
+            if type_name.starts_with("Vec<")
+                && (constraints.min_items.is_some() || constraints.max_items.is_some()) =>
+        {
+            checks.push(format!(
+                "crate::validation::check_items({:?}, {}.len(), {}, {})?;",
+                property.real_name,
+                value.trim_start_matches('&'),
+                to_usize_code(constraints.min_items),
+                to_usize_code(constraints.max_items)
+            ));
+        }
+        _ => (),
+    }
+    checks
+}
+
+/// Body of `Validate::validate` for the properties of a struct
+pub fn get_validation_code(
+    properties: &[PropertyDefinition],
+    validated_structs: &BTreeSet<String>,
+) -> Vec<String> {
+    let mut validation_code = vec![];
+    for property in properties {
+        if let Some(ref pattern) = property.constraints.pattern {
+            if let Err(err) = regex::Regex::new(pattern) {
+                warn!(
+                    "Pattern {:?} of {} is not checked: {}",
+                    pattern, property.real_name, err
+                );
+            }
+        }
+        let checks = get_constraint_checks(property);
+        match property.required {
+            true => validation_code.extend(checks),
+            false if !checks.is_empty() => {
+                validation_code.push(format!("if let Some(value) = &self.{} {{", property.name));
+                validation_code.extend(checks);
+                validation_code.push("}".to_owned());
+            }
+            false => (),
+        }
+
+        if validated_structs.contains(get_inner_type_name(&property.type_name)) {
+            validation_code.push(format!("self.{}.validate()?;", property.name));
+        }
+    }
+    validation_code
+}
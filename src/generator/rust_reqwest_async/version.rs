@@ -0,0 +1,176 @@
+use std::{fmt::Display, fs, path::Path};
+
+use log::{info, warn};
+
+use crate::{
+    parser::{
+        ir::SpecIr,
+        spec_diff::{get_change_level, ChangeLevel, SpecChange},
+    },
+    utils::{config::VersionBump, report::VersionSuggestion},
+};
+
+/// Parsed spec of the last generation, compared with the next one
+pub const IR_SNAPSHOT_FILE_NAME: &str = ".opage-ir.json";
+
+/// `major.minor.patch` version of a crate, pre-release and build metadata are not supported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CrateVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl CrateVersion {
+    pub fn parse(version: &str) -> Result<Self, String> {
+        let parts = version
+            .trim()
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(|err| format!("Invalid version {}: {}", version, err))?;
+        match parts[..] {
+            [major, minor, patch] => Ok(CrateVersion {
+                major,
+                minor,
+                patch,
+            }),
+            _ => Err(format!("Invalid version {}", version)),
+        }
+    }
+
+    /// Next version for changes of `level`. Like cargo, `0.x` versions are breaking with the
+    /// minor and additive with the patch version
+    pub fn bump(&self, level: ChangeLevel) -> Self {
+        let (major, minor, patch) = match (level, self.major) {
+            (ChangeLevel::Breaking, 0) | (ChangeLevel::Additive, 1..) => {
+                (self.major, self.minor + 1, 0)
+            }
+            (ChangeLevel::Breaking, 1..) => (self.major + 1, 0, 0),
+            (ChangeLevel::Additive, 0) | (ChangeLevel::Patch, _) => {
+                (self.major, self.minor, self.patch + 1)
+            }
+        };
+        CrateVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl Display for CrateVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Line index and value of the `version` of the `[package]` table
+fn find_package_version(cargo_content: &str) -> Option<(usize, &str)> {
+    let mut in_package = false;
+    for (index, line) in cargo_content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == "version" {
+                // Quoted value, followed by an optional comment
+                let value = value.trim().strip_prefix('"')?;
+                return Some((index, value.split('"').next()?));
+            }
+        }
+    }
+    None
+}
+
+/// Version of the `[package]` of a Cargo.toml
+pub fn get_package_version(cargo_content: &str) -> Option<String> {
+    find_package_version(cargo_content).map(|(_, version)| version.to_owned())
+}
+
+/// Cargo.toml with another `[package]` version, everything else is kept
+pub fn set_package_version(cargo_content: &str, version: &str) -> Result<String, String> {
+    let (line_index, previous_version) = match find_package_version(cargo_content) {
+        Some(package_version) => package_version,
+        None => return Err("Cargo.toml has no package version".to_owned()),
+    };
+    Ok(cargo_content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(index, line)| match index == line_index {
+            true => line.replacen(
+                &format!("\"{}\"", previous_version),
+                &format!("\"{}\"", version),
+                1,
+            ),
+            false => line.to_owned(),
+        })
+        .collect())
+}
+
+/// IR snapshot of the previous generation in `output_dir`
+pub fn load_ir_snapshot(output_dir: &str) -> Option<SpecIr> {
+    let snapshot_path = Path::new(output_dir).join(IR_SNAPSHOT_FILE_NAME);
+    let snapshot_content = fs::read_to_string(&snapshot_path).ok()?;
+    match SpecIr::from_json(&snapshot_content) {
+        Ok(snapshot) => Some(snapshot),
+        Err(err) => {
+            warn!("Ignoring invalid IR snapshot {:?} {}", snapshot_path, err);
+            None
+        }
+    }
+}
+
+/// Version of the crate for the spec changes since the previous generation. For
+/// `VersionBump::Bump` the version is written to the Cargo.toml of the crates
+pub fn suggest_version(
+    version_bump: VersionBump,
+    changes: &[SpecChange],
+    crate_dirs: &[&str],
+) -> Result<VersionSuggestion, String> {
+    let cargo_file_paths = crate_dirs
+        .iter()
+        .map(|crate_dir| Path::new(crate_dir).join("Cargo.toml"))
+        .collect::<Vec<_>>();
+    let cargo_content = fs::read_to_string(&cargo_file_paths[0])
+        .map_err(|err| format!("Unable to read Cargo.toml {}", err))?;
+    let previous_version = match get_package_version(&cargo_content) {
+        Some(previous_version) => previous_version,
+        None => return Err("Cargo.toml has no package version".to_owned()),
+    };
+
+    let level = get_change_level(changes);
+    let version = match level {
+        Some(level) => CrateVersion::parse(&previous_version)?
+            .bump(level)
+            .to_string(),
+        None => previous_version.clone(),
+    };
+
+    let bumped = level.is_some() && version_bump == VersionBump::Bump;
+    if bumped {
+        for cargo_file_path in &cargo_file_paths {
+            let cargo_content = fs::read_to_string(cargo_file_path)
+                .map_err(|err| format!("Unable to read Cargo.toml {}", err))?;
+            fs::write(
+                cargo_file_path,
+                set_package_version(&cargo_content, &version)?,
+            )
+            .map_err(|err| format!("Unable to write Cargo.toml {}", err))?;
+        }
+        info!("Version bumped from {} to {}", previous_version, version);
+    }
+
+    Ok(VersionSuggestion {
+        previous_version,
+        version,
+        level,
+        changes: changes.iter().map(|change| change.to_string()).collect(),
+        bumped,
+    })
+}
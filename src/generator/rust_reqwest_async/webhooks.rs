@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use askama::Template;
+use log::{trace, warn};
+use oas3::{
+    spec::{Operation, PathItem},
+    Spec,
+};
+
+use crate::{
+    generator::media_type::TransferMediaType,
+    parser::component::object_definition::types::{to_unique_list, ModuleInfo, ObjectDatabase},
+    utils::config::Config,
+};
+
+use super::path::utils::generate_request_body;
+
+/// Request of a webhook or callback which receivers of the api handle
+pub struct WebhookDefinition {
+    pub function_name: String,
+    /// Name of the webhook, or operation and name of the callback
+    pub name: String,
+    pub method: String,
+    /// Url expression of callbacks, e.g. `{$request.body#/callbackUrl}`
+    pub url: Option<String>,
+    pub summary: Option<String>,
+    pub payload: Option<WebhookPayload>,
+}
+
+pub struct WebhookPayload {
+    pub type_name: String,
+    /// Expression which creates the payload from `body: &[u8]`
+    pub parse_expression: String,
+}
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/webhooks.rs.jinja", ext = "rs")]
+struct WebhooksTemplate {
+    module_imports: Vec<ModuleInfo>,
+    webhooks: Vec<WebhookDefinition>,
+}
+
+/// Webhooks of the spec and callbacks of its operations. Payload objects are added to the
+/// object database, so they are shared with the requests
+pub fn generate_webhooks_content(
+    spec: &Spec,
+    config: &Config,
+    object_database: &mut ObjectDatabase,
+) -> Result<Option<String>, String> {
+    let mut module_imports = vec![];
+    let mut webhooks: Vec<WebhookDefinition> = vec![];
+
+    let mut path_items = vec![];
+    for (name, path_item) in &spec.webhooks {
+        path_items.push((name.clone(), None, path_item.clone()));
+    }
+    for (_, _, operation) in spec.operations() {
+        let operation_id = match operation.operation_id {
+            Some(ref operation_id) => operation_id,
+            None => continue,
+        };
+        for (callback_name, callback) in &operation.callbacks {
+            // Callbacks map url expressions to path items
+            let callback_path_items = serde_json::to_value(callback)
+                .and_then(serde_json::from_value::<BTreeMap<String, PathItem>>);
+            match callback_path_items {
+                Ok(callback_path_items) => {
+                    for (url, path_item) in callback_path_items {
+                        path_items.push((
+                            format!("{} {}", operation_id, callback_name),
+                            Some(url),
+                            path_item,
+                        ));
+                    }
+                }
+                Err(err) => warn!("Callback {} skipped: {}", callback_name, err),
+            }
+        }
+    }
+
+    for (name, url, path_item) in &path_items {
+        let methods = path_item.methods().into_iter().collect::<Vec<_>>();
+        for (method, operation) in &methods {
+            // Path items with multiple operations are distinguished by the method
+            let function_name = match methods.len() {
+                1 => config.name_mapping.name_to_function_name(name),
+                _ => config.name_mapping.name_to_function_name(&format!(
+                    "{} {}",
+                    name,
+                    method.as_str()
+                )),
+            };
+            if webhooks
+                .iter()
+                .any(|webhook| webhook.function_name == function_name)
+            {
+                warn!("Webhook {} skipped, {} exists", name, function_name);
+                continue;
+            }
+            trace!("Generating webhook {} {}", method.as_str(), name);
+
+            let payload = generate_webhook_payload(
+                spec,
+                config,
+                object_database,
+                name,
+                operation,
+                &function_name,
+                &mut module_imports,
+            )?;
+
+            webhooks.push(WebhookDefinition {
+                function_name,
+                name: name.clone(),
+                method: method.as_str().to_owned(),
+                url: url.clone(),
+                summary: operation.summary.clone(),
+                payload,
+            });
+        }
+    }
+
+    if webhooks.is_empty() {
+        return Ok(None);
+    }
+
+    WebhooksTemplate {
+        module_imports: to_unique_list(&module_imports),
+        webhooks,
+    }
+    .render()
+    .map(Some)
+    .map_err(|err| err.to_string())
+}
+
+fn generate_webhook_payload(
+    spec: &Spec,
+    config: &Config,
+    object_database: &mut ObjectDatabase,
+    name: &str,
+    operation: &Operation,
+    function_name: &str,
+    module_imports: &mut Vec<ModuleInfo>,
+) -> Result<Option<WebhookPayload>, String> {
+    let request_body = match operation.request_body {
+        Some(ref request_body) => request_body,
+        None => return Ok(None),
+    };
+    let request_entity = generate_request_body(
+        spec,
+        object_database,
+        &vec![name.to_owned()],
+        &config.name_mapping,
+        request_body,
+        &config.generator.content_type_mapping,
+        function_name,
+    )
+    .map_err(|err| format!("Failed to generate payload of webhook {}: {}", name, err))?;
+
+    let media_type = request_entity
+        .content
+        .get("application/json")
+        .or_else(|| request_entity.content.values().next());
+    let payload = match media_type {
+        Some(TransferMediaType::ApplicationJson(Some(type_definition))) => {
+            if let Some(ref module) = type_definition.module {
+                module_imports.push(module.clone());
+            }
+            WebhookPayload {
+                type_name: type_definition.name.clone(),
+                parse_expression: format!(
+                    "serde_json::from_slice::<{}>(body).map_err(|err| err.to_string())?",
+                    type_definition.name
+                ),
+            }
+        }
+        Some(TransferMediaType::ApplicationJson(None)) => WebhookPayload {
+            type_name: "serde_json::Value".to_owned(),
+            parse_expression:
+                "serde_json::from_slice::<serde_json::Value>(body).map_err(|err| err.to_string())?"
+                    .to_owned(),
+        },
+        Some(TransferMediaType::TextPlain) => WebhookPayload {
+            type_name: "String".to_owned(),
+            parse_expression: "String::from_utf8(body.to_vec()).map_err(|err| err.to_string())?"
+                .to_owned(),
+        },
+        // Binary and other payloads are passed unparsed
+        Some(_) => WebhookPayload {
+            type_name: "Vec<u8>".to_owned(),
+            parse_expression: "body.to_vec()".to_owned(),
+        },
+        None => return Ok(None),
+    };
+    Ok(Some(payload))
+}
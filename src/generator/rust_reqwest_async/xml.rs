@@ -0,0 +1,14 @@
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "rust_reqwest_async/xml.rs.jinja", ext = "rs")]
+struct XmlTemplate {
+    /// Request errors include signing errors of `aws_sigv4`
+    aws_sigv4: bool,
+}
+
+pub fn generate_xml_content(aws_sigv4: bool) -> Result<String, String> {
+    XmlTemplate { aws_sigv4 }
+        .render()
+        .map_err(|err| err.to_string())
+}
@@ -0,0 +1,120 @@
+use askama::Template;
+
+use super::names::{property_key, typescript_literal, typescript_type, typescript_type_name};
+use crate::parser::component::object_definition::types::{
+    ObjectDatabase, ObjectDefinition, StructDefinition,
+};
+
+struct PropertyTemplate {
+    /// Json name, quoted if it isn't an identifier
+    key: String,
+    type_name: String,
+    required: bool,
+    /// Only sent by the server
+    read_only: bool,
+}
+
+struct InterfaceTemplate {
+    name: String,
+    properties: Vec<PropertyTemplate>,
+}
+
+struct TypeAliasTemplate {
+    name: String,
+    type_name: String,
+}
+
+#[derive(Template)]
+#[template(path = "typescript_fetch/models.ts.jinja", ext = "txt")]
+struct ModelsTemplate {
+    interfaces: Vec<InterfaceTemplate>,
+    type_aliases: Vec<TypeAliasTemplate>,
+}
+
+/// models.ts with an interface per struct and type aliases for enums (unions), primitives and
+/// consts (literals) of the object database
+pub fn generate_models_content(object_database: &ObjectDatabase) -> Result<String, String> {
+    let mut interfaces = vec![];
+    let mut type_aliases = vec![];
+
+    let mut object_definitions = vec![];
+    for object_definition in object_database.values() {
+        object_definitions.push(object_definition);
+        if let ObjectDefinition::Struct(struct_definition) = object_definition {
+            object_definitions.extend(
+                struct_definition
+                    .local_objects
+                    .values()
+                    .map(|local_object| local_object.as_ref()),
+            );
+        }
+    }
+
+    // Type aliases are hoisted, so the order doesn't matter
+    for object_definition in object_definitions {
+        let (name, type_names) = match object_definition {
+            ObjectDefinition::Struct(struct_definition) => {
+                interfaces.push(generate_interface(struct_definition));
+                continue;
+            }
+            ObjectDefinition::Enum(enum_definition) => (
+                &enum_definition.name,
+                enum_definition
+                    .values
+                    .values()
+                    .map(|enum_value| enum_value.value_type.name.clone())
+                    .collect::<Vec<String>>(),
+            ),
+            ObjectDefinition::Primitive(primitive_definition) => (
+                &primitive_definition.name,
+                vec![primitive_definition.primitive_type.name.clone()],
+            ),
+            ObjectDefinition::Const(const_definition) => {
+                type_aliases.push(TypeAliasTemplate {
+                    name: typescript_type_name(&const_definition.name),
+                    type_name: typescript_literal(&const_definition.value)
+                        .unwrap_or("unknown".to_owned()),
+                });
+                continue;
+            }
+        };
+
+        let mut typescript_types = vec![];
+        for type_name in &type_names {
+            let typescript_type = typescript_type(type_name, "");
+            if !typescript_types.contains(&typescript_type) {
+                typescript_types.push(typescript_type);
+            }
+        }
+        type_aliases.push(TypeAliasTemplate {
+            name: typescript_type_name(name),
+            type_name: match typescript_types.is_empty() {
+                true => "never".to_owned(),
+                false => typescript_types.join(" | "),
+            },
+        });
+    }
+
+    ModelsTemplate {
+        interfaces,
+        type_aliases,
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
+
+fn generate_interface(struct_definition: &StructDefinition) -> InterfaceTemplate {
+    InterfaceTemplate {
+        name: typescript_type_name(&struct_definition.name),
+        properties: struct_definition
+            .properties
+            .values()
+            .map(|property| PropertyTemplate {
+                key: property_key(&property.real_name),
+                type_name: typescript_type(&property.type_name, ""),
+                required: property.required,
+                read_only: property.read_only,
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,179 @@
+use crate::generator::operation::split_generic_type;
+
+/// Reserved words of (strict mode) typescript and predefined types which can't be used as names
+const TYPESCRIPT_KEYWORDS: [&str; 60] = [
+    "any",
+    "arguments",
+    "as",
+    "await",
+    "bigint",
+    "boolean",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "never",
+    "new",
+    "null",
+    "number",
+    "object",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "string",
+    "super",
+    "switch",
+    "symbol",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "undefined",
+    "unknown",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+fn is_keyword(name: &str) -> bool {
+    TYPESCRIPT_KEYWORDS.contains(&name)
+}
+
+/// camelCase typescript name of a generated snake_case (rust) identifier. Raw identifiers lose
+/// their `r#` prefix and keywords get a `_` suffix
+pub fn typescript_identifier(name: &str) -> String {
+    let name = name.trim_start_matches("r#");
+    let leading_underscores = name.len() - name.trim_start_matches('_').len();
+    let mut identifier = name[..leading_underscores].to_owned();
+    for (index, word) in name[leading_underscores..].split('_').enumerate() {
+        let mut characters = word.chars();
+        match (index, characters.next()) {
+            (_, None) => (),
+            (0, Some(first_character)) => {
+                identifier.push(first_character);
+                identifier.extend(characters);
+            }
+            (_, Some(first_character)) => {
+                identifier.extend(first_character.to_uppercase());
+                identifier.extend(characters);
+            }
+        }
+    }
+    match is_keyword(&identifier) {
+        true => format!("{}_", identifier),
+        false => identifier,
+    }
+}
+
+/// Typescript name of a generated struct name
+pub fn typescript_type_name(name: &str) -> String {
+    let name = name.trim_start_matches("r#");
+    match is_keyword(name) {
+        true => format!("{}_", name),
+        false => name.to_owned(),
+    }
+}
+
+/// Typescript type of a generated rust type name. Objects are prefixed with `models_prefix`,
+/// e.g. `models.` outside of the models module
+pub fn typescript_type(type_name: &str, models_prefix: &str) -> String {
+    let type_name = type_name.trim();
+    if let Some((outer_type, inner_types)) = split_generic_type(type_name) {
+        let inner_types = inner_types
+            .into_iter()
+            .map(|inner_type| typescript_type(inner_type, models_prefix))
+            .collect::<Vec<String>>();
+        return match (outer_type, inner_types.as_slice()) {
+            ("Box", [inner_type]) => inner_type.clone(),
+            ("Vec", [item_type]) if item_type.contains(" | ") => format!("({})[]", item_type),
+            ("Vec", [item_type]) => format!("{}[]", item_type),
+            ("Option", [inner_type]) => format!("{} | null", inner_type),
+            // Record would be shadowed by a model named Record
+            ("HashMap" | "BTreeMap", [_, value_type]) => {
+                format!("{{ [key: string]: {} }}", value_type)
+            }
+            _ => "unknown".to_owned(),
+        };
+    }
+
+    match type_name {
+        "bool" => "boolean".to_owned(),
+        "String" | "str" | "&str" => "string".to_owned(),
+        "f32" | "f64" | "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "usize" => {
+            "number".to_owned()
+        }
+        "serde_json::Value" => "unknown".to_owned(),
+        _ => format!("{}{}", models_prefix, typescript_type_name(type_name)),
+    }
+}
+
+/// Typescript literal of a json value, None for arrays and objects
+pub fn typescript_literal(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => None,
+        // Json scalars are valid typescript literals
+        value => Some(value.to_string()),
+    }
+}
+
+pub fn typescript_string(value: &str) -> String {
+    serde_json::Value::String(value.to_owned()).to_string()
+}
+
+/// Property name of an interface or object literal, quoted if it isn't an identifier
+pub fn property_key(name: &str) -> String {
+    match is_plain_identifier(name) {
+        true => name.to_owned(),
+        false => typescript_string(name),
+    }
+}
+
+/// Expression accessing the property `name` of `object`
+pub fn property_access(object: &str, name: &str) -> String {
+    match is_plain_identifier(name) {
+        true => format!("{}.{}", object, name),
+        false => format!("{}[{}]", object, typescript_string(name)),
+    }
+}
+
+fn is_plain_identifier(name: &str) -> bool {
+    let mut characters = name.chars();
+    match characters.next() {
+        Some(first_character)
+            if first_character.is_ascii_alphabetic() || "_$".contains(first_character) =>
+        {
+            characters
+                .all(|character| character.is_ascii_alphanumeric() || "_$".contains(character))
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::BTreeSet;
+
+use askama::Template;
+use oas3::Spec;
+
+use super::names::{
+    property_access, property_key, typescript_identifier, typescript_string, typescript_type,
+};
+use crate::{
+    generator::{
+        media_type::TransferMediaType,
+        operation::{generate_operations, OperationDefinition, PathSegment},
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{config::Config, report::GenerationReport},
+};
+
+const MODELS_PREFIX: &str = "models.";
+
+/// Names of paths.ts which parameters would shadow
+const LOCAL_NAMES: [&str; 13] = [
+    "Headers",
+    "JSON",
+    "String",
+    "URLSearchParams",
+    "client",
+    "encodeURIComponent",
+    "fetch",
+    "headers",
+    "models",
+    "params",
+    "query",
+    "queryString",
+    "response",
+];
+
+struct QueryParameterTemplate {
+    /// Query name as typescript string literal
+    real_name: String,
+    /// Expression reading the parameter from the query object
+    value: String,
+    required: bool,
+    is_array: bool,
+    /// Delimiter (typescript string literal) joining array items into a single value, None
+    /// repeats the parameter per item
+    separator: Option<String>,
+}
+
+impl QueryParameterTemplate {
+    /// Optional parameters are only sent if they are set
+    fn indent(&self) -> &'static str {
+        match self.required {
+            true => "",
+            false => "  ",
+        }
+    }
+}
+
+struct ResponseTemplate {
+    status: u16,
+    body_code: String,
+}
+
+struct OperationTemplate {
+    function_name: String,
+    doc_comment: String,
+    method: String,
+    path_code: String,
+    parameters: Vec<String>,
+    query_parameters: Vec<QueryParameterTemplate>,
+    /// Content type (typescript string literal) and code of the sent body
+    body: Option<(String, String)>,
+    responses: Vec<ResponseTemplate>,
+    body_type: String,
+}
+
+#[derive(Template)]
+#[template(path = "typescript_fetch/paths.ts.jinja", ext = "txt")]
+struct PathsTemplate {
+    operations: Vec<OperationTemplate>,
+}
+
+/// paths.ts with an async fetch function per (not ignored) operation. Generated and skipped
+/// operations are added to the report, inline objects to the object database
+pub fn generate_paths_content(
+    spec: &Spec,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    report: &mut GenerationReport,
+) -> Result<String, String> {
+    PathsTemplate {
+        operations: generate_operations(spec, object_database, config, report, generate_operation)?,
+    }
+    .render()
+    .map_err(|err| err.to_string())
+}
+
+fn generate_operation(
+    operation_definition: &OperationDefinition,
+) -> Result<OperationTemplate, String> {
+    let function_name = typescript_identifier(&operation_definition.function_name);
+    if LOCAL_NAMES.contains(&function_name.as_str()) {
+        return Err(format!("Function name {} is reserved", function_name));
+    }
+
+    let mut parameter_names = BTreeSet::new();
+    let mut parameter_name = |name: &str| {
+        let mut name = typescript_identifier(name);
+        if LOCAL_NAMES.contains(&name.as_str()) {
+            name.push('_');
+        }
+        match parameter_names.insert(name.clone()) {
+            true => Ok(name),
+            false => Err(format!("Parameter {} is used more than once", name)),
+        }
+    };
+
+    // Path parameters are positional, followed by the body and the query object
+    let mut parameters = vec!["client: Client".to_owned()];
+    let mut path_parameter_names = vec![];
+    for path_parameter in &operation_definition.path_parameters {
+        let name = parameter_name(&path_parameter.name)?;
+        parameters.push(format!(
+            "{}: {}",
+            name,
+            typescript_type(&path_parameter.type_name, MODELS_PREFIX)
+        ));
+        path_parameter_names.push(name);
+    }
+    let path_code = get_path_code(operation_definition, path_parameter_names);
+
+    let body = match &operation_definition.request_body {
+        Some(request_body) => {
+            let name = parameter_name("body")?;
+            let (body_type, body_code) = match &request_body.media_type {
+                TransferMediaType::ApplicationJson(type_definition) => (
+                    match type_definition {
+                        Some(type_definition) => {
+                            typescript_type(&type_definition.name, MODELS_PREFIX)
+                        }
+                        None => "unknown".to_owned(),
+                    },
+                    format!("JSON.stringify({})", name),
+                ),
+                TransferMediaType::TextPlain => ("string".to_owned(), name.clone()),
+                TransferMediaType::OctetStream => ("BodyInit".to_owned(), name.clone()),
+                transfer_media_type => {
+                    return Err(format!(
+                        "{} request bodies are not supported by typescript_fetch",
+                        transfer_media_type.content_type()
+                    ))
+                }
+            };
+            parameters.push(format!("{}: {}", name, body_type));
+            Some((typescript_string(&request_body.content_type), body_code))
+        }
+        None => None,
+    };
+
+    // Query parameters are properties of an object named after their query names
+    let mut query_properties = vec![];
+    let mut query_parameters = vec![];
+    for query_parameter in &operation_definition.query_parameters {
+        let property = &query_parameter.property;
+        query_properties.push(format!(
+            "{}{}: {}",
+            property_key(&property.real_name),
+            match property.required {
+                true => "",
+                false => "?",
+            },
+            typescript_type(&property.type_name, MODELS_PREFIX)
+        ));
+        query_parameters.push(QueryParameterTemplate {
+            real_name: typescript_string(&property.real_name),
+            value: property_access("query", &property.real_name),
+            required: property.required,
+            is_array: query_parameter.is_array,
+            separator: query_parameter.separator.as_deref().map(typescript_string),
+        });
+    }
+    if !query_properties.is_empty() {
+        let query_type = format!("{{ {} }}", query_properties.join("; "));
+        let all_optional = operation_definition
+            .query_parameters
+            .iter()
+            .all(|query_parameter| !query_parameter.property.required);
+        parameters.push(match all_optional {
+            true => format!("query: {} = {{}}", query_type),
+            false => format!("query: {}", query_type),
+        });
+    }
+
+    let mut responses = vec![];
+    let mut body_types = vec![];
+    for response in &operation_definition.responses {
+        let (body_type, body_code) = match response.body.as_ref().map(|body| &body.media_type) {
+            Some(TransferMediaType::ApplicationJson(Some(type_definition))) => {
+                let body_type = typescript_type(&type_definition.name, MODELS_PREFIX);
+                let body_code = format!("(await response.json()) as {}", body_type);
+                (body_type, body_code)
+            }
+            Some(TransferMediaType::ApplicationJson(None)) => (
+                "unknown".to_owned(),
+                "(await response.json()) as unknown".to_owned(),
+            ),
+            Some(TransferMediaType::TextPlain) => {
+                ("string".to_owned(), "await response.text()".to_owned())
+            }
+            Some(TransferMediaType::OctetStream) => {
+                ("Blob".to_owned(), "await response.blob()".to_owned())
+            }
+            // Xml and event streams are read from the fetch response
+            _ => continue,
+        };
+        if !body_types.contains(&body_type) {
+            body_types.push(body_type);
+        }
+        responses.push(ResponseTemplate {
+            status: response.status,
+            body_code,
+        });
+    }
+    body_types.push("undefined".to_owned());
+
+    let method = operation_definition.method.as_str();
+    let mut doc_lines = vec![];
+    if let Some(summary) = &operation_definition.summary {
+        doc_lines.extend(
+            summary
+                .trim()
+                .lines()
+                .map(|line| line.trim_end().to_owned()),
+        );
+        doc_lines.push(String::new());
+    }
+    doc_lines.push(format!("{} {}", method, operation_definition.path));
+
+    Ok(OperationTemplate {
+        function_name,
+        doc_comment: doc_lines
+            .iter()
+            .map(|line| match line.is_empty() {
+                true => " *".to_owned(),
+                false => format!(" * {}", line.replace("*/", "*\\/")),
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+        method: method.to_owned(),
+        path_code,
+        parameters,
+        query_parameters,
+        body,
+        responses,
+        body_type: body_types.join(" | "),
+    })
+}
+
+/// Template literal text of the path, path parameters are encoded as a single segment
+fn get_path_code(
+    operation_definition: &OperationDefinition,
+    path_parameter_names: Vec<String>,
+) -> String {
+    let mut path_parameter_names = path_parameter_names.into_iter();
+    operation_definition
+        .path_segments()
+        .into_iter()
+        .map(|path_segment| match path_segment {
+            PathSegment::Parameter(_) => format!(
+                "${{encodeURIComponent(String({}))}}",
+                path_parameter_names.next().unwrap_or_default()
+            ),
+            PathSegment::Literal(path_component) => path_component
+                .replace('\\', "\\\\")
+                .replace('`', "\\`")
+                .replace("${", "\\${"),
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
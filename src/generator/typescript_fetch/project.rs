@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use askama::Template;
+use log::info;
+
+use super::{
+    models::generate_models_content, names::typescript_string, paths::generate_paths_content,
+};
+use crate::{
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{config::Config, output::OutputWriter, report::GenerationReport},
+};
+
+#[derive(Template)]
+#[template(path = "typescript_fetch/package.json.jinja", ext = "txt")]
+struct PackageJsonTemplate {
+    /// Json strings
+    package_name: String,
+    version: String,
+}
+
+#[derive(Template)]
+#[template(path = "typescript_fetch/tsconfig.json.jinja", ext = "txt")]
+struct TsconfigTemplate;
+
+/// Typescript package with interfaces and typed fetch functions per operation
+pub fn generate_project(
+    output_dir: &str,
+    object_database: &mut ObjectDatabase,
+    config: &Config,
+    spec: &oas3::Spec,
+) -> GenerationReport {
+    let output = OutputWriter::new(output_dir);
+    let mut report = GenerationReport::default();
+
+    // Operations add their inline objects, so models are generated last
+    let paths_content = generate_paths_content(spec, object_database, config, &mut report)
+        .expect("Failed to generate paths.ts");
+    output
+        .write("src/paths.ts", &paths_content)
+        .expect("Failed to write paths.ts");
+    output
+        .write(
+            "src/models.ts",
+            &generate_models_content(object_database).expect("Failed to generate models.ts"),
+        )
+        .expect("Failed to write models.ts");
+    output
+        .write(
+            "src/index.ts",
+            "// Generated by opage, changes are overwritten\nexport * as models from \"./models\";\nexport * from \"./paths\";\n",
+        )
+        .expect("Failed to write index.ts");
+    report.objects = object_database.len();
+    report.files = output.finish().expect("Failed to write manifest");
+
+    // Projects without name are named after the spec
+    let project_name = match config.project_metadata.name.is_empty() {
+        true => spec.info.title.clone(),
+        false => config.project_metadata.name.clone(),
+    };
+    let package_name = config
+        .name_mapping
+        .name_to_module_name(&project_name)
+        .replace('_', "-");
+    write_if_missing(
+        output_dir,
+        "package.json",
+        PackageJsonTemplate {
+            package_name: typescript_string(&package_name),
+            version: typescript_string(&config.project_metadata.version),
+        }
+        .render(),
+    );
+    write_if_missing(output_dir, "tsconfig.json", TsconfigTemplate.render());
+    report
+}
+
+/// Project files are created once and belong to the user afterwards
+fn write_if_missing(output_dir: &str, file_name: &str, content: askama::Result<String>) {
+    let file_path = format!("{}/{}", output_dir, file_name);
+    if Path::new(&file_path).exists() {
+        info!("{:?} exists and will be skipped", file_path);
+        return;
+    }
+    let content = content.unwrap_or_else(|err| panic!("Failed to generate {}: {}", file_name, err));
+    std::fs::write(&file_path, content)
+        .unwrap_or_else(|err| panic!("Failed to write {}: {}", file_name, err));
+}
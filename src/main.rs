@@ -5,48 +5,325 @@ pub mod utils;
 
 use std::path::Path;
 
+use clap::ArgMatches;
 use cli::cli;
-use generator::rust_reqwest_async::project::generate_project;
-use parser::component::generate_components;
-use utils::{config::Config, log::Logger};
+use generator::{
+    axum_mock, axum_server, python_httpx,
+    rust_reqwest_async::{
+        paths::{collect_operations, resolve_operation_id},
+        project::generate_project,
+    },
+    typescript_fetch,
+};
+use log::warn;
+use parser::{
+    component::generate_components_with_report,
+    ir::parse_spec_ir,
+    security::parse_spec,
+    spec_diff::{diff_spec_ir, ChangeLevel},
+    validation::validate_spec,
+};
+use utils::{
+    config::{Config, GeneratorTarget, LogLevel},
+    log::Logger,
+    name_mapping::TypeMapping,
+    output::diff_output,
+    report::GenerationReport,
+    template_override::get_unknown_template_overrides,
+};
 
-static LOGGER: Logger = Logger;
+static LOGGER: Logger = Logger::new();
 
-fn main() {
-    let matches = cli().get_matches();
-
-    let output_dir = matches
-        .get_one::<String>("output-dir")
-        .map(String::as_str)
-        .expect("output-dir missing");
+fn load_spec_and_config(matches: &ArgMatches) -> (oas3::Spec, Config) {
     let spec_file_path = matches
         .get_one::<String>("spec")
         .map(String::as_str)
         .expect("spec missing");
-    let config_file_path = matches.get_one::<String>("config").map(String::as_str);
 
-    log::set_logger(&LOGGER).expect("Failed to set logger");
-    log::set_max_level(log::LevelFilter::Trace);
+    // 1. Read spec
+    let spec = read_spec(spec_file_path);
 
-    // Start generating
+    // 2. Load config
+    (spec, load_config(matches))
+}
 
-    // 1. Read spec
+fn read_spec(spec_file_path: &str) -> oas3::Spec {
     let spec_yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
-    let spec = oas3::from_yaml(spec_yaml).expect("Failed to read spec");
+    parse_spec(&spec_yaml).expect("Failed to read spec")
+}
 
-    // 2. Load config (Get mapper for invalid language names, ignores...)
-    let config = match config_file_path {
+fn load_config(matches: &ArgMatches) -> Config {
+    let config_file_path = matches.get_one::<String>("config").map(String::as_str);
+
+    // Get mapper for invalid language names, ignores...
+    let mut config = match config_file_path {
         Some(mapping_file) => {
             Config::from(Path::new(mapping_file)).expect("Failed to parse config")
         }
         None => Config::new(),
     };
+    // Templates are shipped next to the config
+    if let (Some(config_file_path), Some(template_dir)) =
+        (config_file_path, &config.generator.template_dir)
+    {
+        if let Some(config_dir) = Path::new(config_file_path).parent() {
+            config.generator.template_dir =
+                Some(config_dir.join(template_dir).to_string_lossy().into_owned());
+        }
+    }
+
+    // Operation filters given as arguments override the config
+    let get_list_arg = |name: &str| -> Option<Vec<String>> {
+        matches
+            .get_many::<String>(name)
+            .map(|values| values.cloned().collect())
+    };
+    if let Some(include_tags) = get_list_arg("include-tags") {
+        config.ignore = config.ignore.include_tags(include_tags);
+    }
+    if let Some(exclude_tags) = get_list_arg("exclude-tags") {
+        config.ignore = config.ignore.exclude_tags(exclude_tags);
+    }
+    if let Some(methods) = get_list_arg("methods") {
+        config.ignore = config.ignore.methods(methods);
+    }
+    if let Ok(Some(target)) = matches.try_get_one::<String>("target") {
+        config.generator.target = GeneratorTarget::from_name(target).expect("Invalid target");
+    }
+
+    config
+}
+
+fn generate(matches: &ArgMatches) {
+    let output_dir = matches
+        .get_one::<String>("output-dir")
+        .map(String::as_str)
+        .expect("output-dir missing");
+
+    // Start generating
+    let (spec, mut config) = load_spec_and_config(matches);
+
+    let quiet = matches.get_flag("quiet");
+    let log_level = match (quiet, matches.get_count("verbose")) {
+        (true, _) => LogLevel::Error,
+        (false, 0) => config.generator.log_level,
+        (false, 1) => LogLevel::Info,
+        (false, 2) => LogLevel::Debug,
+        (false, _) => LogLevel::Trace,
+    };
+    log::set_logger(&LOGGER).expect("Failed to set logger");
+    LOGGER.set_level(log_level.to_level_filter());
+
+    if let Some(ref template_dir) = config.generator.template_dir {
+        match get_unknown_template_overrides(template_dir) {
+            Ok(unknown_templates) => {
+                for unknown_template in unknown_templates {
+                    warn!(
+                        "{} doesn't override an embedded template and is ignored",
+                        unknown_template
+                    );
+                }
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if config.generator.target != GeneratorTarget::RustReqwestAsync
+        && config.name_mapping.type_mapping != TypeMapping::default()
+    {
+        warn!("type_mapping is only supported by the rust_reqwest_async target and is ignored");
+        config.name_mapping.type_mapping = TypeMapping::default();
+    }
+    if matches!(
+        config.generator.target,
+        GeneratorTarget::PythonHttpx | GeneratorTarget::TypescriptFetch
+    ) && !config.name_mapping.type_overrides.is_empty()
+    {
+        warn!("type_overrides are Rust types and are ignored by this target");
+        config.name_mapping.type_overrides.clear();
+    }
 
     // 3. Generate Code
-    // 3.1 Components and database for type referencing
-    let object_database = &mut generate_components(&spec, &config).unwrap();
-    // 3.2 Generate paths requests
+    let mut report = generate_output(output_dir, &spec, &config);
+    report.warnings = LOGGER.take_warnings();
+    report.unsupported_features = validate_spec(&spec, &config)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    let report_content = match matches
+        .get_one::<String>("report-format")
+        .map(String::as_str)
+    {
+        Some("json") => report.to_json().expect("Failed to serialize report") + "\n",
+        _ => report.to_string(),
+    };
+    match matches.get_one::<String>("report-file") {
+        Some(report_file) => {
+            std::fs::write(report_file, report_content).expect("Failed to write report")
+        }
+        None if !quiet => print!("{}", report_content),
+        None => {}
+    }
+
+    // Strict runs fail instead of producing an incomplete project. The summary lists the reasons
+    let skipped = report.skipped();
+    if matches.get_flag("strict") && !skipped.is_empty() {
+        if quiet {
+            for skipped_item in &skipped {
+                eprintln!("{}", skipped_item);
+            }
+        }
+        eprintln!("{} operations or components skipped", skipped.len());
+        std::process::exit(1);
+    }
+}
+
+fn generate_output(output_dir: &str, spec: &oas3::Spec, config: &Config) -> GenerationReport {
+    match config.generator.target {
+        GeneratorTarget::RustReqwestAsync => {
+            // Components and database for type referencing
+            let mut component_report = GenerationReport::default();
+            let object_database =
+                &mut generate_components_with_report(spec, config, &mut component_report).unwrap();
+            // Paths requests and all registered objects as individual type definitions
+            let mut report = generate_project(output_dir, object_database, config, spec);
+            report.skipped_components = component_report.skipped_components;
+            report
+        }
+        GeneratorTarget::AxumMock => axum_mock::project::generate_project(output_dir, config, spec),
+        GeneratorTarget::AxumServer => {
+            let mut component_report = GenerationReport::default();
+            let object_database =
+                &mut generate_components_with_report(spec, config, &mut component_report).unwrap();
+            let mut report =
+                axum_server::project::generate_project(output_dir, object_database, config, spec);
+            report.skipped_components = component_report.skipped_components;
+            report
+        }
+        GeneratorTarget::PythonHttpx => {
+            let mut component_report = GenerationReport::default();
+            let object_database =
+                &mut generate_components_with_report(spec, config, &mut component_report).unwrap();
+            let mut report =
+                python_httpx::project::generate_project(output_dir, object_database, config, spec);
+            report.skipped_components = component_report.skipped_components;
+            report
+        }
+        GeneratorTarget::TypescriptFetch => {
+            let mut component_report = GenerationReport::default();
+            let object_database =
+                &mut generate_components_with_report(spec, config, &mut component_report).unwrap();
+            let mut report = typescript_fetch::project::generate_project(
+                output_dir,
+                object_database,
+                config,
+                spec,
+            );
+            report.skipped_components = component_report.skipped_components;
+            report
+        }
+    }
+}
+
+fn validate(matches: &ArgMatches) {
+    let (spec, config) = load_spec_and_config(matches);
+    let issues = validate_spec(&spec, &config);
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    if !issues.is_empty() {
+        eprintln!("{} unsupported spec features found", issues.len());
+        std::process::exit(1);
+    }
+}
+
+fn list(matches: &ArgMatches) {
+    let (spec, config) = load_spec_and_config(matches);
+    for (path, method, operation) in collect_operations(&spec, &config) {
+        let operation_id = match resolve_operation_id(&config, &method, path, operation) {
+            Some(operation_id) => config.name_mapping.name_to_module_name(&operation_id),
+            None => "<missing operationId>".to_owned(),
+        };
+        println!("{} {} {}", method.as_str(), path, operation_id);
+    }
+}
+
+fn ir(matches: &ArgMatches) {
+    let (spec, config) = load_spec_and_config(matches);
+    let spec_ir = parse_spec_ir(&spec, &config).expect("Failed to parse spec");
+    println!("{}", spec_ir.to_json().expect("Failed to serialize ir"));
+}
+
+fn diff(matches: &ArgMatches) {
+    if let (Some(old_spec_path), Some(new_spec_path)) = (
+        matches.get_one::<String>("old"),
+        matches.get_one::<String>("new"),
+    ) {
+        return diff_specs(matches, old_spec_path, new_spec_path);
+    }
+
+    let output_dir = matches
+        .get_one::<String>("output-dir")
+        .map(String::as_str)
+        .expect("output-dir missing");
+    let (spec, config) = load_spec_and_config(matches);
+
+    // Generate into a scratch directory and compare it with the output
+    let generated_dir = std::env::temp_dir().join(format!("opage-diff-{}", std::process::id()));
+    let generated_dir = generated_dir.to_str().expect("Invalid temp dir");
+    let _ = std::fs::remove_dir_all(generated_dir);
+
+    generate_output(generated_dir, &spec, &config);
+
+    let changes = diff_output(output_dir, generated_dir);
+    let _ = std::fs::remove_dir_all(generated_dir);
+
+    for change in &changes {
+        println!("{}", change);
+    }
+    if !changes.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+/// Operation and model changes between two spec versions
+fn diff_specs(matches: &ArgMatches, old_spec_path: &str, new_spec_path: &str) {
+    let config = load_config(matches);
+    let old_ir =
+        parse_spec_ir(&read_spec(old_spec_path), &config).expect("Failed to parse old spec");
+    let new_ir =
+        parse_spec_ir(&read_spec(new_spec_path), &config).expect("Failed to parse new spec");
+
+    let changes = diff_spec_ir(&old_ir, &new_ir);
+    for change in &changes {
+        println!("{}", change);
+    }
+    if !changes.is_empty() {
+        eprintln!(
+            "{} changes, {} breaking",
+            changes.len(),
+            changes
+                .iter()
+                .filter(|change| change.level == ChangeLevel::Breaking)
+                .count()
+        );
+        std::process::exit(1);
+    }
+}
+
+fn main() {
+    let matches = cli().get_matches();
 
-    // 3.3 Write all registered objects to individual type definitions
-    generate_project(output_dir, object_database, &config, &spec);
+    match matches.subcommand() {
+        Some(("generate", generate_matches)) => generate(generate_matches),
+        Some(("validate", validate_matches)) => validate(validate_matches),
+        Some(("list", list_matches)) => list(list_matches),
+        Some(("ir", ir_matches)) => ir(ir_matches),
+        Some(("diff", diff_matches)) => diff(diff_matches),
+        _ => generate(&matches),
+    }
 }
@@ -1,43 +1,58 @@
 use log::{error, info, trace, warn};
 use oas3::Spec;
 use object_definition::{
-    generate_object, get_components_base_path, get_object_name, types::ObjectDatabase,
+    generate_object, get_components_base_path,
+    types::{ObjectDatabase, ObjectName, ObjectSource},
 };
 
-use crate::utils::config::Config;
+use crate::utils::{config::Config, log::LogContext, report::GenerationReport};
 
+pub mod example;
 pub mod object_definition;
 pub mod type_definition;
 
 pub fn generate_components(spec: &Spec, config: &Config) -> Result<ObjectDatabase, String> {
+    generate_components_with_report(spec, config, &mut GenerationReport::default())
+}
+
+/// Generates the components and adds the ones which failed to the report
+pub fn generate_components_with_report(
+    spec: &Spec,
+    config: &Config,
+    report: &mut GenerationReport,
+) -> Result<ObjectDatabase, String> {
     let components = match spec.components {
         Some(ref components) => components,
         None => return Ok(ObjectDatabase::new()),
     };
 
     let mut object_database = ObjectDatabase::new();
+    let definition_path = get_components_base_path();
 
+    // Components reserve their names before any reference is resolved. Components which
+    // map to the name of another component get a unique name
+    let mut resolved_components = vec![];
     for (component_name, object_ref) in &components.schemas {
+        let _log_context = LogContext::enter(format!("component {}", component_name));
         if config.ignore.component_ignored(&component_name) {
-            info!("\"{}\" ignored", component_name);
+            info!("Ignored");
+            continue;
+        }
+        let ref_path = format!("{}/{}", definition_path.join("/"), component_name);
+        if config.name_mapping.get_type_override(&ref_path).is_some() {
+            info!("Replaced by its type override");
             continue;
         }
-
-        info!("Generating component \"{}\"", component_name);
 
         let resolved_object = match object_ref.resolve(spec) {
             Ok(object) => object,
             Err(err) => {
-                error!(
-                    "Unable to parse component {} {}",
-                    component_name,
-                    err.to_string()
-                );
+                error!("Unable to parse component {}", err.to_string());
+                report.skip_component(component_name, &err.to_string());
                 continue;
             }
         };
 
-        let definition_path = get_components_base_path();
         let object_name = match resolved_object.title {
             Some(ref title) => config
                 .name_mapping
@@ -47,7 +62,30 @@ pub fn generate_components(spec: &Spec, config: &Config) -> Result<ObjectDatabas
                 .name_to_struct_name(&definition_path, &component_name),
         };
 
-        if object_database.contains_key(&object_name) {
+        let source = ObjectSource::new(spec, definition_path.clone(), resolved_object);
+        let unique_object_name = match object_database.resolve_name(&object_name, &source) {
+            ObjectName::Existing(unique_object_name) | ObjectName::New(unique_object_name) => {
+                unique_object_name
+            }
+        };
+        if unique_object_name != object_name {
+            warn!(
+                "Named {} because {} is already used by another component",
+                unique_object_name, object_name
+            );
+        }
+        object_database.insert_source(&unique_object_name, source.clone());
+        object_database.insert_ref(&ref_path, &unique_object_name);
+        resolved_components.push((component_name, source, unique_object_name));
+    }
+
+    for (component_name, source, object_name) in resolved_components {
+        let _log_context = LogContext::enter(format!("component {}", component_name));
+        info!("Generating");
+
+        if let ObjectName::Existing(object_name) =
+            object_database.resolve_name(&object_name, &source)
+        {
             info!(
                 "Component \"{}\" already found in database and will be skipped",
                 object_name
@@ -55,32 +93,35 @@ pub fn generate_components(spec: &Spec, config: &Config) -> Result<ObjectDatabas
             continue;
         }
 
+        // Cyclic references to the component resolve to its placeholder
+        let checkpoint = object_database.checkpoint();
+        if let Err(err) = object_database.insert_placeholder(&object_name, source.clone()) {
+            error!("{}", err);
+            report.skip_component(component_name, &err);
+            continue;
+        }
+
         let object_definition = match generate_object(
             spec,
             &mut object_database,
-            definition_path,
+            definition_path.clone(),
             &object_name,
-            &resolved_object,
+            &source.schema,
             &config.name_mapping,
         ) {
             Ok(object_definition) => object_definition,
             Err(err) => {
-                error!("{} {}\n", component_name, err);
+                error!("{}", err);
+                report.skip_component(component_name, &err);
+                object_database.rollback(checkpoint);
                 continue;
             }
         };
 
-        let object_name = get_object_name(&object_definition);
-
-        match object_database.contains_key(object_name) {
-            true => {
-                warn!("ObjectDatabase already contains an object {}. This might be caused by cyclic references", object_name);
-                continue;
-            }
-            _ => {
-                trace!("Adding component/struct {} to database", object_name);
-                object_database.insert(object_name.clone(), object_definition);
-            }
+        trace!("Adding component/struct {} to database", object_name);
+        if let Err(err) = object_database.complete_placeholder(&object_name, object_definition) {
+            error!("{}", err);
+            report.skip_component(component_name, &err);
         }
     }
 
@@ -0,0 +1,168 @@
+use oas3::{
+    spec::{MediaType, ObjectOrReference, ObjectSchema, SchemaType, SchemaTypeSet},
+    Spec,
+};
+use serde_json::{Map, Value};
+
+/// Optional properties are only filled up to this depth to keep examples small
+const OPTIONAL_PROPERTY_DEPTH: usize = 2;
+/// Guard against cyclic references
+const MAX_DEPTH: usize = 16;
+
+/// Example given in the media type (`example` or the first of `examples`)
+pub fn get_media_type_explicit_example(spec: &Spec, media_type: &MediaType) -> Option<Value> {
+    media_type
+        .examples(spec)
+        .into_values()
+        .find_map(|example| example.value)
+}
+
+/// Example given in the schema (`example` or the first of `examples`)
+pub fn get_schema_explicit_example(object_schema: &ObjectSchema) -> Option<Value> {
+    object_schema
+        .example
+        .as_ref()
+        .or(object_schema.examples.first())
+        .cloned()
+}
+
+/// Example of a media type (`example`/`examples`) with fallback to the example of its schema
+pub fn get_media_type_example(spec: &Spec, media_type: &MediaType) -> Option<Value> {
+    if let Some(example) = get_media_type_explicit_example(spec, media_type) {
+        return Some(example);
+    }
+
+    match media_type.schema {
+        Some(ref schema_ref) => get_schema_ref_example(spec, schema_ref, 0),
+        None => None,
+    }
+}
+
+/// Example value of a schema. Uses `example`, `examples`, `default`, `const` or the first enum value
+/// and derives a value from the schema type if none of them is set
+pub fn get_schema_example(spec: &Spec, object_schema: &ObjectSchema) -> Value {
+    get_schema_example_with_depth(spec, object_schema, 0)
+}
+
+fn get_schema_ref_example(
+    spec: &Spec,
+    schema_ref: &ObjectOrReference<ObjectSchema>,
+    depth: usize,
+) -> Option<Value> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    match schema_ref.resolve(spec) {
+        Ok(object_schema) => Some(get_schema_example_with_depth(spec, &object_schema, depth)),
+        Err(_) => None,
+    }
+}
+
+fn get_schema_example_with_depth(spec: &Spec, object_schema: &ObjectSchema, depth: usize) -> Value {
+    if let Some(example) = get_schema_explicit_example(object_schema) {
+        return example;
+    }
+    if let Some(ref default) = object_schema.default {
+        return default.clone();
+    }
+    if let Some(ref const_value) = object_schema.const_value {
+        return const_value.clone();
+    }
+    if let Some(enum_value) = object_schema.enum_values.first() {
+        return enum_value.clone();
+    }
+
+    if let Some(ref schema_type) = object_schema.schema_type {
+        let single_type = match schema_type {
+            SchemaTypeSet::Single(single_type) => single_type,
+            SchemaTypeSet::Multiple(multiple_types) => match multiple_types
+                .iter()
+                .find(|&schema_type| schema_type != &SchemaType::Null)
+            {
+                Some(single_type) => single_type,
+                None => return Value::Null,
+            },
+        };
+        return get_type_example(spec, single_type, object_schema, depth);
+    }
+
+    if !object_schema.all_of.is_empty() {
+        let mut merged_object = Map::new();
+        for all_of_ref in &object_schema.all_of {
+            if let Some(Value::Object(all_of_object)) =
+                get_schema_ref_example(spec, all_of_ref, depth + 1)
+            {
+                merged_object.extend(all_of_object);
+            }
+        }
+        return Value::Object(merged_object);
+    }
+
+    if let Some(any_ref) = object_schema
+        .any_of
+        .first()
+        .or(object_schema.one_of.first())
+    {
+        return get_schema_ref_example(spec, any_ref, depth + 1).unwrap_or(Value::Null);
+    }
+
+    if !object_schema.properties.is_empty() {
+        return get_type_example(spec, &SchemaType::Object, object_schema, depth);
+    }
+
+    // Same fallback as the type generation
+    Value::String("string".to_owned())
+}
+
+fn get_type_example(
+    spec: &Spec,
+    schema_type: &SchemaType,
+    object_schema: &ObjectSchema,
+    depth: usize,
+) -> Value {
+    match schema_type {
+        SchemaType::Boolean => Value::Bool(true),
+        SchemaType::Integer => match object_schema.minimum {
+            Some(ref minimum) => Value::Number(minimum.clone()),
+            None => Value::from(1),
+        },
+        SchemaType::Number => match object_schema.minimum {
+            Some(ref minimum) => Value::Number(minimum.clone()),
+            None => Value::from(1.5),
+        },
+        SchemaType::String => Value::String(
+            match object_schema.format.as_deref() {
+                Some("date-time") => "2024-01-01T00:00:00Z",
+                Some("date") => "2024-01-01",
+                Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                Some("email") => "user@example.com",
+                Some("uri") => "https://example.com",
+                _ => "string",
+            }
+            .to_owned(),
+        ),
+        SchemaType::Array => match object_schema.items {
+            Some(ref items) => match get_schema_ref_example(spec, items, depth + 1) {
+                Some(item_example) => Value::Array(vec![item_example]),
+                None => Value::Array(vec![]),
+            },
+            None => Value::Array(vec![]),
+        },
+        SchemaType::Object => {
+            let mut object = Map::new();
+            for (property_name, property_ref) in &object_schema.properties {
+                let required = object_schema.required.contains(property_name);
+                if !required && depth >= OPTIONAL_PROPERTY_DEPTH {
+                    continue;
+                }
+                if let Some(property_example) =
+                    get_schema_ref_example(spec, property_ref, depth + 1)
+                {
+                    object.insert(property_name.clone(), property_example);
+                }
+            }
+            Value::Object(object)
+        }
+        SchemaType::Null => Value::Null,
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use log::{error, info, trace};
 use oas3::{
@@ -6,13 +6,17 @@ use oas3::{
     Spec,
 };
 use types::{
-    EnumDefinition, EnumValue, ModuleInfo, ObjectDefinition, PrimitiveDefinition,
-    PropertyDefinition, StructDefinition,
+    ConstDefinition, EnumDefinition, EnumValue, ModuleInfo, ObjectDefinition, ObjectName,
+    ObjectSource, PrimitiveDefinition, PropertyConstraints, PropertyDefinition, StructDefinition,
 };
 
 use crate::utils::name_mapping::NameMapping;
 
-use super::{type_definition::get_type_from_schema, ObjectDatabase};
+use super::{
+    example::get_schema_explicit_example,
+    type_definition::{get_type_from_schema, get_type_override},
+    ObjectDatabase,
+};
 pub mod types;
 
 pub fn get_components_base_path() -> Vec<String> {
@@ -28,6 +32,7 @@ pub fn get_object_name(object_definition: &ObjectDefinition) -> &String {
         ObjectDefinition::Struct(struct_definition) => &struct_definition.name,
         ObjectDefinition::Enum(enum_definition) => &enum_definition.name,
         ObjectDefinition::Primitive(type_definition) => &type_definition.name,
+        ObjectDefinition::Const(const_definition) => &const_definition.name,
     }
 }
 
@@ -44,9 +49,45 @@ pub fn modules_to_string(modules: &Vec<&ModuleInfo>) -> String {
     module_import_string
 }
 
+/// The only value allowed by the schema, either `const` or the value of a single-value `enum`
+pub fn get_const_value(object_schema: &ObjectSchema) -> Option<&serde_json::Value> {
+    match object_schema.enum_values.as_slice() {
+        [enum_value] if object_schema.const_value.is_none() => Some(enum_value),
+        _ => object_schema.const_value.as_ref(),
+    }
+}
+
+/// Inline schema without title which is generated as its own object (or an array of them).
+/// It can only be named after its parent
+pub fn is_anonymous_object(
+    object_or_reference: &ObjectOrReference<ObjectSchema>,
+    object_schema: &ObjectSchema,
+) -> bool {
+    let is_object_type = match object_schema.schema_type {
+        Some(SchemaTypeSet::Single(oas3::spec::SchemaType::Object)) => true,
+        Some(SchemaTypeSet::Single(oas3::spec::SchemaType::Array)) => {
+            match object_schema.items.as_deref() {
+                Some(item_object_ref @ ObjectOrReference::Object(item_object)) => {
+                    is_anonymous_object(item_object_ref, item_object)
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    };
+
+    matches!(object_or_reference, ObjectOrReference::Object(_))
+        && object_schema.title.is_none()
+        && (is_object_type
+            || get_const_value(object_schema).is_some()
+            || !object_schema.properties.is_empty()
+            || !object_schema.any_of.is_empty()
+            || !object_schema.one_of.is_empty())
+}
+
 pub fn is_object_empty(object_schema: &ObjectSchema) -> bool {
     return object_schema.schema_type.is_none()
-        && object_schema.const_value.is_none()
+        && get_const_value(object_schema).is_none()
         && object_schema.any_of.is_empty()
         && object_schema.all_of.is_empty()
         && object_schema.one_of.is_empty();
@@ -64,6 +105,13 @@ pub fn generate_object(
         return Err("Object is empty".to_string());
     }
 
+    if let Some(const_value) = get_const_value(object_schema) {
+        return Ok(ObjectDefinition::Const(ConstDefinition {
+            name: name_mapping.name_to_struct_name(&definition_path, name),
+            value: const_value.clone(),
+        }));
+    }
+
     if object_schema.any_of.len() > 0 {
         return generate_enum_from_any(
             spec,
@@ -200,6 +248,13 @@ pub fn get_object_or_ref_struct_name(
         ));
     }
 
+    if get_const_value(object_schema).is_some() {
+        return Ok((
+            definition_path.clone(),
+            name_mapping.name_to_struct_name(definition_path, "Const"),
+        ));
+    }
+
     Err(format!("Unable to determine object name"))
 }
 
@@ -216,6 +271,62 @@ pub fn get_base_path_to_ref(ref_path: &str) -> Result<Vec<String>, String> {
     Ok(path_segments)
 }
 
+/// Variant name of an `anyOf`/`oneOf` alternative (`UserValue`, `StringValue`, ...).
+/// Anonymous alternatives without properties fall back to strings like their type does,
+/// repeated names get a counter
+fn get_enum_value_name(
+    spec: &Spec,
+    definition_path: &Vec<String>,
+    name_mapping: &NameMapping,
+    object_or_reference: &ObjectOrReference<ObjectSchema>,
+    object_schema: &ObjectSchema,
+    enum_values: &BTreeMap<String, EnumValue>,
+) -> Result<String, String> {
+    let object_type_struct_name = match get_object_or_ref_struct_name(
+        spec,
+        definition_path,
+        name_mapping,
+        object_or_reference,
+    ) {
+        Ok((_, object_type_struct_name)) => object_type_struct_name,
+        Err(_) if object_schema.properties.is_empty() => {
+            oas3_type_to_string(&oas3::spec::SchemaType::String)
+        }
+        Err(err) => return Err(err),
+    };
+
+    let enum_value_name = name_mapping.name_to_variant_name(
+        definition_path,
+        &format!("{}Value", object_type_struct_name),
+    );
+    Ok((1..)
+        .map(|counter| match counter {
+            1 => enum_value_name.clone(),
+            _ => format!("{}{}", enum_value_name, counter),
+        })
+        .find(|enum_value_name| !enum_values.contains_key(enum_value_name))
+        .unwrap_or(enum_value_name))
+}
+
+/// Fallback type name of an `anyOf`/`oneOf` alternative. Anonymous arrays are named after the
+/// enum (`PickArrayValue`), so their items (`PickArrayValueItem`) don't take the name of
+/// items of other enums
+fn get_enum_value_type_name(
+    enum_name: &str,
+    object_or_reference: &ObjectOrReference<ObjectSchema>,
+    object_schema: &ObjectSchema,
+    enum_value_name: &str,
+) -> String {
+    match object_schema.schema_type {
+        Some(SchemaTypeSet::Single(oas3::spec::SchemaType::Array))
+            if is_anonymous_object(object_or_reference, object_schema) =>
+        {
+            format!("{}{}", enum_name, enum_value_name)
+        }
+        _ => enum_value_name.to_owned(),
+    }
+}
+
 pub fn generate_enum_from_any(
     spec: &Spec,
     object_database: &mut ObjectDatabase,
@@ -229,7 +340,7 @@ pub fn generate_enum_from_any(
         name: name_mapping
             .name_to_struct_name(&definition_path, name)
             .to_owned(),
-        values: HashMap::new(),
+        values: BTreeMap::new(),
         used_modules: vec![],
     };
     definition_path.push(enum_definition.name.clone());
@@ -258,16 +369,15 @@ pub fn generate_enum_from_any(
             }
         };
 
-        let object_type_enum_name = match get_object_or_ref_struct_name(
+        let object_type_enum_name = match get_enum_value_name(
             spec,
             &any_object_definition_path,
             name_mapping,
             any_object_ref,
+            &any_object,
+            &enum_definition.values,
         ) {
-            Ok((_, object_type_struct_name)) => name_mapping.name_to_struct_name(
-                &any_object_definition_path,
-                &format!("{}Value", object_type_struct_name),
-            ),
+            Ok(object_type_enum_name) => object_type_enum_name,
             Err(err) => {
                 return Err(format!(
                     "{} Anonymous enum value are not supported \"{}\"",
@@ -276,16 +386,25 @@ pub fn generate_enum_from_any(
             }
         };
 
-        enum_definition.values.insert(
-            object_type_enum_name.clone(),
-            match get_type_from_schema(
+        let value_type = match get_type_override(name_mapping, any_object_ref) {
+            Some(type_definition) => Ok(type_definition),
+            None => get_type_from_schema(
                 spec,
                 object_database,
                 any_object_definition_path.clone(),
                 &any_object,
-                Some(&object_type_enum_name),
+                Some(&get_enum_value_type_name(
+                    &enum_definition.name,
+                    any_object_ref,
+                    &any_object,
+                    &object_type_enum_name,
+                )),
                 name_mapping,
-            ) {
+            ),
+        };
+        enum_definition.values.insert(
+            object_type_enum_name.clone(),
+            match value_type {
                 Ok(type_definition) => EnumValue {
                     name: object_type_enum_name,
                     value_type: type_definition,
@@ -313,7 +432,7 @@ pub fn generate_enum_from_one_of(
         name: name_mapping
             .name_to_struct_name(&definition_path, name)
             .to_owned(),
-        values: HashMap::new(),
+        values: BTreeMap::new(),
         used_modules: vec![],
     };
     definition_path.push(enum_definition.name.clone());
@@ -342,16 +461,15 @@ pub fn generate_enum_from_one_of(
             }
         };
 
-        let object_type_enum_name = match get_object_or_ref_struct_name(
+        let object_type_enum_name = match get_enum_value_name(
             spec,
             &one_of_object_definition_path,
             name_mapping,
             one_of_object_ref,
+            &one_of_object,
+            &enum_definition.values,
         ) {
-            Ok((_, object_type_struct_name)) => name_mapping.name_to_struct_name(
-                &one_of_object_definition_path,
-                &format!("{}Value", object_type_struct_name),
-            ),
+            Ok(object_type_enum_name) => object_type_enum_name,
             Err(err) => {
                 return Err(format!(
                     "{} Anonymous enum value are not supported \"{}\"",
@@ -360,16 +478,25 @@ pub fn generate_enum_from_one_of(
             }
         };
 
-        enum_definition.values.insert(
-            object_type_enum_name.clone(),
-            match get_type_from_schema(
+        let value_type = match get_type_override(name_mapping, one_of_object_ref) {
+            Some(type_definition) => Ok(type_definition),
+            None => get_type_from_schema(
                 spec,
                 object_database,
                 one_of_object_definition_path.clone(),
                 &one_of_object,
-                Some(&object_type_enum_name),
+                Some(&get_enum_value_type_name(
+                    &enum_definition.name,
+                    one_of_object_ref,
+                    &one_of_object,
+                    &object_type_enum_name,
+                )),
                 name_mapping,
-            ) {
+            ),
+        };
+        enum_definition.values.insert(
+            object_type_enum_name.clone(),
+            match value_type {
                 Ok(type_definition) => EnumValue {
                     name: object_type_enum_name,
                     value_type: type_definition,
@@ -397,9 +524,10 @@ pub fn generate_struct(
         name: name_mapping
             .name_to_struct_name(&definition_path, name)
             .to_owned(),
-        properties: HashMap::new(),
+        properties: BTreeMap::new(),
         used_modules: vec![],
-        local_objects: HashMap::new(),
+        local_objects: BTreeMap::new(),
+        example: get_schema_explicit_example(object_schema),
     };
     definition_path.push(struct_definition.name.clone());
 
@@ -454,30 +582,59 @@ fn get_or_create_property(
     };
 
     let (property_type_definition_path, property_type_name) =
-        match get_object_or_ref_struct_name(spec, &definition_path, name_mapping, property_ref) {
-            Ok(type_naming_data) => type_naming_data,
-            Err(err) => {
-                return Err(format!(
-                    "Unable to determine property name of {} {}",
-                    property_name, err
-                ))
+        match is_anonymous_object(property_ref, &property) {
+            // Named after the struct and property, the name of the schema type would be too generic
+            true => (
+                definition_path.clone(),
+                name_mapping.name_to_struct_name(
+                    &definition_path,
+                    &format!(
+                        "{}_{}",
+                        definition_path.last().map_or("", String::as_str),
+                        property_name
+                    ),
+                ),
+            ),
+            false => {
+                match get_object_or_ref_struct_name(
+                    spec,
+                    &definition_path,
+                    name_mapping,
+                    property_ref,
+                ) {
+                    Ok(type_naming_data) => type_naming_data,
+                    Err(err) => {
+                        return Err(format!(
+                            "Unable to determine property name of {} {}",
+                            property_name, err
+                        ))
+                    }
+                }
             }
         };
 
-    match get_type_from_schema(
-        spec,
-        object_database,
-        property_type_definition_path,
-        &property,
-        Some(&property_type_name),
-        name_mapping,
-    ) {
+    let property_type_definition = match get_type_override(name_mapping, property_ref) {
+        Some(type_definition) => Ok(type_definition),
+        None => get_type_from_schema(
+            spec,
+            object_database,
+            property_type_definition_path,
+            &property,
+            Some(&property_type_name),
+            name_mapping,
+        ),
+    };
+    match property_type_definition {
         Ok(property_type_definition) => Ok(PropertyDefinition {
             type_name: property_type_definition.name,
             module: property_type_definition.module,
             name: name_mapping.name_to_property_name(&definition_path, property_name),
             real_name: property_name.clone(),
             required: required,
+            default_value: property.default.clone(),
+            read_only: property.read_only.unwrap_or(false),
+            write_only: property.write_only.unwrap_or(false),
+            constraints: PropertyConstraints::from_schema(&property),
         }),
         Err(err) => Err(err),
     }
@@ -491,34 +648,30 @@ pub fn get_or_create_object(
     property_ref: &ObjectSchema,
     name_mapping: &NameMapping,
 ) -> Result<ObjectDefinition, String> {
-    if let Some(object_in_database) =
-        object_database.get(&name_mapping.name_to_struct_name(&definition_path, name))
-    {
-        return Ok(object_in_database.clone());
+    let requested_struct_name = name_mapping.name_to_struct_name(&definition_path, name);
+    let source = ObjectSource::new(spec, definition_path.clone(), property_ref.clone());
+    let struct_name = match object_database.resolve_name(&requested_struct_name, &source) {
+        ObjectName::Existing(struct_name) => match object_database.get(&struct_name) {
+            Some(object_in_database) => return Ok(object_in_database.clone()),
+            None => return Err(format!("ObjectDatabase lost object {}", struct_name)),
+        },
+        ObjectName::New(struct_name) => struct_name,
+    };
+
+    if struct_name != requested_struct_name {
+        info!(
+            "{} is already used by another schema, {} is used instead",
+            requested_struct_name, struct_name
+        );
     }
 
     // create shallow hull which will be filled in later
     // the hull is needed to reference for cyclic dependencies where we would
     // otherwise create the same object every time we want to resolve the current one
-    let struct_name = name_mapping.name_to_struct_name(&definition_path, name);
-    if object_database.contains_key(&struct_name) {
-        return Err(format!(
-            "ObjectDatabase already contains an object {}",
-            struct_name
-        ));
-    }
-
     trace!("Adding struct {} to database", struct_name);
 
-    object_database.insert(
-        struct_name.clone(),
-        ObjectDefinition::Struct(StructDefinition {
-            used_modules: vec![],
-            name: struct_name.clone(),
-            properties: HashMap::new(),
-            local_objects: HashMap::new(),
-        }),
-    );
+    let checkpoint = object_database.checkpoint();
+    object_database.insert_placeholder(&struct_name, source)?;
 
     match generate_object(
         spec,
@@ -529,11 +682,14 @@ pub fn get_or_create_object(
         name_mapping,
     ) {
         Ok(created_struct) => {
-            let name = get_object_name(&created_struct);
-            trace!("Updating struct {} in database", name);
-            object_database.insert(name.clone(), created_struct.clone());
+            trace!("Updating struct {} in database", struct_name);
+            object_database.complete_placeholder(&struct_name, created_struct.clone())?;
             Ok(created_struct)
         }
-        Err(err) => Err(format!("Failed to generate object: {}", err)),
+        Err(err) => {
+            // Objects which were generated for this one may reference its hull
+            object_database.rollback(checkpoint);
+            Err(format!("Failed to generate object: {}", err))
+        }
     }
 }
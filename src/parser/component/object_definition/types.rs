@@ -1,4 +1,15 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use oas3::{
+    spec::{FromRef, ObjectSchema},
+    Spec,
+};
+use serde_json::Value;
+
+use crate::utils::output::content_hash;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ModuleInfo {
@@ -19,6 +30,39 @@ pub struct PropertyDefinition {
     pub type_name: String,
     pub module: Option<ModuleInfo>,
     pub required: bool,
+    /// Schema `default` value
+    pub default_value: Option<serde_json::Value>,
+    /// Schema `readOnly`, only sent by the server
+    pub read_only: bool,
+    /// Schema `writeOnly`, only sent by the client
+    pub write_only: bool,
+    pub constraints: PropertyConstraints,
+}
+
+/// Validation keywords of a property schema
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyConstraints {
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    pub pattern: Option<String>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub min_items: Option<u64>,
+    pub max_items: Option<u64>,
+}
+
+impl PropertyConstraints {
+    pub fn from_schema(schema: &ObjectSchema) -> Self {
+        PropertyConstraints {
+            min_length: schema.min_length,
+            max_length: schema.max_length,
+            pattern: schema.pattern.clone(),
+            minimum: schema.minimum.as_ref().and_then(|minimum| minimum.as_f64()),
+            maximum: schema.maximum.as_ref().and_then(|maximum| maximum.as_f64()),
+            min_items: schema.min_items,
+            max_items: schema.max_items,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -26,6 +70,7 @@ pub enum ObjectDefinition {
     Struct(StructDefinition),
     Enum(EnumDefinition),
     Primitive(PrimitiveDefinition),
+    Const(ConstDefinition),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -38,10 +83,395 @@ pub struct EnumValue {
 pub struct EnumDefinition {
     pub name: String,
     pub used_modules: Vec<ModuleInfo>,
-    pub values: HashMap<String, EnumValue>,
+    pub values: BTreeMap<String, EnumValue>,
+}
+
+/// Schema an object of the database was generated from
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectSource {
+    pub definition_path: Vec<String>,
+    pub schema: ObjectSchema,
+    /// Hash of the schema with references replaced by the hashes of their schemas, so
+    /// copies of a component (e.g. `Collider-Input` and `Collider-Output`) are equal
+    pub schema_hash: String,
+}
+
+impl ObjectSource {
+    pub fn new(spec: &Spec, definition_path: Vec<String>, schema: ObjectSchema) -> Self {
+        let schema_value = serde_json::to_value(&schema).unwrap_or(Value::Null);
+        ObjectSource {
+            definition_path,
+            schema_hash: resolved_value_hash(
+                spec,
+                &schema_value,
+                &mut vec![],
+                &mut BTreeMap::new(),
+            )
+            .0,
+            schema,
+        }
+    }
+}
+
+/// Hash of the json value with resolved references and the lowest index of the
+/// references stack which a cyclic reference below the value points to
+fn resolved_value_hash(
+    spec: &Spec,
+    value: &Value,
+    ref_stack: &mut Vec<String>,
+    ref_hashes: &mut BTreeMap<String, String>,
+) -> (String, usize) {
+    let mut cycle_index = usize::MAX;
+    let resolved_value = resolve_value_refs(spec, value, ref_stack, ref_hashes, &mut cycle_index);
+    (
+        content_hash(resolved_value.to_string().as_bytes()),
+        cycle_index,
+    )
+}
+
+fn resolve_value_refs(
+    spec: &Spec,
+    value: &Value,
+    ref_stack: &mut Vec<String>,
+    ref_hashes: &mut BTreeMap<String, String>,
+    cycle_index: &mut usize,
+) -> Value {
+    match value {
+        Value::Object(map) => match map.get("$ref") {
+            Some(Value::String(ref_path)) => Value::String(resolve_ref_hash(
+                spec,
+                ref_path,
+                ref_stack,
+                ref_hashes,
+                cycle_index,
+            )),
+            _ => Value::Object(
+                map.iter()
+                    .map(|(key, value)| {
+                        (
+                            key.clone(),
+                            resolve_value_refs(spec, value, ref_stack, ref_hashes, cycle_index),
+                        )
+                    })
+                    .collect(),
+            ),
+        },
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_value_refs(spec, item, ref_stack, ref_hashes, cycle_index))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+fn resolve_ref_hash(
+    spec: &Spec,
+    ref_path: &str,
+    ref_stack: &mut Vec<String>,
+    ref_hashes: &mut BTreeMap<String, String>,
+    cycle_index: &mut usize,
+) -> String {
+    // Cycles are identified by their distance, which doesn't depend on the ref paths
+    if let Some(index) = ref_stack.iter().position(|stack_ref| stack_ref == ref_path) {
+        *cycle_index = (*cycle_index).min(index);
+        return format!("cycle:{}", ref_stack.len() - index);
+    }
+    if let Some(hash) = ref_hashes.get(ref_path) {
+        return hash.clone();
+    }
+    let schema_value = match ObjectSchema::from_ref(spec, ref_path) {
+        Ok(schema) => serde_json::to_value(&schema).unwrap_or(Value::Null),
+        Err(_) => return ref_path.to_owned(),
+    };
+
+    ref_stack.push(ref_path.to_owned());
+    let (hash, ref_cycle_index) = resolved_value_hash(spec, &schema_value, ref_stack, ref_hashes);
+    ref_stack.pop();
+
+    // Hashes of schemas within a cycle depend on where the cycle was entered
+    if ref_cycle_index >= ref_stack.len() {
+        ref_hashes.insert(ref_path.to_owned(), hash.clone());
+    } else {
+        *cycle_index = (*cycle_index).min(ref_cycle_index);
+    }
+    hash
+}
+
+/// Name of a schema in the database
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObjectName {
+    /// The schema was already generated under this name
+    Existing(String),
+    /// The schema isn't generated yet and this name is free
+    New(String),
+}
+
+/// Generated objects by their (unique) struct name, iterated in name order.
+/// Each object remembers the schema it was generated from, so different schemas which map
+/// to the same name don't silently share one object
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObjectDatabase {
+    objects: BTreeMap<String, ObjectDefinition>,
+    sources: BTreeMap<String, ObjectSource>,
+    /// Struct names of components by their ref path (`#/components/schemas/Name`)
+    refs: BTreeMap<String, String>,
+    /// Objects which are still generated, referenced by cyclic schemas
+    placeholders: BTreeSet<String>,
+    /// Names in insertion order to roll back failed generations
+    insertion_order: Vec<String>,
+    /// Objects which are looked up but not copied, see [`ObjectDatabase::layer`]
+    base: Option<Arc<ObjectDatabase>>,
+}
+
+/// State of the database which can be restored with [`ObjectDatabase::rollback`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectCheckpoint(usize);
+
+impl ObjectDatabase {
+    pub fn new() -> Self {
+        ObjectDatabase::default()
+    }
+
+    /// Empty database on top of `base`, whose objects are found without being copied.
+    /// Added objects are only part of the layer, which is iterated without the base objects
+    pub fn layer(base: &Arc<ObjectDatabase>) -> Self {
+        ObjectDatabase {
+            base: Some(base.clone()),
+            ..ObjectDatabase::default()
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ObjectDefinition> {
+        self.objects
+            .get(name)
+            .or_else(|| self.base.as_ref()?.get(name))
+    }
+
+    /// Object generated for the component at `ref_path`
+    pub fn get_by_ref(&self, ref_path: &str) -> Option<&ObjectDefinition> {
+        match self.refs.get(ref_path) {
+            Some(name) => self.get(name),
+            None => self.base.as_ref()?.get_by_ref(ref_path),
+        }
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.objects.contains_key(name)
+            || self
+                .base
+                .as_ref()
+                .is_some_and(|base| base.contains_key(name))
+    }
+
+    pub fn keys(&self) -> std::collections::btree_map::Keys<'_, String, ObjectDefinition> {
+        self.objects.keys()
+    }
+
+    pub fn values(&self) -> std::collections::btree_map::Values<'_, String, ObjectDefinition> {
+        self.objects.values()
+    }
+
+    pub fn iter(&self) -> std::collections::btree_map::Iter<'_, String, ObjectDefinition> {
+        self.objects.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Adds a new object, fails if the name is already used
+    pub fn insert_unique(
+        &mut self,
+        name: &str,
+        object_definition: ObjectDefinition,
+    ) -> Result<(), String> {
+        if self.contains_key(name) {
+            return Err(format!(
+                "ObjectDatabase already contains an object {}",
+                name
+            ));
+        }
+        self.insertion_order.push(name.to_owned());
+        self.objects.insert(name.to_owned(), object_definition);
+        Ok(())
+    }
+
+    /// Adds an empty struct as stand-in for an object which is generated right now,
+    /// so cyclic references resolve to it instead of generating it again
+    pub fn insert_placeholder(&mut self, name: &str, source: ObjectSource) -> Result<(), String> {
+        self.insert_unique(
+            name,
+            ObjectDefinition::Struct(StructDefinition {
+                used_modules: vec![],
+                name: name.to_owned(),
+                properties: BTreeMap::new(),
+                local_objects: BTreeMap::new(),
+                example: None,
+            }),
+        )?;
+        self.sources.insert(name.to_owned(), source);
+        self.placeholders.insert(name.to_owned());
+        Ok(())
+    }
+
+    /// Replaces the placeholder with the generated object
+    pub fn complete_placeholder(
+        &mut self,
+        name: &str,
+        object_definition: ObjectDefinition,
+    ) -> Result<(), String> {
+        if !self.placeholders.remove(name) {
+            return Err(format!("{} is not a placeholder", name));
+        }
+        self.objects.insert(name.to_owned(), object_definition);
+        Ok(())
+    }
+
+    pub fn is_placeholder(&self, name: &str) -> bool {
+        self.placeholders.contains(name)
+            || self
+                .base
+                .as_ref()
+                .is_some_and(|base| base.is_placeholder(name))
+    }
+
+    pub fn checkpoint(&self) -> ObjectCheckpoint {
+        ObjectCheckpoint(self.insertion_order.len())
+    }
+
+    /// Removes all objects (including placeholders) added after the checkpoint.
+    /// Their names stay reserved for their schemas
+    pub fn rollback(&mut self, checkpoint: ObjectCheckpoint) {
+        for name in self.insertion_order.split_off(checkpoint.0) {
+            self.objects.remove(&name);
+            self.placeholders.remove(&name);
+        }
+    }
+
+    /// Name of the schema of the source: `name` itself, else `name` prefixed by the parent
+    /// object (last element of the definition path) and at last `name` with a counter
+    pub fn resolve_name(&self, name: &str, source: &ObjectSource) -> ObjectName {
+        let mut candidates = vec![name.to_owned()];
+        if let Some(parent) = source.definition_path.last() {
+            if parent.starts_with(|c: char| c.is_ascii_uppercase()) && !name.starts_with(parent) {
+                candidates.push(format!("{}{}", parent, name));
+            }
+        }
+
+        for candidate in candidates
+            .into_iter()
+            .chain((2..).map(|counter| format!("{}{}", name, counter)))
+        {
+            match self.get_source(&candidate) {
+                Some(candidate_source) if candidate_source.schema_hash != source.schema_hash => {
+                    continue
+                }
+                // Generated or at least reserved for this schema
+                Some(_) => match self.contains_key(&candidate) {
+                    true => return ObjectName::Existing(candidate),
+                    false => return ObjectName::New(candidate),
+                },
+                None if self.contains_key(&candidate) => continue,
+                None => return ObjectName::New(candidate),
+            }
+        }
+        unreachable!("Counter names are unbounded")
+    }
+
+    pub fn get_source(&self, name: &str) -> Option<&ObjectSource> {
+        self.sources
+            .get(name)
+            .or_else(|| self.base.as_ref()?.get_source(name))
+    }
+
+    /// Reserves the name for the schema of the source, other schemas won't get this name
+    pub fn insert_source(&mut self, name: &str, source: ObjectSource) {
+        self.sources.insert(name.to_owned(), source);
+    }
+
+    /// Registers the struct name of the component at `ref_path`
+    pub fn insert_ref(&mut self, ref_path: &str, name: &str) {
+        self.refs.insert(ref_path.to_owned(), name.to_owned());
+    }
+
+    /// Objects (with their sources) which are not part of `base`
+    pub fn added_since(self, base: &ObjectDatabase) -> ObjectDatabase {
+        let mut added_objects = ObjectDatabase::new();
+        for name in self.insertion_order {
+            if base.objects.contains_key(&name) {
+                continue;
+            }
+            let object_definition = match self.objects.get(&name) {
+                Some(object_definition) => object_definition.clone(),
+                None => continue,
+            };
+            if let Some(source) = self.sources.get(&name) {
+                added_objects.sources.insert(name.clone(), source.clone());
+            }
+            added_objects
+                .objects
+                .insert(name.clone(), object_definition);
+            added_objects.insertion_order.push(name);
+        }
+        added_objects
+    }
+
+    /// Adds all objects of `other`. Nothing is added if one of them uses a name which is
+    /// already taken by a different schema, the conflicting names are returned instead
+    pub fn merge(&mut self, other: ObjectDatabase) -> Result<(), Vec<String>> {
+        let conflicting_names = other
+            .objects
+            .keys()
+            .filter(|&name| {
+                self.objects.contains_key(name)
+                    && self.sources.get(name).map(|source| &source.schema_hash)
+                        != other.sources.get(name).map(|source| &source.schema_hash)
+            })
+            .cloned()
+            .collect::<Vec<String>>();
+        if !conflicting_names.is_empty() {
+            return Err(conflicting_names);
+        }
+
+        for (name, source) in other.sources {
+            self.sources.entry(name).or_insert(source);
+        }
+        for (ref_path, name) in other.refs {
+            self.refs.entry(ref_path).or_insert(name);
+        }
+        for (name, object_definition) in other.objects {
+            if !self.objects.contains_key(&name) {
+                self.insertion_order.push(name.clone());
+                self.objects.insert(name, object_definition);
+            }
+        }
+        Ok(())
+    }
 }
 
-pub type ObjectDatabase = HashMap<String, ObjectDefinition>;
+impl IntoIterator for ObjectDatabase {
+    type Item = (String, ObjectDefinition);
+    type IntoIter = std::collections::btree_map::IntoIter<String, ObjectDefinition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.objects.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ObjectDatabase {
+    type Item = (&'a String, &'a ObjectDefinition);
+    type IntoIter = std::collections::btree_map::Iter<'a, String, ObjectDefinition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.objects.iter()
+    }
+}
 
 impl EnumDefinition {
     pub fn get_required_modules(&self) -> Vec<&ModuleInfo> {
@@ -61,8 +491,10 @@ impl EnumDefinition {
 pub struct StructDefinition {
     pub used_modules: Vec<ModuleInfo>,
     pub name: String,
-    pub properties: HashMap<String, PropertyDefinition>,
-    pub local_objects: HashMap<String, Box<ObjectDefinition>>,
+    pub properties: BTreeMap<String, PropertyDefinition>,
+    pub local_objects: BTreeMap<String, Box<ObjectDefinition>>,
+    /// Schema `example` (or first of `examples`)
+    pub example: Option<serde_json::Value>,
 }
 
 impl StructDefinition {
@@ -86,6 +518,13 @@ pub struct PrimitiveDefinition {
     pub primitive_type: TypeDefinition,
 }
 
+/// Schema which only allows a single value (`const` or `enum` with one value)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstDefinition {
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
 pub fn to_unique_list(modules: &Vec<ModuleInfo>) -> Vec<ModuleInfo> {
     let mut unique_modules: Vec<ModuleInfo> = vec![];
     for module in modules {
@@ -1,6 +1,6 @@
 use log::trace;
 use oas3::{
-    spec::{ObjectSchema, SchemaTypeSet},
+    spec::{ObjectOrReference, ObjectSchema, SchemaTypeSet},
     Spec,
 };
 
@@ -8,12 +8,32 @@ use crate::utils::name_mapping::NameMapping;
 
 use super::{
     object_definition::{
-        get_object_name, get_object_or_ref_struct_name, get_or_create_object,
+        get_const_value, get_object_name, get_object_or_ref_struct_name, get_or_create_object,
+        is_anonymous_object,
         types::{ModuleInfo, TypeDefinition},
     },
     ObjectDatabase,
 };
 
+/// Type of a referenced component which `name_mapping.type_overrides` replaces, its import
+/// is added to the modules which use it
+pub fn get_type_override(
+    name_mapping: &NameMapping,
+    object_or_reference: &ObjectOrReference<ObjectSchema>,
+) -> Option<TypeDefinition> {
+    let type_override = match object_or_reference {
+        ObjectOrReference::Ref { ref_path } => name_mapping.get_type_override(ref_path)?,
+        ObjectOrReference::Object(_) => return None,
+    };
+    trace!("Type override {:?}", type_override);
+    Some(TypeDefinition {
+        name: type_override.type_name(),
+        module: type_override
+            .import()
+            .map(|(path, name)| ModuleInfo { path, name }),
+    })
+}
+
 pub fn get_type_from_schema(
     spec: &Spec,
     object_database: &mut ObjectDatabase,
@@ -22,6 +42,18 @@ pub fn get_type_from_schema(
     object_variable_fallback_name: Option<&str>,
     name_mapping: &NameMapping,
 ) -> Result<TypeDefinition, String> {
+    // Constants get their own type regardless of the type of their value
+    if get_const_value(object_schema).is_some() {
+        return get_type_from_any_type(
+            spec,
+            object_database,
+            definition_path,
+            object_schema,
+            object_variable_fallback_name,
+            name_mapping,
+        );
+    }
+
     if let Some(ref schema_type) = object_schema.schema_type {
         return get_type_from_schema_type(
             spec,
@@ -107,18 +139,38 @@ pub fn get_type_from_any_type(
         }
     };
 
-    let object_name = get_object_name(&object_definition);
+    Ok(get_object_type(
+        object_database,
+        get_object_name(&object_definition),
+        name_mapping,
+    ))
+}
 
-    Ok(TypeDefinition {
-        name: object_name.clone(),
+/// Type of a generated object. Objects which are still generated are referenced by one of
+/// their own (nested) properties and boxed, otherwise the struct would contain itself
+fn get_object_type(
+    object_database: &ObjectDatabase,
+    object_name: &str,
+    name_mapping: &NameMapping,
+) -> TypeDefinition {
+    let type_name = match object_database.is_placeholder(object_name) {
+        true => {
+            trace!("{} references itself and is boxed", object_name);
+            format!("Box<{}>", object_name)
+        }
+        false => object_name.to_owned(),
+    };
+
+    TypeDefinition {
+        name: type_name,
         module: Some(ModuleInfo {
             path: format!(
                 "crate::objects::{}",
-                name_mapping.name_to_module_name(&object_name)
+                name_mapping.name_to_module_name(object_name)
             ),
-            name: object_name.clone(),
+            name: object_name.to_owned(),
         }),
-    })
+    }
 }
 
 pub fn get_type_from_schema_type(
@@ -154,7 +206,12 @@ pub fn get_type_from_schema_type(
             module: None,
         }),
         oas3::spec::SchemaType::String => Ok(TypeDefinition {
-            name: "String".to_owned(),
+            name: object_schema
+                .format
+                .as_deref()
+                .and_then(|format| name_mapping.type_mapping.get_format_type(format))
+                .unwrap_or("String")
+                .to_owned(),
             module: None,
         }),
         oas3::spec::SchemaType::Number => Ok(TypeDefinition {
@@ -171,15 +228,10 @@ pub fn get_type_from_schema_type(
                 None => return Err(format!("Array has no item type")),
             };
 
-            let (item_type_definition_path, item_type_name) = match get_object_or_ref_struct_name(
-                spec,
-                &definition_path,
-                name_mapping,
-                &item_object_ref,
-            ) {
-                Ok(definition_path_and_name) => definition_path_and_name,
-                Err(err) => return Err(format!("Unable to determine ArrayItem type name {}", err)),
-            };
+            if let Some(mut type_definition) = get_type_override(name_mapping, item_object_ref) {
+                type_definition.name = format!("Vec<{}>", type_definition.name);
+                return Ok(type_definition);
+            }
 
             let item_object = match item_object_ref.resolve(spec) {
                 Ok(item_object) => item_object,
@@ -192,6 +244,29 @@ pub fn get_type_from_schema_type(
                 }
             };
 
+            let (item_type_definition_path, item_type_name) =
+                match is_anonymous_object(item_object_ref, &item_object) {
+                    // Named after the array
+                    true => (
+                        definition_path.clone(),
+                        name_mapping.name_to_struct_name(
+                            &definition_path,
+                            &format!("{}_item", object_variable_name),
+                        ),
+                    ),
+                    false => match get_object_or_ref_struct_name(
+                        spec,
+                        &definition_path,
+                        name_mapping,
+                        &item_object_ref,
+                    ) {
+                        Ok(definition_path_and_name) => definition_path_and_name,
+                        Err(err) => {
+                            return Err(format!("Unable to determine ArrayItem type name {}", err))
+                        }
+                    },
+                };
+
             match get_type_from_schema(
                 spec,
                 object_database,
@@ -201,7 +276,13 @@ pub fn get_type_from_schema_type(
                 name_mapping,
             ) {
                 Ok(mut type_definition) => {
-                    type_definition.name = format!("Vec<{}>", type_definition.name);
+                    // Vec is already an indirection for recursive items
+                    let item_type_name = type_definition
+                        .name
+                        .strip_prefix("Box<")
+                        .and_then(|boxed_type_name| boxed_type_name.strip_suffix('>'))
+                        .unwrap_or(&type_definition.name);
+                    type_definition.name = format!("Vec<{}>", item_type_name);
                     return Ok(type_definition);
                 }
                 Err(err) => Err(err),
@@ -225,18 +306,11 @@ pub fn get_type_from_schema_type(
                 }
             };
 
-            let object_name = get_object_name(&object_definition);
-
-            Ok(TypeDefinition {
-                name: object_name.clone(),
-                module: Some(ModuleInfo {
-                    path: format!(
-                        "crate::objects::{}",
-                        name_mapping.name_to_module_name(&object_name)
-                    ),
-                    name: object_name.clone(),
-                }),
-            })
+            Ok(get_object_type(
+                object_database,
+                get_object_name(&object_definition),
+                name_mapping,
+            ))
         }
         _ => Err(format!("Type {:?} not supported", single_type)),
     }
@@ -0,0 +1,263 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use log::warn;
+use oas3::Spec;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    generator::{
+        media_type::TransferMediaType,
+        operation::{generate_operation_definition, BodyDefinition},
+        rust_reqwest_async::paths::collect_operations,
+    },
+    parser::{
+        component::{
+            generate_components,
+            object_definition::types::{ObjectDefinition, PropertyDefinition},
+        },
+        security::OperationSecurity,
+    },
+    utils::config::Config,
+};
+
+/// Http method of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Get,
+    Put,
+    Post,
+    Delete,
+    Options,
+    Head,
+    Patch,
+    Trace,
+}
+
+impl HttpMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Post => "POST",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Trace => "TRACE",
+        }
+    }
+}
+
+impl Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Methods of path items, extension methods (e.g. `CONNECT`) are not part of the spec
+impl TryFrom<&reqwest::Method> for HttpMethod {
+    type Error = String;
+
+    fn try_from(method: &reqwest::Method) -> Result<Self, Self::Error> {
+        match *method {
+            reqwest::Method::GET => Ok(HttpMethod::Get),
+            reqwest::Method::PUT => Ok(HttpMethod::Put),
+            reqwest::Method::POST => Ok(HttpMethod::Post),
+            reqwest::Method::DELETE => Ok(HttpMethod::Delete),
+            reqwest::Method::OPTIONS => Ok(HttpMethod::Options),
+            reqwest::Method::HEAD => Ok(HttpMethod::Head),
+            reqwest::Method::PATCH => Ok(HttpMethod::Patch),
+            reqwest::Method::TRACE => Ok(HttpMethod::Trace),
+            _ => Err(format!("Unsupported http method {}", method.as_str())),
+        }
+    }
+}
+
+/// Parse result of a spec without code generation, e.g. for docs generators or diff tools.
+/// Types are the rust type names of the generated objects
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpecIr {
+    pub title: String,
+    pub version: String,
+    pub operations: Vec<OperationIr>,
+    /// Objects by their name
+    pub models: BTreeMap<String, ModelIr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationIr {
+    pub operation_id: String,
+    pub method: HttpMethod,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub path_parameters: Vec<ParameterIr>,
+    pub query_parameters: Vec<ParameterIr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<BodyIr>,
+    pub responses: Vec<ResponseIr>,
+    /// None if neither the operation nor the spec declares security
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<OperationSecurity>,
+}
+
+/// Path or query parameter, or property of a model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterIr {
+    /// Name of the spec
+    pub name: String,
+    pub type_name: String,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BodyIr {
+    pub content_type: String,
+    /// None for json bodies without schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseIr {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<BodyIr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ModelIr {
+    Struct {
+        properties: Vec<ParameterIr>,
+    },
+    /// Values of string enums or variants of oneOf/anyOf
+    Enum {
+        variants: Vec<String>,
+    },
+    Primitive {
+        type_name: String,
+    },
+    Const {
+        value: serde_json::Value,
+    },
+}
+
+impl SpecIr {
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| err.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
+}
+
+/// Parses the components and (not ignored) operations of the spec. Operations which the
+/// generator can't analyse (e.g. websockets) are skipped
+pub fn parse_spec_ir(spec: &Spec, config: &Config) -> Result<SpecIr, String> {
+    let mut object_database = generate_components(spec, config)?;
+
+    let mut operations = vec![];
+    for (path, method, operation) in collect_operations(spec, config) {
+        let operation_definition = match generate_operation_definition(
+            spec,
+            &mut object_database,
+            config,
+            &method,
+            path,
+            operation,
+        ) {
+            Ok(operation_definition) => operation_definition,
+            Err(err) => {
+                warn!("{} {} skipped: {}", method.as_str(), path, err);
+                continue;
+            }
+        };
+
+        operations.push(OperationIr {
+            operation_id: operation_definition.operation_id,
+            method: HttpMethod::try_from(&method)?,
+            path: path.clone(),
+            summary: operation_definition.summary,
+            path_parameters: operation_definition
+                .path_parameters
+                .iter()
+                .map(get_parameter_ir)
+                .collect(),
+            query_parameters: operation_definition
+                .query_parameters
+                .iter()
+                .map(|query_parameter| get_parameter_ir(&query_parameter.property))
+                .collect(),
+            request_body: operation_definition.request_body.as_ref().map(get_body_ir),
+            responses: operation_definition
+                .responses
+                .iter()
+                .map(|response| ResponseIr {
+                    status: response.status,
+                    body: response.body.as_ref().map(get_body_ir),
+                })
+                .collect(),
+            security: operation_definition.security,
+        });
+    }
+
+    let models = object_database
+        .iter()
+        .map(|(name, object_definition)| {
+            let model = match object_definition {
+                ObjectDefinition::Struct(struct_definition) => ModelIr::Struct {
+                    properties: struct_definition
+                        .properties
+                        .values()
+                        .map(get_parameter_ir)
+                        .collect(),
+                },
+                ObjectDefinition::Enum(enum_definition) => ModelIr::Enum {
+                    variants: enum_definition.values.keys().cloned().collect(),
+                },
+                ObjectDefinition::Primitive(primitive_definition) => ModelIr::Primitive {
+                    type_name: primitive_definition.primitive_type.name.clone(),
+                },
+                ObjectDefinition::Const(const_definition) => ModelIr::Const {
+                    value: const_definition.value.clone(),
+                },
+            };
+            (name.clone(), model)
+        })
+        .collect();
+
+    Ok(SpecIr {
+        title: spec.info.title.clone(),
+        version: spec.info.version.clone(),
+        operations,
+        models,
+    })
+}
+
+fn get_parameter_ir(property: &PropertyDefinition) -> ParameterIr {
+    ParameterIr {
+        name: property.real_name.clone(),
+        type_name: property.type_name.clone(),
+        required: property.required,
+    }
+}
+
+fn get_body_ir(body: &BodyDefinition) -> BodyIr {
+    let type_name = match body.media_type {
+        TransferMediaType::ApplicationJson(ref type_definition)
+        | TransferMediaType::ApplicationXml(ref type_definition)
+        | TransferMediaType::EventStream(ref type_definition)
+        | TransferMediaType::Ndjson(ref type_definition) => type_definition
+            .as_ref()
+            .map(|type_definition| type_definition.name.clone()),
+        TransferMediaType::TextPlain => Some("String".to_owned()),
+        TransferMediaType::OctetStream => Some("Vec<u8>".to_owned()),
+    };
+    BodyIr {
+        content_type: body.content_type.clone(),
+        type_name,
+    }
+}
@@ -1 +1,5 @@
 pub mod component;
+pub mod ir;
+pub mod security;
+pub mod spec_diff;
+pub mod validation;
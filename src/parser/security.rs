@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use oas3::{
+    spec::{ObjectOrReference, Operation, PathItem, SecurityScheme},
+    Spec,
+};
+use serde::{Deserialize, Serialize};
+
+/// Extension which holds the `security` of the spec or an operation, oas3 doesn't parse it
+const SECURITY_EXTENSION: &str = "opage-security";
+
+/// Security requirement objects of an operation, its own or the global ones of the spec
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationSecurity {
+    /// Alternatives with the names of the schemes which are used together. An empty
+    /// alternative allows requests without credentials
+    pub requirements: Vec<Vec<String>>,
+}
+
+impl OperationSecurity {
+    /// Credentials are optional if an empty requirement (or an empty `security` array)
+    /// allows anonymous requests
+    pub fn is_optional(&self) -> bool {
+        self.requirements.is_empty()
+            || self
+                .requirements
+                .iter()
+                .any(|requirement| requirement.is_empty())
+    }
+}
+
+/// Spec of yaml or json content including the `security` of the spec and its operations
+pub fn parse_spec(spec_content: &str) -> Result<Spec, String> {
+    let mut spec = oas3::from_yaml(spec_content).map_err(|err| err.to_string())?;
+    add_security_requirements(&mut spec, spec_content)?;
+    Ok(spec)
+}
+
+/// Stores the `security` arrays of the spec content as extension of the spec and its
+/// operations, so they are available to [`get_operation_security`]
+pub fn add_security_requirements(spec: &mut Spec, spec_content: &str) -> Result<(), String> {
+    let document: serde_yaml::Value = serde_yaml::from_str(spec_content)
+        .map_err(|err| format!("Failed to read security {}", err))?;
+    let to_json = |security: &serde_yaml::Value| {
+        serde_json::to_value(security).map_err(|err| format!("Invalid security {}", err))
+    };
+
+    if let Some(security) = document.get("security") {
+        spec.extensions
+            .insert(SECURITY_EXTENSION.to_owned(), to_json(security)?);
+    }
+
+    let (paths, document_paths) = match (spec.paths.as_mut(), document.get("paths")) {
+        (Some(paths), Some(document_paths)) => (paths, document_paths),
+        _ => return Ok(()),
+    };
+    for (path, path_item) in paths.iter_mut() {
+        let document_path_item = match document_paths.get(path.as_str()) {
+            Some(document_path_item) => document_path_item,
+            None => continue,
+        };
+        for (method, operation) in get_operations_mut(path_item) {
+            if let Some(security) = document_path_item
+                .get(method)
+                .and_then(|document_operation| document_operation.get("security"))
+            {
+                operation
+                    .extensions
+                    .insert(SECURITY_EXTENSION.to_owned(), to_json(security)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn get_operations_mut(path_item: &mut PathItem) -> Vec<(&'static str, &mut Operation)> {
+    [
+        ("get", path_item.get.as_mut()),
+        ("put", path_item.put.as_mut()),
+        ("post", path_item.post.as_mut()),
+        ("delete", path_item.delete.as_mut()),
+        ("options", path_item.options.as_mut()),
+        ("head", path_item.head.as_mut()),
+        ("patch", path_item.patch.as_mut()),
+        ("trace", path_item.trace.as_mut()),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.map(|operation| (method, operation)))
+    .collect()
+}
+
+/// Security of the operation which overrides the global one, None if neither is declared
+pub fn get_operation_security(
+    spec: &Spec,
+    operation: &Operation,
+) -> Result<Option<OperationSecurity>, String> {
+    let security = match operation
+        .extensions
+        .get(SECURITY_EXTENSION)
+        .or(spec.extensions.get(SECURITY_EXTENSION))
+    {
+        Some(security) => security,
+        None => return Ok(None),
+    };
+    let requirements =
+        serde_json::from_value::<Vec<BTreeMap<String, Vec<String>>>>(security.clone())
+            .map_err(|err| format!("Invalid security requirement {}", err))?;
+    Ok(Some(OperationSecurity {
+        requirements: requirements
+            .into_iter()
+            .map(|requirement| requirement.into_keys().collect())
+            .collect(),
+    }))
+}
+
+/// Security schemes of the components by name
+pub fn get_security_schemes(spec: &Spec) -> Result<BTreeMap<String, SecurityScheme>, String> {
+    let components = match spec.components {
+        Some(ref components) => components,
+        None => return Ok(BTreeMap::new()),
+    };
+    let mut security_schemes = BTreeMap::new();
+    for (name, mut security_scheme) in &components.security_schemes {
+        // Schemes can only reference other schemes of the components
+        for _ in 0..components.security_schemes.len() {
+            let ref_path = match security_scheme {
+                ObjectOrReference::Object(_) => break,
+                ObjectOrReference::Ref { ref_path } => ref_path,
+            };
+            security_scheme = match ref_path
+                .strip_prefix("#/components/securitySchemes/")
+                .and_then(|ref_name| components.security_schemes.get(ref_name))
+            {
+                Some(referenced_scheme) => referenced_scheme,
+                None => return Err(format!("Invalid security scheme reference {}", ref_path)),
+            };
+        }
+        match security_scheme {
+            ObjectOrReference::Object(security_scheme) => {
+                security_schemes.insert(name.clone(), security_scheme.clone())
+            }
+            ObjectOrReference::Ref { ref_path } => {
+                return Err(format!("Cyclic security scheme reference {}", ref_path))
+            }
+        };
+    }
+    Ok(security_schemes)
+}
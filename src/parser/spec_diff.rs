@@ -0,0 +1,427 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::Serialize;
+
+use super::{
+    ir::{BodyIr, ModelIr, OperationIr, ParameterIr, SpecIr},
+    security::OperationSecurity,
+};
+
+/// Impact of a change on users of the generated client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeLevel {
+    /// Docs only, e.g. a changed summary
+    Patch,
+    /// New operations or models
+    Additive,
+    /// Removed operations or fields, type changes and new fields, responses or variants. The
+    /// generated structs and enums are exhaustive, so callers which construct or match them
+    /// break on any new field or variant
+    Breaking,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Change of an operation (`METHOD path`) or model (`model Name`) between two specs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecChange {
+    pub kind: SpecChangeKind,
+    pub subject: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub level: ChangeLevel,
+}
+
+impl SpecChange {
+    fn changed(subject: &str, detail: String, level: ChangeLevel) -> Self {
+        SpecChange {
+            kind: SpecChangeKind::Changed,
+            subject: subject.to_owned(),
+            detail: Some(detail),
+            level,
+        }
+    }
+}
+
+/// `A`dded, `D`eleted or `M`odified like the file diff, breaking changes are marked
+impl Display for SpecChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            SpecChangeKind::Added => "A",
+            SpecChangeKind::Removed => "D",
+            SpecChangeKind::Changed => "M",
+        };
+        write!(f, "{} {}", kind, self.subject)?;
+        if let Some(ref detail) = self.detail {
+            write!(f, ": {}", detail)?;
+        }
+        if self.level == ChangeLevel::Breaking {
+            write!(f, " (breaking)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Highest level of the changes, `None` without changes
+pub fn get_change_level(changes: &[SpecChange]) -> Option<ChangeLevel> {
+    changes.iter().map(|change| change.level).max()
+}
+
+/// Operations are matched by method and path, models by name
+pub fn diff_spec_ir(old: &SpecIr, new: &SpecIr) -> Vec<SpecChange> {
+    let mut changes = vec![];
+
+    let operations = |spec_ir: &SpecIr| {
+        spec_ir
+            .operations
+            .iter()
+            .map(|operation| {
+                (
+                    format!("{} {}", operation.method, operation.path),
+                    operation.clone(),
+                )
+            })
+            .collect::<BTreeMap<String, OperationIr>>()
+    };
+    let old_operations = operations(old);
+    let new_operations = operations(new);
+    for (subject, old_operation) in &old_operations {
+        match new_operations.get(subject) {
+            Some(new_operation) => {
+                diff_operation(subject, old_operation, new_operation, &mut changes)
+            }
+            None => changes.push(SpecChange {
+                kind: SpecChangeKind::Removed,
+                subject: subject.clone(),
+                detail: None,
+                level: ChangeLevel::Breaking,
+            }),
+        }
+    }
+    for subject in new_operations.keys() {
+        if !old_operations.contains_key(subject) {
+            changes.push(SpecChange {
+                kind: SpecChangeKind::Added,
+                subject: subject.clone(),
+                detail: None,
+                level: ChangeLevel::Additive,
+            });
+        }
+    }
+
+    for (name, old_model) in &old.models {
+        let subject = format!("model {}", name);
+        match new.models.get(name) {
+            Some(new_model) => diff_model(&subject, old_model, new_model, &mut changes),
+            None => changes.push(SpecChange {
+                kind: SpecChangeKind::Removed,
+                subject,
+                detail: None,
+                level: ChangeLevel::Breaking,
+            }),
+        }
+    }
+    for name in new.models.keys() {
+        if !old.models.contains_key(name) {
+            changes.push(SpecChange {
+                kind: SpecChangeKind::Added,
+                subject: format!("model {}", name),
+                detail: None,
+                level: ChangeLevel::Additive,
+            });
+        }
+    }
+
+    changes
+}
+
+fn diff_operation(
+    subject: &str,
+    old: &OperationIr,
+    new: &OperationIr,
+    changes: &mut Vec<SpecChange>,
+) {
+    // The operationId names the generated function and module
+    if old.operation_id != new.operation_id {
+        changes.push(SpecChange::changed(
+            subject,
+            format!(
+                "operationId {} renamed to {}",
+                old.operation_id, new.operation_id
+            ),
+            ChangeLevel::Breaking,
+        ));
+    }
+    if old.summary != new.summary {
+        changes.push(SpecChange::changed(
+            subject,
+            "summary changed".to_owned(),
+            ChangeLevel::Patch,
+        ));
+    }
+
+    // Generated functions take credentials depending on the security
+    let old_auth = get_auth_usage(&old.security);
+    let new_auth = get_auth_usage(&new.security);
+    if old_auth != new_auth {
+        changes.push(SpecChange::changed(
+            subject,
+            format!("auth is {}", new_auth),
+            ChangeLevel::Breaking,
+        ));
+    } else if old.security != new.security {
+        changes.push(SpecChange::changed(
+            subject,
+            "security requirements changed".to_owned(),
+            ChangeLevel::Patch,
+        ));
+    }
+
+    diff_parameters(
+        subject,
+        "path parameter",
+        &old.path_parameters,
+        &new.path_parameters,
+        changes,
+    );
+    diff_parameters(
+        subject,
+        "query parameter",
+        &old.query_parameters,
+        &new.query_parameters,
+        changes,
+    );
+
+    if let Some(detail) = diff_body(&old.request_body, &new.request_body) {
+        changes.push(SpecChange::changed(
+            subject,
+            format!("request body {}", detail),
+            ChangeLevel::Breaking,
+        ));
+    }
+
+    for old_response in &old.responses {
+        match new
+            .responses
+            .iter()
+            .find(|new_response| new_response.status == old_response.status)
+        {
+            Some(new_response) => {
+                if let Some(detail) = diff_body(&old_response.body, &new_response.body) {
+                    changes.push(SpecChange::changed(
+                        subject,
+                        format!("response {} body {}", old_response.status, detail),
+                        ChangeLevel::Breaking,
+                    ));
+                }
+            }
+            None => changes.push(SpecChange::changed(
+                subject,
+                format!("response {} removed", old_response.status),
+                ChangeLevel::Breaking,
+            )),
+        }
+    }
+    for new_response in &new.responses {
+        if !old
+            .responses
+            .iter()
+            .any(|old_response| old_response.status == new_response.status)
+        {
+            // Response enums are matched exhaustively
+            changes.push(SpecChange::changed(
+                subject,
+                format!("response {} added", new_response.status),
+                ChangeLevel::Breaking,
+            ));
+        }
+    }
+}
+
+fn get_auth_usage(security: &Option<OperationSecurity>) -> &'static str {
+    match security {
+        None => "not used",
+        Some(security) if security.is_optional() => "optional",
+        Some(_) => "required",
+    }
+}
+
+/// Parameters or properties, new ones are breaking because callers construct the structs (or
+/// pass flat parameters) with all fields
+fn diff_parameters(
+    subject: &str,
+    label: &str,
+    old: &[ParameterIr],
+    new: &[ParameterIr],
+    changes: &mut Vec<SpecChange>,
+) {
+    for old_parameter in old {
+        let new_parameter = match new
+            .iter()
+            .find(|new_parameter| new_parameter.name == old_parameter.name)
+        {
+            Some(new_parameter) => new_parameter,
+            None => {
+                changes.push(SpecChange::changed(
+                    subject,
+                    format!("{} {} removed", label, old_parameter.name),
+                    ChangeLevel::Breaking,
+                ));
+                continue;
+            }
+        };
+        if old_parameter.type_name != new_parameter.type_name {
+            changes.push(SpecChange::changed(
+                subject,
+                format!(
+                    "{} {} type changed from {} to {}",
+                    label, old_parameter.name, old_parameter.type_name, new_parameter.type_name
+                ),
+                ChangeLevel::Breaking,
+            ));
+        }
+        if old_parameter.required != new_parameter.required {
+            changes.push(SpecChange::changed(
+                subject,
+                format!(
+                    "{} {} is {}",
+                    label,
+                    old_parameter.name,
+                    match new_parameter.required {
+                        true => "required",
+                        false => "optional",
+                    }
+                ),
+                ChangeLevel::Breaking,
+            ));
+        }
+    }
+    for new_parameter in new {
+        if !old
+            .iter()
+            .any(|old_parameter| old_parameter.name == new_parameter.name)
+        {
+            changes.push(SpecChange::changed(
+                subject,
+                format!(
+                    "{} {} {} added",
+                    match new_parameter.required {
+                        true => "required",
+                        false => "optional",
+                    },
+                    label,
+                    new_parameter.name
+                ),
+                ChangeLevel::Breaking,
+            ));
+        }
+    }
+}
+
+/// Description of the body change, `None` if the body is unchanged
+fn diff_body(old: &Option<BodyIr>, new: &Option<BodyIr>) -> Option<String> {
+    let body_name = |body: &BodyIr| match body.type_name {
+        Some(ref type_name) => format!("{} ({})", type_name, body.content_type),
+        None => body.content_type.clone(),
+    };
+    match (old, new) {
+        (Some(old), Some(new)) if old != new => Some(format!(
+            "changed from {} to {}",
+            body_name(old),
+            body_name(new)
+        )),
+        (Some(_), Some(_)) | (None, None) => None,
+        (Some(_), None) => Some("removed".to_owned()),
+        (None, Some(_)) => Some("added".to_owned()),
+    }
+}
+
+fn diff_model(subject: &str, old: &ModelIr, new: &ModelIr, changes: &mut Vec<SpecChange>) {
+    match (old, new) {
+        (
+            ModelIr::Struct {
+                properties: old_properties,
+            },
+            ModelIr::Struct {
+                properties: new_properties,
+            },
+        ) => diff_parameters(subject, "property", old_properties, new_properties, changes),
+        (
+            ModelIr::Enum {
+                variants: old_variants,
+            },
+            ModelIr::Enum {
+                variants: new_variants,
+            },
+        ) => {
+            for old_variant in old_variants {
+                if !new_variants.contains(old_variant) {
+                    changes.push(SpecChange::changed(
+                        subject,
+                        format!("variant {} removed", old_variant),
+                        ChangeLevel::Breaking,
+                    ));
+                }
+            }
+            for new_variant in new_variants {
+                if !old_variants.contains(new_variant) {
+                    changes.push(SpecChange::changed(
+                        subject,
+                        format!("variant {} added", new_variant),
+                        ChangeLevel::Breaking,
+                    ));
+                }
+            }
+        }
+        (
+            ModelIr::Primitive {
+                type_name: old_type_name,
+            },
+            ModelIr::Primitive {
+                type_name: new_type_name,
+            },
+        ) => {
+            if old_type_name != new_type_name {
+                changes.push(SpecChange::changed(
+                    subject,
+                    format!("type changed from {} to {}", old_type_name, new_type_name),
+                    ChangeLevel::Breaking,
+                ));
+            }
+        }
+        (ModelIr::Const { value: old_value }, ModelIr::Const { value: new_value }) => {
+            if old_value != new_value {
+                changes.push(SpecChange::changed(
+                    subject,
+                    format!("value changed from {} to {}", old_value, new_value),
+                    ChangeLevel::Breaking,
+                ));
+            }
+        }
+        _ => changes.push(SpecChange::changed(
+            subject,
+            format!(
+                "changed from {} to {}",
+                get_model_kind(old),
+                get_model_kind(new)
+            ),
+            ChangeLevel::Breaking,
+        )),
+    }
+}
+
+fn get_model_kind(model: &ModelIr) -> &'static str {
+    match model {
+        ModelIr::Struct { .. } => "struct",
+        ModelIr::Enum { .. } => "enum",
+        ModelIr::Primitive { .. } => "primitive",
+        ModelIr::Const { .. } => "const",
+    }
+}
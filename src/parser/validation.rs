@@ -0,0 +1,273 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use oas3::{
+    spec::{MediaType, ObjectOrReference, ObjectSchema, Operation, ParameterIn, SchemaTypeSet},
+    Spec,
+};
+
+use crate::{
+    generator::{
+        media_type::{resolve_content_type, REQUEST_CONTENT_TYPES, RESPONSE_CONTENT_TYPES},
+        rust_reqwest_async::path::{
+            conditional::is_conditional_header, idempotency::is_idempotency_key_header,
+            pagination::get_pagination_extension, utils::get_path_parameter_issues,
+        },
+    },
+    utils::config::{Config, OperationIdFallback},
+};
+
+/// Spec construct which the generator can't handle
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub location: String,
+    pub message: String,
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Walks all (not ignored) components and operations and reports every unsupported construct
+pub fn validate_spec(spec: &Spec, config: &Config) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+
+    if let Some(ref components) = spec.components {
+        for (component_name, object_ref) in &components.schemas {
+            if config.ignore.component_ignored(component_name) {
+                continue;
+            }
+            validate_schema(
+                spec,
+                &format!("#/components/schemas/{}", component_name),
+                object_ref,
+                &mut issues,
+            );
+        }
+    }
+
+    if let Some(ref paths) = spec.paths {
+        for (path, path_item) in paths {
+            if config.ignore.path_ignored(path) {
+                continue;
+            }
+            for (method, operation) in path_item.methods() {
+                if config.ignore.operation_ignored(&method, operation) {
+                    continue;
+                }
+                let location = format!("{} {}", method.as_str(), path);
+                if matches!(
+                    method,
+                    reqwest::Method::HEAD | reqwest::Method::OPTIONS | reqwest::Method::TRACE
+                ) {
+                    issues.push(ValidationIssue {
+                        location,
+                        message: "Method is not supported".to_owned(),
+                    });
+                    continue;
+                }
+                validate_operation(spec, config, path, &location, operation, &mut issues);
+            }
+        }
+    }
+
+    issues
+}
+
+fn validate_operation(
+    spec: &Spec,
+    config: &Config,
+    path: &str,
+    location: &str,
+    operation: &Operation,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut push_issue = |message: String| {
+        issues.push(ValidationIssue {
+            location: location.to_owned(),
+            message,
+        })
+    };
+
+    if operation.operation_id.is_none()
+        && config.generator.operation_id_fallback == OperationIdFallback::None
+    {
+        push_issue("Missing operationId".to_owned());
+    }
+
+    if let Some(extension_value) = operation.extensions.get("serverstream") {
+        if !extension_value.is_boolean() {
+            push_issue("x-serverstream must be a boolean".to_owned());
+        }
+    }
+
+    if let Err(err) = get_pagination_extension(operation) {
+        push_issue(err);
+    }
+
+    for parameter_ref in &operation.parameters {
+        match parameter_ref.resolve(spec) {
+            Ok(parameter) => match parameter.location {
+                ParameterIn::Path | ParameterIn::Query => (),
+                ParameterIn::Header
+                    if is_conditional_header(&parameter.name)
+                        || is_idempotency_key_header(&parameter.name) => {}
+                _ => push_issue(format!(
+                    "Parameter {} in {:?} is not supported",
+                    parameter.name, parameter.location
+                )),
+            },
+            Err(err) => push_issue(format!("Failed to resolve parameter {}", err)),
+        }
+    }
+
+    for issue in get_path_parameter_issues(spec, operation, path) {
+        push_issue(issue);
+    }
+
+    let request_content = match operation.request_body {
+        Some(ref request_body) => match request_body.resolve(spec) {
+            Ok(request_body) => request_body.content,
+            Err(err) => {
+                push_issue(format!("Failed to resolve request body {}", err));
+                BTreeMap::new()
+            }
+        },
+        None => BTreeMap::new(),
+    };
+
+    for content_type in request_content.keys() {
+        let content_type_mapping = &config.generator.content_type_mapping;
+        if !REQUEST_CONTENT_TYPES
+            .contains(&resolve_content_type(content_type, content_type_mapping).as_str())
+        {
+            push_issue(format!(
+                "Request Content-Type {} is not supported",
+                content_type
+            ));
+        }
+    }
+
+    let responses = operation.responses(spec);
+    for (response_key, response) in &responses {
+        for content_type in response.content.keys() {
+            let content_type_mapping = &config.generator.content_type_mapping;
+            if !RESPONSE_CONTENT_TYPES
+                .contains(&resolve_content_type(content_type, content_type_mapping).as_str())
+            {
+                push_issue(format!(
+                    "Response {} Content-Type {} is not supported",
+                    response_key, content_type
+                ));
+            }
+        }
+    }
+
+    validate_content_schemas(
+        spec,
+        &format!("{} requestBody", location),
+        &request_content,
+        issues,
+    );
+    for (response_key, response) in &responses {
+        validate_content_schemas(
+            spec,
+            &format!("{} responses/{}", location, response_key),
+            &response.content,
+            issues,
+        );
+    }
+}
+
+fn validate_content_schemas(
+    spec: &Spec,
+    location: &str,
+    content: &BTreeMap<String, MediaType>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for (content_type, media_type) in content {
+        if let Some(ref schema_ref) = media_type.schema {
+            validate_schema(
+                spec,
+                &format!("{} {}", location, content_type),
+                schema_ref,
+                issues,
+            );
+        }
+    }
+}
+
+/// Referenced schemas are validated as components, only inline schemas are walked
+fn validate_schema(
+    spec: &Spec,
+    location: &str,
+    schema_ref: &ObjectOrReference<ObjectSchema>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let schema = match schema_ref {
+        ObjectOrReference::Ref { ref_path } => {
+            if let Err(err) = schema_ref.resolve(spec) {
+                issues.push(ValidationIssue {
+                    location: location.to_owned(),
+                    message: format!("Failed to resolve {} {}", ref_path, err),
+                });
+            }
+            return;
+        }
+        ObjectOrReference::Object(schema) => schema,
+    };
+
+    let mut push_issue = |message: &str| {
+        issues.push(ValidationIssue {
+            location: location.to_owned(),
+            message: message.to_owned(),
+        })
+    };
+
+    if !schema.all_of.is_empty() {
+        push_issue("allOf is not supported");
+    }
+
+    match schema.schema_type {
+        Some(SchemaTypeSet::Multiple(_)) => push_issue("Multiple types are not supported"),
+        Some(SchemaTypeSet::Single(oas3::spec::SchemaType::Null)) => {
+            push_issue("Type null is not supported")
+        }
+        Some(SchemaTypeSet::Single(oas3::spec::SchemaType::Array)) if schema.items.is_none() => {
+            push_issue("Array has no item type")
+        }
+        _ => (),
+    }
+
+    for (property_name, property_ref) in &schema.properties {
+        validate_schema(
+            spec,
+            &format!("{}/properties/{}", location, property_name),
+            property_ref,
+            issues,
+        );
+    }
+
+    if let Some(ref items) = schema.items {
+        validate_schema(spec, &format!("{}/items", location), items, issues);
+    }
+
+    for (index, any_of_ref) in schema.any_of.iter().enumerate() {
+        validate_schema(
+            spec,
+            &format!("{}/anyOf/{}", location, index),
+            any_of_ref,
+            issues,
+        );
+    }
+
+    for (index, one_of_ref) in schema.one_of.iter().enumerate() {
+        validate_schema(
+            spec,
+            &format!("{}/oneOf/{}", location, index),
+            one_of_ref,
+            issues,
+        );
+    }
+}
@@ -1,41 +1,405 @@
-use std::{fs::File, path::Path};
+use std::{collections::BTreeMap, fs::File, path::Path};
 
 use serde::Deserialize;
 
-use super::{name_mapping::NameMapping, spec_ignore::SpecIgnore};
+use super::{extensions::ExtensionCallback, name_mapping::NameMapping, spec_ignore::SpecIgnore};
 
+/// Dependency of the generated Cargo.toml, a version or a table like in Cargo.toml
 #[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum DependencyConfig {
+    Version(String),
+    Detailed {
+        version: Option<String>,
+        path: Option<String>,
+        #[serde(rename = "default-features")]
+        default_features: Option<bool>,
+        #[serde(default)]
+        features: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
 pub struct ProjectMetadata {
     pub name: String,
     pub version: String,
+    pub edition: String,
+    pub license: Option<String>,
+    pub repository: Option<String>,
+    /// Overrides the version (and adds features) of dependencies of the generated crate,
+    /// other dependencies are added, e.g. `{"reqwest": {"default-features": false, "features": ["rustls-tls"]}}`
+    pub dependencies: BTreeMap<String, DependencyConfig>,
 }
 
 impl ProjectMetadata {
     pub fn new() -> Self {
         ProjectMetadata {
             name: String::new(),
-            version: String::new(),
+            version: String::from("0.0.0"),
+            edition: String::from("2021"),
+            license: None,
+            repository: None,
+            dependencies: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for ProjectMetadata {
+    fn default() -> Self {
+        ProjectMetadata::new()
+    }
+}
+
+/// Naming of operations without operationId
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationIdFallback {
+    /// Operations without operationId are skipped
+    #[default]
+    None,
+    /// Derive the name from method and path, e.g. `GET /users/{id}` -> `get_users_by_id`
+    MethodPath,
+}
+
+/// Crates of a generated client
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputLayout {
+    /// Single crate with objects and paths
+    #[default]
+    Crate,
+    /// Workspace with a `<name>-models` crate (objects only, without reqwest) and a
+    /// `<name>-client` crate (paths) which re-exports the objects
+    Workspace,
+}
+
+/// Response compression which generated clients decompress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseCompression {
+    Gzip,
+    Deflate,
+    Brotli,
+}
+
+impl ResponseCompression {
+    /// Value of the `Content-Encoding` header
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            ResponseCompression::Gzip => "gzip",
+            ResponseCompression::Deflate => "deflate",
+            ResponseCompression::Brotli => "br",
+        }
+    }
+
+    /// Reqwest feature and `ClientBuilder` method of the compression
+    pub fn reqwest_feature(&self) -> &'static str {
+        match self {
+            ResponseCompression::Gzip => "gzip",
+            ResponseCompression::Deflate => "deflate",
+            ResponseCompression::Brotli => "brotli",
+        }
+    }
+}
+
+/// Version of the generated crate after spec changes since the last generation
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionBump {
+    #[default]
+    Off,
+    /// Reports the semver version for the changes
+    Suggest,
+    /// Sets the version of the existing Cargo.toml to the suggested one
+    Bump,
+}
+
+/// Kind of project which is generated
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratorTarget {
+    /// Async reqwest client
+    #[default]
+    RustReqwestAsync,
+    /// Axum server which answers every operation with its example response
+    AxumMock,
+    /// Axum server with a trait the user implements per operation
+    AxumServer,
+    /// Python package with pydantic models and async httpx functions
+    PythonHttpx,
+    /// Typescript package with interfaces and typed fetch functions
+    TypescriptFetch,
+}
+
+impl GeneratorTarget {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "rust_reqwest_async" => Ok(GeneratorTarget::RustReqwestAsync),
+            "axum_mock" => Ok(GeneratorTarget::AxumMock),
+            "axum_server" => Ok(GeneratorTarget::AxumServer),
+            "python_httpx" => Ok(GeneratorTarget::PythonHttpx),
+            "typescript_fetch" => Ok(GeneratorTarget::TypescriptFetch),
+            _ => Err(format!("Unknown generator target {}", name)),
         }
     }
 }
 
+/// Messages of this and more severe levels are logged
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Off,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn to_level_filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Off => log::LevelFilter::Off,
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// Derives of generated types in addition to `Serialize` and `Deserialize` of models
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct DeriveConfig {
+    /// Structs and enums of the objects module
+    pub models: Vec<String>,
+    /// Response enums of operations
+    pub responses: Vec<String>,
+    /// Path and query parameter structs of operations
+    pub parameters: Vec<String>,
+}
+
+impl Default for DeriveConfig {
+    fn default() -> Self {
+        let derives = |names: &[&str]| names.iter().map(|&name| name.to_owned()).collect();
+        DeriveConfig {
+            models: derives(&["Debug", "Clone", "PartialEq"]),
+            responses: derives(&["Debug"]),
+            parameters: derives(&["Debug", "Clone", "PartialEq"]),
+        }
+    }
+}
+
+/// Serde functions of a property, paths like in `#[serde(with = "...")]`
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct PropertySerde {
+    pub with: Option<String>,
+    pub serialize_with: Option<String>,
+    pub deserialize_with: Option<String>,
+}
+
+impl PropertySerde {
+    /// Contents of the `#[serde(..)]` attributes. Optional properties with a custom
+    /// deserializer are `default`, so missing fields are still `None`
+    pub fn attributes(&self, required: bool) -> Vec<String> {
+        let mut attributes = [
+            ("with", &self.with),
+            ("serialize_with", &self.serialize_with),
+            ("deserialize_with", &self.deserialize_with),
+        ]
+        .iter()
+        .filter_map(|(name, path)| path.as_ref().map(|path| format!("{} = {:?}", name, path)))
+        .collect::<Vec<String>>();
+        if !required && (self.with.is_some() || self.deserialize_with.is_some()) {
+            attributes.push("default".to_owned());
+        }
+        attributes
+    }
+}
+
+/// Region and service name of AWS Signature Version 4 signatures
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AwsSigV4Config {
+    pub region: String,
+    /// Signing name of the service, e.g. `execute-api` for API Gateway
+    pub service: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct GeneratorConfig {
+    #[serde(default)]
+    pub target: GeneratorTarget,
+    /// Adds a `RequestOptions` parameter (retries, backoff, timeout) to every http function
+    #[serde(default)]
+    pub request_options: bool,
+    #[serde(default)]
+    pub operation_id_fallback: OperationIdFallback,
+    /// Request bodies of these methods (e.g. GET, DELETE) are not generated for servers which reject them
+    #[serde(default)]
+    pub skip_request_body_methods: Vec<String>,
+    /// Emits a wiremock based integration test per http operation into `tests/operations`
+    #[serde(default)]
+    pub generate_tests: bool,
+    /// readOnly properties are not serialized and writeOnly properties are not deserialized
+    #[serde(default)]
+    pub skip_read_write_only: bool,
+    /// Structs get a `validate()` for minLength, maxLength, pattern, minimum, maximum, minItems
+    /// and maxItems, which http functions call on request bodies
+    #[serde(default)]
+    pub validation: bool,
+    /// Handles content types of the spec like supported ones, e.g. `application/hal+json` ->
+    /// `application/json` or `text/csv` -> `text/plain`
+    #[serde(default)]
+    pub content_type_mapping: BTreeMap<String, String>,
+    /// `UndefinedResponse` holds status, headers and body which are read eagerly instead of the
+    /// `reqwest::Response`
+    #[serde(default)]
+    pub capture_undefined_response: bool,
+    /// Response enums get an `into_result()` which returns successful (2xx) responses as `Ok`
+    /// of a success enum and all others as `Err` of an error enum
+    #[serde(default)]
+    pub response_results: bool,
+    #[serde(default)]
+    pub derives: DeriveConfig,
+    /// Models reject unknown fields on deserialization to detect contract drift
+    #[serde(default)]
+    pub deny_unknown_fields: bool,
+    /// Adds a cargo feature per tag (all enabled by default) which gates the path modules of
+    /// its operations and the objects they use
+    #[serde(default)]
+    pub tag_features: bool,
+    /// Adds an `ApiClient` with a service struct per tag, e.g. `api_client.users().get_user(..)`,
+    /// whose methods call the http functions of the operations
+    #[serde(default)]
+    pub tag_services: bool,
+    /// Adds a trait per service of `tag_services`, e.g. `UsersApi`, which the service
+    /// implements, so code using the client can be tested with another implementation
+    #[serde(default)]
+    pub service_traits: bool,
+    /// Service traits get a `mockall` mock, e.g. `MockUsersApi`, if the `mock` feature of the
+    /// generated crate is enabled
+    #[serde(default)]
+    pub service_mocks: bool,
+    #[serde(default)]
+    pub layout: OutputLayout,
+    /// Http functions log method, url, status and elapsed time with `log::debug!` if the
+    /// `logging` feature of the generated crate is enabled
+    #[serde(default)]
+    pub request_logging: bool,
+    /// Log level of the generator, overridden by `-v`, `-vv`, `-vvv` and `--quiet`
+    #[serde(default)]
+    pub log_level: LogLevel,
+    /// Directory with minijinja templates which override the embedded `base.rs.jinja`,
+    /// `http.rs.jinja` and `cargo.toml.jinja`
+    #[serde(default)]
+    pub template_dir: Option<String>,
+    /// `x-` extensions of operations and schemas which are passed to templates and the
+    /// extension callback
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Http functions of operations with at most this many path and query parameters take
+    /// them as arguments instead of `PathParameters`/`QueryParameters` structs
+    #[serde(default)]
+    pub flat_parameters: Option<usize>,
+    /// Operation ids which get a `_batch` function calling them concurrently, like `x-batch`
+    #[serde(default)]
+    pub batch_operations: Vec<String>,
+    /// Servers by operation id for their `{OPERATION}_SERVER` constant, which otherwise holds
+    /// the server of the operation or its path in the spec, e.g. `{"listRobots": "https://read.example.com"}`
+    #[serde(default)]
+    pub operation_servers: BTreeMap<String, String>,
+    /// Operation ids which send an `Idempotency-Key` header, like operations with this header
+    /// parameter
+    #[serde(default)]
+    pub idempotency_key_operations: Vec<String>,
+    /// Http functions of operations with security requirements (their own or the global ones)
+    /// take an `auth` parameter with the credentials, `Option` if the requirements allow
+    /// anonymous requests
+    #[serde(default)]
+    pub security: bool,
+    /// Signs every request with AWS Signature Version 4 via `aws_sigv4::sign_request` before it
+    /// is sent, e.g. for IAM authorized API Gateway APIs. Unsignable requests are not sent
+    #[serde(default)]
+    pub aws_sigv4: Option<AwsSigV4Config>,
+    /// Responses with a larger `Content-Length` (bytes) are not buffered but returned unread as
+    /// `BodyTooLarge`
+    #[serde(default)]
+    pub max_response_body_size: Option<u64>,
+    /// Serde functions of struct properties by `Struct.property` (the property by its name in
+    /// the spec or in rust), e.g. `{"Robot.created_at": {"with": "crate::serde_helpers::ms"}}`
+    #[serde(default)]
+    pub property_serde: BTreeMap<String, PropertySerde>,
+    /// Enables the reqwest features of these compressions, which `client::client_builder()`
+    /// of the generated crate decompresses
+    #[serde(default)]
+    pub response_compression: Vec<ResponseCompression>,
+    /// Adds `client::ApiClientBuilder`, which builds the client with a client certificate,
+    /// custom root certificates and proxies over rustls
+    #[serde(default)]
+    pub client_tls: bool,
+    /// Unix domain socket of the api, e.g. `/var/run/docker.sock`. Adds clients which send
+    /// their requests over a socket to the `unix-socket` feature of the generated crate
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    /// Adds `client::ClientOptions` with the http and https proxies of the client, which
+    /// default to the proxy environment variables
+    #[serde(default)]
+    pub client_options: bool,
+    /// Stores the parsed spec in `.opage-ir.json` and suggests or bumps the crate version by
+    /// the changes since the previous generation
+    #[serde(default)]
+    pub version_bump: VersionBump,
+    /// Adds the spec changes since the previous generation to `CHANGELOG.md`, with the IR
+    /// snapshot of `version_bump`
+    #[serde(default)]
+    pub changelog: bool,
+    /// Appends code to operations and objects with extensions, only set by library users
+    #[serde(skip)]
+    pub extension_callback: Option<ExtensionCallback>,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct Config {
+    #[serde(default)]
     pub project_metadata: ProjectMetadata,
+    #[serde(default)]
     pub name_mapping: NameMapping,
+    #[serde(default)]
     pub ignore: SpecIgnore,
+    #[serde(default)]
+    pub generator: GeneratorConfig,
 }
 
 impl Config {
+    /// Reads a json, yaml (.yaml/.yml) or toml (.toml) config detected by its extension
     pub fn from(config_file_path: &Path) -> Result<Self, String> {
-        let file = match File::open(config_file_path) {
-            Ok(file) => file,
-            Err(err) => return Err(err.to_string()),
-        };
-        match serde_json::from_reader(file) {
-            Ok(config_object) => Ok(config_object),
-            Err(err) => return Err(err.to_string()),
-        }
+        let extension = config_file_path
+            .extension()
+            .and_then(|extension| extension.to_str());
+
+        let config: Config = match extension {
+            Some("yaml") | Some("yml") => {
+                let file = File::open(config_file_path).map_err(|err| err.to_string())?;
+                serde_yaml::from_reader(file).map_err(|err| err.to_string())
+            }
+            Some("toml") => {
+                let content =
+                    std::fs::read_to_string(config_file_path).map_err(|err| err.to_string())?;
+                toml::from_str(&content).map_err(|err| err.to_string())
+            }
+            _ => {
+                let file = File::open(config_file_path).map_err(|err| err.to_string())?;
+                serde_json::from_reader(file).map_err(|err| err.to_string())
+            }
+        }?;
+
+        config.ignore.validate()?;
+        Ok(config)
     }
 
     pub fn new() -> Self {
@@ -43,6 +407,7 @@ impl Config {
             project_metadata: ProjectMetadata::new(),
             name_mapping: NameMapping::new(),
             ignore: SpecIgnore::new(),
+            generator: GeneratorConfig::default(),
         }
     }
 }
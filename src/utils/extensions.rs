@@ -0,0 +1,92 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use super::config::GeneratorConfig;
+
+/// Kind of the generated item whose extensions are passed to the callback
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExtensionTarget {
+    Operation,
+    Object,
+}
+
+/// Generated item with its configured extensions
+#[derive(Debug)]
+pub struct ExtensionContext<'a> {
+    pub target: ExtensionTarget,
+    /// Function name of operations, struct name of objects
+    pub name: &'a str,
+    /// Extension values by their name in the spec (e.g. `x-rate-limit`)
+    pub extensions: &'a BTreeMap<String, serde_json::Value>,
+    /// Output of the embedded template
+    pub content: &'a str,
+}
+
+pub type ExtensionFn = dyn Fn(&ExtensionContext) -> Result<Option<String>, String> + Send + Sync;
+
+/// Library callback which returns code to append to a generated item, called for items with
+/// at least one configured extension
+#[derive(Clone)]
+pub struct ExtensionCallback(pub Arc<ExtensionFn>);
+
+impl ExtensionCallback {
+    pub fn new(
+        callback: impl Fn(&ExtensionContext) -> Result<Option<String>, String> + Send + Sync + 'static,
+    ) -> Self {
+        ExtensionCallback(Arc::new(callback))
+    }
+}
+
+impl std::fmt::Debug for ExtensionCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ExtensionCallback")
+    }
+}
+
+impl PartialEq for ExtensionCallback {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Values of the configured extensions by their name in the spec. oas3 strips the `x-`
+/// prefix, configured names are accepted with and without it
+pub fn collect_extensions(
+    generator_config: &GeneratorConfig,
+    extensions: &BTreeMap<String, serde_json::Value>,
+) -> BTreeMap<String, serde_json::Value> {
+    generator_config
+        .extensions
+        .iter()
+        .map(|name| name.strip_prefix("x-").unwrap_or(name))
+        .filter_map(|name| {
+            extensions
+                .get(name)
+                .map(|value| (format!("x-{}", name), value.clone()))
+        })
+        .collect()
+}
+
+/// Appends the code of the extension callback to `content`
+pub fn apply_extension_callback(
+    generator_config: &GeneratorConfig,
+    target: ExtensionTarget,
+    name: &str,
+    extensions: &BTreeMap<String, serde_json::Value>,
+    content: String,
+) -> Result<String, String> {
+    let callback = match generator_config.extension_callback {
+        Some(ref callback) if !extensions.is_empty() => callback,
+        _ => return Ok(content),
+    };
+    let extension_code = (callback.0)(&ExtensionContext {
+        target,
+        name,
+        extensions,
+        content: &content,
+    })
+    .map_err(|err| format!("Extension callback failed for {} {}", name, err))?;
+    Ok(match extension_code {
+        Some(extension_code) => format!("{}\n{}", content, extension_code),
+        None => content,
+    })
+}
@@ -1,17 +1,94 @@
-use log::{Level, Metadata, Record};
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
 
-pub struct Logger;
+use log::{Level, LevelFilter, Metadata, Record};
+
+thread_local! {
+    static CONTEXT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Prints messages up to its level to stderr and collects warnings and errors for the report
+pub struct Logger {
+    level: AtomicUsize,
+    warnings: Mutex<Vec<String>>,
+}
+
+impl Logger {
+    pub const fn new() -> Self {
+        Logger {
+            level: AtomicUsize::new(LevelFilter::Trace as usize),
+            warnings: Mutex::new(vec![]),
+        }
+    }
+
+    /// Messages up to `level` are printed, warnings and errors are always collected
+    pub fn set_level(&self, level: LevelFilter) {
+        self.level.store(level as usize, Ordering::Relaxed);
+        log::set_max_level(level.max(LevelFilter::Warn));
+    }
+
+    /// Warnings and errors logged so far
+    pub fn take_warnings(&self) -> Vec<String> {
+        match self.warnings.lock() {
+            Ok(mut warnings) => std::mem::take(&mut *warnings),
+            Err(_) => vec![],
+        }
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Logger::new()
+    }
+}
 
 impl log::Log for Logger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= Level::Trace
+        metadata.level() <= log::max_level()
     }
 
     fn log(&self, record: &Record) {
-        if self.enabled(record.metadata()) {
-            println!("[{}] {}", record.level(), record.args());
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = CONTEXT.with_borrow(|context| match context {
+            Some(context) => format!("{}: {}", context, record.args()),
+            None => record.args().to_string(),
+        });
+        if record.level() as usize <= self.level.load(Ordering::Relaxed) {
+            eprintln!("[{}] {}", record.level(), message);
+        }
+        if record.level() <= Level::Warn {
+            if let Ok(mut warnings) = self.warnings.lock() {
+                warnings.push(message);
+            }
         }
     }
 
     fn flush(&self) {}
 }
+
+/// Operation or component which is generated by the current thread. Messages logged while the
+/// context is alive are prefixed with it, the previous context is restored on drop
+pub struct LogContext {
+    previous: Option<String>,
+}
+
+impl LogContext {
+    pub fn enter(context: String) -> Self {
+        LogContext {
+            previous: CONTEXT.replace(Some(context)),
+        }
+    }
+}
+
+impl Drop for LogContext {
+    fn drop(&mut self) {
+        CONTEXT.set(self.previous.take());
+    }
+}
@@ -1,4 +1,8 @@
 pub mod config;
+pub mod extensions;
 pub mod log;
 pub mod name_mapping;
+pub mod output;
+pub mod report;
 pub mod spec_ignore;
+pub mod template_override;
@@ -2,14 +2,171 @@ use convert_case::Casing;
 use log::trace;
 use reqwest::StatusCode;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
 pub struct NameMapping {
     pub struct_mapping: HashMap<String, String>,
     pub property_mapping: HashMap<String, String>,
     pub module_mapping: HashMap<String, String>,
     pub status_code_mapping: HashMap<String, String>,
+    /// Enum variants (response status and content type variants, anyOf/oneOf variants) by
+    /// their path, e.g. `/users/{id}/GetUserResponseType/Ok`
+    pub variant_mapping: HashMap<String, String>,
+    /// Operation functions by operationId
+    pub function_mapping: HashMap<String, String>,
+    pub type_mapping: TypeMapping,
+    /// Rust types which replace components, by ref path (`#/components/schemas/Money`) or
+    /// component name (`Money`)
+    pub type_overrides: BTreeMap<String, TypeOverride>,
+}
+
+/// Rust type of an overridden component
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum TypeOverride {
+    /// Fully qualified type which is imported, e.g. `rust_decimal::Decimal`
+    Path(String),
+    /// Type as it is written in code and the import which brings it into scope
+    Detailed {
+        #[serde(rename = "type")]
+        type_name: String,
+        import: Option<String>,
+    },
+}
+
+impl TypeOverride {
+    /// Type name in code, generic types of the path form are written as they are
+    pub fn type_name(&self) -> String {
+        match self {
+            TypeOverride::Path(path) => match self.import() {
+                Some((_, name)) => name,
+                None => path.clone(),
+            },
+            TypeOverride::Detailed { type_name, .. } => type_name.clone(),
+        }
+    }
+
+    /// Module path and name of the `use` statement
+    pub fn import(&self) -> Option<(String, String)> {
+        let import = match self {
+            TypeOverride::Path(path) if path.contains('<') => return None,
+            TypeOverride::Path(path) => path,
+            TypeOverride::Detailed { import, .. } => import.as_ref()?,
+        };
+        import
+            .rsplit_once("::")
+            .map(|(path, name)| (path.to_owned(), name.to_owned()))
+    }
+}
+
+/// String formats which are generated as validated types instead of `String`
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct TypeMapping {
+    /// `format: uri` as `url::Url`
+    pub uri: bool,
+    /// `format: email` as `formats::Email`
+    pub email: bool,
+    /// `format: hostname` as `formats::Hostname`
+    pub hostname: bool,
+}
+
+impl TypeMapping {
+    /// Type of a string with this format, None if it stays a `String`
+    pub fn get_format_type(&self, format: &str) -> Option<&'static str> {
+        match format {
+            "uri" if self.uri => Some("url::Url"),
+            "email" if self.email => Some("crate::formats::Email"),
+            "hostname" if self.hostname => Some("crate::formats::Hostname"),
+            _ => None,
+        }
+    }
+
+    /// The `formats` module is generated
+    pub fn uses_formats_module(&self) -> bool {
+        self.email || self.hostname
+    }
+}
+
+/// Strict and reserved keywords which can't be used as identifiers
+const RUST_KEYWORDS: [&str; 52] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Ascii words of a name before case conversion. Letters are transliterated (`größe` ->
+/// `grosse`), symbols separate words (`user-name[]` -> `user name`) and emojis become words
+/// of their own (`🚀launch` -> `rocket launch`). Characters without transliteration are dropped
+fn transliterate_name(name: &str) -> String {
+    let mut transliterated_name = String::new();
+    for character in name.chars() {
+        if character.is_ascii_alphanumeric() || character == '_' {
+            transliterated_name.push(character);
+            continue;
+        }
+        let transliteration = match character.is_ascii() {
+            true => "",
+            false => deunicode::deunicode_char(character).unwrap_or_default(),
+        };
+        let words = transliteration
+            .chars()
+            .map(|character| match character.is_ascii_alphanumeric() {
+                true => character,
+                false => ' ',
+            })
+            .collect::<String>();
+        match character.is_alphanumeric() {
+            true => transliterated_name += &words,
+            false => transliterated_name += &format!(" {} ", words),
+        }
+    }
+    transliterated_name
+}
+
+/// Valid identifier of a converted name. Invalid characters are replaced by `_`, names
+/// starting with a digit get a `_` prefix and keywords a `_` suffix (`type` -> `type_`).
+/// Empty if the name has no letters or digits
+pub fn sanitize_identifier(name: &str) -> String {
+    let mut identifier = name
+        .chars()
+        .map(|character| match character.is_ascii_alphanumeric() {
+            true => character,
+            false => '_',
+        })
+        .collect::<String>();
+    if identifier.trim_matches('_').is_empty() {
+        return String::new();
+    }
+    if identifier.starts_with(|character: char| character.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if RUST_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Identifier of a name in a case. Names without any usable character are named after their
+/// code points, so they stay distinct and don't change between generations (`[]` ->
+/// `value_5b_5d` or `Value5b5d`)
+fn to_identifier(name: &str, case: convert_case::Case) -> String {
+    let identifier = sanitize_identifier(&transliterate_name(name).to_case(case));
+    if !identifier.is_empty() {
+        return identifier;
+    }
+    let code_points = name
+        .chars()
+        .map(|character| format!("{:x}", character as u32))
+        .collect::<Vec<String>>();
+    match case {
+        convert_case::Case::Pascal => format!("Value{}", code_points.join("")),
+        _ => format!("value_{}", code_points.join("_")),
+    }
 }
 
 fn path_to_string(path: &Vec<String>, token_name: &str) -> String {
@@ -28,9 +185,24 @@ impl NameMapping {
             property_mapping: HashMap::new(),
             struct_mapping: HashMap::new(),
             status_code_mapping: HashMap::new(),
+            variant_mapping: HashMap::new(),
+            function_mapping: HashMap::new(),
+            type_mapping: TypeMapping::default(),
+            type_overrides: BTreeMap::new(),
         }
     }
 
+    /// Override of the schema a `$ref` points to
+    pub fn get_type_override(&self, ref_path: &str) -> Option<&TypeOverride> {
+        let component_name = ref_path.strip_prefix("#/components/schemas/");
+        self.type_overrides
+            .iter()
+            .find(|(schema, _)| {
+                schema.as_str() == ref_path || Some(schema.as_str()) == component_name
+            })
+            .map(|(_, type_override)| type_override)
+    }
+
     pub fn name_to_struct_name(&self, path: &Vec<String>, name: &str) -> String {
         let converted_name = name.to_case(convert_case::Case::Pascal);
         let path_str = path_to_string(path, &converted_name);
@@ -38,7 +210,19 @@ impl NameMapping {
         trace!("name_to_struct_name {}", path_str);
         match self.struct_mapping.get(&path_str) {
             Some(name) => name.clone(),
-            None => converted_name,
+            None => to_identifier(name, convert_case::Case::Pascal),
+        }
+    }
+
+    /// Variants without variant mapping are mapped like structs
+    pub fn name_to_variant_name(&self, path: &Vec<String>, name: &str) -> String {
+        let converted_name = name.to_case(convert_case::Case::Pascal);
+        let path_str = path_to_string(path, &converted_name);
+
+        trace!("name_to_variant_name {}", path_str);
+        match self.variant_mapping.get(&path_str) {
+            Some(name) => name.clone(),
+            None => self.name_to_struct_name(path, name),
         }
     }
 
@@ -48,17 +232,79 @@ impl NameMapping {
         trace!("name_to_property_name {}", path_str);
         match self.property_mapping.get(&path_str) {
             Some(name) => name.clone(),
-            None => converted_name,
+            None => to_identifier(name, convert_case::Case::Snake),
         }
     }
 
+    /// Module of a name, mapped by the original name (e.g. `getUser`) or the converted one
+    /// (`get_user`)
     pub fn name_to_module_name(&self, name: &str) -> String {
         let converted_name = name.to_case(convert_case::Case::Snake);
 
-        match self.module_mapping.get(&converted_name) {
+        match self
+            .module_mapping
+            .get(name)
+            .or_else(|| self.module_mapping.get(&converted_name))
+        {
             Some(name) => name.clone(),
-            None => converted_name,
+            None => to_identifier(name, convert_case::Case::Snake),
+        }
+    }
+
+    /// Function of an operation, functions without function mapping are named like their module
+    pub fn name_to_function_name(&self, operation_id: &str) -> String {
+        match self.function_mapping.get(operation_id) {
+            Some(name) => name.clone(),
+            None => self.name_to_module_name(operation_id),
+        }
+    }
+
+    /// Errors for `(name, description)` entries which are converted to the same module name
+    /// and would overwrite each other. Each one suggests the `module_mapping` entry to add
+    pub fn get_module_name_collisions(&self, names: &[(String, String)]) -> Vec<String> {
+        let mut module_names: BTreeMap<String, Vec<&(String, String)>> = BTreeMap::new();
+        for entry in names {
+            module_names
+                .entry(self.name_to_module_name(&entry.0))
+                .or_default()
+                .push(entry);
+        }
+
+        module_names
+            .iter()
+            .filter(|(_, entries)| entries.len() > 1)
+            .map(|(module_name, entries)| {
+                format!(
+                    "{} are all generated as module {}. Add a module_mapping entry for all but one of them, e.g. \"module_mapping\": {{\"{}\": \"{}_2\"}}",
+                    entries
+                        .iter()
+                        .map(|(_, description)| description.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(", "),
+                    module_name,
+                    entries[entries.len() - 1].0,
+                    module_name
+                )
+            })
+            .collect()
+    }
+
+    /// Operation id derived from method and path (`GET /users/{id}` -> `get_users_by_id`)
+    pub fn operation_id_from_path(&self, method: &str, path: &str) -> String {
+        let mut name_parts = vec![method.to_lowercase()];
+        for path_component in path
+            .split("/")
+            .filter(|path_component| !path_component.is_empty())
+        {
+            match path_component
+                .strip_prefix("{")
+                .and_then(|parameter| parameter.strip_suffix("}"))
+            {
+                Some(parameter) => name_parts.push(format!("by_{}", parameter)),
+                None => name_parts.push(path_component.to_owned()),
+            }
         }
+        name_parts.join("_").to_case(convert_case::Case::Snake)
     }
 
     pub fn status_code_to_canonical_name(&self, status_code: StatusCode) -> Result<String, String> {
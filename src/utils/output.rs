@@ -0,0 +1,429 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    fs::{self, File},
+    path::Path,
+    sync::Mutex,
+};
+
+use log::{info, trace, warn};
+use serde::{Deserialize, Serialize};
+
+use super::report::OutputStats;
+
+const MANIFEST_FILE_NAME: &str = ".opage-manifest.json";
+const KEEP_START_MARKER: &str = "// <opage:keep>";
+const KEEP_END_MARKER: &str = "// </opage:keep>";
+
+/// Content hashes of all files written by the last generation
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct GenerationManifest {
+    pub files: BTreeMap<String, String>,
+}
+
+impl GenerationManifest {
+    pub fn load(output_dir: &str) -> Self {
+        let manifest_path = Path::new(output_dir).join(MANIFEST_FILE_NAME);
+        match File::open(&manifest_path) {
+            Ok(manifest_file) => match serde_json::from_reader(manifest_file) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    warn!("Ignoring invalid manifest {:?} {}", manifest_path, err);
+                    GenerationManifest::default()
+                }
+            },
+            Err(_) => GenerationManifest::default(),
+        }
+    }
+}
+
+/// FNV-1a hash which (unlike DefaultHasher) is stable across rust versions
+pub fn content_hash(content: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// User code between `// <opage:keep>` and `// </opage:keep>` of an existing file.
+/// The region is placed after its anchor, the nearest line in front of it which isn't only
+/// punctuation (e.g. the signature of an item instead of a closing `}`), and the closing lines
+/// which followed the anchor
+struct ProtectedRegion {
+    /// Anchor without whitespace, so it is found in formatted and unformatted content
+    anchor: Option<String>,
+    /// Lines of only punctuation between anchor and region
+    closing_lines: usize,
+    /// Blank lines between anchor and region are kept
+    after_blank_line: bool,
+    content: String,
+}
+
+/// Line without whitespace if it can anchor a region. Lines of only punctuation like `}`
+/// are too frequent to find the place of a region again
+fn get_anchor(line: &str) -> Option<String> {
+    match line.chars().any(char::is_alphanumeric) {
+        true => Some(line.split_whitespace().collect()),
+        false => None,
+    }
+}
+
+fn count_anchors(content: &str) -> BTreeMap<String, usize> {
+    let mut anchor_counts = BTreeMap::new();
+    for anchor in content.lines().filter_map(get_anchor) {
+        *anchor_counts.entry(anchor).or_default() += 1;
+    }
+    anchor_counts
+}
+
+/// Protected regions of a file in order
+fn get_protected_regions(content: &str) -> Result<Vec<ProtectedRegion>, String> {
+    let mut regions = vec![];
+    let mut region: Option<ProtectedRegion> = None;
+    let mut anchor = None;
+    let mut closing_lines = 0;
+    let mut after_blank_line = false;
+
+    for (line_index, line) in content.split_inclusive('\n').enumerate() {
+        let trimmed_line = line.trim();
+        match (trimmed_line, region.as_mut()) {
+            (KEEP_START_MARKER, Some(_)) => {
+                return Err(format!(
+                    "Nested {} in line {}",
+                    KEEP_START_MARKER,
+                    line_index + 1
+                ))
+            }
+            (KEEP_START_MARKER, None) => {
+                region = Some(ProtectedRegion {
+                    anchor: anchor.clone(),
+                    closing_lines,
+                    after_blank_line,
+                    content: line.to_owned(),
+                })
+            }
+            (KEEP_END_MARKER, None) => {
+                return Err(format!(
+                    "{} without {} in line {}",
+                    KEEP_END_MARKER,
+                    KEEP_START_MARKER,
+                    line_index + 1
+                ))
+            }
+            (KEEP_END_MARKER, Some(current_region)) => {
+                current_region.content += line;
+                if !line.ends_with('\n') {
+                    current_region.content.push('\n');
+                }
+                regions.extend(region.take());
+                after_blank_line = false;
+            }
+            (_, Some(current_region)) => current_region.content += line,
+            ("", None) => after_blank_line = true,
+            (_, None) => {
+                after_blank_line = false;
+                match get_anchor(trimmed_line) {
+                    Some(line_anchor) => {
+                        anchor = Some(line_anchor);
+                        closing_lines = 0;
+                    }
+                    None => closing_lines += 1,
+                }
+            }
+        }
+    }
+
+    match region {
+        Some(_) => Err(format!("{} is not closed", KEEP_START_MARKER)),
+        None => Ok(regions),
+    }
+}
+
+/// Content without protected regions, as it was generated
+pub fn strip_protected_regions(content: &str) -> String {
+    let mut stripped_content = String::new();
+    let mut in_region = false;
+    for line in content.split_inclusive('\n') {
+        match line.trim() {
+            KEEP_START_MARKER => in_region = true,
+            KEEP_END_MARKER => in_region = false,
+            _ if !in_region => stripped_content += line,
+            _ => (),
+        }
+    }
+    stripped_content
+}
+
+/// Inserts the protected regions of the existing file into the generated content.
+/// Regions whose anchor isn't generated anymore or isn't unique are appended to the end
+pub fn merge_protected_regions(existing_content: &str, content: &str) -> Result<String, String> {
+    let regions = get_protected_regions(existing_content)?;
+    if regions.is_empty() {
+        return Ok(content.to_owned());
+    }
+
+    // The occurrences of an anchor change when the output is formatted, e.g. joined lines
+    let existing_anchor_counts = count_anchors(&strip_protected_regions(existing_content));
+    let anchor_counts = count_anchors(content);
+    let unique = regions
+        .iter()
+        .map(|region| match region.anchor {
+            Some(ref anchor) => {
+                existing_anchor_counts.get(anchor) == Some(&1)
+                    && anchor_counts.get(anchor).is_none_or(|count| *count == 1)
+            }
+            None => true,
+        })
+        .collect::<Vec<bool>>();
+
+    let mut merged_content = String::new();
+    let mut inserted = vec![false; regions.len()];
+    let mut insert_regions = |merged_content: &mut String,
+                              anchor: &Option<String>,
+                              closing_lines: usize,
+                              after_blank_line: bool| {
+        for ((region, inserted), unique) in regions.iter().zip(inserted.iter_mut()).zip(&unique) {
+            if *unique
+                && !*inserted
+                && region.anchor == *anchor
+                && region.closing_lines == closing_lines
+                && region.after_blank_line == after_blank_line
+            {
+                if !merged_content.is_empty() && !merged_content.ends_with('\n') {
+                    merged_content.push('\n');
+                }
+                *merged_content += &region.content;
+                *inserted = true;
+            }
+        }
+    };
+
+    insert_regions(&mut merged_content, &None, 0, false);
+    let mut anchor = None;
+    let mut closing_lines = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed_line = line.trim();
+        if trimmed_line.is_empty() {
+            merged_content += line;
+            continue;
+        }
+
+        // Regions which were separated from their anchor by blank lines
+        insert_regions(&mut merged_content, &anchor, closing_lines, true);
+        merged_content += line;
+        match get_anchor(trimmed_line) {
+            Some(line_anchor) => {
+                anchor = Some(line_anchor);
+                closing_lines = 0;
+            }
+            None => closing_lines += 1,
+        }
+        insert_regions(&mut merged_content, &anchor, closing_lines, false);
+    }
+    insert_regions(&mut merged_content, &anchor, closing_lines, true);
+
+    for ((region, inserted), unique) in regions.iter().zip(inserted).zip(unique) {
+        if !inserted {
+            match unique {
+                true => warn!(
+                    "Anchor of protected region {:?} not found, region is appended",
+                    region.content.lines().nth(1).unwrap_or_default()
+                ),
+                false => warn!(
+                    "Anchor of protected region {:?} is not unique, region is appended",
+                    region.content.lines().nth(1).unwrap_or_default()
+                ),
+            }
+            if !merged_content.is_empty() && !merged_content.ends_with('\n') {
+                merged_content.push('\n');
+            }
+            merged_content += &region.content;
+        }
+    }
+    Ok(merged_content)
+}
+
+/// Writes generated files relative to the output directory.
+/// Files with unchanged content, or whose generated content is the one of the previous
+/// generation (e.g. formatted since), are not rewritten to keep their mtime (and incremental
+/// cargo builds) intact. Files of the previous generation which are not generated anymore
+/// are removed on `finish` unless they have protected regions. Protected regions of existing
+/// files are kept, the manifest stores the hash of the generated content without them.
+pub struct OutputWriter {
+    output_dir: String,
+    previous_manifest: GenerationManifest,
+    written_files: Mutex<BTreeMap<String, String>>,
+    unchanged_files: Mutex<BTreeSet<String>>,
+}
+
+impl OutputWriter {
+    pub fn new(output_dir: &str) -> Self {
+        OutputWriter {
+            output_dir: output_dir.to_owned(),
+            previous_manifest: GenerationManifest::load(output_dir),
+            written_files: Mutex::new(BTreeMap::new()),
+            unchanged_files: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    pub fn write(&self, relative_path: &str, content: &str) -> Result<(), String> {
+        let file_path = Path::new(&self.output_dir).join(relative_path);
+        let hash = content_hash(content.as_bytes());
+
+        // Output which was formatted after the last generation differs from the generated
+        // content, so the hash of the previous generation decides if it changed
+        let generated_unchanged =
+            self.previous_manifest.files.get(relative_path) == Some(&hash) && file_path.is_file();
+        let existing_content = match generated_unchanged {
+            true => None,
+            false => fs::read_to_string(&file_path).ok(),
+        };
+        let merged_content = match existing_content {
+            Some(ref existing_content) => merge_protected_regions(existing_content, content)
+                .map_err(|err| format!("Invalid protected region in {} {}", relative_path, err))?,
+            None => content.to_owned(),
+        };
+
+        if generated_unchanged || existing_content.as_ref() == Some(&merged_content) {
+            trace!("{} unchanged", relative_path);
+            self.unchanged_files
+                .lock()
+                .map_err(|err| err.to_string())?
+                .insert(relative_path.to_owned());
+        } else {
+            self.unchanged_files
+                .lock()
+                .map_err(|err| err.to_string())?
+                .remove(relative_path);
+            if let Some(parent_dir) = file_path.parent() {
+                fs::create_dir_all(parent_dir).map_err(|err| {
+                    format!("Unable to create directory {:?} {}", parent_dir, err)
+                })?;
+            }
+            fs::write(&file_path, merged_content)
+                .map_err(|err| format!("Unable to write file {} {}", relative_path, err))?;
+        }
+
+        self.written_files
+            .lock()
+            .map_err(|err| err.to_string())?
+            .insert(relative_path.to_owned(), hash);
+        Ok(())
+    }
+
+    /// Removes stale files of the previous generation and stores the new manifest
+    pub fn finish(self) -> Result<OutputStats, String> {
+        let written_files = self
+            .written_files
+            .into_inner()
+            .map_err(|err| err.to_string())?;
+        let unchanged = self
+            .unchanged_files
+            .into_inner()
+            .map_err(|err| err.to_string())?
+            .len();
+        let mut stats = OutputStats {
+            written: written_files.len() - unchanged,
+            unchanged,
+            removed: 0,
+        };
+
+        for relative_path in self.previous_manifest.files.keys() {
+            if written_files.contains_key(relative_path) {
+                continue;
+            }
+
+            // The hash of the generated content doesn't match formatted output, only user code
+            // in protected regions keeps a stale file
+            let file_path = Path::new(&self.output_dir).join(relative_path);
+            let has_protected_regions = match fs::read(&file_path) {
+                Ok(current_content) => String::from_utf8_lossy(&current_content)
+                    .lines()
+                    .any(|line| line.trim() == KEEP_START_MARKER),
+                Err(_) => continue,
+            };
+
+            if has_protected_regions {
+                warn!(
+                    "{} has protected regions and will not be removed",
+                    relative_path
+                );
+                continue;
+            }
+
+            info!("Removing stale file {}", relative_path);
+            fs::remove_file(&file_path)
+                .map_err(|err| format!("Unable to remove file {} {}", relative_path, err))?;
+            stats.removed += 1;
+        }
+
+        if written_files == self.previous_manifest.files {
+            return Ok(stats);
+        }
+
+        let manifest = GenerationManifest {
+            files: written_files,
+        };
+        let manifest_content =
+            serde_json::to_string_pretty(&manifest).map_err(|err| err.to_string())?;
+        fs::write(
+            Path::new(&self.output_dir).join(MANIFEST_FILE_NAME),
+            manifest_content,
+        )
+        .map_err(|err| format!("Unable to write manifest {}", err))?;
+        Ok(stats)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputChange {
+    Added(String),
+    Modified(String),
+    Removed(String),
+}
+
+impl Display for OutputChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputChange::Added(path) => write!(f, "A {}", path),
+            OutputChange::Modified(path) => write!(f, "M {}", path),
+            OutputChange::Removed(path) => write!(f, "D {}", path),
+        }
+    }
+}
+
+/// Changes a generation into `generated_dir` would apply to `output_dir`
+pub fn diff_output(output_dir: &str, generated_dir: &str) -> Vec<OutputChange> {
+    let previous_manifest = GenerationManifest::load(output_dir);
+    let generated_manifest = GenerationManifest::load(generated_dir);
+
+    let mut changes = vec![];
+    for (relative_path, generated_hash) in &generated_manifest.files {
+        // Like `OutputWriter::write`, output which was formatted since is unchanged if the
+        // previous generation has the same hash
+        let file_path = Path::new(output_dir).join(relative_path);
+        if previous_manifest.files.get(relative_path) == Some(generated_hash) && file_path.is_file()
+        {
+            continue;
+        }
+        match fs::read_to_string(&file_path) {
+            Ok(current_content)
+                if &content_hash(strip_protected_regions(&current_content).as_bytes())
+                    == generated_hash => {}
+            Ok(_) => changes.push(OutputChange::Modified(relative_path.clone())),
+            Err(_) => changes.push(OutputChange::Added(relative_path.clone())),
+        }
+    }
+
+    for relative_path in previous_manifest.files.keys() {
+        if !generated_manifest.files.contains_key(relative_path)
+            && Path::new(output_dir).join(relative_path).exists()
+        {
+            changes.push(OutputChange::Removed(relative_path.clone()));
+        }
+    }
+
+    changes
+}
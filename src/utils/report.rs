@@ -0,0 +1,162 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::parser::{ir::HttpMethod, spec_diff::ChangeLevel};
+
+/// Operation which wasn't generated
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedOperation {
+    pub method: HttpMethod,
+    pub path: String,
+    pub reason: String,
+}
+
+/// Component which wasn't added to the object database
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedComponent {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Files handled by an `OutputWriter`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct OutputStats {
+    /// New or changed files
+    pub written: usize,
+    /// Files which already had the generated content
+    pub unchanged: usize,
+    /// Stale files of the previous generation
+    pub removed: usize,
+}
+
+impl OutputStats {
+    pub fn add(&mut self, other: OutputStats) {
+        self.written += other.written;
+        self.unchanged += other.unchanged;
+        self.removed += other.removed;
+    }
+}
+
+/// Crate version for the spec changes since the previous generation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VersionSuggestion {
+    pub previous_version: String,
+    pub version: String,
+    /// Highest level of the changes, `None` if the spec is unchanged
+    pub level: Option<ChangeLevel>,
+    pub changes: Vec<String>,
+    /// Cargo.toml has the suggested version
+    pub bumped: bool,
+}
+
+/// Result of a generation run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GenerationReport {
+    /// Generated operations as `METHOD path`
+    pub operations: Vec<String>,
+    pub skipped_operations: Vec<SkippedOperation>,
+    pub skipped_components: Vec<SkippedComponent>,
+    /// Objects of the objects module
+    pub objects: usize,
+    pub files: OutputStats,
+    /// Logged warnings and errors
+    pub warnings: Vec<String>,
+    /// Spec constructs which the generator can't handle, see `validate`
+    pub unsupported_features: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<VersionSuggestion>,
+}
+
+impl GenerationReport {
+    pub fn add_operation(&mut self, method: &reqwest::Method, path: &str) {
+        self.operations
+            .push(format!("{} {}", method.as_str(), path));
+    }
+
+    pub fn skip_operation(&mut self, method: &reqwest::Method, path: &str, reason: &str) {
+        match HttpMethod::try_from(method) {
+            Ok(method) => self.skipped_operations.push(SkippedOperation {
+                method,
+                path: path.to_owned(),
+                reason: reason.to_owned(),
+            }),
+            Err(err) => self
+                .warnings
+                .push(format!("Skipped {} {}: {} ({})", method, path, reason, err)),
+        }
+    }
+
+    pub fn skip_component(&mut self, name: &str, reason: &str) {
+        self.skipped_components.push(SkippedComponent {
+            name: name.to_owned(),
+            reason: reason.to_owned(),
+        });
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| err.to_string())
+    }
+
+    /// Every skipped operation and component with its reason
+    pub fn skipped(&self) -> Vec<String> {
+        self.skipped_operations
+            .iter()
+            .map(|skipped_operation| {
+                format!(
+                    "Skipped {} {}: {}",
+                    skipped_operation.method, skipped_operation.path, skipped_operation.reason
+                )
+            })
+            .chain(self.skipped_components.iter().map(|skipped_component| {
+                format!(
+                    "Skipped component {}: {}",
+                    skipped_component.name, skipped_component.reason
+                )
+            }))
+            .collect()
+    }
+}
+
+/// Summary table, followed by the reasons of skipped operations and components
+impl Display for GenerationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = [
+            ("Operations generated", self.operations.len()),
+            ("Operations skipped", self.skipped_operations.len()),
+            ("Components skipped", self.skipped_components.len()),
+            ("Objects", self.objects),
+            ("Files written", self.files.written),
+            ("Files unchanged", self.files.unchanged),
+            ("Files removed", self.files.removed),
+        ];
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        for (label, count) in rows {
+            writeln!(f, "{:<width$}  {:>6}", label, count, width = label_width)?;
+        }
+
+        if let Some(ref version) = self.version {
+            match (version.level, version.bumped) {
+                (None, _) => writeln!(f, "Spec unchanged, version {}", version.version)?,
+                (Some(_), true) => writeln!(
+                    f,
+                    "Version bumped from {} to {}",
+                    version.previous_version, version.version
+                )?,
+                (Some(_), false) => writeln!(
+                    f,
+                    "Suggested version {} (was {})",
+                    version.version, version.previous_version
+                )?,
+            }
+            for change in &version.changes {
+                writeln!(f, "  {}", change)?;
+            }
+        }
+
+        for skipped in self.skipped() {
+            writeln!(f, "{}", skipped)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,72 @@
+use log::warn;
+use oas3::spec::Operation;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+/// Paths and components are matched against patterns which are either
+/// - exact names (`/robots`)
+/// - globs (`/internal/**`) where `*` matches within a path segment and `**` across segments
+/// - regular expressions prefixed with `regex:` (`regex:^/v[0-9]+/`)
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[serde(default)]
 pub struct SpecIgnore {
     paths: Vec<String>,
     components: Vec<String>,
+    /// Allow-list, if not empty only matching paths are generated
+    only_paths: Vec<String>,
+    /// Allow-list, if not empty only matching components are generated
+    only_components: Vec<String>,
+    /// If not empty only operations with one of these tags are generated
+    include_tags: Vec<String>,
+    /// Operations with one of these tags are not generated
+    exclude_tags: Vec<String>,
+    /// If not empty only operations with one of these http methods are generated
+    methods: Vec<String>,
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex += ".*";
+            }
+            '*' => regex += "[^/]*",
+            '?' => regex += "[^/]",
+            _ => regex += &regex::escape(&char.to_string()),
+        }
+    }
+    regex + "$"
+}
+
+fn pattern_to_regex(pattern: &str) -> Result<Regex, String> {
+    let regex = match pattern.strip_prefix("regex:") {
+        Some(regex) => regex.to_owned(),
+        None => glob_to_regex(pattern),
+    };
+    Regex::new(&regex).map_err(|err| format!("Invalid pattern {} {}", pattern, err))
+}
+
+fn pattern_matches(pattern: &str, value: &str) -> bool {
+    if pattern == value {
+        return true;
+    }
+
+    match pattern_to_regex(pattern) {
+        Ok(regex) => regex.is_match(value),
+        Err(err) => {
+            warn!("{}", err);
+            false
+        }
+    }
+}
+
+fn matches_any(patterns: &[String], value: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| pattern_matches(pattern, value))
 }
 
 impl SpecIgnore {
@@ -11,14 +74,71 @@ impl SpecIgnore {
         SpecIgnore {
             paths: vec![],
             components: vec![],
+            only_paths: vec![],
+            only_components: vec![],
+            include_tags: vec![],
+            exclude_tags: vec![],
+            methods: vec![],
         }
     }
 
+    pub fn include_tags(mut self, include_tags: Vec<String>) -> Self {
+        self.include_tags = include_tags;
+        self
+    }
+
+    pub fn exclude_tags(mut self, exclude_tags: Vec<String>) -> Self {
+        self.exclude_tags = exclude_tags;
+        self
+    }
+
+    pub fn methods(mut self, methods: Vec<String>) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    /// Checks that all patterns are valid
+    pub fn validate(&self) -> Result<(), String> {
+        self.paths
+            .iter()
+            .chain(&self.components)
+            .chain(&self.only_paths)
+            .chain(&self.only_components)
+            .try_for_each(|pattern| pattern_to_regex(pattern).map(|_| ()))
+    }
+
     pub fn path_ignored(&self, path: &str) -> bool {
-        self.paths.contains(&path.to_owned())
+        matches_any(&self.paths, path)
+            || (!self.only_paths.is_empty() && !matches_any(&self.only_paths, path))
+    }
+
+    pub fn operation_ignored(&self, method: &reqwest::Method, operation: &Operation) -> bool {
+        if !self.methods.is_empty()
+            && !self
+                .methods
+                .iter()
+                .any(|allowed_method| allowed_method.eq_ignore_ascii_case(method.as_str()))
+        {
+            return true;
+        }
+
+        if operation
+            .tags
+            .iter()
+            .any(|tag| self.exclude_tags.contains(tag))
+        {
+            return true;
+        }
+
+        !self.include_tags.is_empty()
+            && !operation
+                .tags
+                .iter()
+                .any(|tag| self.include_tags.contains(tag))
     }
 
     pub fn component_ignored(&self, component: &str) -> bool {
-        self.components.contains(&component.to_owned())
+        matches_any(&self.components, component)
+            || (!self.only_components.is_empty() && !matches_any(&self.only_components, component))
     }
 }
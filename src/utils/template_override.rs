@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use log::trace;
+
+use super::config::GeneratorConfig;
+
+/// Embedded templates which can be overridden by a file of the same name in the template
+/// directory
+pub const OVERRIDABLE_TEMPLATES: [&str; 3] = ["base.rs.jinja", "cargo.toml.jinja", "http.rs.jinja"];
+
+/// Renders the override of `template_name` in the template directory of the config with
+/// minijinja, or returns `content` if there is none. Overrides get the output of the embedded
+/// template as `content` next to the values of `context`
+pub fn apply_template_override(
+    generator_config: &GeneratorConfig,
+    template_name: &str,
+    content: String,
+    context: serde_json::Value,
+) -> Result<String, String> {
+    let template_dir = match generator_config.template_dir {
+        Some(ref template_dir) => template_dir,
+        None => return Ok(content),
+    };
+    let template_path = Path::new(template_dir).join(template_name);
+    if !template_path.is_file() {
+        return Ok(content);
+    }
+    trace!("Rendering {:?}", template_path);
+
+    let source = std::fs::read_to_string(&template_path)
+        .map_err(|err| format!("Failed to read {:?} {}", template_path, err))?;
+    let mut context = match context {
+        serde_json::Value::Object(context) => context,
+        _ => serde_json::Map::new(),
+    };
+    context.insert("content".to_owned(), serde_json::Value::String(content));
+
+    let mut environment = minijinja::Environment::new();
+    environment.set_keep_trailing_newline(true);
+    environment
+        .render_str(&source, context)
+        .map_err(|err| format!("Failed to render {:?} {}", template_path, err))
+}
+
+/// Files of the template directory which don't override an embedded template
+pub fn get_unknown_template_overrides(template_dir: &str) -> Result<Vec<String>, String> {
+    let entries = std::fs::read_dir(template_dir)
+        .map_err(|err| format!("Failed to read template directory {} {}", template_dir, err))?;
+    let mut unknown_templates = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|file_name| !OVERRIDABLE_TEMPLATES.contains(&file_name.as_str()))
+        .collect::<Vec<String>>();
+    unknown_templates.sort();
+    Ok(unknown_templates)
+}
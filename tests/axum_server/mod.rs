@@ -0,0 +1 @@
+pub mod routes;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+
+use opage::{
+    generator::axum_server::routes::generate_server_content,
+    parser::component::generate_components,
+    utils::{config::Config, report::GenerationReport},
+};
+
+/// server.rs of a spec relative to the tests directory
+fn generate_server(spec_path: &str) -> (String, GenerationReport) {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests");
+    spec_file_path.push(spec_path);
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    let mut report = GenerationReport::default();
+    let server_content =
+        generate_server_content(&spec, &mut object_database, &config, &mut report).unwrap();
+    (server_content, report)
+}
+
+#[test]
+fn operations_routed_to_trait() {
+    let (server_content, report) = generate_server("projects/generated_tests/spec.openapi.yaml");
+
+    assert!(server_content.contains(
+        "    fn get_pet(\n        &self,\n        pet_id: String,\n    ) -> impl Future<Output = GetPetResponseType> + Send;"
+    ));
+    assert!(server_content.contains("    Path(pet_id): Path<String>,\n"));
+    assert!(server_content.contains("    Json(body): Json<Pet>,\n"));
+    assert!(server_content.contains("    Query(query): Query<ListPetsQueryParameters>,\n"));
+    assert!(server_content.contains("    pub tags: Option<Vec<String>>,\n"));
+    assert!(server_content.contains("    NotFound(String),\n"));
+    assert!(server_content.contains("get(handle_get_pet::<A>).delete(handle_delete_pet::<A>)"));
+    assert!(server_content.contains("use crate::objects::pet::Pet;"));
+    assert_eq!(5, report.operations.len());
+}
+
+#[test]
+fn unsupported_operations_skipped() {
+    let (server_content, report) = generate_server("mock_server/specs/mock.openapi.yaml");
+
+    assert!(server_content.contains("get(handle_get_status::<A>)"));
+    assert!(!server_content.contains("/files/"));
+    assert!(!server_content.contains("fn stream("));
+
+    let skipped_reasons = report
+        .skipped_operations
+        .iter()
+        .map(|skipped_operation| {
+            format!(
+                "{} {}: {}",
+                skipped_operation.method, skipped_operation.path, skipped_operation.reason
+            )
+        })
+        .collect::<Vec<String>>();
+    assert!(skipped_reasons.contains(
+        &"GET /files/{name}.json: Path parameters within a segment are not supported".to_owned()
+    ));
+    assert!(skipped_reasons.contains(
+        &"GET /stream: Websocket operations are not supported by this target".to_owned()
+    ));
+}
@@ -0,0 +1,107 @@
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+use opage::{
+    parser::component::{
+        generate_components, generate_components_with_report,
+        object_definition::{
+            get_object_name,
+            types::{ObjectDatabase, ObjectDefinition, StructDefinition},
+        },
+    },
+    utils::{config::Config, report::GenerationReport},
+};
+
+fn read_spec(spec_file_name: &str) -> oas3::Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs");
+    spec_file_path.push(spec_file_name);
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+
+    oas3::from_yaml(yaml).expect("Failed to read spec")
+}
+
+fn generate_database(spec_file_name: &str) -> ObjectDatabase {
+    generate_components(&read_spec(spec_file_name), &Config::new()).unwrap()
+}
+
+#[test]
+fn failed_object_leaves_no_placeholder() {
+    let object_database = generate_database("failed_object.openapi.yaml");
+
+    assert_eq!(
+        vec!["Broken", "Valid"],
+        object_database.keys().collect::<Vec<&String>>()
+    );
+}
+
+#[test]
+fn renamed_component_found_by_ref() {
+    let object_database = generate_database("name_collision.openapi.yaml");
+
+    let object_name = |ref_path: &str| {
+        get_object_name(
+            object_database
+                .get_by_ref(ref_path)
+                .expect("Component not found"),
+        )
+        .clone()
+    };
+    assert_eq!("UserInfo", object_name("#/components/schemas/UserInfo"));
+    assert_eq!("UserInfo2", object_name("#/components/schemas/user_info"));
+}
+
+#[test]
+fn skipped_component_reported() {
+    let spec = read_spec("skipped_component.openapi.yaml");
+    let mut report = GenerationReport::default();
+    let object_database =
+        generate_components_with_report(&spec, &Config::new(), &mut report).unwrap();
+
+    assert_eq!(
+        vec!["Valid"],
+        object_database.keys().collect::<Vec<&String>>()
+    );
+    assert_eq!(
+        vec!["Dangling"],
+        report
+            .skipped_components
+            .iter()
+            .map(|skipped_component| skipped_component.name.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert!(report.skipped()[0].starts_with("Skipped component Dangling: "));
+}
+
+#[test]
+fn layer_finds_base_objects_without_copying_them() {
+    let base = Arc::new(generate_database("name_collision.openapi.yaml"));
+    let mut layer = ObjectDatabase::layer(&base);
+
+    assert!(layer.contains_key("UserInfo2"));
+    assert!(layer.get_by_ref("#/components/schemas/user_info").is_some());
+    assert!(layer.keys().next().is_none());
+    // Names of base objects stay taken
+    let inline_object = |name: &str| {
+        ObjectDefinition::Struct(StructDefinition {
+            used_modules: vec![],
+            name: name.to_owned(),
+            properties: BTreeMap::new(),
+            local_objects: BTreeMap::new(),
+            example: None,
+        })
+    };
+    assert!(layer
+        .insert_unique("UserInfo", inline_object("UserInfo"))
+        .is_err());
+    layer
+        .insert_unique("UserInfoRequest", inline_object("UserInfoRequest"))
+        .unwrap();
+
+    let added_objects = layer.added_since(&base);
+    assert_eq!(
+        vec!["UserInfoRequest"],
+        added_objects.keys().collect::<Vec<&String>>()
+    );
+    assert_eq!(1, Arc::strong_count(&base));
+}
@@ -0,0 +1,63 @@
+use askama::Template;
+use opage::{
+    generator::rust_reqwest_async::templates::BaseTemplate,
+    parser::component::{
+        example::get_schema_example, generate_components,
+        object_definition::types::ObjectDefinition,
+    },
+    utils::config::Config,
+};
+use std::path::PathBuf;
+
+#[test]
+fn schema_example_from_properties() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/examples.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let pet_schema = spec.components.as_ref().unwrap().schemas["Pet"]
+        .resolve(&spec)
+        .unwrap();
+
+    let example = get_schema_example(&spec, &pet_schema);
+
+    assert_eq!(
+        example,
+        serde_json::json!({
+            "id": 1,
+            "name": "Rex",
+            "status": "available",
+            "owner": {
+                "name": "Alex",
+                "born": "2024-01-01",
+                "address": {}
+            }
+        })
+    );
+}
+
+#[test]
+fn struct_example_constructor() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/examples.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let render_struct = |name: &str| match object_database.get(name).unwrap() {
+        ObjectDefinition::Struct(struct_definition) => BaseTemplate::from(struct_definition)
+            .render()
+            .expect("Failed to render struct"),
+        _ => panic!("Expected a struct"),
+    };
+
+    let tag_code = render_struct("Tag");
+    assert!(tag_code.contains("pub fn example() -> Self"));
+    assert!(tag_code.contains(r##"serde_json::from_str(r#"{"name":"friendly"}"#)"##));
+
+    // Examples derived from the schema are not emitted
+    assert!(!render_struct("Pet").contains("pub fn example()"));
+}
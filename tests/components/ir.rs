@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use opage::{
+    parser::ir::{parse_spec_ir, BodyIr, HttpMethod, ModelIr, ParameterIr, SpecIr},
+    utils::config::Config,
+};
+
+fn parse_ir(spec_file_name: &str) -> SpecIr {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs");
+    spec_file_path.push(spec_file_name);
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    parse_spec_ir(&spec, &Config::new()).unwrap()
+}
+
+#[test]
+fn operations_with_parameters_bodies_and_responses() {
+    let spec_ir = parse_ir("ir.openapi.yaml");
+
+    assert_eq!("Pets", spec_ir.title);
+    assert_eq!("1.2.0", spec_ir.version);
+    assert_eq!(
+        vec![("getPet", HttpMethod::Get), ("updatePet", HttpMethod::Put)],
+        spec_ir
+            .operations
+            .iter()
+            .map(|operation| (operation.operation_id.as_str(), operation.method))
+            .collect::<Vec<_>>()
+    );
+
+    let get_pet = &spec_ir.operations[0];
+    assert_eq!("/pets/{petId}", get_pet.path);
+    assert_eq!(Some("Pet by id".to_owned()), get_pet.summary);
+    assert_eq!(
+        vec![ParameterIr {
+            name: "petId".to_owned(),
+            type_name: "i32".to_owned(),
+            required: true,
+        }],
+        get_pet.path_parameters
+    );
+    assert_eq!("fields", get_pet.query_parameters[0].name);
+    assert!(!get_pet.query_parameters[0].required);
+    assert_eq!(
+        vec![200, 404],
+        get_pet
+            .responses
+            .iter()
+            .map(|response| response.status)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(BodyIr {
+            content_type: "application/json".to_owned(),
+            type_name: Some("Pet".to_owned()),
+        }),
+        get_pet.responses[0].body
+    );
+    assert_eq!(None, get_pet.responses[1].body);
+
+    let update_pet = &spec_ir.operations[1];
+    assert_eq!(
+        Some("Pet"),
+        update_pet
+            .request_body
+            .as_ref()
+            .and_then(|body| body.type_name.as_deref())
+    );
+}
+
+#[test]
+fn models_and_json_round_trip() {
+    let spec_ir = parse_ir("ir.openapi.yaml");
+
+    match spec_ir.models.get("Pet") {
+        Some(ModelIr::Struct { properties }) => assert_eq!(
+            vec![("kind", false), ("name", true)],
+            properties
+                .iter()
+                .map(|property| (property.name.as_str(), property.required))
+                .collect::<Vec<_>>()
+        ),
+        model => panic!("Pet is no struct: {:?}", model),
+    }
+    assert_eq!(
+        Some(&ModelIr::Primitive {
+            type_name: "String".to_owned()
+        }),
+        spec_ir.models.get("Kind")
+    );
+
+    let json = spec_ir.to_json().unwrap();
+    let json_value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!("GET", json_value["operations"][0]["method"]);
+    assert_eq!("struct", json_value["models"]["Pet"]["kind"]);
+    assert_eq!(spec_ir, SpecIr::from_json(&json).unwrap());
+}
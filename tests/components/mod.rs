@@ -1,2 +1,6 @@
+pub mod database;
+pub mod example;
+pub mod ir;
 pub mod name;
 pub mod properties;
+pub mod spec_diff;
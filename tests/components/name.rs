@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use opage::{parser::component::generate_components, utils::config::Config};
+use opage::{
+    generator::rust_reqwest_async::{objects::write_object_database, paths::generate_paths},
+    parser::component::{generate_components, object_definition::types::ObjectDefinition},
+    utils::{config::Config, output::OutputWriter, report::GenerationReport},
+};
 
 #[test]
 fn title_of_component_used() {
@@ -18,3 +22,270 @@ fn title_of_component_used() {
         object_database.keys().collect::<Vec<&String>>()
     );
 }
+
+#[test]
+fn operation_id_from_method_and_path() {
+    let config = Config::new();
+    assert_eq!(
+        "get_users_by_id",
+        config
+            .name_mapping
+            .operation_id_from_path("GET", "/users/{id}")
+    );
+    assert_eq!(
+        "post_cells_by_cell_robot_controllers",
+        config
+            .name_mapping
+            .operation_id_from_path("POST", "/cells/{cell}/robotControllers")
+    );
+}
+
+#[test]
+fn unicode_and_symbol_names_converted() {
+    let name_mapping = Config::new().name_mapping;
+    let path = vec!["/#/components/schemas/Größe".to_owned()];
+
+    assert_eq!(
+        "user_name",
+        name_mapping.name_to_property_name(&path, "user-name[]")
+    );
+    assert_eq!("grosse", name_mapping.name_to_property_name(&path, "größe"));
+    assert_eq!("cafe", name_mapping.name_to_property_name(&path, "café"));
+    assert_eq!(
+        "rocket_launch",
+        name_mapping.name_to_property_name(&path, "🚀launch")
+    );
+    assert_eq!(
+        "ming_qian",
+        name_mapping.name_to_property_name(&path, "名前")
+    );
+    assert_eq!("id", name_mapping.name_to_property_name(&path, "@id"));
+    assert_eq!("Grosse", name_mapping.name_to_struct_name(&vec![], "Größe"));
+    assert_eq!(
+        "grossen_lesen",
+        name_mapping.name_to_module_name("größenLesen")
+    );
+
+    // Names without letters or digits fall back to their code points
+    assert_eq!(
+        "value_5b_5d",
+        name_mapping.name_to_property_name(&path, "[]")
+    );
+    assert_eq!("value_2d", name_mapping.name_to_property_name(&path, "-"));
+    assert_eq!("Value5b5d", name_mapping.name_to_struct_name(&vec![], "[]"));
+}
+
+#[test]
+fn different_schemas_with_same_name_disambiguated() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/name_collision.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+
+    let property_type =
+        |struct_name: &str, property_name: &str| match object_database.get(struct_name) {
+            Some(ObjectDefinition::Struct(struct_definition)) => struct_definition
+                .properties
+                .get(property_name)
+                .expect("Property not found")
+                .type_name
+                .clone(),
+            _ => panic!("{} is not a struct", struct_name),
+        };
+
+    assert_eq!("UserInfo2", property_type("Account", "owner"));
+    assert_eq!("UserInfo", property_type("Team", "lead"));
+    assert_eq!("Settings", property_type("Account", "settings"));
+    assert_eq!("TeamSettings", property_type("Team", "settings"));
+}
+
+#[test]
+fn anonymous_objects_named_after_parent_and_property() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/anonymous_objects.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+
+    let property_type =
+        |struct_name: &str, property_name: &str| match object_database.get(struct_name) {
+            Some(ObjectDefinition::Struct(struct_definition)) => struct_definition
+                .properties
+                .get(property_name)
+                .expect("Property not found")
+                .type_name
+                .clone(),
+            _ => panic!("{} is not a struct", struct_name),
+        };
+
+    // The component keeps its name
+    assert_eq!("RobotTool2", property_type("Robot", "tool"));
+    assert_eq!("RobotTool2Mount", property_type("RobotTool2", "mount"));
+    assert_eq!("Vec<RobotJointsItem>", property_type("Robot", "joints"));
+    assert_eq!("CellTool", property_type("Cell", "tool"));
+    assert_eq!("String", property_type("Robot", "name"));
+    assert!(!object_database.contains_key("Object"));
+}
+
+#[test]
+fn nested_containers_named_after_parent() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/nested_containers.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+
+    let property_type = |property_name: &str| match object_database.get("Matrix") {
+        Some(ObjectDefinition::Struct(struct_definition)) => struct_definition
+            .properties
+            .get(property_name)
+            .expect("Property not found")
+            .type_name
+            .clone(),
+        _ => panic!("Matrix is not a struct"),
+    };
+    let variant_type = |enum_name: &str, variant_name: &str| match object_database.get(enum_name) {
+        Some(ObjectDefinition::Enum(enum_definition)) => enum_definition
+            .values
+            .get(variant_name)
+            .expect("Variant not found")
+            .value_type
+            .name
+            .clone(),
+        _ => panic!("{} is not an enum", enum_name),
+    };
+
+    assert_eq!("Vec<Vec<f64>>", property_type("rows"));
+    assert_eq!("Vec<Vec<MatrixCellsItemItem>>", property_type("cells"));
+    assert_eq!(
+        "Vec<Vec<Vec<MatrixCubesItemItemItem>>>",
+        property_type("cubes")
+    );
+    // Items of anonymous arrays in anyOf/oneOf are named after the enum
+    assert_eq!(
+        "Vec<Vec<MatrixShapeArrayValueItemItem>>",
+        variant_type("MatrixShape", "ArrayValue")
+    );
+    assert_eq!(
+        "Vec<LayerShapeArrayValueItem>",
+        variant_type("LayerShape", "ArrayValue")
+    );
+    match object_database.get("Grid") {
+        Some(ObjectDefinition::Primitive(primitive_definition)) => assert_eq!(
+            "Vec<Vec<GridItemItem>>",
+            primitive_definition.primitive_type.name
+        ),
+        _ => panic!("Grid is not a primitive"),
+    }
+    assert!(object_database.contains_key("GridItemItem"));
+    assert!(!object_database.contains_key("ArrayValueItem"));
+}
+
+#[test]
+fn module_name_collisions_reported() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/module_name_collision.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let mut config = Config::new();
+    config.name_mapping.struct_mapping.insert(
+        "/#/components/schemas/LegacyConfig".to_owned(),
+        "HTTPConfig".to_owned(),
+    );
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("module_name_collision");
+    let output = OutputWriter::new(output_dir.to_str().unwrap());
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    assert_eq!(
+        Err("get_user (GET /user), getUser (GET /users/{id}) are all generated as module get_user. Add a module_mapping entry for all but one of them, e.g. \"module_mapping\": {\"getUser\": \"get_user_2\"}".to_owned()),
+        generate_paths(
+            &output,
+            &spec,
+            &mut object_database,
+            &config,
+            &mut GenerationReport::default()
+        ).map(|_| ())
+    );
+    assert_eq!(
+        Err("HTTPConfig, HttpConfig are all generated as module http_config. Add a module_mapping entry for all but one of them, e.g. \"module_mapping\": {\"HttpConfig\": \"http_config_2\"}".to_owned()),
+        write_object_database(
+            &output,
+            &object_database,
+            &config.name_mapping,
+            &config.generator,
+            &Default::default()
+        )
+    );
+
+    // Mapped by their original names
+    config
+        .name_mapping
+        .module_mapping
+        .insert("getUser".to_owned(), "get_user_by_id".to_owned());
+    config
+        .name_mapping
+        .module_mapping
+        .insert("HTTPConfig".to_owned(), "legacy_http_config".to_owned());
+    let operations = generate_paths(
+        &output,
+        &spec,
+        &mut object_database,
+        &config,
+        &mut GenerationReport::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec!["get_user", "get_user_by_id"],
+        operations
+            .iter()
+            .map(|operation| operation.module_name.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert!(write_object_database(
+        &output,
+        &object_database,
+        &config.name_mapping,
+        &config.generator,
+        &Default::default()
+    )
+    .is_ok());
+}
+
+#[test]
+fn copies_of_component_share_name() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/name_collision.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+
+    match object_database.get("Scene") {
+        Some(ObjectDefinition::Struct(struct_definition)) => assert_eq!(
+            "Collider",
+            struct_definition.properties["collider"].type_name
+        ),
+        _ => panic!("Scene is not a struct"),
+    }
+    assert!(object_database.get("Scene2").is_none());
+    assert!(object_database.get("Collider2").is_none());
+}
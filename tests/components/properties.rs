@@ -1,11 +1,19 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
+use askama::Template;
 use opage::{
+    generator::rust_reqwest_async::{
+        templates::{BaseTemplate, StructTemplateOptions},
+        validation::get_validated_structs,
+    },
     parser::component::{
         generate_components,
-        object_definition::types::{ModuleInfo, ObjectDefinition},
+        object_definition::types::{
+            ConstDefinition, ModuleInfo, ObjectDefinition, PrimitiveDefinition,
+            PropertyConstraints, PropertyDefinition, StructDefinition, TypeDefinition,
+        },
     },
-    utils::config::Config,
+    utils::config::{Config, PropertySerde},
 };
 
 #[test]
@@ -53,3 +61,611 @@ fn self_ref_component() {
         configuration_resource.get_required_modules()
     );
 }
+
+#[test]
+fn self_ref_property_boxed() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/self_ref.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let configuration_resource = match object_database.get("ConfigurationResource").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+
+    assert_eq!(
+        "Box<ConfigurationResource>",
+        configuration_resource.properties["parent"].type_name
+    );
+    // Vec is already an indirection
+    assert_eq!(
+        "Vec<ConfigurationResource>",
+        configuration_resource.properties["children"].type_name
+    );
+}
+
+#[test]
+fn renamed_property_serialized_with_real_name() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/renamed_property.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let robot = match object_database.get("Robot").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+
+    let content = BaseTemplate::from(robot).render().unwrap();
+    assert!(content.contains("#[serde(rename = \"robotName\")]"));
+    assert!(!content.contains("#[serde(alias"));
+}
+
+#[test]
+fn keywords_and_invalid_names_sanitized() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/keyword_properties.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let keywords = match object_database.get("Keywords").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+    assert_eq!("_2DPoint", keywords.properties["point"].type_name);
+
+    let content = BaseTemplate::from(keywords).render().unwrap();
+    assert!(content.contains("#[serde(rename = \"type\")]"));
+    assert!(content.contains("pub type_: String"));
+    assert!(content.contains("#[serde(rename = \"self\")]"));
+    assert!(content.contains("pub self_: Option<String>"));
+    assert!(content.contains("#[serde(rename = \"3d_model\")]"));
+    assert!(content.contains("pub _3_d_model: Option<String>"));
+    assert!(content.contains("#[serde(rename = \"@id\")]"));
+    assert!(content.contains("pub fn with_id(mut self, id: String)"));
+    assert_eq!(
+        "_2_d_point",
+        config.name_mapping.name_to_module_name("2DPoint")
+    );
+}
+
+#[test]
+fn unknown_fields_denied_in_strict_mode() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/renamed_property.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let robot = match object_database.get("Robot").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+
+    let content = BaseTemplate::from(robot).render().unwrap();
+    assert!(!content.contains("deny_unknown_fields"));
+
+    let content = BaseTemplate::from_struct(
+        robot,
+        &StructTemplateOptions {
+            deny_unknown_fields: true,
+            ..Default::default()
+        },
+    )
+    .render()
+    .unwrap();
+    assert!(content.contains("#[serde(deny_unknown_fields)]"));
+}
+
+#[test]
+fn read_write_only_properties_skipped() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/read_write_only.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let user = match object_database.get("User").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+    assert!(user.properties["id"].read_only);
+    assert!(user.properties["password"].write_only);
+
+    let render_struct = |skip_read_write_only: bool| {
+        BaseTemplate::from_struct(
+            user,
+            &StructTemplateOptions {
+                skip_read_write_only,
+                ..Default::default()
+            },
+        )
+        .render()
+        .expect("Failed to render struct")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+    };
+
+    let user_code = render_struct(true);
+    assert!(user_code.contains("#[serde(skip_serializing)] pub id: Option<String>,"));
+    assert!(user_code.contains("#[serde(skip_deserializing)] pub password: Option<String>,"));
+    assert!(user_code.contains("pub name: String,"));
+
+    // Both directions are serialized without the mode
+    let user_code = render_struct(false);
+    assert!(!user_code.contains("skip_serializing"));
+    assert!(user_code.contains("pub id: String,"));
+}
+
+#[test]
+fn property_constraints_validated() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/constraints.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let validated_structs = get_validated_structs(&object_database);
+    assert_eq!(
+        vec!["Address", "User"],
+        validated_structs.iter().collect::<Vec<&String>>()
+    );
+
+    let user = match object_database.get("User").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+    let user_code = BaseTemplate::from_struct(
+        user,
+        &StructTemplateOptions {
+            validated_structs: Some(&validated_structs),
+            ..Default::default()
+        },
+    )
+    .render()
+    .expect("Failed to render struct");
+
+    assert!(user_code.contains("use crate::validation::Validate;"));
+    assert!(user_code.contains("impl Validate for User"));
+    assert!(user_code
+        .contains(r#"crate::validation::check_length("name", &self.name, Some(1), Some(32))?;"#));
+    assert!(user_code.contains(
+        r#"crate::validation::check_pattern("name", &self.name, &PATTERN, "^[a-z]+$")?;"#
+    ));
+    assert!(user_code.contains(
+        "static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();"
+    ));
+    // Invalid patterns are reported instead of being skipped on every check
+    assert!(!user_code.contains("\"nickname\""));
+    assert!(user_code.contains(
+        r#"crate::validation::check_range("age", *value as f64, Some(0.0), Some(150.0))?;"#
+    ));
+    assert!(user_code.contains(
+        r#"crate::validation::check_items("tags", self.tags.len(), Some(1), Some(8))?;"#
+    ));
+    // Nested and recursive structs
+    assert!(user_code.contains("self.address.validate()?;"));
+    assert!(user_code.contains("self.friends.validate()?;"));
+
+    let user_code = BaseTemplate::from(user)
+        .render()
+        .expect("Failed to render struct");
+    assert!(!user_code.contains("impl Validate"));
+}
+
+#[test]
+fn const_properties_as_unit_structs() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/const.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let dog = match object_database.get("Dog").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+    assert_eq!("DogPetType", dog.properties["pet_type"].type_name);
+    assert_eq!("DogLegs", dog.properties["legs"].type_name);
+    // Enums with multiple values are no constants
+    assert_eq!("String", dog.properties["color"].type_name);
+
+    let pet_type = match object_database.get("DogPetType").unwrap() {
+        ObjectDefinition::Const(const_definition) => const_definition,
+        _ => panic!("Expected a const"),
+    };
+    assert_eq!(serde_json::json!("dog"), pet_type.value);
+    let legs = match object_database.get("DogLegs").unwrap() {
+        ObjectDefinition::Const(const_definition) => const_definition,
+        _ => panic!("Expected a const"),
+    };
+    assert_eq!(serde_json::json!(4), legs.value);
+
+    let pet_type_code = BaseTemplate::from(pet_type)
+        .render()
+        .expect("Failed to render const");
+    assert!(pet_type_code.contains("pub struct DogPetType;"));
+    assert!(pet_type_code.contains(r##"pub const VALUE: &'static str = r#""dog""#;"##));
+    assert!(pet_type_code.contains("impl Serialize for DogPetType"));
+    assert!(pet_type_code.contains("impl<'de> Deserialize<'de> for DogPetType"));
+}
+
+#[test]
+fn const_types_displayed_and_parsed() {
+    let pet_type = ConstDefinition {
+        name: "DogPetType".to_owned(),
+        value: serde_json::json!("dog"),
+    };
+    let pet_type_code = BaseTemplate::from(&pet_type)
+        .render()
+        .expect("Failed to render const");
+    assert!(pet_type_code.contains("impl std::fmt::Display for DogPetType"));
+    assert!(pet_type_code.contains("f.write_str(\"dog\")"));
+    assert!(pet_type_code.contains("impl std::str::FromStr for DogPetType"));
+    assert!(pet_type_code.contains("match value == \"dog\""));
+
+    // Other values are written as json
+    let legs = ConstDefinition {
+        name: "DogLegs".to_owned(),
+        value: serde_json::json!(4),
+    };
+    let legs_code = BaseTemplate::from(&legs)
+        .render()
+        .expect("Failed to render const");
+    assert!(legs_code.contains("f.write_str(\"4\")"));
+}
+
+#[test]
+fn primitive_components_as_newtypes() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/primitive_component/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let name_list = match object_database.get("NameList").unwrap() {
+        ObjectDefinition::Primitive(primitive_definition) => primitive_definition,
+        _ => panic!("Expected a primitive"),
+    };
+
+    let name_list_code = BaseTemplate::from(name_list)
+        .render()
+        .expect("Failed to render primitive");
+    assert!(name_list_code.contains("#[serde(transparent)]"));
+    assert!(name_list_code.contains("pub struct NameList(pub Vec<String>);"));
+    assert!(name_list_code.contains("impl From<Vec<String>> for NameList"));
+    assert!(name_list_code.contains("impl std::ops::Deref for NameList"));
+    // Only scalar values are displayed
+    assert!(!name_list_code.contains("impl std::fmt::Display"));
+
+    let cell_name = PrimitiveDefinition {
+        name: "CellName".to_owned(),
+        primitive_type: TypeDefinition {
+            name: "String".to_owned(),
+            module: None,
+        },
+    };
+    let cell_name_code = BaseTemplate::from(&cell_name)
+        .render()
+        .expect("Failed to render primitive");
+    assert!(cell_name_code.contains("impl std::fmt::Display for CellName"));
+    assert!(cell_name_code.contains("impl std::str::FromStr for CellName"));
+}
+
+#[test]
+fn any_of_primitives_as_untagged_enum() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/any_of_primitives.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let setting_value = match object_database.get("SettingValue").unwrap() {
+        ObjectDefinition::Enum(enum_definition) => enum_definition,
+        _ => panic!("Expected an enum"),
+    };
+    // Anonymous and repeated alternatives get numbered names
+    assert_eq!(
+        vec![
+            "IntegerValue",
+            "RangeValue",
+            "StringValue",
+            "StringValue2",
+            "StringValue3"
+        ],
+        setting_value.values.keys().collect::<Vec<&String>>()
+    );
+    assert_eq!("i32", setting_value.values["IntegerValue"].value_type.name);
+    assert_eq!(
+        "String",
+        setting_value.values["StringValue3"].value_type.name
+    );
+
+    let setting_value_code = BaseTemplate::from(setting_value)
+        .render()
+        .expect("Failed to render enum");
+    assert!(setting_value_code.contains("#[serde(untagged)]"));
+}
+
+#[test]
+fn one_of_variants_converted_from_inner_types() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/one_of_conversions.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let payload = match object_database.get("Payload").unwrap() {
+        ObjectDefinition::Enum(enum_definition) => enum_definition,
+        _ => panic!("Expected an enum"),
+    };
+
+    let payload_code = BaseTemplate::from(payload)
+        .conversions()
+        .render()
+        .expect("Failed to render enum");
+    assert!(payload_code.contains("impl From<bool> for Payload"));
+    assert!(payload_code.contains("impl TryFrom<Payload> for bool"));
+    assert!(payload_code.contains("impl From<RangeValue> for Payload"));
+    assert!(payload_code.contains("impl TryFrom<Payload> for RangeValue"));
+    // `Name` and the inline string are both `String`, their conversions would conflict
+    assert!(!payload_code.contains("impl From<String>"));
+    assert!(!payload_code.contains("impl TryFrom<Payload> for String"));
+}
+
+#[test]
+fn any_of_variants_mapped() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/any_of_primitives.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let mut config = Config::new();
+    config.name_mapping.variant_mapping.insert(
+        "/#/components/schemas/Setting/SettingValue/IntegerValue".to_owned(),
+        "Count".to_owned(),
+    );
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let setting_value = match object_database.get("SettingValue").unwrap() {
+        ObjectDefinition::Enum(enum_definition) => enum_definition,
+        _ => panic!("Expected an enum"),
+    };
+    assert_eq!("i32", setting_value.values["Count"].value_type.name);
+    assert!(!setting_value.values.contains_key("IntegerValue"));
+}
+
+#[test]
+fn local_objects_rendered_with_struct() {
+    let property = |name: &str, type_name: &str, module: Option<ModuleInfo>| PropertyDefinition {
+        name: name.to_owned(),
+        real_name: name.to_owned(),
+        type_name: type_name.to_owned(),
+        module,
+        required: true,
+        default_value: None,
+        read_only: false,
+        write_only: false,
+        constraints: PropertyConstraints::default(),
+    };
+    let local_module = |name: &str| ModuleInfo {
+        name: name.to_owned(),
+        path: format!("crate::objects::{}", name.to_lowercase()),
+    };
+
+    let mount = StructDefinition {
+        used_modules: vec![],
+        name: "Mount".to_owned(),
+        properties: BTreeMap::from([(
+            "kind".to_owned(),
+            property("kind", "MountKind", Some(local_module("MountKind"))),
+        )]),
+        local_objects: BTreeMap::new(),
+        example: None,
+    };
+    let tool = StructDefinition {
+        used_modules: vec![],
+        name: "Tool".to_owned(),
+        properties: BTreeMap::from([(
+            "mount".to_owned(),
+            property("mount", "Mount", Some(local_module("Mount"))),
+        )]),
+        local_objects: BTreeMap::from([
+            (
+                "Mount".to_owned(),
+                Box::new(ObjectDefinition::Struct(mount)),
+            ),
+            (
+                "MountKind".to_owned(),
+                Box::new(ObjectDefinition::Const(ConstDefinition {
+                    name: "MountKind".to_owned(),
+                    value: serde_json::json!("flange"),
+                })),
+            ),
+        ]),
+        example: None,
+    };
+
+    let tool_code = BaseTemplate::from(&tool)
+        .render()
+        .expect("Failed to render struct");
+    assert!(tool_code.contains("pub struct Tool {"));
+    assert!(tool_code.contains("pub struct Mount {"));
+    assert!(tool_code.contains("pub struct MountKind;"));
+    // Local objects are not imported from the objects module
+    assert!(!tool_code.contains("crate::objects::"));
+    assert_eq!(1, tool_code.matches("use serde::Serialize;").count());
+}
+
+#[test]
+fn string_formats_mapped_to_newtypes() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/format_types/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let property_type =
+        |config: &Config, struct_name: &str, property_name: &str| match generate_components(
+            &spec, config,
+        )
+        .unwrap()
+        .get(struct_name)
+        {
+            Some(ObjectDefinition::Struct(struct_definition)) => struct_definition
+                .properties
+                .get(property_name)
+                .expect("Property not found")
+                .type_name
+                .clone(),
+            _ => panic!("{} is not a struct", struct_name),
+        };
+
+    // Formats stay strings unless they are mapped
+    let mut config = Config::new();
+    assert_eq!("String", property_type(&config, "Contact", "email"));
+    assert_eq!("String", property_type(&config, "Webhook", "url"));
+
+    config.name_mapping.type_mapping.uri = true;
+    config.name_mapping.type_mapping.email = true;
+    assert_eq!("url::Url", property_type(&config, "Webhook", "url"));
+    assert_eq!(
+        "Vec<url::Url>",
+        property_type(&config, "Webhook", "fallback_urls")
+    );
+    assert_eq!(
+        "crate::formats::Email",
+        property_type(&config, "Contact", "email")
+    );
+    assert_eq!("String", property_type(&config, "Webhook", "host"));
+    assert_eq!("String", property_type(&config, "Contact", "name"));
+}
+
+#[test]
+fn type_overrides_replace_components() {
+    let project_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/projects/type_overrides");
+
+    let yaml = std::fs::read_to_string(project_path.join("spec.openapi.yaml"))
+        .expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::from(&project_path.join("config.json")).expect("Failed to read config");
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    assert!(!object_database.contains_key("ResourceId"));
+    assert!(!object_database.contains_key("Metadata"));
+
+    let robot = match object_database.get("Robot") {
+        Some(ObjectDefinition::Struct(struct_definition)) => struct_definition,
+        _ => panic!("Robot is not a struct"),
+    };
+    let id = robot.properties.get("id").expect("Property not found");
+    assert_eq!("Uuid", id.type_name);
+    assert_eq!(
+        Some(ModuleInfo {
+            path: "uuid".to_owned(),
+            name: "Uuid".to_owned()
+        }),
+        id.module
+    );
+    assert_eq!("Vec<Uuid>", robot.properties["peers"].type_name);
+    // Types without import are written as they are
+    assert_eq!(
+        "serde_json::Map<String, serde_json::Value>",
+        robot.properties["metadata"].type_name
+    );
+    assert_eq!(None, robot.properties["metadata"].module);
+
+    match object_database.get("Selector") {
+        Some(ObjectDefinition::Enum(enum_definition)) => assert_eq!(
+            "Uuid",
+            enum_definition.values["ResourceIdValue"].value_type.name
+        ),
+        _ => panic!("Selector is not an enum"),
+    }
+
+    let robot_code = BaseTemplate::from_struct(robot, &StructTemplateOptions::default())
+        .render()
+        .unwrap();
+    assert!(robot_code.contains("use uuid::Uuid;"));
+}
+
+#[test]
+fn property_serde_attributes_rendered() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs/property_serde.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let object_database = generate_components(&spec, &config).unwrap();
+    let event = match object_database.get("Event").unwrap() {
+        ObjectDefinition::Struct(struct_definition) => struct_definition,
+        _ => panic!("Expected a struct"),
+    };
+
+    // Properties are configured by their spec or rust name
+    let property_serde = BTreeMap::from([
+        (
+            "Event.createdAt".to_owned(),
+            PropertySerde {
+                with: Some("crate::serde_helpers::ms_timestamp".to_owned()),
+                ..Default::default()
+            },
+        ),
+        (
+            "Event.count".to_owned(),
+            PropertySerde {
+                deserialize_with: Some("crate::serde_helpers::string_or_number".to_owned()),
+                ..Default::default()
+            },
+        ),
+    ]);
+    let event_code = BaseTemplate::from_struct(
+        event,
+        &StructTemplateOptions {
+            property_serde: Some(&property_serde),
+            ..Default::default()
+        },
+    )
+    .render()
+    .expect("Failed to render struct")
+    .split_whitespace()
+    .collect::<Vec<&str>>()
+    .join(" ");
+
+    assert!(event_code.contains(
+        "#[serde(rename = \"createdAt\")] #[serde(with = \"crate::serde_helpers::ms_timestamp\")] pub created_at: i32,"
+    ));
+    // Missing optional fields are None without calling the deserializer
+    assert!(event_code.contains(
+        "#[serde(deserialize_with = \"crate::serde_helpers::string_or_number\")] #[serde(default)] pub count: Option<String>,"
+    ));
+    assert_eq!(2, event_code.matches("with = ").count());
+}
@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+
+use opage::{
+    parser::{
+        ir::{parse_spec_ir, ModelIr, SpecIr},
+        security::OperationSecurity,
+        spec_diff::{diff_spec_ir, get_change_level, ChangeLevel},
+    },
+    utils::config::Config,
+};
+
+fn parse_ir(spec_file_name: &str) -> SpecIr {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs");
+    spec_file_path.push(spec_file_name);
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    parse_spec_ir(&spec, &Config::new()).unwrap()
+}
+
+#[test]
+fn operation_and_model_changes() {
+    let changes = diff_spec_ir(
+        &parse_ir("ir.openapi.yaml"),
+        &parse_ir("ir_changed.openapi.yaml"),
+    );
+
+    assert_eq!(
+        vec![
+            "M GET /pets/{petId}: summary changed",
+            "M GET /pets/{petId}: query parameter fields is required (breaking)",
+            "M GET /pets/{petId}: optional query parameter limit added (breaking)",
+            "M PUT /pets/{petId}: response 204 removed (breaking)",
+            "M PUT /pets/{petId}: response 200 added (breaking)",
+            "A DELETE /pets/{petId}",
+            "M model Pet: property kind removed (breaking)",
+            "M model Pet: optional property age added (breaking)",
+        ],
+        changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<String>>()
+    );
+    assert_eq!(Some(ChangeLevel::Breaking), get_change_level(&changes));
+}
+
+#[test]
+fn unchanged_and_type_changes() {
+    let spec_ir = parse_ir("ir.openapi.yaml");
+    assert!(diff_spec_ir(&spec_ir, &spec_ir).is_empty());
+    assert_eq!(None, get_change_level(&[]));
+
+    let mut changed_ir = spec_ir.clone();
+    changed_ir.models.insert(
+        "Kind".to_owned(),
+        ModelIr::Primitive {
+            type_name: "i32".to_owned(),
+        },
+    );
+    changed_ir.models.remove("Pet");
+
+    let changes = diff_spec_ir(&spec_ir, &changed_ir);
+    assert_eq!(
+        vec![
+            "M model Kind: type changed from String to i32 (breaking)",
+            "D model Pet (breaking)",
+        ],
+        changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<String>>()
+    );
+}
+
+#[test]
+fn security_changes() {
+    let spec_ir = parse_ir("ir.openapi.yaml");
+    let security = |requirements: Vec<Vec<&str>>| {
+        Some(OperationSecurity {
+            requirements: requirements
+                .into_iter()
+                .map(|requirement| requirement.into_iter().map(str::to_owned).collect())
+                .collect(),
+        })
+    };
+
+    let mut required_ir = spec_ir.clone();
+    required_ir.operations[0].security = security(vec![vec!["bearer"]]);
+    let mut optional_ir = required_ir.clone();
+    optional_ir.operations[0].security = security(vec![vec![], vec!["bearer"]]);
+    let mut other_scheme_ir = required_ir.clone();
+    other_scheme_ir.operations[0].security = security(vec![vec!["api_key"]]);
+
+    let operation = format!(
+        "{} {}",
+        spec_ir.operations[0].method, spec_ir.operations[0].path
+    );
+    for (old, new, expected_change, expected_level) in [
+        (
+            &spec_ir,
+            &required_ir,
+            "auth is required (breaking)",
+            ChangeLevel::Breaking,
+        ),
+        (
+            &required_ir,
+            &optional_ir,
+            "auth is optional (breaking)",
+            ChangeLevel::Breaking,
+        ),
+        (
+            &required_ir,
+            &other_scheme_ir,
+            "security requirements changed",
+            ChangeLevel::Patch,
+        ),
+    ] {
+        let changes = diff_spec_ir(old, new);
+        assert_eq!(
+            vec![format!("M {}: {}", operation, expected_change)],
+            changes
+                .iter()
+                .map(|change| change.to_string())
+                .collect::<Vec<String>>()
+        );
+        assert_eq!(Some(expected_level), get_change_level(&changes));
+    }
+}
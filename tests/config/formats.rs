@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use opage::utils::config::{Config, LogLevel};
+
+fn config_file_path(file_name: &str) -> PathBuf {
+    let mut config_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    config_file_path.push("tests/config/configs");
+    config_file_path.push(file_name);
+    config_file_path
+}
+
+#[test]
+fn minimal_json_config() {
+    let config = Config::from(&config_file_path("minimal.json")).unwrap();
+    assert_eq!("minimal", config.project_metadata.name);
+    assert_eq!("0.0.0", config.project_metadata.version);
+    assert_eq!(Config::new().name_mapping, config.name_mapping);
+    assert_eq!(Config::new().ignore, config.ignore);
+    assert_eq!(LogLevel::Warn, config.generator.log_level);
+}
+
+#[test]
+fn minimal_yaml_config() {
+    let config = Config::from(&config_file_path("minimal.yaml")).unwrap();
+    assert_eq!("minimal", config.project_metadata.name);
+    assert_eq!("0.0.0", config.project_metadata.version);
+}
+
+#[test]
+fn toml_config() {
+    let config = Config::from(&config_file_path("full.toml")).unwrap();
+    assert_eq!("full", config.project_metadata.name);
+    assert_eq!("1.2.3", config.project_metadata.version);
+    assert_eq!(
+        Some(&"TestObjectData".to_owned()),
+        config
+            .name_mapping
+            .struct_mapping
+            .get("/Component/TestObject")
+    );
+    assert!(config.ignore.path_ignored("/internal"));
+    assert!(config.generator.request_options);
+    assert_eq!(LogLevel::Debug, config.generator.log_level);
+}
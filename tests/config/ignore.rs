@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use opage::{generator::rust_reqwest_async::paths::collect_operations, utils::config::Config};
+
+fn load_config(file_name: &str) -> Result<Config, String> {
+    let mut config_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    config_file_path.push("tests/config/configs");
+    config_file_path.push(file_name);
+    Config::from(&config_file_path)
+}
+
+#[test]
+fn path_patterns() {
+    let config = load_config("ignore_patterns.yaml").unwrap();
+    assert!(config.ignore.path_ignored("/internal/status"));
+    assert!(config.ignore.path_ignored("/internal/cells/{cell}"));
+    assert!(config.ignore.path_ignored("/cells/{cell}/debug"));
+    assert!(!config.ignore.path_ignored("/cells/{cell}/robots/debug"));
+    assert!(config.ignore.path_ignored("/v1/legacy/robots"));
+    assert!(!config.ignore.path_ignored("/robots"));
+}
+
+#[test]
+fn component_allow_list() {
+    let config = load_config("ignore_patterns.yaml").unwrap();
+    assert!(!config.ignore.component_ignored("RobotState"));
+    assert!(config.ignore.component_ignored("InternalRobotState"));
+    assert!(config.ignore.component_ignored("Cell"));
+}
+
+#[test]
+fn invalid_pattern_rejected() {
+    assert!(load_config("invalid_pattern.yaml").is_err());
+}
+
+#[test]
+fn operation_filter() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/config/specs/tagged.openapi.yaml");
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let config = load_config("operation_filter.yaml").unwrap();
+    let operation_ids = collect_operations(&spec, &config)
+        .iter()
+        .map(|(_, _, operation)| operation.operation_id.clone().unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(vec!["listCells", "listRobots"], operation_ids);
+
+    let mut config = Config::new();
+    config.ignore = config.ignore.methods(vec!["POST".to_owned()]);
+    let operation_ids = collect_operations(&spec, &config)
+        .iter()
+        .map(|(_, _, operation)| operation.operation_id.clone().unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(vec!["addRobot"], operation_ids);
+}
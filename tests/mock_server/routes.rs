@@ -0,0 +1,47 @@
+use opage::{
+    generator::axum_mock::routes::generate_main_content,
+    utils::{config::Config, report::GenerationReport},
+};
+use std::path::PathBuf;
+
+#[test]
+fn example_responses_routed() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/mock_server/specs/mock.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let mut report = GenerationReport::default();
+    let main_content =
+        generate_main_content(&spec, &Config::new(), &mut report).expect("Failed to generate");
+
+    assert!(main_content.contains("get(get_status).put(put_status)"));
+    assert!(main_content.contains(r##"r#"{"state":"running"}"#"##));
+    assert!(main_content.contains("StatusCode::from_u16(202)"));
+
+    // Parameters within a segment and websockets are not mocked
+    assert!(!main_content.contains("/files/"));
+    assert!(!main_content.contains("async fn stream"));
+
+    assert_eq!(vec!["GET /status", "PUT /status"], report.operations);
+    assert_eq!(
+        vec!["GET /files/{name}.json", "GET /stream"],
+        report
+            .skipped_operations
+            .iter()
+            .map(|skipped_operation| format!(
+                "{} {}",
+                skipped_operation.method, skipped_operation.path
+            ))
+            .collect::<Vec<_>>()
+    );
+    let summary = report.to_string();
+    assert!(summary.contains("Operations generated       2"));
+    assert!(summary.contains("Skipped GET /stream: websocket operations are not mocked"));
+
+    let json_report: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!("GET /status", json_report["operations"][0]);
+    assert_eq!("/stream", json_report["skipped_operations"][1]["path"]);
+    assert_eq!(0, json_report["files"]["written"]);
+}
@@ -1,2 +1,10 @@
 pub mod response;
-pub mod components;
\ No newline at end of file
+pub mod request;
+pub mod components;
+pub mod output;
+pub mod validation;
+pub mod config;
+pub mod mock_server;
+pub mod python_httpx;
+pub mod typescript_fetch;
+pub mod axum_server;
\ No newline at end of file
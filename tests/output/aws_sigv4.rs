@@ -0,0 +1,101 @@
+use std::{fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::project::generate_project,
+    parser::{component::generate_components, security::parse_spec},
+    utils::config::{AwsSigV4Config, Config},
+};
+
+#[test]
+fn requests_signed_before_send() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/security/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = parse_spec(&yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "signed".to_owned();
+    config.generator.security = true;
+    config.generator.aws_sigv4 = Some(AwsSigV4Config {
+        region: "eu-central-1".to_owned(),
+        service: "execute-api".to_owned(),
+    });
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_aws_sigv4");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    assert!(read("src/lib.rs").contains("pub mod aws_sigv4;"));
+    let cargo_toml = read("Cargo.toml");
+    assert!(cargo_toml.contains("aws-credential-types = \"1\"\n"));
+    assert!(cargo_toml.contains("aws-sigv4 = \"1\"\n"));
+
+    let aws_sigv4 = read("src/aws_sigv4.rs");
+    assert!(aws_sigv4.contains("pub const REGION: &str = \"eu-central-1\";"));
+    assert!(aws_sigv4.contains("pub const SERVICE: &str = \"execute-api\";"));
+    assert!(aws_sigv4
+        .contains("pub fn sign_request(request: &mut reqwest::Request) -> Result<(), String> {"));
+
+    // Signed after the credentials of `auth` are added, unsignable requests are not sent
+    let list_orders = read("src/paths/list_orders.rs");
+    assert!(list_orders.contains("let request_builder = auth.apply(request_builder);"));
+    assert!(
+        list_orders.contains("let response = match crate::aws_sigv4::send(request_builder).await")
+    );
+    assert!(list_orders
+        .contains(") -> Result<ListOrdersResponseType, crate::aws_sigv4::SignedRequestError> {"));
+    let stream_events = read("src/paths/stream_events.rs");
+    assert!(stream_events.contains("crate::aws_sigv4::send(request_builder).await"));
+    assert!(!stream_events.contains(".send().await"));
+}
+
+#[test]
+fn request_option_attempts_signed() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/aws_sigv4/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = parse_spec(&yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "signed_options".to_owned();
+    config.generator.security = true;
+    config.generator.request_options = true;
+    config.generator.aws_sigv4 = Some(AwsSigV4Config {
+        region: "eu-central-1".to_owned(),
+        service: "execute-api".to_owned(),
+    });
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_aws_sigv4_request_options");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    let request_options = read("src/request_options.rs");
+    assert!(request_options
+        .contains(") -> Result<reqwest::Response, crate::aws_sigv4::SignedRequestError> {"));
+    assert!(request_options
+        .contains("let delay = match crate::aws_sigv4::send(attempt_request_builder).await {"));
+    assert!(!request_options.contains(".send().await"));
+
+    // The option headers are added by `send_with_options`, before every attempt is signed
+    let create_order = read("src/paths/create_order.rs");
+    assert!(create_order.contains("crate::request_options::send_with_options(request_builder"));
+    assert!(!create_order.contains("crate::aws_sigv4::send("));
+
+    // Every page gets the credentials and options of the first one
+    let list_orders = read("src/paths/list_orders.rs");
+    let pages = list_orders.split("_pages<'a>(").nth(1).unwrap();
+    assert!(pages.contains("let request_builder = auth.apply(request_builder);"));
+    assert!(pages.contains("crate::request_options::send_with_options(request_builder"));
+}
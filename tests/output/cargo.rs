@@ -0,0 +1,337 @@
+use std::{collections::BTreeSet, fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::{
+        cargo::{add_missing_dependencies, generate_cargo_content, get_missing_features},
+        client::generate_client_content,
+        project::generate_project,
+    },
+    parser::component::generate_components,
+    utils::{
+        config::{Config, ResponseCompression},
+        template_override::get_unknown_template_overrides,
+    },
+};
+
+#[test]
+fn cargo_metadata_and_dependencies_configurable() {
+    let mut config_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    config_file_path.push("tests/config/configs/cargo.yaml");
+    let config = Config::from(&config_file_path).unwrap();
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        false,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+
+    assert!(cargo_content.contains(
+        "edition = \"2024\"\nlicense = \"MIT\"\nrepository = \"https://example.com/robots\"\n"
+    ));
+    // Features of the generated code are kept
+    assert!(cargo_content.contains(
+        "reqwest = { version = \"0.12.9\", default-features = false, features = [\"json\", \"rustls-tls\"] }"
+    ));
+    assert!(cargo_content.contains("serde_json = \"1.0.140\""));
+    assert!(cargo_content.contains("chrono = \"0.4\""));
+    assert!(cargo_content.contains("serde = { version = \"1.0.215\", features = [\"derive\"] }"));
+}
+
+#[test]
+fn default_cargo_content() {
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        false,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+
+    assert!(cargo_content.starts_with(
+        "[package]\nname = \"robots\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n[dependencies]\n"
+    ));
+    assert!(!cargo_content.contains("license"));
+    assert!(!cargo_content.contains("[features]"));
+}
+
+#[test]
+fn optional_dependencies_only_if_used() {
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+
+    let used_crates = BTreeSet::from(["futures".to_owned(), "tungstenite".to_owned()]);
+    let cargo_content =
+        generate_cargo_content(&config, false, false, &used_crates, &BTreeSet::new(), None)
+            .unwrap();
+    assert!(cargo_content.contains("futures = \"0.3\""));
+    assert!(cargo_content.contains("tungstenite = \"0.24.0\""));
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        false,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+    assert!(!cargo_content.contains("futures"));
+    assert!(!cargo_content.contains("tungstenite"));
+}
+
+#[test]
+fn missing_dependencies_added_to_existing_cargo_file() {
+    let cargo_content = "[package]\nname = \"robots\"\n\n[dependencies]\n# pinned by us\nreqwest = \"=0.12.9\"\n\n[features]\ndefault = []\n";
+    let generated_content = "[package]\nname = \"robots\"\n\n[dependencies]\nfutures = \"0.3\"\nreqwest = { version = \"0.12.9\", features = [\"json\"] }\n\n[features]\ndefault = []\nlogging = [\"dep:log\"]\n\n[dev-dependencies]\nwiremock = \"0.6\"\n";
+
+    let (content, added_dependencies) =
+        add_missing_dependencies(cargo_content, generated_content).unwrap();
+    assert_eq!(vec!["futures", "wiremock"], added_dependencies);
+    assert_eq!(
+        "[package]\nname = \"robots\"\n\n[dependencies]\n# pinned by us\nreqwest = \"=0.12.9\"\nfutures = \"0.3\"\n\n[features]\ndefault = []\n\n[dev-dependencies]\nwiremock = \"0.6\"\n",
+        content
+    );
+    assert_eq!(
+        (content.clone(), vec![]),
+        add_missing_dependencies(&content, generated_content).unwrap()
+    );
+    assert_eq!(
+        vec!["logging"],
+        get_missing_features(cargo_content, generated_content).unwrap()
+    );
+    assert!(add_missing_dependencies("[dependencies", generated_content).is_err());
+}
+
+#[test]
+fn regeneration_adds_dependencies_of_new_operations() {
+    let mut config = Config::new();
+    config.project_metadata.name = "pagination".to_owned();
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_cargo_regeneration");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+    let cargo_file_path = format!("{}/Cargo.toml", output_dir);
+
+    let generate = |spec_file_path: &str| {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push(spec_file_path);
+        let spec = oas3::from_yaml(fs::read_to_string(path).unwrap()).unwrap();
+        let mut object_database = generate_components(&spec, &config).unwrap();
+        generate_project(output_dir, &mut object_database, &config, &spec);
+    };
+
+    generate("tests/components/specs/ir.openapi.yaml");
+    let cargo_content = fs::read_to_string(&cargo_file_path).unwrap();
+    assert!(!cargo_content.contains("futures"));
+    fs::write(
+        &cargo_file_path,
+        cargo_content.replace("[dependencies]\n", "[dependencies]\nanyhow = \"1\"\n"),
+    )
+    .unwrap();
+
+    // Pages are streamed with futures
+    generate("tests/projects/pagination/spec.openapi.yaml");
+    let cargo_content = fs::read_to_string(&cargo_file_path).unwrap();
+    let cargo_table = cargo_content.parse::<toml::Table>().unwrap();
+    let dependencies = cargo_table["dependencies"].as_table().unwrap();
+    assert!(dependencies.contains_key("anyhow"));
+    assert_eq!(Some("0.3"), dependencies["futures"].as_str());
+}
+
+#[test]
+fn reqwest_stream_feature_for_ndjson() {
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        true,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+    assert!(cargo_content
+        .contains("reqwest = { version = \"0.12.9\", features = [\"json\", \"stream\"] }"));
+}
+
+#[test]
+fn logging_feature_not_enabled_by_default() {
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+    config.generator.request_logging = true;
+    config.generator.tag_features = true;
+
+    let features = BTreeSet::from(["robots".to_owned()]);
+    let cargo_content =
+        generate_cargo_content(&config, false, false, &BTreeSet::new(), &features, None).unwrap();
+
+    assert!(cargo_content.contains("log = { version = \"0.4\", optional = true }"));
+    assert!(cargo_content
+        .ends_with("[features]\ndefault = [\"robots\"]\nrobots = []\nlogging = [\"dep:log\"]\n"));
+}
+
+#[test]
+fn cargo_template_overridden() {
+    let mut template_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    template_dir.push("cargo_template_override");
+    let _ = std::fs::remove_dir_all(&template_dir);
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join("cargo.toml.jinja"),
+        "# {{ name }} {{ version }}\n{{ content }}{% if \"serde\" in dependencies %}# serde\n{% endif %}",
+    )
+    .unwrap();
+    std::fs::write(template_dir.join("unknown.jinja"), "").unwrap();
+
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+    config.generator.template_dir = Some(template_dir.to_str().unwrap().to_owned());
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        false,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+    assert!(cargo_content.starts_with("# robots 0.0.0\n[package]\nname = \"robots\"\n"));
+    assert!(cargo_content.ends_with("\n# serde\n"));
+
+    assert_eq!(
+        vec!["unknown.jinja".to_owned()],
+        get_unknown_template_overrides(template_dir.to_str().unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn response_compression_enables_decompression() {
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+    config.generator.response_compression =
+        vec![ResponseCompression::Brotli, ResponseCompression::Gzip];
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        false,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+    assert!(cargo_content.contains(
+        "reqwest = { version = \"0.12.9\", features = [\"json\", \"brotli\", \"gzip\"] }"
+    ));
+
+    let client_content = generate_client_content(&config.generator).unwrap();
+    assert!(client_content.contains("decompresses gzip, br responses"));
+    assert!(client_content
+        .contains("reqwest::Client::builder()\n        .gzip(true)\n        .brotli(true)\n}"));
+}
+
+#[test]
+fn client_tls_builder_uses_rustls() {
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+    config.generator.client_tls = true;
+    config.generator.response_compression = vec![ResponseCompression::Gzip];
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        false,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+    assert!(cargo_content.contains(
+        "reqwest = { version = \"0.12.9\", features = [\"json\", \"gzip\", \"rustls-tls\"] }"
+    ));
+
+    let client_content = generate_client_content(&config.generator).unwrap();
+    assert!(client_content.contains("pub fn client_builder() -> reqwest::ClientBuilder {"));
+    assert!(client_content.contains("pub struct ApiClientBuilder {"));
+    assert!(client_content
+        .contains("    pub fn identity_pem(self, pem: &[u8]) -> Result<Self, reqwest::Error> {"));
+    assert!(client_content.contains("    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {"));
+    // The builder keeps the decompression of `client_builder()`
+    assert!(client_content
+        .contains("        let mut client_builder = client_builder().use_rustls_tls();\n"));
+    // Without services there is no `ApiClient` to build
+    assert!(!client_content.contains("build_api_client"));
+
+    config.generator.tag_services = true;
+    assert!(generate_client_content(&config.generator)
+        .unwrap()
+        .contains("    pub fn build_api_client(self, server: &str) -> Result<crate::services::ApiClient, reqwest::Error> {"));
+}
+
+#[test]
+fn unix_socket_clients_behind_feature() {
+    let mut config = Config::new();
+    config.project_metadata.name = "docker".to_owned();
+    config.generator.unix_socket = Some("/var/run/docker.sock".to_owned());
+
+    let cargo_content = generate_cargo_content(
+        &config,
+        false,
+        false,
+        &BTreeSet::new(),
+        &BTreeSet::new(),
+        None,
+    )
+    .unwrap();
+    assert!(cargo_content.contains("reqwest = { version = \"0.12.23\", features = [\"json\"] }"));
+    assert!(cargo_content.contains("[features]\ndefault = []\nunix-socket = []"));
+
+    let client_content = generate_client_content(&config.generator).unwrap();
+    assert!(client_content.starts_with("/// Default unix domain socket of the api\n#[cfg(all(unix, feature = \"unix-socket\"))]\npub const UNIX_SOCKET_PATH: &str = \"/var/run/docker.sock\";"));
+    assert!(client_content
+        .contains("    reqwest::Client::builder().unix_socket(path.as_ref().to_path_buf())\n"));
+    assert!(!client_content.contains("pub fn client_builder()"));
+}
+
+#[test]
+fn client_options_with_proxy_settings() {
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+    config.generator.client_options = true;
+
+    let client_content = generate_client_content(&config.generator).unwrap();
+    assert!(client_content.starts_with("/// Proxy settings of the client."));
+    assert!(client_content.contains("    pub http_proxy: Option<reqwest::Url>,\n"));
+    assert!(client_content.contains("    pub https_proxy: Option<reqwest::Url>,\n"));
+    assert!(client_content.contains("    pub no_proxy: Vec<String>,\n"));
+    // Defaults of the environment
+    assert!(client_content
+        .contains("impl Default for ClientOptions {\n    fn default() -> Self {\n        ClientOptions::from_env()\n    }\n}"));
+    assert!(client_content
+        .contains("        let mut client_builder = reqwest::Client::builder().no_proxy();\n"));
+    assert!(client_content.contains("                reqwest::Proxy::http(http_proxy.clone())?\n"));
+    assert!(client_content.contains("                reqwest::Proxy::https(https_proxy.clone())?\n"));
+
+    // The TLS builder takes the options
+    config.generator.client_tls = true;
+    let client_content = generate_client_content(&config.generator).unwrap();
+    assert!(
+        client_content.contains("    pub fn options(mut self, options: ClientOptions) -> Self {")
+    );
+    assert!(client_content.contains(
+        "    pub fn client_builder(self) -> Result<reqwest::ClientBuilder, reqwest::Error> {"
+    ));
+}
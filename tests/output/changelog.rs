@@ -0,0 +1,106 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use opage::{
+    generator::rust_reqwest_async::{
+        changelog::{add_changelog_entry, format_date, generate_changelog_entry},
+        project::generate_project,
+    },
+    parser::{
+        component::generate_components,
+        spec_diff::{ChangeLevel, SpecChange, SpecChangeKind},
+    },
+    utils::config::{Config, VersionBump},
+};
+
+#[test]
+fn dates_of_timestamps() {
+    assert_eq!("1970-01-01", format_date(UNIX_EPOCH));
+    assert_eq!(
+        "2024-02-29",
+        format_date(UNIX_EPOCH + Duration::from_secs(1709208000))
+    );
+    assert_eq!(
+        "2026-10-17",
+        format_date(UNIX_EPOCH + Duration::from_secs(1792238400))
+    );
+}
+
+#[test]
+fn entries_grouped_by_kind() {
+    let changes = vec![
+        SpecChange {
+            kind: SpecChangeKind::Removed,
+            subject: "model Pet".to_owned(),
+            detail: None,
+            level: ChangeLevel::Breaking,
+        },
+        SpecChange {
+            kind: SpecChangeKind::Added,
+            subject: "DELETE /pets/{petId}".to_owned(),
+            detail: None,
+            level: ChangeLevel::Additive,
+        },
+        SpecChange {
+            kind: SpecChangeKind::Changed,
+            subject: "GET /pets/{petId}".to_owned(),
+            detail: Some("summary changed".to_owned()),
+            level: ChangeLevel::Patch,
+        },
+    ];
+    let entry = generate_changelog_entry("0.2.0", "2026-10-17", &changes);
+    assert_eq!(
+        "## 0.2.0 - 2026-10-17\n\n### Added\n\n- `DELETE /pets/{petId}`\n\n### Changed\n\n- `GET /pets/{petId}`: summary changed\n\n### Removed\n\n- `model Pet` (breaking)\n",
+        entry
+    );
+
+    let changelog = add_changelog_entry(None, "## 0.1.0 - 2026-01-01\n");
+    assert_eq!("# Changelog\n\n## 0.1.0 - 2026-01-01\n", changelog);
+    // Newer entries come first
+    assert_eq!(
+        "# Changelog\n\n## 0.2.0 - 2026-10-17\n\n## 0.1.0 - 2026-01-01\n",
+        add_changelog_entry(Some(&changelog), "## 0.2.0 - 2026-10-17\n")
+    );
+}
+
+#[test]
+fn changelog_of_regenerations() {
+    let mut config = Config::new();
+    config.project_metadata.name = "pets".to_owned();
+    config.project_metadata.version = "0.3.1".to_owned();
+    config.generator.version_bump = VersionBump::Bump;
+    config.generator.changelog = true;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_changelog");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let generate = |spec_file_name: &str| {
+        let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        spec_file_path.push("tests/components/specs");
+        spec_file_path.push(spec_file_name);
+        let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+        let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+        let mut object_database = generate_components(&spec, &config).unwrap();
+        generate_project(output_dir, &mut object_database, &config, &spec);
+    };
+    let changelog_path = format!("{}/CHANGELOG.md", output_dir);
+
+    generate("ir.openapi.yaml");
+    assert!(!fs::exists(&changelog_path).unwrap());
+
+    generate("ir_changed.openapi.yaml");
+    // Unchanged specs add no entry
+    generate("ir_changed.openapi.yaml");
+
+    let changelog = fs::read_to_string(&changelog_path).unwrap();
+    assert_eq!(1, changelog.matches("\n## ").count());
+    assert!(changelog.starts_with("# Changelog\n\n## 0.4.0 - "));
+    assert!(changelog.contains("### Added\n\n- `DELETE /pets/{petId}`\n"));
+    assert!(changelog.contains("- `model Pet`: property kind removed (breaking)\n"));
+}
@@ -0,0 +1,116 @@
+use std::{collections::BTreeSet, fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::{
+        features::{get_cfg_attribute, get_object_features, get_tag_feature_name},
+        project::generate_project,
+        readme::OperationSummary,
+    },
+    parser::component::generate_components,
+    utils::config::Config,
+};
+
+fn operation(module_name: &str, tags: &[&str], object_modules: &[&str]) -> OperationSummary {
+    OperationSummary {
+        module_name: module_name.to_owned(),
+        method: "GET".to_owned(),
+        path: "/".to_owned(),
+        tags: tags.iter().map(|&tag| tag.to_owned()).collect(),
+        functions: vec![],
+        object_modules: object_modules
+            .iter()
+            .map(|&object_module| object_module.to_owned())
+            .collect(),
+        used_crates: BTreeSet::new(),
+        items: BTreeSet::new(),
+    }
+}
+
+#[test]
+fn objects_gated_by_tags_of_their_operations() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/tag_features/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+    let object_database = generate_components(&spec, &config).unwrap();
+
+    let operations = vec![
+        operation("list_robots", &["Robots"], &["robot"]),
+        operation("start_motion", &["Motion Planning"], &["motion", "pose"]),
+        operation("get_health", &[], &["health"]),
+    ];
+    let object_features = get_object_features(&operations, &object_database, &config.name_mapping);
+
+    let features = |names: &[&str]| {
+        names
+            .iter()
+            .map(|&name| name.to_owned())
+            .collect::<BTreeSet<String>>()
+    };
+    assert_eq!(object_features["robot"], features(&["robots"]));
+    assert_eq!(object_features["motion"], features(&["motion_planning"]));
+    // Used through Robot and Motion
+    assert_eq!(
+        object_features["position"],
+        features(&["motion_planning", "robots"])
+    );
+    // Used by an untagged operation or not at all
+    assert!(!object_features.contains_key("health"));
+    assert!(!object_features.contains_key("unused"));
+
+    assert_eq!(
+        get_cfg_attribute(&object_features["position"]),
+        "#[cfg(any(feature = \"motion_planning\", feature = \"robots\"))]\n"
+    );
+    assert_eq!(get_tag_feature_name("Motion Planning"), "motion_planning");
+}
+
+#[test]
+fn tags_named_like_built_in_features_prefixed() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/tag_feature_names/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let mut config = Config::new();
+    config.project_metadata.name = "tag_feature_names".to_owned();
+    config.generator.tag_features = true;
+    config.generator.tag_services = true;
+    config.generator.service_traits = true;
+    config.generator.service_mocks = true;
+    config.generator.request_logging = true;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_tag_feature_names");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    // Duplicate keys are rejected by cargo
+    let cargo_toml = fs::read_to_string(format!("{}/Cargo.toml", output_dir)).unwrap();
+    let cargo_toml = cargo_toml
+        .parse::<toml::Table>()
+        .expect("Invalid Cargo.toml");
+    let features = cargo_toml["features"].as_table().unwrap();
+    assert_eq!(
+        features.keys().collect::<BTreeSet<&String>>(),
+        BTreeSet::from(
+            [
+                "default",
+                "logging",
+                "mock",
+                "tag_default",
+                "tag_logging",
+                "tag_mock"
+            ]
+            .map(|feature| feature.to_owned())
+        )
+        .iter()
+        .collect()
+    );
+    assert_eq!(get_tag_feature_name("Logging"), "tag_logging");
+}
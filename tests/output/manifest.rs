@@ -0,0 +1,229 @@
+use std::{fs, path::PathBuf};
+
+use opage::utils::{
+    output::{diff_output, OutputChange, OutputWriter},
+    report::OutputStats,
+};
+
+#[test]
+fn stale_files_removed() {
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_manifest");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let output = OutputWriter::new(output_dir);
+    output.write("src/objects/kept.rs", "kept").unwrap();
+    output.write("src/objects/stale.rs", "stale").unwrap();
+    output.write("src/objects/modified.rs", "modified").unwrap();
+    output
+        .write("src/objects/formatted.rs", "formatted")
+        .unwrap();
+    assert_eq!(
+        OutputStats {
+            written: 4,
+            unchanged: 0,
+            removed: 0
+        },
+        output.finish().unwrap()
+    );
+
+    fs::write(
+        format!("{}/src/objects/modified.rs", output_dir),
+        "modified\n// <opage:keep>\nuser change\n// </opage:keep>\n",
+    )
+    .unwrap();
+    // Like `cargo fmt` of the output
+    fs::write(
+        format!("{}/src/objects/formatted.rs", output_dir),
+        "formatted\n",
+    )
+    .unwrap();
+
+    let output = OutputWriter::new(output_dir);
+    output.write("src/objects/kept.rs", "kept").unwrap();
+    // The file with user code is kept and not counted as removed
+    assert_eq!(
+        OutputStats {
+            written: 0,
+            unchanged: 1,
+            removed: 2
+        },
+        output.finish().unwrap()
+    );
+
+    assert!(fs::exists(format!("{}/src/objects/kept.rs", output_dir)).unwrap());
+    assert!(!fs::exists(format!("{}/src/objects/stale.rs", output_dir)).unwrap());
+    assert!(fs::exists(format!("{}/src/objects/modified.rs", output_dir)).unwrap());
+    assert!(!fs::exists(format!("{}/src/objects/formatted.rs", output_dir)).unwrap());
+}
+
+#[test]
+fn protected_regions_kept() {
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_protected_regions");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+    let lib_path = format!("{}/src/lib.rs", output_dir);
+
+    let output = OutputWriter::new(output_dir);
+    output
+        .write("src/lib.rs", "pub mod objects;\npub mod paths;\n")
+        .unwrap();
+    output.finish().unwrap();
+
+    fs::write(
+        &lib_path,
+        "// <opage:keep>\n#![allow(clippy::all)]\n// </opage:keep>\npub mod objects;\n// <opage:keep>\npub mod client;\n// </opage:keep>\npub mod paths;\n",
+    )
+    .unwrap();
+    assert!(diff_output(output_dir, output_dir).is_empty());
+
+    let output = OutputWriter::new(output_dir);
+    output
+        .write(
+            "src/lib.rs",
+            "pub mod objects;\npub mod paths;\npub mod prelude;\n",
+        )
+        .unwrap();
+    output.finish().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&lib_path).unwrap(),
+        "// <opage:keep>\n#![allow(clippy::all)]\n// </opage:keep>\npub mod objects;\n// <opage:keep>\npub mod client;\n// </opage:keep>\npub mod paths;\npub mod prelude;\n"
+    );
+
+    // Files with user code are not removed
+    let output = OutputWriter::new(output_dir);
+    output.finish().unwrap();
+    assert!(fs::exists(&lib_path).unwrap());
+
+    fs::write(&lib_path, "// <opage:keep>\npub mod client;\n").unwrap();
+    let output = OutputWriter::new(output_dir);
+    assert!(output.write("src/lib.rs", "pub mod objects;\n").is_err());
+}
+
+#[test]
+fn protected_regions_kept_in_formatted_output() {
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_protected_regions_formatted");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+    let order_path = format!("{}/src/objects/order.rs", output_dir);
+
+    let output = OutputWriter::new(output_dir);
+    output
+        .write(
+            "src/objects/order.rs",
+            "pub struct Order{\npub item:String,\n}\n\n\nimpl Order{\npub fn new(item:String)->Self{\nOrder{item}\n}\n}\n\n\nimpl Default for Order{\nfn default()->Self{\nOrder{item:String::new()}\n}\n}\n",
+        )
+        .unwrap();
+    output.finish().unwrap();
+
+    // Like `cargo fmt` of the output, with a method in the generated impl and an impl after it
+    fs::write(
+        &order_path,
+        "pub struct Order {\n    pub item: String,\n}\n\nimpl Order {\n    pub fn new(item: String) -> Self {\n        Order { item }\n    }\n    // <opage:keep>\n    pub fn is_empty(&self) -> bool {\n        self.item.is_empty()\n    }\n    // </opage:keep>\n}\n\n// <opage:keep>\nimpl AsRef<str> for Order {\n    fn as_ref(&self) -> &str {\n        &self.item\n    }\n}\n// </opage:keep>\n\nimpl Default for Order {\n    fn default() -> Self {\n        Order {\n            item: String::new(),\n        }\n    }\n}\n",
+    )
+    .unwrap();
+
+    let output = OutputWriter::new(output_dir);
+    output
+        .write(
+            "src/objects/order.rs",
+            "pub struct Order{\npub item:String,\npub count:u32,\n}\n\n\nimpl Order{\npub fn new(item:String)->Self{\nOrder{item}\n}\n}\n\n\nimpl Default for Order{\nfn default()->Self{\nOrder{item:String::new()}\n}\n}\n",
+        )
+        .unwrap();
+    output.finish().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&order_path).unwrap(),
+        "pub struct Order{\npub item:String,\npub count:u32,\n}\n\n\nimpl Order{\npub fn new(item:String)->Self{\nOrder{item}\n}\n    // <opage:keep>\n    pub fn is_empty(&self) -> bool {\n        self.item.is_empty()\n    }\n    // </opage:keep>\n}\n\n\n// <opage:keep>\nimpl AsRef<str> for Order {\n    fn as_ref(&self) -> &str {\n        &self.item\n    }\n}\n// </opage:keep>\nimpl Default for Order{\nfn default()->Self{\nOrder{item:String::new()}\n}\n}\n"
+    );
+
+    // A region after a repeated line can't be placed reliably and is appended
+    fs::write(
+        &order_path,
+        "#[derive(Debug)]\n// <opage:keep>\n#[derive(Clone)]\n// </opage:keep>\npub struct Order;\n\n#[derive(Debug)]\npub struct Item;\n",
+    )
+    .unwrap();
+    let output = OutputWriter::new(output_dir);
+    output
+        .write(
+            "src/objects/order.rs",
+            "#[derive(Debug)]\npub struct Order;\n\n#[derive(Debug)]\npub struct Item;\n",
+        )
+        .unwrap();
+    output.finish().unwrap();
+    assert_eq!(
+        fs::read_to_string(&order_path).unwrap(),
+        "#[derive(Debug)]\npub struct Order;\n\n#[derive(Debug)]\npub struct Item;\n// <opage:keep>\n#[derive(Clone)]\n// </opage:keep>\n"
+    );
+}
+
+#[test]
+fn formatted_output_not_rewritten() {
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_formatted");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+    let lib_path = format!("{}/src/lib.rs", output_dir);
+
+    let output = OutputWriter::new(output_dir);
+    output
+        .write("src/lib.rs", "pub mod objects;pub mod paths;")
+        .unwrap();
+    output.finish().unwrap();
+
+    // Like `cargo fmt` of the output
+    fs::write(&lib_path, "pub mod objects;\npub mod paths;\n").unwrap();
+    let modified = fs::metadata(&lib_path).unwrap().modified().unwrap();
+
+    let output = OutputWriter::new(output_dir);
+    output
+        .write("src/lib.rs", "pub mod objects;pub mod paths;")
+        .unwrap();
+    assert_eq!(
+        OutputStats {
+            written: 0,
+            unchanged: 1,
+            removed: 0
+        },
+        output.finish().unwrap()
+    );
+    assert_eq!(
+        "pub mod objects;\npub mod paths;\n",
+        fs::read_to_string(&lib_path).unwrap()
+    );
+    assert_eq!(
+        modified,
+        fs::metadata(&lib_path).unwrap().modified().unwrap()
+    );
+
+    // Diffs of the formatted output only report changed generated content
+    let mut generated_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    generated_dir.push("output_formatted_generated");
+    let _ = fs::remove_dir_all(&generated_dir);
+    let generated_dir = generated_dir.to_str().unwrap();
+    let output = OutputWriter::new(generated_dir);
+    output
+        .write("src/lib.rs", "pub mod objects;pub mod paths;")
+        .unwrap();
+    output.finish().unwrap();
+    assert!(diff_output(output_dir, generated_dir).is_empty());
+
+    let _ = fs::remove_dir_all(generated_dir);
+    let output = OutputWriter::new(generated_dir);
+    output.write("src/lib.rs", "pub mod objects;").unwrap();
+    output.finish().unwrap();
+    assert_eq!(
+        vec![OutputChange::Modified("src/lib.rs".to_owned())],
+        diff_output(output_dir, generated_dir)
+    );
+
+    // Changed generated content replaces the formatted file
+    let output = OutputWriter::new(output_dir);
+    output.write("src/lib.rs", "pub mod objects;").unwrap();
+    output.finish().unwrap();
+    assert_eq!("pub mod objects;", fs::read_to_string(&lib_path).unwrap());
+}
@@ -0,0 +1,13 @@
+pub mod aws_sigv4;
+pub mod cargo;
+pub mod changelog;
+pub mod features;
+pub mod manifest;
+pub mod pagination;
+pub mod prelude;
+pub mod readme;
+pub mod security;
+pub mod services;
+pub mod version;
+pub mod webhooks;
+pub mod workspace;
\ No newline at end of file
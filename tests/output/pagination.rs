@@ -0,0 +1,49 @@
+use std::{fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::project::generate_project,
+    parser::component::generate_components, utils::config::Config,
+};
+
+#[test]
+fn pages_sent_like_first_page() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/request_options/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "pages".to_owned();
+    config.generator.request_options = true;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_pagination");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    assert!(read("src/lib.rs").contains("pub mod pagination;"));
+    assert!(read("src/pagination.rs")
+        .contains("pub fn get_next_link(headers: &HeaderMap, url: &Url) -> Option<Url> {"));
+
+    // Follow-up pages reuse the first request, so the options apply to every page
+    let list_group_members = read("src/paths/list_group_members.rs");
+    assert!(list_group_members
+        .contains("request_options: Option<&'a crate::request_options::RequestOptions>,"));
+    assert!(list_group_members.contains(
+        "let link_next_url = crate::pagination::get_next_link(response.headers(), &url);"
+    ));
+    assert!(list_group_members.contains("*request.url_mut() = next_url;"));
+    let pages = list_group_members.split("_pages<'a>(").nth(1).unwrap();
+    assert!(pages.contains("crate::request_options::send_with_options(request_builder"));
+    assert!(!pages.contains(".send().await"));
+
+    // Cursor pages don't need the Link header parser
+    let list_users = read("src/paths/list_users.rs");
+    assert!(list_users.contains("let next_url = next_cursor_url;"));
+    assert!(!list_users.contains("crate::pagination::"));
+}
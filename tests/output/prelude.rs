@@ -0,0 +1,67 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use opage::{
+    generator::rust_reqwest_async::{
+        prelude::{generate_prelude_content, get_public_items},
+        readme::OperationSummary,
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::config::Config,
+};
+
+fn operation(module_name: &str, code: &str) -> OperationSummary {
+    OperationSummary {
+        module_name: module_name.to_owned(),
+        method: "GET".to_owned(),
+        path: "/".to_owned(),
+        tags: vec!["Robots".to_owned()],
+        functions: vec![],
+        object_modules: BTreeSet::new(),
+        used_crates: BTreeSet::new(),
+        items: get_public_items(code),
+    }
+}
+
+#[test]
+fn prelude_skips_ambiguous_path_items() {
+    let create_robot = operation(
+        "create_robot",
+        "pub enum NotFoundValue {}\npub struct CreateRobotPathParameters {}\nimpl CreateRobotPathParameters {\n    pub fn new() -> Self {}\n}\npub async fn create_robot() {}\n",
+    );
+    let update_robot = operation(
+        "update_robot",
+        "pub enum NotFoundValue {}\npub async fn update_robot() {}\npub fn update_robot_404_response_example() {}\n",
+    );
+    let config = Config::new();
+
+    let prelude_content = generate_prelude_content(
+        &[create_robot, update_robot],
+        &ObjectDatabase::new(),
+        &config.name_mapping,
+        &BTreeMap::new(),
+        &config.generator,
+    );
+    assert_eq!(
+        prelude_content,
+        "pub use crate::paths::create_robot::{CreateRobotPathParameters, create_robot};\n\
+        pub use crate::paths::update_robot::{update_robot, update_robot_404_response_example};\n"
+    );
+}
+
+#[test]
+fn prelude_exports_enabled_client_types() {
+    let mut config = Config::new();
+    config.generator.request_options = true;
+
+    let prelude_content = generate_prelude_content(
+        &[],
+        &ObjectDatabase::new(),
+        &config.name_mapping,
+        &BTreeMap::new(),
+        &config.generator,
+    );
+    assert_eq!(
+        prelude_content,
+        "pub use crate::request_options::RequestOptions;\n"
+    );
+}
@@ -0,0 +1,72 @@
+use opage::{
+    generator::rust_reqwest_async::{
+        features::get_used_object_modules,
+        path::http_request::generate_operation,
+        prelude::get_public_items,
+        readme::{generate_readme_content, get_function_signatures, OperationSummary},
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::config::Config,
+};
+use reqwest::Method;
+use std::{collections::BTreeSet, path::PathBuf};
+
+#[test]
+fn readme_lists_operations_by_tag() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/request/specs/request_body_methods.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/items").unwrap();
+
+    let mut object_database = ObjectDatabase::new();
+    let mut config = Config::new();
+    config.project_metadata.name = "items-client".to_owned();
+    config.project_metadata.version = "1.2.3".to_owned();
+
+    let mut operations = vec![];
+    for (method, operation, tags) in [
+        (Method::GET, path_spec.get.as_ref().unwrap(), vec!["items"]),
+        (Method::DELETE, path_spec.delete.as_ref().unwrap(), vec![]),
+    ] {
+        let operation_id = operation.operation_id.clone().unwrap();
+        let code = generate_operation(
+            &spec,
+            &config,
+            &method,
+            "/items",
+            operation,
+            &mut object_database,
+        )
+        .expect("Failed to generate operation");
+        operations.push(OperationSummary {
+            functions: get_function_signatures(&code, &operation_id),
+            module_name: operation_id,
+            method: method.as_str().to_owned(),
+            path: "/items".to_owned(),
+            tags: tags.into_iter().map(String::from).collect(),
+            object_modules: get_used_object_modules(&code),
+            used_crates: BTreeSet::new(),
+            items: get_public_items(&code),
+        });
+    }
+
+    assert_eq!(operations[0].functions.len(), 1);
+    assert_eq!(
+        operations[0].functions[0].signature,
+        "async fn search_items(client: &reqwest::Client, server: &str, content: ItemFilter) -> Result<SearchItemsResponseType, reqwest::Error>"
+    );
+
+    let readme = generate_readme_content(&config, &spec, &operations).expect("Failed to render");
+
+    assert!(readme.starts_with("# items-client 1.2.3\n"));
+    assert!(readme.contains("use items_client::paths::search_items;"));
+    assert!(readme.contains("search_items::search_items(&client, server, /* parameters */)"));
+    let items_group = readme.find("### items").expect("Missing tag group");
+    let other_group = readme.find("### Other").expect("Missing untagged group");
+    let search_items = readme.find("#### `GET /items`").unwrap();
+    let delete_items = readme.find("#### `DELETE /items`").unwrap();
+    assert!(items_group < search_items && search_items < other_group);
+    assert!(other_group < delete_items);
+}
@@ -0,0 +1,165 @@
+use std::{fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::project::generate_project,
+    parser::{
+        component::generate_components,
+        ir::parse_spec_ir,
+        security::{parse_spec, OperationSecurity},
+    },
+    utils::config::Config,
+};
+
+#[test]
+fn auth_parameters_by_operation_security() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/security/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = parse_spec(&yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "security".to_owned();
+    config.generator.security = true;
+
+    let spec_ir = parse_spec_ir(&spec, &config).unwrap();
+    let security = |operation_id: &str| {
+        spec_ir
+            .operations
+            .iter()
+            .find(|operation| operation.operation_id == operation_id)
+            .and_then(|operation| operation.security.clone())
+    };
+    // Operations without security of their own use the global one
+    assert_eq!(
+        Some(OperationSecurity {
+            requirements: vec![vec!["bearer".to_owned()]]
+        }),
+        security("list_orders")
+    );
+    assert_eq!(
+        Some(OperationSecurity {
+            requirements: vec![vec!["api_key".to_owned()], vec!["basic".to_owned()]]
+        }),
+        security("create_order")
+    );
+    assert!(security("get_catalog").unwrap().is_optional());
+    assert!(security("get_health").unwrap().is_optional());
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_security");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    assert!(read("src/lib.rs").contains("pub mod auth;"));
+    assert!(read("src/prelude.rs").contains("pub use crate::auth::Auth;"));
+
+    let auth = read("src/auth.rs");
+    assert!(auth.contains("    Bearer(String),"));
+    assert!(auth.contains("    ApiKey(String),"));
+    assert!(auth.contains(
+        "            Auth::ApiKey(credentials) => request_builder.header(\"X-Api-Key\", credentials),"
+    ));
+    assert!(auth.contains(
+        "            Auth::Session(credentials) => request_builder.query(&[(\"session\", credentials)]),"
+    ));
+    // Client certificates aren't credentials of a request
+    assert!(!auth.contains("ClientCertificate"));
+
+    for operation in ["list_orders", "stream_events"] {
+        let content = read(&format!("src/paths/{}.rs", operation));
+        assert!(content.contains("    auth: &crate::auth::Auth,\n"));
+        assert!(content.contains("    let request_builder = auth.apply(request_builder);\n"));
+    }
+    for operation in ["get_catalog", "get_health"] {
+        let content = read(&format!("src/paths/{}.rs", operation));
+        assert!(content.contains("    auth: Option<&crate::auth::Auth>,\n"));
+        assert!(content.contains("        Some(auth) => auth.apply(request_builder),\n"));
+    }
+}
+
+#[test]
+fn security_is_opt_in() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/security/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = parse_spec(&yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "security".to_owned();
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_security_disabled");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    assert!(!PathBuf::from(format!("{}/src/auth.rs", output_dir)).exists());
+    let list_orders =
+        fs::read_to_string(format!("{}/src/paths/list_orders.rs", output_dir)).unwrap();
+    assert!(!list_orders.contains("auth"));
+}
+
+#[test]
+fn auth_requirement_combinations() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/security/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = parse_spec(&yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "security".to_owned();
+    config.generator.security = true;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_security_requirements");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+
+    // Alternative schemes
+    let create_order = read("src/paths/create_order.rs");
+    assert!(create_order.contains("pub enum CreateOrderAuthRequirement {"));
+    assert!(create_order.contains("    ApiKey(crate::auth::ApiKeyCredentials),\n"));
+    assert!(create_order.contains("    Basic(crate::auth::BasicCredentials),\n"));
+    assert!(create_order.contains("    auth: &CreateOrderAuthRequirement,\n"));
+
+    // Schemes which are required together, mutual TLS has no credentials of the request
+    let get_report = read("src/paths/get_report.rs");
+    assert!(get_report.contains(
+        "    ApiKeyAndSession {\n        api_key: crate::auth::ApiKeyCredentials,\n        session: crate::auth::SessionCredentials,\n    },\n"
+    ));
+    assert!(get_report.contains(
+        "            GetReportAuthRequirement::ApiKeyAndSession { api_key, session } => {\n                let request_builder = api_key.apply(request_builder);\n                session.apply(request_builder)\n            }\n"
+    ));
+    assert!(get_report
+        .contains("            GetReportAuthRequirement::ClientCertificate => request_builder,\n"));
+    assert!(get_report.contains("    auth: &GetReportAuthRequirement,\n"));
+    assert!(get_report.contains("    let request_builder = auth.apply(request_builder);\n"));
+
+    // Each field only accepts the credentials of its scheme
+    let auth = read("src/auth.rs");
+    assert!(auth.contains("pub struct ApiKeyCredentials(pub String);\n"));
+    assert!(auth.contains(
+        "pub struct BasicCredentials {\n    pub username: String,\n    pub password: Option<String>,\n}\n"
+    ));
+    assert!(auth.contains(
+        "        let Self(credentials) = self;\n        request_builder.query(&[(\"session\", credentials)])\n"
+    ));
+
+    // A single scheme besides anonymous requests takes an `Auth`
+    assert!(!read("src/paths/get_catalog.rs").contains("AuthRequirement"));
+    assert!(read("src/prelude.rs").contains("GetReportAuthRequirement"));
+}
@@ -0,0 +1,113 @@
+use std::{fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::project::generate_project,
+    parser::component::generate_components, utils::config::Config,
+};
+
+#[test]
+fn service_per_tag_with_operation_methods() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/tag_services/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "shop".to_owned();
+    config.generator.tag_services = true;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_services");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    assert!(read("src/lib.rs").contains("pub mod services;"));
+
+    let services = read("src/services.rs");
+    assert!(services.contains("pub struct ApiClient {"));
+    for (accessor, service) in [
+        ("users", "UsersService"),
+        ("orders", "OrdersService"),
+        ("other", "OtherService"),
+    ] {
+        assert!(services.contains(&format!("pub fn {}(&self) -> {}<'_> {{", accessor, service)));
+        assert!(services.contains(&format!("pub struct {}<'a> {{", service)));
+    }
+    // Without tag features services aren't gated
+    assert!(!services.contains("#[cfg("));
+
+    let get_user = read("src/paths/get_user.rs");
+    assert!(get_user.contains("impl crate::services::UsersService<'_> {"));
+    assert!(get_user.contains(
+        "    pub async fn get_user(\n        &self,\n        path_parameters: GetUserPathParameters,\n    ) -> Result<GetUserResponseType, reqwest::Error> {\n        get_user(\n            &self.api_client.client,\n            &self.api_client.server,\n            path_parameters,\n        )\n        .await\n    }"
+    ));
+
+    // Operations with several tags are methods of each service
+    let create_order = read("src/paths/create_order.rs");
+    assert!(create_order.contains("impl crate::services::OrdersService<'_> {"));
+    assert!(create_order.contains("impl crate::services::UsersService<'_> {"));
+
+    // Additional functions of an operation are methods too
+    let get_invoice = read("src/paths/get_invoice.rs");
+    assert!(get_invoice.contains("    pub async fn get_invoice_to_file("));
+    assert!(get_invoice.contains("        progress: impl FnMut(u64, Option<u64>),\n"));
+
+    assert!(read("src/paths/get_health.rs").contains("impl crate::services::OtherService<'_> {"));
+    // Websockets connect to a host instead of the client
+    assert!(!read("src/paths/stream_orders.rs").contains("impl crate::services"));
+}
+
+#[test]
+fn service_traits_with_mocks() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/tag_services/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "shop".to_owned();
+    config.generator.tag_services = true;
+    config.generator.service_traits = true;
+    config.generator.service_mocks = true;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_service_traits");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    let services = read("src/services.rs");
+    assert!(services.starts_with("use crate::prelude::*;\n"));
+    for (service_trait, service) in [
+        ("UsersApi", "UsersService"),
+        ("OrdersApi", "OrdersService"),
+        ("OtherApi", "OtherService"),
+    ] {
+        assert!(services.contains(&format!(
+            "#[cfg_attr(feature = \"mock\", mockall::automock)]\npub trait {} {{",
+            service_trait
+        )));
+        assert!(services.contains(&format!("impl {} for {}<'_> {{", service_trait, service)));
+    }
+    assert!(services.contains(
+        "    fn get_user(\n        &self,\n        path_parameters: GetUserPathParameters,\n    ) -> impl std::future::Future<Output = Result<GetUserResponseType, reqwest::Error>> + Send;"
+    ));
+    assert!(services.contains("        UsersService::get_user(\n            self,\n            path_parameters,\n        )\n        .await"));
+    // Downloads take an `impl FnMut` progress callback which a trait can't forward
+    assert!(services.contains("    fn get_invoice("));
+    assert!(!services.contains("get_invoice_to_file"));
+
+    let cargo = read("Cargo.toml");
+    assert!(cargo.contains("mockall = { version = \"0.13\", optional = true }"));
+    assert!(cargo.contains("mock = [\"dep:mockall\"]"));
+    assert!(cargo.contains("default = []"));
+}
@@ -0,0 +1,89 @@
+use std::{fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::{
+        project::generate_project,
+        version::{get_package_version, set_package_version, CrateVersion},
+    },
+    parser::{component::generate_components, spec_diff::ChangeLevel},
+    utils::config::{Config, VersionBump},
+};
+
+fn read_spec(spec_file_name: &str) -> oas3::Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/components/specs");
+    spec_file_path.push(spec_file_name);
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    oas3::from_yaml(yaml).expect("Failed to read spec")
+}
+
+#[test]
+fn semver_bumps() {
+    let bump = |version: &str, level: ChangeLevel| {
+        CrateVersion::parse(version)
+            .unwrap()
+            .bump(level)
+            .to_string()
+    };
+
+    assert_eq!("2.0.0", bump("1.4.2", ChangeLevel::Breaking));
+    assert_eq!("1.5.0", bump("1.4.2", ChangeLevel::Additive));
+    assert_eq!("1.4.3", bump("1.4.2", ChangeLevel::Patch));
+    // 0.x versions are breaking with the minor version
+    assert_eq!("0.5.0", bump("0.4.2", ChangeLevel::Breaking));
+    assert_eq!("0.4.3", bump("0.4.2", ChangeLevel::Additive));
+    assert!(CrateVersion::parse("1.0.0-beta.1").is_err());
+}
+
+#[test]
+fn package_version_replaced() {
+    let cargo_content = "[package]\nname = \"pets\"\nversion = \"0.1.0\" # released\n\n[dependencies]\nserde = { version = \"1\" }\n";
+
+    assert_eq!(Some("0.1.0".to_owned()), get_package_version(cargo_content));
+    assert_eq!(
+        "[package]\nname = \"pets\"\nversion = \"0.2.0\" # released\n\n[dependencies]\nserde = { version = \"1\" }\n",
+        set_package_version(cargo_content, "0.2.0").unwrap()
+    );
+    assert!(set_package_version("[dependencies]\nversion = \"1\"\n", "0.2.0").is_err());
+}
+
+#[test]
+fn version_bumped_by_spec_changes() {
+    let mut config = Config::new();
+    config.project_metadata.name = "pets".to_owned();
+    config.project_metadata.version = "1.4.2".to_owned();
+    config.generator.version_bump = VersionBump::Bump;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_version_bump");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let generate = |spec_file_name: &str| {
+        let spec = read_spec(spec_file_name);
+        let mut object_database = generate_components(&spec, &config).unwrap();
+        generate_project(output_dir, &mut object_database, &config, &spec)
+    };
+    let cargo_version =
+        || get_package_version(&fs::read_to_string(format!("{}/Cargo.toml", output_dir)).unwrap());
+
+    // Without snapshot there is nothing to compare
+    assert_eq!(None, generate("ir.openapi.yaml").version);
+    assert!(fs::exists(format!("{}/.opage-ir.json", output_dir)).unwrap());
+
+    let version = generate("ir_changed.openapi.yaml").version.unwrap();
+    assert_eq!("1.4.2", version.previous_version);
+    assert_eq!("2.0.0", version.version);
+    assert_eq!(Some(ChangeLevel::Breaking), version.level);
+    assert!(version.bumped);
+    assert!(version
+        .changes
+        .contains(&"M model Pet: property kind removed (breaking)".to_owned()));
+    assert_eq!(Some("2.0.0".to_owned()), cargo_version());
+
+    let version = generate("ir_changed.openapi.yaml").version.unwrap();
+    assert_eq!(None, version.level);
+    assert!(!version.bumped);
+    assert_eq!(Some("2.0.0".to_owned()), cargo_version());
+}
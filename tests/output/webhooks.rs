@@ -0,0 +1,47 @@
+use std::{fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::project::generate_project,
+    parser::component::generate_components, utils::config::Config,
+};
+
+#[test]
+fn webhook_and_callback_handlers() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/webhooks/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_webhooks");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    assert!(read("src/lib.rs").contains("pub mod webhooks;"));
+
+    let webhooks = read("src/webhooks.rs");
+    assert!(
+        webhooks.contains("//! | [`WebhookHandler::robot_added`] | robotAdded | POST | `Robot` |")
+    );
+    assert!(webhooks.contains("    fn robot_added(\n        &self,\n        payload: Robot,\n"));
+    assert!(webhooks.contains("    fn heartbeat(\n        &self,\n    )"));
+    assert!(webhooks.contains("payload: String,"));
+
+    // Inline payloads of callbacks are objects
+    assert!(webhooks
+        .contains("/// `POST` createSubscription onJobFinished to `{$request.body#/callbackUrl}`"));
+    assert!(webhooks.contains("payload: CreateSubscriptionOnJobFinishedRequestBodyJson,"));
+    assert!(fs::exists(format!(
+        "{}/src/objects/create_subscription_on_job_finished_request_body_json.rs",
+        output_dir
+    ))
+    .unwrap());
+}
@@ -0,0 +1,51 @@
+use std::{fs, path::PathBuf};
+
+use opage::{
+    generator::rust_reqwest_async::project::generate_project,
+    parser::component::generate_components,
+    utils::config::{Config, OutputLayout},
+};
+
+#[test]
+fn workspace_with_models_and_client_crate() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/workspace_layout/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let mut config = Config::new();
+    config.project_metadata.name = "robots".to_owned();
+    config.generator.layout = OutputLayout::Workspace;
+    config.generator.validation = true;
+
+    let mut output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    output_dir.push("output_workspace");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_dir = output_dir.to_str().unwrap();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    generate_project(output_dir, &mut object_database, &config, &spec);
+
+    let read = |path: &str| fs::read_to_string(format!("{}/{}", output_dir, path)).unwrap();
+    assert!(read("Cargo.toml").contains("members = [\"robots-models\", \"robots-client\"]"));
+
+    let models_cargo = read("robots-models/Cargo.toml");
+    assert!(!models_cargo.contains("reqwest"));
+    assert!(models_cargo.contains("regex"));
+    assert!(read("robots-models/src/lib.rs").contains("pub mod objects;"));
+    assert!(fs::exists(format!("{}/robots-models/src/objects/robot.rs", output_dir)).unwrap());
+
+    assert!(read("robots-client/Cargo.toml")
+        .contains("robots-models = { path = \"../robots-models\" }"));
+    assert!(read("robots-client/src/lib.rs").contains("pub use robots_models::objects;"));
+    assert!(
+        read("robots-client/src/validation.rs").contains("pub use robots_models::validation::*;")
+    );
+    assert!(fs::exists(format!(
+        "{}/robots-client/src/paths/list_robots.rs",
+        output_dir
+    ))
+    .unwrap());
+    assert!(!fs::exists(format!("{}/robots-client/src/objects", output_dir)).unwrap());
+}
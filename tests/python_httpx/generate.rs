@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use opage::{
+    generator::python_httpx::{models::generate_models_content, paths::generate_paths_content},
+    parser::component::generate_components,
+    utils::{config::Config, report::GenerationReport},
+};
+
+/// (models.py, paths.py) of a spec of tests/projects
+fn generate_package(project_name: &str) -> (String, String) {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects");
+    spec_file_path.push(project_name);
+    spec_file_path.push("spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    let paths_content = generate_paths_content(
+        &spec,
+        &mut object_database,
+        &config,
+        &mut GenerationReport::default(),
+    )
+    .unwrap();
+    let models_content = generate_models_content(&object_database, &config).unwrap();
+    (models_content, paths_content)
+}
+
+#[test]
+fn models_and_operations_generated() {
+    let (models_content, paths_content) = generate_package("generated_tests");
+
+    assert!(models_content.contains("class Pet(pydantic.BaseModel):"));
+    assert!(models_content.contains("Pet.model_rebuild()"));
+
+    assert!(paths_content.contains(
+        "async def list_pets(\n    client: httpx.AsyncClient,\n    *,\n    limit: int,\n"
+    ));
+    assert!(paths_content.contains("    tags: typing.Optional[list[str]] = None,\n"));
+    assert!(paths_content
+        .contains("        _params.extend((\"tags\", _encode_value(item)) for item in tags)"));
+    assert!(paths_content.contains("f\"/pets/{_encode_path(pet_id)}\""));
+    assert!(paths_content
+        .contains("pydantic.TypeAdapter(list[models.Pet]).validate_python(_response.json())"));
+    assert!(paths_content.contains("-> Response[typing.Union[models.Pet, str, None]]:"));
+}
+
+#[test]
+fn python_names_sanitized() {
+    let (models_content, paths_content) = generate_package("keyword_identifiers");
+
+    assert!(models_content.contains("class Model2DPoint(pydantic.BaseModel):"));
+    assert!(models_content.contains(
+        "    async_: typing.Optional[bool] = pydantic.Field(default=None, alias=\"async\")"
+    ));
+    assert!(models_content.contains("    type_: str = pydantic.Field(alias=\"type\")"));
+    assert!(paths_content.contains("async def match_("));
+    assert!(paths_content.contains("        _params.append((\"type\", _encode_value(type_)))"));
+}
@@ -0,0 +1,68 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn read_spec() -> oas3::Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/batch/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    oas3::from_yaml(yaml).expect("Failed to read spec")
+}
+
+#[test]
+fn batch_items_are_parameter_tuples() {
+    let spec = read_spec();
+    let path_item = &spec.paths.as_ref().unwrap()["/robots/{robot_id}/notes"];
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::POST,
+        "/robots/{robot_id}/notes",
+        path_item.post.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+
+    assert!(generated_code.contains("requests: Vec<(String, AddNotePathParameters)>,"));
+    assert!(generated_code.contains(".map(|(index, (content, path_parameters))| async move {"));
+    assert!(generated_code.contains("add_note(client, server, &content, path_parameters).await"));
+    assert!(generated_code.contains(".buffer_unordered(concurrency.max(1))"));
+}
+
+#[test]
+fn batch_operations_configurable() {
+    let spec = read_spec();
+    let path_item = &spec.paths.as_ref().unwrap()["/robots/{robot_id}"];
+    let mut config = Config::new();
+
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::PUT,
+        "/robots/{robot_id}",
+        path_item.put.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+    assert!(!generated_code.contains("update_robot_batch"));
+
+    config.generator.batch_operations = vec!["update_robot".to_owned()];
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::PUT,
+        "/robots/{robot_id}",
+        path_item.put.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+    assert!(generated_code.contains("pub async fn update_robot_batch("));
+    assert!(generated_code.contains(
+        "requests: Vec<(Robot, UpdateRobotPathParameters, UpdateRobotQueryParameters)>,"
+    ));
+}
@@ -0,0 +1,60 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(method: Method, path: &str) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/conditional_requests/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_item = &spec.paths.as_ref().unwrap()[path];
+    let operation = match method {
+        Method::GET => path_item.get.as_ref(),
+        _ => path_item.put.as_ref(),
+    };
+
+    generate_operation(
+        &spec,
+        &Config::new(),
+        &method,
+        path,
+        operation.unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+#[test]
+fn if_none_match_returns_not_modified() {
+    let generated_code = generate(Method::GET, "/robots/{robot_id}");
+
+    assert!(generated_code.contains("NotModified(Option<String>),"));
+    assert!(
+        generated_code.contains("cache_validation: Option<&crate::conditional::CacheValidation>,")
+    );
+    assert!(generated_code.contains(
+        "304 => Ok(GetRobotResponseType::NotModified(crate::conditional::get_etag(&response))),"
+    ));
+    assert!(generated_code
+        .contains(") -> Result<(GetRobotResponseType, Option<String>), reqwest::Error> {"));
+
+    // Without documented ETag the response type is returned as is
+    let generated_code = generate(Method::GET, "/robots");
+    assert!(generated_code.contains("NotModified(Option<String>),"));
+    assert!(!generated_code.contains("list_robots_with_etag"));
+}
+
+#[test]
+fn if_match_sends_entity_tag() {
+    let generated_code = generate(Method::PUT, "/robots/{robot_id}");
+
+    assert!(!generated_code.contains("NotModified"));
+    assert!(generated_code.contains("pub async fn update_robot_with_etag("));
+    assert!(generated_code.contains(
+        "Some(cache_validation) => request_builder.header(cache_validation.header_name(), cache_validation.entity_tag()),"
+    ));
+}
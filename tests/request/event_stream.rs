@@ -0,0 +1,49 @@
+use opage::{
+    generator::rust_reqwest_async::path::{
+        event_stream_request::generate_operation, utils::is_event_stream_operation,
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(config: &Config, path: &str) -> (bool, String) {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/event_stream/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let operation = spec.paths.as_ref().unwrap()[path].get.as_ref().unwrap();
+
+    let is_event_stream = is_event_stream_operation(
+        &operation.responses(&spec),
+        &config.generator.content_type_mapping,
+    );
+    let generated_code = generate_operation(
+        &spec,
+        config,
+        &Method::GET,
+        path,
+        operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+    (is_event_stream, generated_code)
+}
+
+#[test]
+fn event_streams_by_resolved_content_type() {
+    let mut config = Config::new();
+    let (is_event_stream, generated_code) = generate(&config, "/jobs/{job_id}/progress");
+    assert!(is_event_stream);
+    assert!(generated_code.contains("-> Result<StreamJobProgressEventStream, reqwest::Error>"));
+
+    config.generator.content_type_mapping.insert(
+        "application/vnd.alerts+stream".to_owned(),
+        "text/event-stream".to_owned(),
+    );
+    let (is_event_stream, generated_code) = generate(&config, "/alerts");
+    assert!(is_event_stream);
+    assert!(generated_code.contains("-> Result<StreamAlertsEventStream, reqwest::Error>"));
+}
@@ -0,0 +1,101 @@
+use opage::{
+    generator::{
+        operation::generate_operations, rust_reqwest_async::path::http_request::generate_operation,
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{
+        config::Config,
+        extensions::{ExtensionCallback, ExtensionTarget},
+        report::GenerationReport,
+    },
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn read_spec() -> oas3::Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/request/specs/extensions.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    oas3::from_yaml(yaml).expect("Failed to read spec")
+}
+
+fn extension_config() -> Config {
+    let mut config = Config::new();
+    config.generator.extensions = vec!["x-rate-limit".to_owned()];
+    config
+}
+
+#[test]
+fn configured_extensions_collected() {
+    let spec = read_spec();
+
+    let operations = generate_operations(
+        &spec,
+        &mut ObjectDatabase::new(),
+        &extension_config(),
+        &mut GenerationReport::default(),
+        |operation| Ok(operation.clone()),
+    )
+    .unwrap();
+
+    let list_robots = operations
+        .iter()
+        .find(|operation| operation.operation_id == "list_robots")
+        .unwrap();
+    // Extensions which aren't configured are ignored
+    assert_eq!(
+        vec!["x-rate-limit"],
+        list_robots.extensions.keys().collect::<Vec<&String>>()
+    );
+    assert_eq!(
+        serde_json::json!({"requests": 10, "per": "second"}),
+        list_robots.extensions["x-rate-limit"]
+    );
+    let delete_robots = operations
+        .iter()
+        .find(|operation| operation.operation_id == "delete_robots")
+        .unwrap();
+    assert!(delete_robots.extensions.is_empty());
+}
+
+#[test]
+fn extension_callback_appends_code() {
+    let spec = read_spec();
+    let mut config = extension_config();
+    config.generator.extension_callback = Some(ExtensionCallback::new(|context| {
+        assert_eq!(ExtensionTarget::Operation, context.target);
+        let requests = context.extensions["x-rate-limit"]["requests"]
+            .as_u64()
+            .ok_or("requests missing")?;
+        Ok(Some(format!(
+            "pub const {}_REQUESTS_PER_SECOND: u64 = {};\n",
+            context.name.to_uppercase(),
+            requests
+        )))
+    }));
+    let path_item = &spec.paths.as_ref().unwrap()["/robots"];
+
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::GET,
+        "/robots",
+        path_item.get.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+    assert!(generated_code.ends_with("\npub const LIST_ROBOTS_REQUESTS_PER_SECOND: u64 = 10;\n"));
+
+    // Operations without extensions are not passed to the callback
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::DELETE,
+        "/robots",
+        path_item.delete.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+    assert!(!generated_code.contains("REQUESTS_PER_SECOND"));
+}
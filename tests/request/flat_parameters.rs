@@ -0,0 +1,136 @@
+use oas3::Spec;
+use opage::{
+    generator::rust_reqwest_async::path::http_request::{
+        generate_operation, generate_operation_test,
+    },
+    parser::{component::object_definition::types::ObjectDatabase, security::parse_spec},
+    utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn read_spec() -> Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/flat_parameters/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    parse_spec(&yaml).expect("Failed to read spec")
+}
+
+fn generate(config: &Config, method: Method, path: &str) -> String {
+    let spec = read_spec();
+    let path_item = &spec.paths.as_ref().unwrap()[path];
+    let operation = match method {
+        Method::POST => path_item.post.as_ref(),
+        _ => path_item.get.as_ref(),
+    }
+    .unwrap();
+
+    generate_operation(
+        &spec,
+        config,
+        &method,
+        path,
+        operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+fn flat_config() -> Config {
+    let mut config = Config::new();
+    config.generator.flat_parameters = Some(3);
+    config
+}
+
+#[test]
+fn flat_parameters_passed_as_arguments() {
+    let generated_code = generate(&flat_config(), Method::GET, "/robots/{robot_id}/motions");
+
+    assert!(generated_code.contains("robot_id: String,"));
+    assert!(generated_code.contains("limit: Option<i32>,"));
+    assert!(!generated_code.contains("path_parameters: ListMotionsPathParameters,"));
+    assert!(
+        generated_code.contains("let path_parameters = ListMotionsPathParameters { robot_id };")
+    );
+    assert!(generated_code.contains("let query_parameters = ListMotionsQueryParameters { limit };"));
+
+    // Parameter structs are kept without the config
+    let generated_code = generate(&Config::new(), Method::GET, "/robots/{robot_id}/motions");
+    assert!(generated_code.contains("path_parameters: ListMotionsPathParameters,"));
+    assert!(!generated_code.contains("let path_parameters ="));
+}
+
+#[test]
+fn flat_parameters_above_limit_keep_structs() {
+    let generated_code = generate(
+        &flat_config(),
+        Method::GET,
+        "/robots/{robot_id}/motions/{motion_id}/steps",
+    );
+
+    assert!(generated_code.contains("path_parameters: ListMotionStepsPathParameters,"));
+    assert!(generated_code.contains("query_parameters: ListMotionStepsQueryParameters,"));
+    assert!(!generated_code.contains("let path_parameters ="));
+}
+
+#[test]
+fn flat_parameters_of_multiple_content_types() {
+    let generated_code = generate(&flat_config(), Method::POST, "/robots/{robot_id}/programs");
+
+    assert_eq!(
+        generated_code
+            .matches("let query_parameters = UploadProgramQueryParameters { dry_run };")
+            .count(),
+        2
+    );
+    assert!(generated_code.contains("dry_run: bool,"));
+    // The private function still takes the query struct
+    assert!(generated_code.contains("query_parameters: UploadProgramQueryParameters,"));
+}
+
+#[test]
+fn flat_parameters_colliding_with_arguments_keep_structs() {
+    let generated_code = generate(&flat_config(), Method::GET, "/search");
+
+    assert!(generated_code.contains("query_parameters: SearchQueryParameters,"));
+    assert!(!generated_code.contains("let query_parameters ="));
+}
+
+#[test]
+fn flat_parameters_colliding_with_auth_keep_structs() {
+    let mut config = flat_config();
+    config.generator.security = true;
+    let generated_code = generate(&config, Method::GET, "/tokens");
+
+    assert!(generated_code.contains("auth: &crate::auth::Auth,"));
+    assert!(generated_code.contains("query_parameters: ListTokensQueryParameters,"));
+    assert!(!generated_code.contains("let query_parameters ="));
+}
+
+#[test]
+fn test_of_flat_parameters_colliding_with_auth_passes_credentials() {
+    let spec = read_spec();
+    let mut config = flat_config();
+    config.project_metadata.name = "flat-parameters".to_owned();
+    config.generator.security = true;
+    let operation = spec.paths.as_ref().unwrap()["/tokens"]
+        .get
+        .as_ref()
+        .unwrap();
+
+    let test_code = generate_operation_test(
+        &spec,
+        &config,
+        &Method::GET,
+        "/tokens",
+        operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate test")
+    .expect("Test skipped");
+
+    assert!(test_code.contains("&flat_parameters::auth::Auth::Bearer(\"token\".to_owned()),"));
+    assert!(test_code.contains("ListTokensQueryParameters { auth: "));
+    assert_eq!(1, test_code.matches("query_param(\"auth\"").count());
+}
@@ -0,0 +1,50 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(config: &Config, path: &str) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/idempotency_key/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let operation = spec.paths.as_ref().unwrap()[path].post.as_ref().unwrap();
+
+    generate_operation(
+        &spec,
+        config,
+        &Method::POST,
+        path,
+        operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+#[test]
+fn idempotency_key_header_parameter() {
+    let generated_code = generate(&Config::new(), "/payments");
+
+    assert!(generated_code.contains("idempotency_key: Option<&str>,"));
+    assert!(generated_code.contains(
+        "Some(idempotency_key) => request_builder.header(\"Idempotency-Key\", idempotency_key),"
+    ));
+    assert!(generated_code.contains(
+        "None => request_builder.header(\"Idempotency-Key\", uuid::Uuid::new_v4().to_string()),"
+    ));
+}
+
+#[test]
+fn idempotency_key_configured_operations() {
+    let generated_code = generate(&Config::new(), "/orders");
+    assert!(!generated_code.contains("idempotency_key"));
+
+    let mut config = Config::new();
+    config.generator.idempotency_key_operations = vec!["create_order".to_owned()];
+    let generated_code = generate(&config, "/orders");
+    assert!(generated_code.contains("idempotency_key: Option<&str>,"));
+    assert!(generated_code.contains("uuid::Uuid::new_v4().to_string()"));
+}
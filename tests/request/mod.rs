@@ -0,0 +1,13 @@
+pub mod batch;
+pub mod conditional;
+pub mod operation_test;
+pub mod path_parameters;
+pub mod query_styles;
+pub mod request_body;
+pub mod servers;
+pub mod websocket;
+pub mod extensions;
+pub mod idempotency;
+pub mod flat_parameters;
+pub mod ndjson;
+pub mod event_stream;
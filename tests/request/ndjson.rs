@@ -0,0 +1,51 @@
+use opage::{
+    generator::rust_reqwest_async::path::ndjson_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(method: Method, path: &str) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/ndjson_stream/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_item = &spec.paths.as_ref().unwrap()[path];
+    let operation = match method {
+        Method::POST => path_item.post.as_ref(),
+        _ => path_item.get.as_ref(),
+    };
+
+    generate_operation(
+        &spec,
+        &Config::new(),
+        &method,
+        path,
+        operation.unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+#[test]
+fn ndjson_stream_of_items() {
+    let generated_code = generate(Method::GET, "/robots/{robot_id}/logs");
+    assert!(generated_code.contains(
+        "-> Result<impl futures::Stream<Item = Result<LogEntry, String>>, reqwest::Error>"
+    ));
+    assert!(generated_code.contains(".header(\"Accept\", \"application/x-ndjson\")"));
+    assert!(generated_code.contains("response.bytes_stream()"));
+    assert!(generated_code.contains("serde_json::from_slice::<LogEntry>(&line)"));
+
+    // Lines without schema are json values
+    let generated_code = generate(Method::POST, "/events/export");
+    assert!(generated_code.contains("Item = Result<serde_json::Value, String>"));
+    assert!(generated_code.contains(".json(&content)"));
+}
+
+#[test]
+fn ndjson_stream_with_content_type_parameters() {
+    let generated_code = generate(Method::GET, "/robots/{robot_id}/states");
+    assert!(generated_code.contains("Item = Result<LogEntry, String>"));
+}
@@ -0,0 +1,38 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation_test,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+#[test]
+fn wiremock_test_for_json_request() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/request/specs/request_body_methods.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/items").unwrap();
+
+    let mut object_database = ObjectDatabase::new();
+    let mut config = Config::new();
+    config.project_metadata.name = "items-client".to_owned();
+
+    let generated_test = generate_operation_test(
+        &spec,
+        &config,
+        &Method::DELETE,
+        "/items",
+        &path_spec.delete.as_ref().unwrap(),
+        &mut object_database,
+    )
+    .expect("Failed to generate test")
+    .expect("No test generated");
+
+    assert!(generated_test.contains("use items_client::paths::delete_items::*;"));
+    assert!(generated_test.contains("Mock::given(method(\"DELETE\"))"));
+    assert!(generated_test.contains(".and(path(\"/items\"))"));
+    assert!(generated_test.contains(".and(header(\"content-type\", \"application/json\"))"));
+    assert!(generated_test.contains("ResponseTemplate::new(204)"));
+    assert!(generated_test.contains("DeleteItemsResponseType::NoContent"));
+}
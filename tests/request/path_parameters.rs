@@ -0,0 +1,124 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::{
+        generate_operation, generate_operation_test,
+    },
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+const PATH: &str = "/robots/{robot_id}/joints/{index}/enabled/{enabled}";
+
+fn read_spec() -> oas3::Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/request/specs/path_parameters.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    oas3::from_yaml(yaml).expect("Failed to read spec")
+}
+
+#[test]
+fn path_parameters_typed_by_schema() {
+    let spec = read_spec();
+    let operation = spec.paths.as_ref().unwrap()[PATH].put.clone().unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::PUT,
+        PATH,
+        &operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+
+    assert!(generated_code.contains("pub robot_id: String,"));
+    assert!(generated_code.contains("pub index: i32,"));
+    assert!(generated_code.contains("pub enabled: bool,"));
+}
+
+#[test]
+fn path_parameters_percent_encoded() {
+    let spec = read_spec();
+    let operation = spec.paths.as_ref().unwrap()[PATH].put.clone().unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::PUT,
+        PATH,
+        &operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+
+    assert!(generated_code.contains(
+        "crate::path_encoding::encode_path_parameter(&path_parameters.robot_id), \
+         crate::path_encoding::encode_path_parameter(&path_parameters.index)"
+    ));
+}
+
+#[test]
+fn derives_configurable_per_kind() {
+    let spec = read_spec();
+    let operation = spec.paths.as_ref().unwrap()[PATH].put.clone().unwrap();
+    let generate = |config: &Config| {
+        generate_operation(
+            &spec,
+            config,
+            &Method::PUT,
+            PATH,
+            &operation,
+            &mut ObjectDatabase::new(),
+        )
+        .expect("Failed to generate path")
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n")
+    };
+
+    let generated_code = generate(&Config::new());
+    assert!(generated_code.contains(
+        "#[derive(Debug, Clone, PartialEq)]\n#[derive(Default)]\npub struct SetJointEnabledPathParameters"
+    ));
+    assert!(generated_code.contains("#[derive(Debug)]\npub enum SetJointEnabledResponseType"));
+
+    let mut config = Config::new();
+    config.generator.derives.parameters = vec!["Debug".to_owned(), "Hash".to_owned()];
+    config.generator.derives.responses = vec![];
+
+    let generated_code = generate(&config);
+    assert!(generated_code.contains(
+        "#[derive(Debug, Hash)]\n#[derive(Default)]\npub struct SetJointEnabledPathParameters"
+    ));
+    assert!(generated_code.contains("\npub enum SetJointEnabledResponseType"));
+    assert!(!generated_code.contains("#[derive(Debug)]\npub enum SetJointEnabledResponseType"));
+}
+
+#[test]
+fn typed_path_parameters_parsed_in_test() {
+    let spec = read_spec();
+    let operation = spec.paths.as_ref().unwrap()[PATH].put.clone().unwrap();
+    let mut config = Config::new();
+    config.project_metadata.name = "robots-client".to_owned();
+
+    let generated_test = generate_operation_test(
+        &spec,
+        &config,
+        &Method::PUT,
+        PATH,
+        &operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate test")
+    .expect("No test generated");
+
+    assert!(
+        generated_test.contains(r#"robot_id: "3fa85f64-5717-4562-b3fc-2c963f66afa6".to_owned()"#)
+    );
+    assert!(generated_test.contains(r#"index: "3".parse().expect("#));
+    assert!(generated_test.contains(r#"enabled: "true".parse().expect("#));
+    assert!(generated_test.contains(r#"/joints/3/enabled/true"))"#));
+}
@@ -0,0 +1,54 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate_list_robots() -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/request/specs/query_styles.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let operation = spec.paths.as_ref().unwrap()["/robots"].get.clone().unwrap();
+
+    generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::GET,
+        "/robots",
+        &operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+#[test]
+fn query_parameters_serialized_by_style() {
+    let generated_code = generate_list_robots();
+
+    // form, exploded: one pair per item
+    assert!(generated_code
+        .contains("query_pairs.push((\"ids\".to_owned(), query_parameter_item.to_string()))"));
+    // form, not exploded: comma separated
+    assert!(generated_code.contains(".join(\",\")"));
+    assert!(generated_code.contains(".join(\" \")"));
+    assert!(generated_code.contains(".join(\"|\")"));
+    assert!(generated_code.contains("format!(\"filter[{}]\", field_name)"));
+}
+
+#[test]
+fn object_query_parameters_serialized_by_fields() {
+    let generated_code = generate_list_robots();
+
+    assert!(!generated_code.contains("query_parameters.position.to_string()"));
+    // form, exploded: one pair per field
+    assert!(generated_code.contains("let query_parameter = &query_parameters.position;"));
+    assert!(generated_code
+        .contains("query_parameter_fields.for_each(|field_pair| query_pairs.push(field_pair));"));
+    // form, not exploded: fields and values joined
+    assert!(
+        generated_code.contains(".flat_map(|(field_name, field_value)| [field_name, field_value])")
+    );
+}
@@ -0,0 +1,112 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate_items_operation(config: &Config, method: &Method) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/request/specs/request_body_methods.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/items").unwrap();
+    let operation = match *method {
+        Method::GET => path_spec.get.as_ref().unwrap(),
+        Method::DELETE => path_spec.delete.as_ref().unwrap(),
+        _ => panic!("Unexpected method"),
+    };
+
+    let mut object_database = ObjectDatabase::new();
+
+    generate_operation(
+        &spec,
+        config,
+        method,
+        "/items",
+        operation,
+        &mut object_database,
+    )
+    .expect("Failed to generated path")
+}
+
+#[test]
+fn delete_with_request_body() {
+    let generated_code = generate_items_operation(&Config::new(), &Method::DELETE);
+
+    assert!(generated_code.contains("content: ItemFilter"));
+    assert!(generated_code.contains(".json(&content)"));
+}
+
+#[test]
+fn get_with_request_body() {
+    let generated_code = generate_items_operation(&Config::new(), &Method::GET);
+
+    assert!(generated_code.contains("content: ItemFilter"));
+    assert!(generated_code.contains(".json(&content)"));
+}
+
+#[test]
+fn skipped_request_body_methods() {
+    let mut config = Config::new();
+    config.generator.skip_request_body_methods = vec!["get".to_owned(), "DELETE".to_owned()];
+
+    for method in [Method::GET, Method::DELETE] {
+        let generated_code = generate_items_operation(&config, &method);
+        assert!(!generated_code.contains("content: ItemFilter"));
+        assert!(!generated_code.contains(".json(&content)"));
+    }
+}
+
+#[test]
+fn requests_logged_behind_feature() {
+    let generated_code = generate_items_operation(&Config::new(), &Method::DELETE);
+    assert!(!generated_code.contains("log::debug!"));
+
+    let mut config = Config::new();
+    config.generator.request_logging = true;
+    let generated_code = generate_items_operation(&config, &Method::DELETE);
+
+    assert!(generated_code.contains("#[cfg(feature = \"logging\")]\n    log::debug!("));
+    assert!(generated_code.contains(": DELETE {} -> {} in {:?}\""));
+}
+
+fn generate_optional_body_operation(path: &str) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/optional_request_body/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get(path).unwrap();
+    let (method, operation) = match path_spec.post {
+        Some(ref operation) => (Method::POST, operation),
+        None => (Method::PUT, path_spec.put.as_ref().unwrap()),
+    };
+
+    generate_operation(
+        &spec,
+        &Config::new(),
+        &method,
+        path,
+        operation,
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path")
+}
+
+#[test]
+fn optional_request_body() {
+    let generated_code = generate_optional_body_operation("/robots/{robot_id}/reset");
+    assert!(generated_code.contains("content: Option<ResetOptions>,"));
+    assert!(generated_code.contains("let request_builder = match content {"));
+    assert!(generated_code.contains("None => request_builder,"));
+
+    let generated_code = generate_optional_body_operation("/robots/{robot_id}/note");
+    assert!(generated_code.contains("content: Option<&String>,"));
+    assert!(generated_code.contains("request_builder.body(content.to_owned())"));
+
+    let generated_code = generate_optional_body_operation("/robots/{robot_id}");
+    assert!(generated_code.contains("content: ResetOptions,"));
+    assert!(!generated_code.contains("let request_builder = match content {"));
+}
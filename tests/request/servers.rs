@@ -0,0 +1,65 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(config: &Config, method: Method, path: &str) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/operation_servers/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_item = &spec.paths.as_ref().unwrap()[path];
+    let operation = match method {
+        Method::POST => path_item.post.as_ref(),
+        _ => path_item.get.as_ref(),
+    };
+
+    generate_operation(
+        &spec,
+        config,
+        &method,
+        path,
+        operation.unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+#[test]
+fn path_and_operation_servers() {
+    let generated_code = generate(&Config::new(), Method::GET, "/robots");
+    assert!(generated_code
+        .contains("pub const LIST_ROBOTS_SERVER: &str = \"https://read.example.com\";"));
+
+    // Operation servers replace the ones of the path, variables have their default value
+    let generated_code = generate(&Config::new(), Method::POST, "/robots");
+    assert!(generated_code
+        .contains("pub const CREATE_ROBOT_SERVER: &str = \"https://eu.write.example.com\";"));
+
+    let generated_code = generate(&Config::new(), Method::GET, "/health");
+    assert!(!generated_code.contains("_SERVER"));
+}
+
+#[test]
+fn configured_operation_servers() {
+    let mut config = Config::new();
+    config.generator.operation_servers.insert(
+        "listRobots".to_owned(),
+        "https://replica.example.com".to_owned(),
+    );
+    config
+        .generator
+        .operation_servers
+        .insert("health".to_owned(), "https://status.example.com".to_owned());
+
+    let generated_code = generate(&config, Method::GET, "/robots");
+    assert!(generated_code
+        .contains("pub const LIST_ROBOTS_SERVER: &str = \"https://replica.example.com\";"));
+    let generated_code = generate(&config, Method::GET, "/health");
+    assert!(
+        generated_code.contains("pub const HEALTH_SERVER: &str = \"https://status.example.com\";")
+    );
+}
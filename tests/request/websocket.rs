@@ -0,0 +1,37 @@
+use opage::{
+    generator::rust_reqwest_async::path::websocket_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use std::path::PathBuf;
+
+#[test]
+fn websocket_parameters_keep_struct_names() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/request/specs/websocket.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path = "/robots/{robot}/positions";
+    let path_spec = spec.paths.as_ref().unwrap().get(path).unwrap();
+
+    let mut object_database = ObjectDatabase::new();
+    let config = Config::new();
+
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        path,
+        path_spec.get.as_ref().unwrap(),
+        &mut object_database,
+    )
+    .expect("Failed to generate websocket");
+
+    assert!(
+        generated_code.contains("stream_positions_path_parameters: StreamPositionsPathParameters,")
+    );
+    assert!(generated_code
+        .contains("stream_positions_query_parameters: StreamPositionsQueryParameters,"));
+    assert!(generated_code.contains("stream_positions_query_parameters.joints.iter()"));
+    assert!(generated_code
+        .contains("if let Some(ref query_parameter) = stream_positions_query_parameters.rate"));
+}
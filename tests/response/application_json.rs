@@ -1,12 +1,12 @@
 use opage::{
     generator::rust_reqwest_async::path::http_request::generate_operation,
     parser::component::object_definition::types::ObjectDatabase,
-    utils::{log::Logger, name_mapping::NameMapping},
+    utils::{config::Config, log::Logger},
 };
 use reqwest::Method;
 use std::path::PathBuf;
 
-static LOGGER: Logger = Logger;
+static LOGGER: Logger = Logger::new();
 
 #[test]
 fn empty_json() {
@@ -21,11 +21,11 @@ fn empty_json() {
     let path_spec = spec.paths.as_ref().unwrap().get("/test").unwrap();
 
     let mut object_database = ObjectDatabase::new();
-    let name_mapping = NameMapping::new();
+    let config = Config::new();
 
     generate_operation(
         &spec,
-        &name_mapping,
+        &config,
         &Method::POST,
         "/test",
         &path_spec.post.as_ref().unwrap(),
@@ -33,3 +33,56 @@ fn empty_json() {
     )
     .expect("Failed to generated path");
 }
+
+#[test]
+fn content_type_parameters_ignored() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/charset.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/status").unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::GET,
+        "/status",
+        path_spec.get.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains(".split(';')"));
+    assert!(generated_code.contains("\"application/json\" =>"));
+    assert!(generated_code.contains("response.json::<Status>().await"));
+}
+
+#[test]
+fn large_bodies_returned_unread() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/charset.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/status").unwrap();
+
+    let mut config = Config::new();
+    config.generator.max_response_body_size = Some(1024);
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::GET,
+        "/status",
+        path_spec.get.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains("BodyTooLarge(reqwest::Response),"));
+    assert!(generated_code.contains(".is_some_and(|content_length| content_length > 1024)"));
+    assert!(generated_code.contains("return Ok(GetStatusResponseType::BodyTooLarge(response));"));
+    // Bodies without Content-Length are limited while they are read
+    assert!(generated_code.contains("while let Some(chunk) = response.chunk().await? {"));
+    assert!(generated_code.contains("if body.len() as u64 > 1024 {"));
+}
@@ -0,0 +1,89 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+#[test]
+fn octet_stream_as_bytes() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/octet_stream.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/download").unwrap();
+
+    let mut object_database = ObjectDatabase::new();
+    let config = Config::new();
+
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::GET,
+        "/download",
+        &path_spec.get.as_ref().unwrap(),
+        &mut object_database,
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains("Ok(Vec<u8>)"));
+    assert!(generated_code.contains("response.bytes().await"));
+}
+
+#[test]
+fn undefined_response_captured() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/octet_stream.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/download").unwrap();
+
+    let mut object_database = ObjectDatabase::new();
+    let mut config = Config::new();
+    config.generator.capture_undefined_response = true;
+
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::GET,
+        "/download",
+        &path_spec.get.as_ref().unwrap(),
+        &mut object_database,
+    )
+    .expect("Failed to generated path");
+
+    assert!(
+        generated_code.contains("UndefinedResponse(crate::undefined_response::UndefinedResponse)")
+    );
+    assert!(generated_code.contains("UndefinedResponse::from_response(response).await?"));
+}
+
+#[test]
+fn octet_stream_downloaded_to_file() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/octet_stream.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec.paths.as_ref().unwrap().get("/download").unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::GET,
+        "/download",
+        &path_spec.get.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains("pub async fn download_to_file("));
+    assert!(generated_code.contains("progress: impl FnMut(u64, Option<u64>),"));
+    assert!(generated_code.contains(
+        "200 => crate::download::write_response_to_file(response, file_path, progress).await,"
+    ));
+    assert!(generated_code
+        .contains("_ => Err(crate::download::DownloadError::UnexpectedResponse(response)),"));
+}
@@ -0,0 +1,92 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+const PATH: &str = "/pets/{pet_id}";
+
+fn read_spec() -> oas3::Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/xml.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    oas3::from_yaml(yaml).expect("Failed to read spec")
+}
+
+#[test]
+fn xml_bodies_serialized_as_xml() {
+    let spec = read_spec();
+    let path_spec = spec.paths.as_ref().unwrap().get(PATH).unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::PUT,
+        PATH,
+        path_spec.put.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains("Result<UpdatePetResponseType, crate::xml::XmlRequestError>"));
+    assert!(generated_code.contains("crate::xml::to_string(&content)"));
+    assert!(generated_code.contains(".header(\"content-type\", \"application/xml\")"));
+    assert!(generated_code.contains("crate::xml::from_str::<Pet>(&response_text)"));
+}
+
+#[test]
+fn xml_and_json_responses_as_variants() {
+    let spec = read_spec();
+    let path_spec = spec.paths.as_ref().unwrap().get(PATH).unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::GET,
+        PATH,
+        path_spec.get.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains("Json(Pet)"));
+    assert!(generated_code.contains("Xml(Pet)"));
+    assert!(generated_code.contains("\"application/xml\" =>"));
+}
+
+#[test]
+fn variants_and_functions_mapped() {
+    let spec = read_spec();
+    let path_spec = spec.paths.as_ref().unwrap().get(PATH).unwrap();
+    let mut config = Config::new();
+    config
+        .name_mapping
+        .function_mapping
+        .insert("get_pet".to_owned(), "fetch_pet".to_owned());
+    config.name_mapping.variant_mapping.insert(
+        "/pets/{pet_id}/FetchPetResponseType/Ok".to_owned(),
+        "Found".to_owned(),
+    );
+    config
+        .name_mapping
+        .variant_mapping
+        .insert("/pets/{pet_id}/OkValue/Xml".to_owned(), "PetXml".to_owned());
+
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::GET,
+        PATH,
+        path_spec.get.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains("pub async fn fetch_pet("));
+    assert!(generated_code.contains("Found(OkValue)"));
+    assert!(generated_code.contains("PetXml(Pet)"));
+    assert!(generated_code.contains("Ok(FetchPetResponseType::Found"));
+    assert!(generated_code.contains("(OkValue::PetXml"));
+}
@@ -0,0 +1,26 @@
+use opage::{
+    generator::operation::generate_operations,
+    parser::component::object_definition::types::ObjectDatabase,
+    utils::{config::Config, report::GenerationReport},
+};
+use std::path::PathBuf;
+
+#[test]
+fn content_encodings_of_response_headers() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/content_encoding.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+
+    let operations = generate_operations(
+        &spec,
+        &mut ObjectDatabase::new(),
+        &Config::new(),
+        &mut GenerationReport::default(),
+        |operation| Ok(operation.content_encodings.clone()),
+    )
+    .unwrap();
+
+    assert_eq!(vec![vec!["gzip".to_owned(), "br".to_owned()]], operations);
+}
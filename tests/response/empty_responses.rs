@@ -0,0 +1,40 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+#[test]
+fn empty_responses_have_unit_variants() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/empty_responses.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_spec = spec
+        .paths
+        .as_ref()
+        .unwrap()
+        .get("/robots/{robot_id}")
+        .unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::DELETE,
+        "/robots/{robot_id}",
+        path_spec.delete.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path");
+
+    assert!(generated_code.contains("NoContent,"));
+    assert!(generated_code.contains("204 => Ok(DeleteRobotResponseType::NoContent),"));
+    assert!(generated_code.contains("404 => Ok(DeleteRobotResponseType::NotFound),"));
+    // 429 responses without content still contain the rate limit headers
+    assert!(generated_code.contains("TooManyRequests(crate::rate_limit::RateLimitInfo),"));
+    assert!(
+        generated_code.contains("429 => Ok(DeleteRobotResponseType::TooManyRequests(rate_limit)),")
+    );
+}
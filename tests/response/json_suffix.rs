@@ -0,0 +1,59 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn read_spec() -> oas3::Spec {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/response/specs/json_suffix.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    oas3::from_yaml(yaml).expect("Failed to read spec")
+}
+
+#[test]
+fn json_suffix_content_types_as_json() {
+    let spec = read_spec();
+    let path_spec = spec.paths.as_ref().unwrap().get("/orders").unwrap();
+
+    let generated_code = generate_operation(
+        &spec,
+        &Config::new(),
+        &Method::POST,
+        "/orders",
+        path_spec.post.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains(".header(\"content-type\", \"application/vnd.api+json\")"));
+    assert!(generated_code.contains(".json(&content)"));
+    assert!(generated_code.contains("response.json::<Order>().await"));
+    assert!(generated_code.contains("response.json::<Problem>().await"));
+}
+
+#[test]
+fn content_type_mapping_applied() {
+    let spec = read_spec();
+    let path_spec = spec.paths.as_ref().unwrap().get("/orders").unwrap();
+
+    let mut config = Config::new();
+    config
+        .generator
+        .content_type_mapping
+        .insert("text/csv".to_owned(), "text/plain".to_owned());
+
+    let generated_code = generate_operation(
+        &spec,
+        &config,
+        &Method::GET,
+        "/orders",
+        path_spec.get.as_ref().unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generated path");
+
+    assert!(generated_code.contains("response.text().await"));
+}
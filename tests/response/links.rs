@@ -0,0 +1,60 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::generate_components, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(method: Method, path: &str) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/links/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_item = &spec.paths.as_ref().unwrap()[path];
+    let operation = match method {
+        Method::POST => path_item.post.as_ref(),
+        _ => path_item.get.as_ref(),
+    };
+    let config = Config::new();
+    let mut object_database = generate_components(&spec, &config).unwrap();
+
+    generate_operation(
+        &spec,
+        &config,
+        &method,
+        path,
+        operation.unwrap(),
+        &mut object_database,
+    )
+    .expect("Failed to generate path")
+}
+
+#[test]
+fn link_parameters_from_response_body() {
+    let generated_code = generate(Method::POST, "/users");
+
+    assert!(generated_code.contains(
+        "pub fn get_user_by_id_path_parameters(&self) -> Option<crate::paths::get_user::GetUserPathParameters> {"
+    ));
+    assert!(generated_code.contains(
+        "CreateUserResponseType::Created(value) => Some(crate::paths::get_user::GetUserPathParameters { user_id: value.id.clone() }),"
+    ));
+
+    // Links of the components, optional values and parameters without value
+    assert!(generated_code.contains("pub fn list_user_jobs_path_parameters(&self)"));
+    assert!(generated_code
+        .contains("ListUserJobsQueryParameters { limit: None, team: Some(value.team.clone()?) }"));
+}
+
+#[test]
+fn link_parameters_of_nested_properties() {
+    let generated_code = generate(Method::GET, "/users/{userId}");
+
+    assert!(generated_code
+        .contains("GetUserPathParameters { user_id: value.manager.as_ref()?.id.clone() }"));
+
+    // Responses without links have no helpers
+    let generated_code = generate(Method::GET, "/users/{userId}/jobs");
+    assert!(!generated_code.contains("_parameters(&self)"));
+}
@@ -1 +1,9 @@
 pub mod application_json;
+pub mod application_octet_stream;
+pub mod application_xml;
+pub mod content_encoding;
+pub mod empty_responses;
+pub mod json_suffix;
+pub mod links;
+pub mod rate_limit;
+pub mod response_result;
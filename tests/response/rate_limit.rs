@@ -0,0 +1,45 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(method: Method, path: &str) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/rate_limit/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_item = &spec.paths.as_ref().unwrap()[path];
+    let operation = match method {
+        Method::GET => path_item.get.as_ref(),
+        _ => path_item.delete.as_ref(),
+    };
+
+    generate_operation(
+        &spec,
+        &Config::new(),
+        &method,
+        path,
+        operation.unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+#[test]
+fn too_many_requests_contains_rate_limit_info() {
+    let generated_code = generate(Method::GET, "/robots");
+
+    assert!(generated_code.contains("TooManyRequests(crate::rate_limit::RateLimited<Error>),"));
+    assert!(generated_code.contains(
+        "let rate_limit = crate::rate_limit::RateLimitInfo::from_headers(response.headers());"
+    ));
+    assert!(generated_code.contains(
+        "Ok(ListRobotsResponseType::TooManyRequests(crate::rate_limit::RateLimited { rate_limit, content: "
+    ));
+
+    let generated_code = generate(Method::DELETE, "/robots/{robot_id}");
+    assert!(generated_code.contains("TooManyRequests(crate::rate_limit::RateLimited<String>),"));
+}
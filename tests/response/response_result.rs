@@ -0,0 +1,104 @@
+use opage::{
+    generator::rust_reqwest_async::path::http_request::generate_operation,
+    parser::component::object_definition::types::ObjectDatabase, utils::config::Config,
+};
+use reqwest::Method;
+use std::path::PathBuf;
+
+fn generate(config: &Config, method: Method) -> String {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects/response_results/spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read spec yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let path_item = &spec.paths.as_ref().unwrap()["/robots/{robot_id}"];
+    let operation = match method {
+        Method::GET => path_item.get.as_ref(),
+        _ => path_item.delete.as_ref(),
+    };
+
+    generate_operation(
+        &spec,
+        config,
+        &method,
+        "/robots/{robot_id}",
+        operation.unwrap(),
+        &mut ObjectDatabase::new(),
+    )
+    .expect("Failed to generate path")
+}
+
+fn response_results_config() -> Config {
+    let mut config = Config::new();
+    config.generator.response_results = true;
+    config
+}
+
+#[test]
+fn into_result_splits_success_and_error_variants() {
+    let generated_code = generate(&response_results_config(), Method::GET);
+
+    assert!(generated_code.contains(
+        "pub fn into_result(self) -> Result<GetRobotResponseSuccess, GetRobotResponseError> {"
+    ));
+    assert!(generated_code
+        .contains("GetRobotResponseType::Ok(value) => Ok(GetRobotResponseSuccess::Ok(value)),"));
+    assert!(generated_code.contains(
+        "GetRobotResponseType::NotModified(value) => Ok(GetRobotResponseSuccess::NotModified(value)),"
+    ));
+    assert!(generated_code.contains(
+        "GetRobotResponseType::NotFound(value) => Err(GetRobotResponseError::NotFound(value)),"
+    ));
+    assert!(generated_code.contains("TooManyRequests(crate::rate_limit::RateLimited<Error>),"));
+    assert!(generated_code.contains(
+        "GetRobotResponseType::UndefinedResponse(value) if value.status().is_success() => Ok(GetRobotResponseSuccess::UndefinedResponse(value)),"
+    ));
+    assert!(generated_code.contains(
+        "GetRobotResponseType::UndefinedResponse(value) => Err(GetRobotResponseError::UndefinedResponse(value)),"
+    ));
+
+    let generated_code = generate(&response_results_config(), Method::DELETE);
+    assert!(generated_code.contains(
+        "DeleteRobotResponseType::Accepted => Ok(DeleteRobotResponseSuccess::Accepted),"
+    ));
+}
+
+#[test]
+fn into_result_of_captured_undefined_responses() {
+    let mut config = response_results_config();
+    config.generator.capture_undefined_response = true;
+    let generated_code = generate(&config, Method::DELETE);
+
+    assert!(generated_code.contains(
+        "DeleteRobotResponseType::UndefinedResponse(value) if (200..300).contains(&value.status) =>"
+    ));
+}
+
+#[test]
+fn into_result_disabled_by_default() {
+    let generated_code = generate(&Config::new(), Method::GET);
+
+    assert!(!generated_code.contains("into_result"));
+    assert!(!generated_code.contains("GetRobotResponseSuccess"));
+}
+
+#[test]
+fn status_accessors_of_response_enum() {
+    let generated_code = generate(&Config::new(), Method::GET);
+
+    assert!(generated_code.contains("pub fn status(&self) -> Option<u16> {"));
+    assert!(generated_code.contains("GetRobotResponseType::Ok(_) => Some(200),"));
+    assert!(generated_code.contains("GetRobotResponseType::NotModified(_) => Some(304),"));
+    assert!(generated_code.contains(
+        "GetRobotResponseType::UndefinedResponse(response) => Some(response.status().as_u16()),"
+    ));
+    assert!(generated_code.contains("pub fn is_success(&self) -> bool {"));
+
+    let mut config = Config::new();
+    config.generator.capture_undefined_response = true;
+    let generated_code = generate(&config, Method::DELETE);
+    assert!(generated_code.contains("DeleteRobotResponseType::Accepted => Some(202),"));
+    assert!(generated_code.contains(
+        "DeleteRobotResponseType::UndefinedResponse(response) => Some(response.status),"
+    ));
+}
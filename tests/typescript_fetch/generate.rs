@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use opage::{
+    generator::typescript_fetch::{models::generate_models_content, paths::generate_paths_content},
+    parser::component::generate_components,
+    utils::{config::Config, report::GenerationReport},
+};
+
+/// (models.ts, paths.ts) of a spec of tests/projects
+fn generate_package(project_name: &str) -> (String, String) {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/projects");
+    spec_file_path.push(project_name);
+    spec_file_path.push("spec.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let mut object_database = generate_components(&spec, &config).unwrap();
+    let paths_content = generate_paths_content(
+        &spec,
+        &mut object_database,
+        &config,
+        &mut GenerationReport::default(),
+    )
+    .unwrap();
+    let models_content = generate_models_content(&object_database).unwrap();
+    (models_content, paths_content)
+}
+
+#[test]
+fn interfaces_and_operations_generated() {
+    let (models_content, paths_content) = generate_package("generated_tests");
+
+    assert!(models_content.contains("export interface Pet {\n  id: number;\n"));
+    assert!(models_content.contains("  owner?: Owner;\n"));
+
+    assert!(paths_content.contains(
+        "  query: { limit: number; name?: string; tags?: string[] },\n): Promise<ApiResponse<models.Pet[] | undefined>> {"
+    ));
+    assert!(paths_content.contains("      params.append(\"tags\", String(item));"));
+    assert!(paths_content.contains("/pets/${encodeURIComponent(String(petId))}"));
+    assert!(paths_content.contains("body: (await response.json()) as models.Pet[]"));
+    assert!(paths_content.contains("Promise<ApiResponse<models.Pet | string | undefined>>"));
+}
+
+#[test]
+fn typescript_names_sanitized() {
+    let (models_content, paths_content) = generate_package("keyword_identifiers");
+
+    assert!(models_content.contains("  \"3d_model\"?: string;\n"));
+    assert!(models_content.contains("  type: string;\n"));
+    assert!(paths_content.contains("export async function match("));
+    assert!(paths_content.contains("  query: { type?: string } = {},\n"));
+}
@@ -0,0 +1 @@
+pub mod generate;
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use opage::{parser::validation::validate_spec, utils::config::Config};
+
+#[test]
+fn unsupported_features_reported() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/validation/specs/unsupported.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let issues = validate_spec(&spec, &config)
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>();
+
+    assert_eq!(
+        vec![
+            "#/components/schemas/Item/properties/extended: allOf is not supported",
+            "#/components/schemas/Item/properties/id: Multiple types are not supported",
+            "GET /items: Missing operationId",
+            "GET /items: Parameter X-Trace in Header is not supported",
+            "POST /upload: Request Content-Type multipart/form-data is not supported",
+            "POST /upload: Response 200 Content-Type application/yaml is not supported",
+            "HEAD /upload: Method is not supported",
+        ],
+        issues
+    );
+}
+
+#[test]
+fn path_parameter_mismatches_reported() {
+    let mut spec_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    spec_file_path.push("tests/validation/specs/path_parameters.openapi.yaml");
+
+    let yaml = std::fs::read_to_string(spec_file_path).expect("Failed to read yaml");
+    let spec = oas3::from_yaml(yaml).expect("Failed to read spec");
+    let config = Config::new();
+
+    let issues = validate_spec(&spec, &config)
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>();
+
+    assert_eq!(
+        vec![
+            "GET /files/{name}.json: Placeholder in path segment {name}.json is not supported, it is sent literally",
+            "GET /robots/{robotId}/programs/{program}: Placeholder {robotId} is not declared as path parameter (declared: robot_id)",
+            "GET /robots/{robotId}/programs/{program}: Placeholder {program} is not declared as path parameter and generated as String",
+            "GET /robots/{robotId}/programs/{program}: Path parameter robot_id is not in the path template and skipped",
+            "GET /robots/{robotId}/programs/{program}: Path parameter version is not in the path template and skipped",
+        ],
+        issues
+    );
+}